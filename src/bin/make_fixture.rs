@@ -0,0 +1,74 @@
+//! Record a confirmed transaction as a parser regression fixture.
+//!
+//! Fetch the transaction from an RPC node and record it in one step:
+//!
+//!     cargo run --bin make_fixture -- --rpc <url> <signature> <tests/fixtures/transactions/name.json> "<description>"
+//!
+//! or save a `getTransaction` response (`json` or `base64` encoding,
+//! `maxSupportedTransactionVersion: 0`) and run:
+//!
+//!     cargo run --bin make_fixture -- <transaction.json> <tests/fixtures/transactions/name.json> "<description>"
+//!
+//! The expected output is taken from the current parser and rejected when its base amounts
+//! disagree with the pool vault balance changes in the transaction. Check the remaining fields
+//! (fees, reserves, accounts) against the explorer's decoded instruction before committing.
+
+use std::path::Path;
+use std::str::FromStr;
+use anyhow::{anyhow, Context, Result};
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::{commitment_config::CommitmentConfig, signature::Signature};
+use solana_transaction_status::{EncodedConfirmedTransactionWithStatusMeta, UiTransactionEncoding};
+use solana_vntr_sniper::engine::transaction_fixture::{subscribe_update_from_confirmed, TransactionFixture};
+
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+    let (confirmed, rest) = match args.get(1).map(String::as_str) {
+        Some("--rpc") if args.len() >= 5 => (fetch_transaction(&args[2], &args[3])?, &args[4..]),
+        Some(input) if args.len() >= 3 && input != "--rpc" => (read_transaction(Path::new(input))?, &args[2..]),
+        _ => {
+            return Err(anyhow!(
+                "Usage: make_fixture <transaction.json> <fixture.json> [description]\n       make_fixture --rpc <url> <signature> <fixture.json> [description]"
+            ));
+        }
+    };
+    let output = Path::new(&rest[0]);
+    let description = rest.get(1).map(String::as_str).unwrap_or("");
+
+    let txn = subscribe_update_from_confirmed(&confirmed)?;
+    let fixture = TransactionFixture::record(description, &txn)?;
+    fixture.save(output)?;
+
+    println!(
+        "Wrote {} ({} trades, launch: {}, migration: {})",
+        output.display(),
+        fixture.expected_trades.len(),
+        fixture.expected_launch.is_some(),
+        fixture.expected_migration.is_some()
+    );
+    Ok(())
+}
+
+/// A saved `getTransaction` response, or just its `result`
+fn read_transaction(input: &Path) -> Result<EncodedConfirmedTransactionWithStatusMeta> {
+    let contents = std::fs::read_to_string(input)
+        .with_context(|| format!("Failed to read {}", input.display()))?;
+    let mut value: serde_json::Value = serde_json::from_str(&contents)?;
+    if let Some(result) = value.get_mut("result") {
+        value = result.take();
+    }
+    serde_json::from_value(value).context("Input is not a getTransaction result")
+}
+
+/// The confirmed transaction as the node returns it, including versioned transactions
+fn fetch_transaction(rpc_url: &str, signature: &str) -> Result<EncodedConfirmedTransactionWithStatusMeta> {
+    let signature = Signature::from_str(signature).context("Invalid signature")?;
+    RpcClient::new(rpc_url.to_string())
+        .get_transaction_with_config(&signature, RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(CommitmentConfig::confirmed()),
+            max_supported_transaction_version: Some(0),
+        })
+        .with_context(|| format!("Failed to fetch {} from {}", signature, rpc_url))
+}
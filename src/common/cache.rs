@@ -123,6 +123,12 @@ pub struct WalletTokenAccounts {
     accounts: RwLock<HashSet<Pubkey>>,
 }
 
+impl Default for WalletTokenAccounts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl WalletTokenAccounts {
     pub fn new() -> Self {
         Self {
@@ -161,9 +167,39 @@ impl WalletTokenAccounts {
     }
 }
 
+/// Owning token program of each mint seen so far; a mint never changes program
+pub struct MintTokenPrograms {
+    programs: RwLock<HashMap<Pubkey, Pubkey>>,
+}
+
+impl Default for MintTokenPrograms {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MintTokenPrograms {
+    pub fn new() -> Self {
+        Self {
+            programs: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn get(&self, mint: &Pubkey) -> Option<Pubkey> {
+        let programs = self.programs.read().unwrap();
+        programs.get(mint).copied()
+    }
+
+    pub fn insert(&self, mint: Pubkey, token_program: Pubkey) {
+        let mut programs = self.programs.write().unwrap();
+        programs.insert(mint, token_program);
+    }
+}
+
 // Global cache instances with reasonable TTL values
 lazy_static! {
     pub static ref TOKEN_ACCOUNT_CACHE: TokenAccountCache = TokenAccountCache::new(60); // 60 seconds TTL
     pub static ref TOKEN_MINT_CACHE: TokenMintCache = TokenMintCache::new(300); // 5 minutes TTL
     pub static ref WALLET_TOKEN_ACCOUNTS: WalletTokenAccounts = WalletTokenAccounts::new();
+    pub static ref MINT_TOKEN_PROGRAMS: MintTokenPrograms = MintTokenPrograms::new();
 } 
\ No newline at end of file
@@ -1,14 +1,14 @@
 use anyhow::Result;
-use bs58;
 use colored::Colorize;
 use dotenv::dotenv;
 use reqwest::Error;
 use serde::Deserialize;
-use anchor_client::solana_sdk::{commitment_config::CommitmentConfig, signature::Keypair, signer::Signer};
+use anchor_client::solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Keypair, signer::Signer};
 use tokio::sync::{Mutex, OnceCell};
-use tokio_tungstenite::tungstenite::http::request;
 use std::{env, sync::Arc};
 use crate::engine::swap::SwapProtocol;
+use crate::dex::venue::DexRegistry;
+use crate::dex::raydium_launchpad::{GlobalConfig, ShareFee, FEE_RATE_DENOMINATOR, RAYDIUM_GLOBAL_CONFIG};
 use crate::{
     common::{constants::INIT_MSG, logger::Logger},
     engine::swap::{SwapDirection, SwapInType},
@@ -18,16 +18,14 @@ use std::time::Duration;
 static GLOBAL_CONFIG: OnceCell<Mutex<Config>> = OnceCell::const_new();
 
 #[derive(Clone, Debug)]
+#[derive(Default)]
 pub enum TransactionLandingMode {
     Zeroslot,
+    #[default]
     Normal,
+    Jito, // Atomic bundle with a tip transaction, used for whale emergency sells
 }
 
-impl Default for TransactionLandingMode {
-    fn default() -> Self {
-        TransactionLandingMode::Normal
-    }
-}
 
 impl FromStr for TransactionLandingMode {
     type Err = String;
@@ -36,7 +34,8 @@ impl FromStr for TransactionLandingMode {
         match s {
             "0" | "zeroslot" => Ok(TransactionLandingMode::Zeroslot),
             "1" | "normal" => Ok(TransactionLandingMode::Normal),
-            _ => Err(format!("Invalid transaction landing mode: {}. Use 'zeroslot' or 'normal'", s)),
+            "2" | "jito" => Ok(TransactionLandingMode::Jito),
+            _ => Err(format!("Invalid transaction landing mode: {}. Use 'zeroslot', 'normal' or 'jito'", s)),
         }
     }
 }
@@ -53,7 +52,6 @@ pub struct Config {
     pub copy_selling_limit: f64, // Add this field
     pub selling_unit_price: u64,  // New: Priority fee for selling transactions
     pub selling_unit_limit: u32,  // New: Compute units for selling transactions
-    pub zero_slot_tip_value: f64, // New: Tip value for zeroslot selling
     // Sniper configuration
     pub focus_drop_threshold_pct: f64, // percentage drop from initial to flag "dropped"
     pub focus_trigger_sol: f64,        // SOL size to trigger buy after drop
@@ -83,7 +81,6 @@ impl Config {
             // Read selling configuration for front-running
             let selling_unit_price = import_env_var("SELLING_UNIT_PRICE").parse::<u64>().unwrap_or(4000000);
             let selling_unit_limit = import_env_var("SELLING_UNIT_LIMIT").parse::<u32>().unwrap_or(2000000);
-            let zero_slot_tip_value = import_env_var("ZERO_SLOT_TIP_VALUE").parse::<f64>().unwrap_or(0.0025);
             // Sniper thresholds
             let focus_drop_threshold_pct = import_env_var("FOCUS_DROP_THRESHOLD_PCT").parse::<f64>().unwrap_or(0.15);
             let focus_trigger_sol = import_env_var("FOCUS_TRIGGER_SOL").parse::<f64>().unwrap_or(1.0);
@@ -95,9 +92,18 @@ impl Config {
                 slippage_input
             };
             let solana_price = create_coingecko_proxy().await.unwrap_or(200_f64);
-            let rpc_client = create_rpc_client().unwrap();
+            let _rpc_client = create_rpc_client().unwrap();
             let rpc_nonblocking_client = create_nonblocking_rpc_client().await.unwrap();
             let zeroslot_rpc_client = create_zeroslot_rpc_client().await.unwrap();
+            let jito_client = create_jito_client().await.unwrap();
+            let relay_fanout = crate::services::relay_fanout::RelayFanout::from_env(
+                zeroslot_rpc_client.clone(),
+                jito_client.clone(),
+                rpc_nonblocking_client.clone(),
+            ).map(Arc::new);
+            if let Some(relay_fanout) = &relay_fanout {
+                logger.log(format!("[SELL FAN-OUT RELAYS]: {}", relay_fanout.relay_names().join(", ")).purple().to_string());
+            }
             let wallet: std::sync::Arc<anchor_client::solana_sdk::signature::Keypair> = import_wallet().unwrap();
             let balance = match rpc_nonblocking_client
                 .get_account(&wallet.pubkey())
@@ -111,7 +117,11 @@ impl Config {
 
             let wallet_cloned = wallet.clone();
             let swap_direction = SwapDirection::Buy; //SwapDirection::Sell
-            let in_type = SwapInType::Qty; //SwapInType::Pct
+            // Buys spend TOKEN_AMOUNT SOL, or receive exactly TOKEN_AMOUNT tokens with BUY_IN_TYPE=exact_out
+            let in_type = match env::var("BUY_IN_TYPE").ok().as_deref() {
+                Some("exact_out") => SwapInType::ExactOut,
+                _ => SwapInType::Qty,
+            };
             let amount_in = import_env_var("TOKEN_AMOUNT")
                 .parse::<f64>()
                 .unwrap_or(0.001_f64); //quantity
@@ -126,12 +136,32 @@ impl Config {
             };
 
             let rpc_client = create_rpc_client().unwrap();
+            let share_fee = import_share_fee(&rpc_client);
+            if let Some(share_fee) = &share_fee {
+                logger.log(format!(
+                    "[SHARE FEE]: {} / {} to {}",
+                    share_fee.rate,
+                    FEE_RATE_DENOMINATOR,
+                    share_fee.receiver,
+                ).purple().to_string());
+            }
+            let dex_registry = Arc::new(DexRegistry::new(
+                wallet.clone(),
+                rpc_client.clone(),
+                rpc_nonblocking_client.clone(),
+                share_fee,
+            ));
             let app_state = AppState {
                 rpc_client,
                 rpc_nonblocking_client,
                 zeroslot_rpc_client,
+                jito_client,
+                relay_fanout,
+                transaction_landing_mode: transaction_landing_mode.clone(),
                 wallet,
                 protocol_preference: SwapProtocol::default(),
+                dex_registry,
+                share_fee,
             };
            logger.log(
                     format!(
@@ -159,7 +189,6 @@ impl Config {
                 copy_selling_limit, // Set the field
                 selling_unit_price,
                 selling_unit_limit,
-                zero_slot_tip_value,
                 focus_drop_threshold_pct,
                 focus_trigger_sol,
             })
@@ -175,9 +204,9 @@ impl Config {
     }
 }
 
-//TODO: raydium launchpad
-pub const RAYDIUM_LAUNCHPAD_LOG_INSTRUCTION: &str = "MintTo";
-pub const RAYDIUM_LAUNCHPAD_PROGRAM_DATA_PREFIX: &str = "Program data: G3KpTd7rY3Y";
+// Raydium Launchpad log markers
+pub const RAYDIUM_LAUNCHPAD_LOG_INSTRUCTION: &str = "Initialize";
+pub const RAYDIUM_LAUNCHPAD_PROGRAM_DATA_PREFIX: &str = "Program data: l9fiCXahc6"; // PoolCreateEvent
 pub const RAYDIUM_LAUNCHPAD_BUY_LOG_INSTRUCTION: &str = "Buy";
 pub const RAYDIUM_LAUNCHPAD_BUY_OR_SELL_PROGRAM_DATA_PREFIX: &str = "Program data: vdt/007mYe";
 pub const RAYDIUM_LAUNCHPAD_SELL_LOG_INSTRUCTION: &str = "Sell";
@@ -229,8 +258,23 @@ pub struct AppState {
     pub rpc_client: Arc<anchor_client::solana_client::rpc_client::RpcClient>,
     pub rpc_nonblocking_client: Arc<anchor_client::solana_client::nonblocking::rpc_client::RpcClient>,
     pub zeroslot_rpc_client: Arc<crate::services::zeroslot::ZeroSlotClient>,
+    pub jito_client: Arc<crate::services::jito::JitoClient>,
+    pub relay_fanout: Option<Arc<crate::services::relay_fanout::RelayFanout>>, // Sells go to every relay when set
+    pub transaction_landing_mode: TransactionLandingMode, // Copy of the config's, for code that only sees the app state
     pub wallet: Arc<Keypair>,
     pub protocol_preference: SwapProtocol,
+    pub dex_registry: Arc<DexRegistry>,
+    pub share_fee: Option<ShareFee>, // Launchpad referral fee added to every swap
+}
+
+impl AppState {
+    /// Share fee paid on the quote side of each leg of a trade on `protocol`, as a fraction
+    pub fn share_fee_fraction(&self, protocol: &SwapProtocol) -> f64 {
+        match &self.share_fee {
+            Some(share_fee) if *protocol == SwapProtocol::RaydiumLaunchpad => share_fee.fraction(),
+            _ => 0.0,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -245,8 +289,8 @@ pub fn import_env_var(key: &str) -> String {
     match env::var(key){
         Ok(res) => res,
         Err(e) => {
-            println!("{}", format!("{}: {}", e, key).red().to_string());
-            loop{}
+            println!("{}", format!("{}: {}", e, key).red());
+            std::process::exit(1);
         }
     }
 }
@@ -289,6 +333,12 @@ pub async fn create_zeroslot_rpc_client() -> Result<Arc<crate::services::zeroslo
     Ok(Arc::new(client))
 }
 
+pub async fn create_jito_client() -> Result<Arc<crate::services::jito::JitoClient>> {
+    let client = crate::services::jito::JitoClient::new(
+        &crate::services::jito::get_block_engine_url()
+    );
+    Ok(Arc::new(client))
+}
 
 pub async fn create_coingecko_proxy() -> Result<f64, Error> {
 
@@ -302,11 +352,45 @@ pub async fn create_coingecko_proxy() -> Result<f64, Error> {
     Ok(sol_price)
 }
 
+/// Read the launchpad share fee from `SHARE_FEE_RATE` (over 1_000_000) and `SHARE_FEE_RECEIVER`
+/// (a wallet or a token account). Exits when the rate exceeds the platform maximum.
+pub fn import_share_fee(rpc_client: &anchor_client::solana_client::rpc_client::RpcClient) -> Option<ShareFee> {
+    let rate = env::var("SHARE_FEE_RATE").ok()?.parse::<u64>().unwrap_or(0);
+    if rate == 0 {
+        return None;
+    }
+    let receiver = match env::var("SHARE_FEE_RECEIVER").ok().and_then(|receiver| receiver.parse().ok()) {
+        Some(receiver) => receiver,
+        None => {
+            println!("{}", "SHARE_FEE_RECEIVER must be a valid address when SHARE_FEE_RATE is set".red());
+            std::process::exit(1);
+        }
+    };
+    // A token account receives the fee directly; a wallet (or an unfunded address) through its ATAs
+    let receiver_mint = rpc_client
+        .get_account(&receiver)
+        .ok()
+        .filter(|account| crate::core::token::is_token_program(&account.owner))
+        .and_then(|account| Pubkey::try_from(account.data.get(..32)?).ok());
+    let share_fee = ShareFee { rate, receiver, receiver_mint };
+
+    let validation = rpc_client
+        .get_account_data(&RAYDIUM_GLOBAL_CONFIG)
+        .map_err(anyhow::Error::from)
+        .and_then(|data| GlobalConfig::decode(&data))
+        .and_then(|global_config| share_fee.validate(&global_config));
+    if let Err(e) = validation {
+        println!("{}", format!("Invalid share fee configuration: {}", e).red());
+        std::process::exit(1);
+    }
+    Some(share_fee)
+}
+
 pub fn import_wallet() -> Result<Arc<Keypair>> {
     let priv_key = import_env_var("PRIVATE_KEY");
     if priv_key.len() < 85 {
-        println!("{}", format!("Please check wallet priv key: Invalid length => {}", priv_key.len()).red().to_string());
-        loop{}
+        println!("{}", format!("Please check wallet priv key: Invalid length => {}", priv_key.len()).red());
+        std::process::exit(1);
     }
     let wallet: Keypair = Keypair::from_base58_string(priv_key.as_str());
 
@@ -0,0 +1,116 @@
+use std::str::FromStr;
+use std::sync::RwLock;
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use anchor_client::solana_sdk::{
+    address_lookup_table::{self, state::AddressLookupTable, AddressLookupTableAccount},
+    compute_budget,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::dex::raydium_launchpad::{
+    ASSOCIATED_TOKEN_PROGRAM, EVENT_AUTHORITY, RAYDIUM_GLOBAL_CONFIG, RAYDIUM_LAUNCHPAD_AUTHORITY,
+    RAYDIUM_LAUNCHPAD_PROGRAM, RAYDIUM_PLATFORM_CONFIG, SOL_MINT, TOKEN_2022_PROGRAM, TOKEN_PROGRAM,
+};
+
+pub const MAX_EXTEND_ADDRESSES: usize = 20; // Addresses per extend instruction, so each fits in one transaction
+pub const MAX_LOOKUP_TABLE_ADDRESSES: usize = 256; // Lookup table program limit
+
+lazy_static! {
+    /// The bot's lookup table, once loaded from `LOOKUP_TABLE_ADDRESS`
+    static ref LOOKUP_TABLE: RwLock<Option<AddressLookupTableAccount>> = RwLock::new(None);
+}
+
+/// Accounts every launchpad swap references whatever the pool: worth a lookup table slot each.
+/// Programs a transaction invokes stay in its static keys; the table only saves them where they
+/// are passed as accounts.
+pub fn static_launchpad_accounts() -> Vec<Pubkey> {
+    vec![
+        RAYDIUM_LAUNCHPAD_AUTHORITY,
+        RAYDIUM_GLOBAL_CONFIG,
+        RAYDIUM_PLATFORM_CONFIG,
+        EVENT_AUTHORITY,
+        RAYDIUM_LAUNCHPAD_PROGRAM,
+        SOL_MINT,
+        TOKEN_PROGRAM,
+        TOKEN_2022_PROGRAM,
+        ASSOCIATED_TOKEN_PROGRAM,
+        system_program::id(),
+        compute_budget::id(),
+    ]
+}
+
+/// Lookup table address from `LOOKUP_TABLE_ADDRESS`, if set
+pub fn lookup_table_address() -> Result<Option<Pubkey>> {
+    match std::env::var("LOOKUP_TABLE_ADDRESS") {
+        Ok(address) if !address.trim().is_empty() => Pubkey::from_str(address.trim())
+            .map(Some)
+            .map_err(|e| anyhow!("Invalid LOOKUP_TABLE_ADDRESS {}: {}", address, e)),
+        _ => Ok(None),
+    }
+}
+
+/// Decode a lookup table account
+pub fn parse_lookup_table(key: Pubkey, data: &[u8]) -> Result<AddressLookupTableAccount> {
+    let table = AddressLookupTable::deserialize(data)
+        .map_err(|e| anyhow!("Invalid lookup table {}: {}", key, e))?;
+    Ok(AddressLookupTableAccount {
+        key,
+        addresses: table.addresses.to_vec(),
+    })
+}
+
+pub async fn fetch_lookup_table(rpc_client: &RpcClient, key: Pubkey) -> Result<AddressLookupTableAccount> {
+    let account = rpc_client
+        .get_account(&key)
+        .await
+        .map_err(|e| anyhow!("Failed to get lookup table {}: {}", key, e))?;
+    if account.owner != address_lookup_table::program::id() {
+        return Err(anyhow!("{} is not an address lookup table", key));
+    }
+    parse_lookup_table(key, &account.data)
+}
+
+/// Use `table` for every transaction built from now on
+pub fn set_lookup_table(table: AddressLookupTableAccount) {
+    if let Ok(mut lookup_table) = LOOKUP_TABLE.write() {
+        *lookup_table = Some(table);
+    }
+}
+
+/// Tables to compile v0 messages against: the bot's table when loaded, none otherwise
+pub fn lookup_tables() -> Vec<AddressLookupTableAccount> {
+    LOOKUP_TABLE
+        .read()
+        .ok()
+        .and_then(|lookup_table| lookup_table.clone())
+        .into_iter()
+        .collect()
+}
+
+/// Instructions creating a lookup table owned by `authority` and filling it with the static
+/// launchpad accounts, with the table's address. `recent_slot` must be a recent finalized slot.
+pub fn create_lookup_table_instructions(authority: Pubkey, recent_slot: u64) -> (Vec<Instruction>, Pubkey) {
+    let (create, table) = address_lookup_table::instruction::create_lookup_table(authority, authority, recent_slot);
+    let extend = extend_lookup_table_instruction(table, authority, &static_launchpad_accounts());
+    (vec![create, extend], table)
+}
+
+/// Instruction adding `addresses` to the table, at most `MAX_EXTEND_ADDRESSES` per transaction
+pub fn extend_lookup_table_instruction(table: Pubkey, authority: Pubkey, addresses: &[Pubkey]) -> Instruction {
+    address_lookup_table::instruction::extend_lookup_table(table, authority, Some(authority), addresses.to_vec())
+}
+
+/// `addresses` not already in `existing`, without duplicates, in order
+pub fn missing_addresses(existing: &[Pubkey], addresses: Vec<Pubkey>) -> Vec<Pubkey> {
+    let mut missing: Vec<Pubkey> = Vec::new();
+    for address in addresses {
+        if !existing.contains(&address) && !missing.contains(&address) {
+            missing.push(address);
+        }
+    }
+    missing
+}
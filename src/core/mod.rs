@@ -1,2 +1,3 @@
+pub mod lookup_table;
 pub mod token;
 pub mod tx;
@@ -11,8 +11,75 @@ use spl_token_client::{
 use std::sync::Arc;
 use anyhow::{Result, anyhow};
 use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_token_2022::extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions};
 
-use crate::common::cache::{TOKEN_ACCOUNT_CACHE, TOKEN_MINT_CACHE};
+use crate::common::cache::{MINT_TOKEN_PROGRAMS, TOKEN_ACCOUNT_CACHE, TOKEN_MINT_CACHE};
+
+/// True for the legacy token program and Token-2022
+pub fn is_token_program(program_id: &Pubkey) -> bool {
+    *program_id == spl_token::ID || *program_id == spl_token_2022::ID
+}
+
+/// Owning token program of a mint, fetched once and cached
+pub async fn get_mint_token_program(
+    client: Arc<anchor_client::solana_client::nonblocking::rpc_client::RpcClient>,
+    mint: &Pubkey,
+) -> Result<Pubkey> {
+    if let Some(token_program) = MINT_TOKEN_PROGRAMS.get(mint) {
+        return Ok(token_program);
+    }
+    let account = client.get_account(mint).await
+        .map_err(|e| anyhow!("Failed to fetch mint {}: {}", mint, e))?;
+    if !is_token_program(&account.owner) {
+        return Err(anyhow!("Mint {} is owned by {}, not a token program", mint, account.owner));
+    }
+    MINT_TOKEN_PROGRAMS.insert(*mint, account.owner);
+    Ok(account.owner)
+}
+
+/// Associated token address of `owner` for `mint` under the mint's owning program
+pub fn get_associated_token_address_for_program(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, mint, token_program)
+}
+
+/// Wallet ATA for a mint under its owning program, read from chain the first time the mint is seen
+pub async fn get_wallet_token_address(
+    client: Arc<anchor_client::solana_client::nonblocking::rpc_client::RpcClient>,
+    owner: &Pubkey,
+    mint: &Pubkey,
+) -> Result<Pubkey> {
+    let token_program = get_mint_token_program(client, mint).await?;
+    Ok(get_associated_token_address_with_program_id(owner, mint, &token_program))
+}
+
+/// Raw amount held by a token account. Both token programs share the base account layout,
+/// so this also reads Token-2022 accounts with extensions.
+pub fn token_account_amount(data: &[u8]) -> Option<u64> {
+    let bytes = data.get(64..72)?;
+    Some(u64::from_le_bytes(bytes.try_into().ok()?))
+}
+
+/// Token-2022 transfer fee withheld when moving `amount` of this mint. The epoch is not known
+/// here, so the larger of the scheduled fees is used.
+pub fn transfer_fee(mint: &StateWithExtensionsOwned<Mint>, amount: u64) -> u64 {
+    let Ok(config) = mint.get_extension::<TransferFeeConfig>() else {
+        return 0;
+    };
+    let older = config.older_transfer_fee.calculate_fee(amount).unwrap_or(0);
+    let newer = config.newer_transfer_fee.calculate_fee(amount).unwrap_or(0);
+    older.max(newer)
+}
+
+/// Amount that must be sent so that `received` arrives after the Token-2022 transfer fee
+pub fn amount_before_transfer_fee(mint: &StateWithExtensionsOwned<Mint>, received: u64) -> u64 {
+    let Ok(config) = mint.get_extension::<TransferFeeConfig>() else {
+        return received;
+    };
+    let older = config.older_transfer_fee.calculate_inverse_fee(received).unwrap_or(0);
+    let newer = config.newer_transfer_fee.calculate_inverse_fee(received).unwrap_or(0);
+    received.saturating_add(older.max(newer))
+}
 
 pub fn get_token_address(
     client: Arc<anchor_client::solana_client::nonblocking::rpc_client::RpcClient>,
@@ -25,7 +92,7 @@ pub fn get_token_address(
             client.clone(),
             ProgramRpcClientSendTransaction,
         )),
-        &spl_token::ID,
+        &MINT_TOKEN_PROGRAMS.get(address).unwrap_or(spl_token::ID),
         address,
         None,
         Arc::new(Keypair::from_bytes(&keypair.to_bytes()).expect("failed to copy keypair")),
@@ -60,7 +127,7 @@ pub async fn get_account_info(
             // ));
         })?;
 
-    if account_data.owner != spl_token::ID {
+    if !is_token_program(&account_data.owner) {
         return Err(TokenError::AccountInvalidOwner);
     }
     let account_info = StateWithExtensionsOwned::<Account>::unpack(account_data.data)?;
@@ -96,9 +163,10 @@ pub async fn get_mint_info(
         .ok_or(TokenError::AccountNotFound)
         .inspect_err(|err| println!("{} {}: mint {}", address, err, address))?;
 
-    if account.owner != spl_token::ID {
+    if !is_token_program(&account.owner) {
         return Err(TokenError::AccountInvalidOwner);
     }
+    MINT_TOKEN_PROGRAMS.insert(address, account.owner);
 
     let mint_result = StateWithExtensionsOwned::<Mint>::unpack(account.data).map_err(Into::into);
     let decimals: Option<u8> = None;
@@ -132,7 +200,7 @@ pub async fn account_exists(
             match response.value {
                 Some(acc) => {
                     // Check if the account is owned by the token program
-                    if acc.owner == spl_token::ID {
+                    if is_token_program(&acc.owner) {
                         // Try to parse the account to cache it for future use
                         if let Ok(token_account) = StateWithExtensionsOwned::<Account>::unpack(acc.data.clone()) {
                             TOKEN_ACCOUNT_CACHE.insert(*account, token_account, None);
@@ -195,7 +263,7 @@ pub async fn get_multiple_token_accounts(
         
         for (i, maybe_account) in fetched_accounts.iter().enumerate() {
             if let Some(account_data) = maybe_account {
-                if account_data.owner == spl_token::ID {
+                if is_token_program(&account_data.owner) {
                     if let Ok(token_account) = StateWithExtensionsOwned::<Account>::unpack(account_data.data.clone()) {
                         // Cache the account
                         TOKEN_ACCOUNT_CACHE.insert(accounts_to_fetch[i], token_account.clone(), None);
@@ -242,17 +310,15 @@ pub fn create_wsol_account_with_amount(
 pub fn create_wsol_account(
     owner: Pubkey,
 ) -> Result<(Pubkey, Vec<Instruction>), anyhow::Error> {
-    let mut instructions = Vec::new();
-    
     // Create the associated token account for WSOL
-    instructions.push(
+    let instructions = vec![
         create_associated_token_account_idempotent(
             &owner,
             &owner,
             &spl_token::native_mint::id(),
             &spl_token::ID,
         )
-    );
+    ];
     
     // Get the WSOL ATA address using the SPL token function directly
     let wsol_account = spl_associated_token_account::get_associated_token_address(
@@ -263,16 +329,16 @@ pub fn create_wsol_account(
     Ok((wsol_account, instructions))
 }
 
-/// Close a token account
+/// Close a token account owned by `token_program`
 pub fn close_account(
-    _owner: Pubkey,
+    token_program: &Pubkey,
     token_account: Pubkey,
     destination: Pubkey,
     authority: Pubkey,
     signers: &[&Pubkey],
 ) -> Result<Instruction, anyhow::Error> {
-    Ok(spl_token::instruction::close_account(
-        &spl_token::id(),
+    Ok(spl_token_2022::instruction::close_account(
+        token_program,
         &token_account,
         &destination,
         &authority,
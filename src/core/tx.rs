@@ -2,43 +2,27 @@ use std::sync::Arc;
 use std::str::FromStr;
 use anyhow::{Result, anyhow};
 use colored::Colorize;
-use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
 use anchor_client::solana_sdk::{
     instruction::Instruction,
+    message::{v0, VersionedMessage},
     signature::Keypair,
     system_instruction,
-    transaction::Transaction,
+    transaction::VersionedTransaction,
 };
 use std::env;
-use anchor_client::solana_sdk::pubkey::Pubkey;
 use spl_token::ui_amount_to_amount;
 use solana_sdk::signature::Signer;
-use tokio::time::{Instant, sleep};
-use tokio::sync::Mutex;
-use once_cell::sync::Lazy;
-use reqwest::{Client, ClientBuilder};
-use base64;
-use bs58;
-use std::time::Duration;
+use tokio::time::Instant;
 use crate::{
     common::{
         logger::Logger,
         config::TransactionLandingMode,
     },
-    services::{
-        zeroslot::{self, ZeroSlotClient},
-    },
+    core::lookup_table,
+    services::{jito, priority_fees::{self, FeeIntent}, relay_fanout::RelayFanout, zeroslot::{self}, zeroslot_tips},
 };
-use dotenv::dotenv;
-
-// prioritization fee = UNIT_PRICE * UNIT_LIMIT
-fn get_unit_price() -> u64 {
-    env::var("UNIT_PRICE")
-        .ok()
-        .and_then(|v| u64::from_str(&v).ok())
-        .unwrap_or(20000)
-}
 
+// prioritization fee = unit price * UNIT_LIMIT; the unit price comes from `priority_fees` per intent
 fn get_unit_limit() -> u32 {
     env::var("UNIT_LIMIT")
         .ok()
@@ -46,123 +30,61 @@ fn get_unit_limit() -> u32 {
         .unwrap_or(200_000)
 }
 
-
-// Cache the FlashBlock API key
-static FLASHBLOCK_API_KEY: Lazy<String> = Lazy::new(|| {
-    std::env::var("FLASHBLOCK_API_KEY")
-        .ok()
-        .unwrap_or_else(|| "da07907679634859".to_string())
-});
-
-// Create a static HTTP client with optimized configuration for FlashBlock API
-static HTTP_CLIENT: Lazy<Client> = Lazy::new(|| {
-   let client = reqwest::Client::new();
-   client
-});
-
-pub async fn new_signed_and_send_zeroslot(
-    zeroslot_rpc_client: Arc<crate::services::zeroslot::ZeroSlotClient>,
-    recent_blockhash: solana_sdk::hash::Hash,
+/// Sign `instructions` as a v0 transaction paid by `keypair`, loading the static launchpad
+/// accounts from the bot's lookup table when one is loaded
+pub fn new_signed_versioned_transaction(
     keypair: &Keypair,
-    mut instructions: Vec<Instruction>,
-    logger: &Logger,
-) -> Result<Vec<String>> {
-    let tip_account = zeroslot::get_tip_account()?;
-    let start_time = Instant::now();
-    let mut txs: Vec<String> = vec![];
-    
-    // zeroslot tip, the upper limit is 0.1
-    let tip = zeroslot::get_tip_value().await?;
-    let tip_lamports = ui_amount_to_amount(tip, spl_token::native_mint::DECIMALS);
-
-    let zeroslot_tip_instruction = 
-        system_instruction::transfer(&keypair.pubkey(), &tip_account, tip_lamports);
-        
-        let unit_limit = get_unit_limit(); // TODO: update in mev boost
-        let unit_price = get_unit_price(); // TODO: update in mev boost
-        let modify_compute_units =
-        solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_limit(unit_limit);
-        let add_priority_fee =
-        solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_price(unit_price);
-        instructions.insert(1, modify_compute_units);
-        instructions.insert(2, add_priority_fee);
-        
-        instructions.push(zeroslot_tip_instruction); // zeroslot is different with others.
-    // send init tx
-    let txn = Transaction::new_signed_with_payer(
-        &instructions,
-        Some(&keypair.pubkey()),
-        &vec![keypair],
+    instructions: &[Instruction],
+    recent_blockhash: solana_sdk::hash::Hash,
+) -> Result<VersionedTransaction> {
+    let message = v0::Message::try_compile(
+        &keypair.pubkey(),
+        instructions,
+        &lookup_table::lookup_tables(),
         recent_blockhash,
-    );
-
-    let tx_result = zeroslot_rpc_client.send_transaction(&txn).await;
-    
-    match tx_result {
-        Ok(signature) => {
-            txs.push(signature.to_string());
-            logger.log(
-                format!("[TXN-ELAPSED(ZEROSLOT)]: {:?}", start_time.elapsed())
-                    .yellow()
-                    .to_string(),
-            );
-        }
-        Err(_) => {
-            // Convert the error to a Send-compatible form
-            return Err(anyhow::anyhow!("zeroslot send_transaction failed"));
-        }
-    };
-
-    Ok(txs)
+    )
+    .map_err(|e| anyhow!("Failed to compile v0 message: {}", e))?;
+    VersionedTransaction::try_new(VersionedMessage::V0(message), &[keypair])
+        .map_err(|e| anyhow!("Failed to sign transaction: {}", e))
 }
 
-
-pub async fn new_signed_and_send_zeroslot_fast(
-    compute_unit_limit: u32,
-    compute_unit_price: u64,
-    tip_lamports: u64,
+pub async fn new_signed_and_send_zeroslot(
     zeroslot_rpc_client: Arc<crate::services::zeroslot::ZeroSlotClient>,
     recent_blockhash: solana_sdk::hash::Hash,
     keypair: &Keypair,
     mut instructions: Vec<Instruction>,
     logger: &Logger,
+    intent: FeeIntent,
 ) -> Result<Vec<String>> {
     let tip_account = zeroslot::get_tip_account()?;
     let start_time = Instant::now();
     let mut txs: Vec<String> = vec![];
     
-    // zeroslot tip, the upper limit is 0.1
-    let tip = zeroslot::get_tip_value().await?;
-    let tip_lamports = ui_amount_to_amount(tip, spl_token::native_mint::DECIMALS);
+    // zeroslot tip, adapted to the intent's urgency and what recently landed
+    let tip = zeroslot_tips::choose_tip(intent);
 
-    let zeroslot_tip_instruction = 
-        system_instruction::transfer(&keypair.pubkey(), &tip_account, tip_lamports);
-        
-        let unit_limit = get_unit_limit(); // TODO: update in mev boost
-        let unit_price = get_unit_price(); // TODO: update in mev boost
-        let modify_compute_units =
-        solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_limit(unit_limit);
-        let add_priority_fee =
-        solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_price(unit_price);
-        instructions.insert(1, modify_compute_units);
-        instructions.insert(2, add_priority_fee);
-        
-        instructions.push(zeroslot_tip_instruction); // zeroslot is different with others.
+    let zeroslot_tip_instruction =
+        system_instruction::transfer(&keypair.pubkey(), &tip_account, tip.lamports);
+
+    let modify_compute_units =
+        solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_limit(get_unit_limit());
+    let add_priority_fee =
+        solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_price(priority_fees::unit_price(intent));
+    instructions.insert(0, modify_compute_units);
+    instructions.insert(1, add_priority_fee);
+
+    instructions.push(zeroslot_tip_instruction); // zeroslot is different with others.
     // send init tx
-    let txn = Transaction::new_signed_with_payer(
-        &instructions,
-        Some(&keypair.pubkey()),
-        &vec![keypair],
-        recent_blockhash,
-    );
+    let txn = new_signed_versioned_transaction(keypair, &instructions, recent_blockhash)?;
 
     let tx_result = zeroslot_rpc_client.send_transaction(&txn).await;
     
     match tx_result {
         Ok(signature) => {
+            zeroslot_tips::track_sent(signature, tip.level);
             txs.push(signature.to_string());
             logger.log(
-                format!("[TXN-ELAPSED(ZEROSLOT)]: {:?}", start_time.elapsed())
+                format!("[TXN-ELAPSED(ZEROSLOT)]: {:?} (tip {} lamports)", start_time.elapsed(), tip.lamports)
                     .yellow()
                     .to_string(),
             );
@@ -176,33 +98,27 @@ pub async fn new_signed_and_send_zeroslot_fast(
     Ok(txs)
 }
 
-/// Send transaction using normal RPC without any service or tips
+/// Send transaction using normal RPC without any service or tips, at the intent's priority fee
 pub async fn new_signed_and_send_normal(
     rpc_client: Arc<anchor_client::solana_client::nonblocking::rpc_client::RpcClient>,
     recent_blockhash: anchor_client::solana_sdk::hash::Hash,
     keypair: &Keypair,
     mut instructions: Vec<Instruction>,
     logger: &Logger,
+    intent: FeeIntent,
 ) -> Result<Vec<String>> {
     let start_time = Instant::now();
     
     // Add compute budget instructions for priority fee
-    // let unit_limit = 200000;
-    // let unit_price = 20000;
-    // let modify_compute_units =
-    //     solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_limit(unit_limit);
-    // let add_priority_fee =
-    //     solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_price(unit_price);
-    // instructions.insert(0, modify_compute_units);
-    // instructions.insert(1, add_priority_fee);
+    let modify_compute_units =
+        solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_limit(get_unit_limit());
+    let add_priority_fee =
+        solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_price(priority_fees::unit_price(intent));
+    instructions.insert(0, modify_compute_units);
+    instructions.insert(1, add_priority_fee);
     
     // Create and send transaction
-    let txn = Transaction::new_signed_with_payer(
-        &instructions,
-        Some(&keypair.pubkey()),
-        &vec![keypair],
-        recent_blockhash,
-    );
+    let txn = new_signed_versioned_transaction(keypair, &instructions, recent_blockhash)?;
 
     match rpc_client.send_transaction(&txn).await {
         Ok(signature) => {
@@ -217,6 +133,91 @@ pub async fn new_signed_and_send_normal(
     }
 }
 
+/// Send the swap as a Jito bundle followed by a tip transfer to a random tip account, and wait
+/// for the bundle to land. The bundle is atomic: a reverted swap pays no tip.
+pub async fn new_signed_and_send_jito(
+    jito_client: Arc<jito::JitoClient>,
+    recent_blockhash: solana_sdk::hash::Hash,
+    keypair: &Keypair,
+    mut instructions: Vec<Instruction>,
+    logger: &Logger,
+    intent: FeeIntent,
+) -> Result<Vec<String>> {
+    let tip_account = jito::get_tip_account()?;
+    let start_time = Instant::now();
+
+    let tip_lamports = ui_amount_to_amount(jito::get_tip_value(), spl_token::native_mint::DECIMALS);
+    let jito_tip_instruction =
+        system_instruction::transfer(&keypair.pubkey(), &tip_account, tip_lamports);
+
+    let modify_compute_units =
+        solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_limit(get_unit_limit());
+    let add_priority_fee =
+        solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_price(priority_fees::unit_price(intent));
+    instructions.insert(0, modify_compute_units);
+    instructions.insert(1, add_priority_fee);
+
+    let swap_txn = new_signed_versioned_transaction(keypair, &instructions, recent_blockhash)?;
+    let tip_txn = new_signed_versioned_transaction(keypair, &[jito_tip_instruction], recent_blockhash)?;
+
+    let bundle_id = jito_client
+        .send_bundle(&[swap_txn.clone(), tip_txn])
+        .await
+        .map_err(|e| anyhow!("jito sendBundle failed: {}", e))?;
+    logger.log(format!("Jito bundle {} submitted with a {} lamport tip", bundle_id, tip_lamports));
+
+    let landed_slot = jito_client
+        .wait_for_bundle(&bundle_id, jito::BUNDLE_STATUS_TIMEOUT)
+        .await
+        .map_err(|e| anyhow!("jito bundle {} did not land: {}", bundle_id, e))?;
+    logger.log(
+        format!("[TXN-ELAPSED(JITO)]: {:?} (landed in slot {})", start_time.elapsed(), landed_slot)
+            .yellow()
+            .to_string(),
+    );
+
+    Ok(vec![swap_txn.signatures[0].to_string()])
+}
+
+/// Send one signed transaction through every fan-out relay at once. It carries the zeroslot and
+/// Jito tips when those relays are configured, since each only accepts transactions tipping it.
+pub async fn new_signed_and_send_fanout(
+    relay_fanout: &RelayFanout,
+    recent_blockhash: solana_sdk::hash::Hash,
+    keypair: &Keypair,
+    mut instructions: Vec<Instruction>,
+    logger: &Logger,
+    intent: FeeIntent,
+) -> Result<Vec<String>> {
+    let modify_compute_units =
+        solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_limit(get_unit_limit());
+    let add_priority_fee =
+        solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_price(priority_fees::unit_price(intent));
+    instructions.insert(0, modify_compute_units);
+    instructions.insert(1, add_priority_fee);
+
+    let zeroslot_tip = if relay_fanout.needs_zeroslot_tip() {
+        let tip = zeroslot_tips::choose_tip(intent);
+        instructions.push(system_instruction::transfer(&keypair.pubkey(), &zeroslot::get_tip_account()?, tip.lamports));
+        Some(tip)
+    } else {
+        None
+    };
+    if relay_fanout.needs_jito_tip() {
+        let tip_lamports = ui_amount_to_amount(jito::get_tip_value(), spl_token::native_mint::DECIMALS);
+        instructions.push(system_instruction::transfer(&keypair.pubkey(), &jito::get_tip_account()?, tip_lamports));
+    }
+
+    let txn = new_signed_versioned_transaction(keypair, &instructions, recent_blockhash)?;
+
+    let (signature, relay) = relay_fanout.send(&txn, logger).await?;
+    if let Some(tip) = zeroslot_tip {
+        zeroslot_tips::track_sent(signature, tip.level);
+    }
+    logger.log(format!("Fan-out transaction {} accepted first by {}", signature, relay).green().to_string());
+    Ok(vec![signature.to_string()])
+}
+
 /// Universal transaction landing function that routes to the appropriate service
 pub async fn new_signed_and_send_with_landing_mode(
     transaction_landing_mode: TransactionLandingMode,
@@ -225,6 +226,7 @@ pub async fn new_signed_and_send_with_landing_mode(
     keypair: &Keypair,
     instructions: Vec<Instruction>,
     logger: &Logger,
+    intent: FeeIntent,
 ) -> Result<Vec<String>> {
     // Route to the appropriate service
     match transaction_landing_mode {
@@ -236,6 +238,7 @@ pub async fn new_signed_and_send_with_landing_mode(
                 keypair,
                 instructions,
                 logger,
+                intent,
             ).await
         },
         TransactionLandingMode::Normal => {
@@ -246,6 +249,18 @@ pub async fn new_signed_and_send_with_landing_mode(
                 keypair,
                 instructions,
                 logger,
+                intent,
+            ).await
+        },
+        TransactionLandingMode::Jito => {
+            logger.log("Using Jito bundle for transaction landing".green().to_string());
+            new_signed_and_send_jito(
+                app_state.jito_client.clone(),
+                recent_blockhash,
+                keypair,
+                instructions,
+                logger,
+                intent,
             ).await
         },
     }
@@ -0,0 +1,44 @@
+//! Constant-product (x * y = k) math shared by the post-migration AMM venues.
+//!
+//! Amounts are raw units. Outputs round down and required inputs round up, matching the
+//! on-chain programs, so a quote never promises more than the pool will pay.
+
+/// Output of swapping `amount_in` into a pool holding `reserve_in` / `reserve_out`
+pub fn amount_out(amount_in: u64, reserve_in: u64, reserve_out: u64) -> u64 {
+    let denominator = reserve_in as u128 + amount_in as u128;
+    if amount_in == 0 || denominator == 0 {
+        return 0;
+    }
+    (reserve_out as u128 * amount_in as u128 / denominator) as u64
+}
+
+/// Input needed to take exactly `amount_out` out of the pool; `None` when the pool cannot fill it
+pub fn amount_in(amount_out: u64, reserve_in: u64, reserve_out: u64) -> Option<u64> {
+    if amount_out == 0 || amount_out >= reserve_out {
+        return None;
+    }
+    let numerator = reserve_in as u128 * amount_out as u128;
+    u64::try_from(numerator.div_ceil((reserve_out - amount_out) as u128)).ok()
+}
+
+/// Fee of `rate / denominator` on `amount`, rounded up
+pub fn fee(amount: u64, rate: u64, denominator: u64) -> u64 {
+    if denominator == 0 {
+        return 0;
+    }
+    (amount as u128 * rate as u128).div_ceil(denominator as u128) as u64
+}
+
+/// Smallest amount that still leaves `net_amount` once a fee of `rate / denominator` is taken
+pub fn gross_up(net_amount: u64, rate: u64, denominator: u64) -> Option<u64> {
+    if rate >= denominator {
+        return None;
+    }
+    let estimate = (net_amount as u128 * denominator as u128).div_ceil((denominator - rate) as u128);
+    let mut gross = u64::try_from(estimate).ok()?;
+    // Per-amount rounding of the fee can leave the estimate a unit short
+    while gross.saturating_sub(fee(gross, rate, denominator)) < net_amount {
+        gross = gross.checked_add(1)?;
+    }
+    Some(gross)
+}
@@ -1 +1,9 @@
+pub mod constant_product;
+pub mod launchpad_curve;
+pub mod pool_index;
+pub mod pump_fun;
+pub mod pump_swap;
+pub mod quote_mint;
+pub mod raydium_cpmm;
 pub mod raydium_launchpad;
+pub mod venue;
@@ -0,0 +1,283 @@
+//! Mint → launchpad pool index.
+//!
+//! Pool ids and vaults are learned from the pool creation and trade events the bot already
+//! parses, and persisted to a JSON snapshot so a restart starts warm. Swap building looks
+//! pools up here before falling back to RPC.
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use anyhow::{Context, Result};
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::dex::raydium_launchpad::{RaydiumPool, RAYDIUM_LAUNCHPAD_PROGRAM};
+use crate::engine::transaction_parser::{DexType, LaunchEvent, TradeInfoFromToken};
+
+pub const POOL_SEED: &[u8] = b"pool";
+pub const POOL_VAULT_SEED: &[u8] = b"pool_vault";
+pub const DEFAULT_POOL_INDEX_PATH: &str = "pool_index.json";
+pub const MAX_POOL_INDEX_ENTRIES: usize = 100_000; // Least recently seen pools are dropped from the snapshot beyond this
+
+lazy_static! {
+    pub static ref POOL_INDEX: Arc<PoolIndex> = Arc::new(PoolIndex::new());
+}
+
+/// Snapshot file location, from `POOL_INDEX_PATH` or the working directory
+pub fn pool_index_path() -> PathBuf {
+    std::env::var("POOL_INDEX_PATH")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(DEFAULT_POOL_INDEX_PATH))
+}
+
+/// Launchpad pool PDA for a mint pair
+pub fn derive_pool_id(base_mint: &Pubkey, quote_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[POOL_SEED, base_mint.as_ref(), quote_mint.as_ref()],
+        &RAYDIUM_LAUNCHPAD_PROGRAM,
+    ).0
+}
+
+/// Launchpad vault PDA holding `mint` for a pool
+pub fn derive_pool_vault(pool_id: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[POOL_VAULT_SEED, pool_id.as_ref(), mint.as_ref()],
+        &RAYDIUM_LAUNCHPAD_PROGRAM,
+    ).0
+}
+
+/// One indexed pool; keys are base58 strings so the snapshot stays readable
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PoolIndexEntry {
+    pub pool_id: String,
+    pub base_mint: String,
+    pub quote_mint: String,
+    pub base_vault: String,
+    pub quote_vault: String,
+    pub slot: u64, // Slot the pool was last traded or looked up at
+}
+
+impl PoolIndexEntry {
+    /// Entry with vaults derived from the pool id
+    pub fn derived(pool_id: &Pubkey, base_mint: &Pubkey, quote_mint: &Pubkey, slot: u64) -> Self {
+        Self {
+            pool_id: pool_id.to_string(),
+            base_mint: base_mint.to_string(),
+            quote_mint: quote_mint.to_string(),
+            base_vault: derive_pool_vault(pool_id, base_mint).to_string(),
+            quote_vault: derive_pool_vault(pool_id, quote_mint).to_string(),
+            slot,
+        }
+    }
+
+    pub fn to_raydium_pool(&self) -> Option<RaydiumPool> {
+        Some(RaydiumPool {
+            pool_id: Pubkey::from_str(&self.pool_id).ok()?,
+            base_mint: Pubkey::from_str(&self.base_mint).ok()?,
+            quote_mint: Pubkey::from_str(&self.quote_mint).ok()?,
+            pool_base_account: Pubkey::from_str(&self.base_vault).ok()?,
+            pool_quote_account: Pubkey::from_str(&self.quote_vault).ok()?,
+        })
+    }
+}
+
+/// In-memory index keyed by base mint, with a dirty flag for snapshotting
+pub struct PoolIndex {
+    entries: DashMap<String, PoolIndexEntry>,
+    mints_by_pool: DashMap<String, String>, // Pool id → base mint
+    dirty: AtomicBool,
+    latest_slot: AtomicU64, // Highest slot seen in a trade or launch, stamped on lookups
+}
+
+impl PoolIndex {
+    pub fn new() -> Self {
+        Self {
+            entries: DashMap::new(),
+            mints_by_pool: DashMap::new(),
+            dirty: AtomicBool::new(false),
+            latest_slot: AtomicU64::new(0),
+        }
+    }
+
+    /// Highest slot seen so far, for entries resolved without a trade
+    pub fn latest_slot(&self) -> u64 {
+        self.latest_slot.load(Ordering::Relaxed)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Look a pool up, marking it as seen at the latest slot so pools in use are not evicted
+    pub fn get(&self, mint: &str) -> Option<RaydiumPool> {
+        let pool = self.entries.get(mint).and_then(|entry| entry.to_raydium_pool())?;
+        self.touch(mint, self.latest_slot());
+        Some(pool)
+    }
+
+    /// Look a pool up by its id, for events that name only the pool
+    pub fn get_by_pool_id(&self, pool_id: &str) -> Option<RaydiumPool> {
+        let mint = self.mints_by_pool.get(pool_id)?.clone();
+        self.get(&mint).filter(|pool| pool.pool_id.to_string() == pool_id)
+    }
+
+    /// Move an entry's last seen slot forward
+    pub fn touch(&self, mint: &str, slot: u64) {
+        if let Some(mut entry) = self.entries.get_mut(mint) {
+            if slot > entry.slot {
+                entry.slot = slot;
+                self.dirty.store(true, Ordering::Relaxed);
+            }
+        }
+    }
+
+    pub fn insert(&self, entry: PoolIndexEntry) {
+        self.latest_slot.fetch_max(entry.slot, Ordering::Relaxed);
+        let changed = match self.entries.get(&entry.base_mint) {
+            Some(existing) => existing.pool_id != entry.pool_id
+                || existing.base_vault != entry.base_vault
+                || existing.quote_vault != entry.quote_vault,
+            None => true,
+        };
+        if changed {
+            self.mints_by_pool.insert(entry.pool_id.clone(), entry.base_mint.clone());
+            self.entries.insert(entry.base_mint.clone(), entry);
+            self.dirty.store(true, Ordering::Relaxed);
+        } else {
+            self.touch(&entry.base_mint, entry.slot);
+        }
+    }
+
+    /// Index a pool from its creation event
+    pub fn record_launch(&self, launch: &LaunchEvent) {
+        let (Ok(pool_id), Ok(base_mint), Ok(quote_mint)) = (
+            Pubkey::from_str(&launch.pool_id),
+            Pubkey::from_str(&launch.base_mint),
+            Pubkey::from_str(&launch.quote_mint),
+        ) else {
+            return;
+        };
+        self.insert(PoolIndexEntry::derived(&pool_id, &base_mint, &quote_mint, launch.slot));
+    }
+
+    /// Index a pool from a launchpad trade; vaults come from the swap accounts when they were resolved
+    pub fn record_trade(&self, trade_info: &TradeInfoFromToken) {
+        if trade_info.dex_type != DexType::RaydiumLaunchpad {
+            return;
+        }
+        self.latest_slot.fetch_max(trade_info.slot, Ordering::Relaxed);
+        if self.entries.get(&trade_info.mint).is_some_and(|entry| entry.pool_id == trade_info.pool_id) {
+            self.touch(&trade_info.mint, trade_info.slot);
+            return;
+        }
+        let (Ok(pool_id), Ok(base_mint)) = (
+            Pubkey::from_str(&trade_info.pool_id),
+            Pubkey::from_str(&trade_info.mint),
+        ) else {
+            return;
+        };
+        let mut entry = PoolIndexEntry::derived(&pool_id, &base_mint, &trade_info.quote_mint_pubkey(), trade_info.slot);
+        if !trade_info.base_vault.is_empty() && !trade_info.quote_vault.is_empty() {
+            entry.base_vault = trade_info.base_vault.clone();
+            entry.quote_vault = trade_info.quote_vault.clone();
+        }
+        self.insert(entry);
+    }
+
+    /// Load a snapshot written by `save_snapshot`; a missing file is an empty index
+    pub fn load_snapshot(&self, path: &Path) -> Result<usize> {
+        if !path.exists() {
+            return Ok(0);
+        }
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read pool index {}", path.display()))?;
+        let entries: Vec<PoolIndexEntry> = serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse pool index {}", path.display()))?;
+        let count = entries.len();
+        for entry in entries {
+            self.mints_by_pool.entry(entry.pool_id.clone()).or_insert_with(|| entry.base_mint.clone());
+            self.entries.entry(entry.base_mint.clone()).or_insert(entry);
+        }
+        Ok(count)
+    }
+
+    /// Write the index to disk if it changed since the last save. The
+    /// `MAX_POOL_INDEX_ENTRIES` most recently traded or looked up pools are kept.
+    pub fn save_snapshot(&self, path: &Path) -> Result<bool> {
+        if !self.dirty.swap(false, Ordering::Relaxed) {
+            return Ok(false);
+        }
+        let mut entries: Vec<PoolIndexEntry> = self.entries.iter().map(|entry| entry.value().clone()).collect();
+        entries.sort_by(|a, b| b.slot.cmp(&a.slot).then_with(|| a.base_mint.cmp(&b.base_mint)));
+        if entries.len() > MAX_POOL_INDEX_ENTRIES {
+            for evicted in entries.drain(MAX_POOL_INDEX_ENTRIES..) {
+                self.entries.remove(&evicted.base_mint);
+                self.mints_by_pool.remove(&evicted.pool_id);
+            }
+        }
+
+        // Write to a temporary file first so a crash never leaves a truncated snapshot
+        let tmp_path = path.with_extension("json.tmp");
+        let result = serde_json::to_string(&entries)
+            .map_err(anyhow::Error::from)
+            .and_then(|json| std::fs::write(&tmp_path, json).map_err(anyhow::Error::from))
+            .and_then(|_| std::fs::rename(&tmp_path, path).map_err(anyhow::Error::from))
+            .with_context(|| format!("Failed to write pool index {}", path.display()));
+        if result.is_err() {
+            self.dirty.store(true, Ordering::Relaxed);
+        }
+        result.map(|_| true)
+    }
+}
+
+impl Default for PoolIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(slot: u64) -> PoolIndexEntry {
+        let base_mint = Pubkey::new_unique();
+        let quote_mint = Pubkey::new_unique();
+        PoolIndexEntry::derived(&derive_pool_id(&base_mint, &quote_mint), &base_mint, &quote_mint, slot)
+    }
+
+    #[test]
+    fn trades_and_lookups_refresh_the_last_seen_slot() {
+        let index = PoolIndex::new();
+        let traded = entry(0);
+        index.insert(traded.clone());
+        let trade = TradeInfoFromToken {
+            dex_type: DexType::RaydiumLaunchpad,
+            slot: 500,
+            pool_id: traded.pool_id.clone(),
+            mint: traded.base_mint.clone(),
+            quote_mint: traded.quote_mint.clone(),
+            ..Default::default()
+        };
+        index.dirty.store(false, Ordering::Relaxed);
+        index.record_trade(&trade);
+        assert_eq!(index.entries.get(&traded.base_mint).unwrap().slot, 500);
+        assert!(index.dirty.load(Ordering::Relaxed));
+
+        // A lookup stamps the latest slot seen, and re-inserting an older entry keeps it
+        let looked_up = entry(100);
+        index.insert(looked_up.clone());
+        index.record_trade(&TradeInfoFromToken { slot: 900, ..trade });
+        assert!(index.get(&looked_up.base_mint).is_some());
+        assert_eq!(index.entries.get(&looked_up.base_mint).unwrap().slot, 900);
+        index.insert(looked_up.clone());
+        assert_eq!(index.entries.get(&looked_up.base_mint).unwrap().slot, 900);
+    }
+}
@@ -0,0 +1,997 @@
+//! Pump.fun bonding-curve venue.
+//!
+//! Decodes the `BondingCurve` account and the program's `TradeEvent`, and builds `buy` / `sell`
+//! instructions priced against the curve's virtual reserves. Bonding-curve tokens trade against
+//! native SOL, so unlike the launchpad no wrapped SOL account is involved.
+
+use std::{str::FromStr, sync::Arc};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use colored::Colorize;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use spl_token::ui_amount_to_amount;
+use yellowstone_grpc_proto::geyser::SubscribeUpdateTransaction;
+
+use crate::common::{cache::WALLET_TOKEN_ACCOUNTS, config::SwapConfig, logger::Logger};
+use crate::core::token;
+use crate::dex::pump_swap;
+use crate::dex::raydium_launchpad::{apply_max_slippage, apply_slippage, SOL_MINT};
+use crate::dex::venue::{Dex, PoolKeys, SwapInstructions, SwapQuote};
+use crate::engine::monitor::BondingCurveInfo;
+use crate::engine::swap::{SwapDirection, SwapInType, SwapProtocol};
+use crate::engine::transaction_parser::{
+    self, calculate_price, DexType, MigrationEvent, MigrationVenue, TradeInfoFromToken, LAUNCHPAD_TOKEN_DECIMALS,
+    TRADE_EVENT_DISCRIMINATOR,
+};
+use crate::services::priority_fees;
+
+pub const PUMP_FUN_PROGRAM: Pubkey = solana_sdk::pubkey!("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P");
+pub const PUMP_FUN_GLOBAL: Pubkey = solana_sdk::pubkey!("4wTV1YmiEkRvAtNtsSGPtUrqRYQMe5SKy2uB4Jjaxnjf");
+pub const PUMP_FUN_FEE_RECIPIENT: Pubkey = solana_sdk::pubkey!("CebN5WGQ4jvEPvsVU4EoHEpgzq1VV7AbicfhtW4xC9iM");
+pub const PUMP_FUN_EVENT_AUTHORITY: Pubkey = solana_sdk::pubkey!("Ce6TQqeHC9p8KetsN6JsjHK7UTZk7nasjjnr7XxXp9F1");
+pub const PUMP_FEE_PROGRAM: Pubkey = solana_sdk::pubkey!("pfeeUxB6jkeY1Hxd7CsFCAjcbHA9rWtchMGdZ6VojVZ");
+pub const BUY_DISCRIMINATOR: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234]; // buy discriminator
+pub const SELL_DISCRIMINATOR: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173]; // sell discriminator
+pub const BONDING_CURVE_DISCRIMINATOR: [u8; 8] = [23, 183, 248, 55, 96, 216, 172, 96]; // account:BondingCurve
+
+pub const BONDING_CURVE_SEED: &[u8] = b"bonding-curve";
+pub const CREATOR_VAULT_SEED: &[u8] = b"creator-vault";
+pub const GLOBAL_VOLUME_ACCUMULATOR_SEED: &[u8] = b"global_volume_accumulator";
+pub const USER_VOLUME_ACCUMULATOR_SEED: &[u8] = b"user_volume_accumulator";
+pub const FEE_CONFIG_SEED: &[u8] = b"fee_config";
+
+pub const DEFAULT_FEE_BASIS_POINTS: u64 = 95; // Protocol fee when no trade has been seen for the mint
+pub const DEFAULT_CREATOR_FEE_BASIS_POINTS: u64 = 30; // Creator fee when no trade has been seen for the mint
+const BASIS_POINTS: u64 = 10_000;
+
+// TradeEvent body sizes: fee and creator fields were appended after launch
+const TRADE_EVENT_LEGACY_LEN: usize = 121;
+const TRADE_EVENT_FEES_LEN: usize = 217;
+
+/// Bonding curve PDA of a mint
+pub fn bonding_curve_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[BONDING_CURVE_SEED, mint.as_ref()], &PUMP_FUN_PROGRAM).0
+}
+
+/// Vault collecting the creator fee of every curve launched by `creator`
+pub fn creator_vault_address(creator: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[CREATOR_VAULT_SEED, creator.as_ref()], &PUMP_FUN_PROGRAM).0
+}
+
+pub fn global_volume_accumulator_address() -> Pubkey {
+    Pubkey::find_program_address(&[GLOBAL_VOLUME_ACCUMULATOR_SEED], &PUMP_FUN_PROGRAM).0
+}
+
+pub fn user_volume_accumulator_address(user: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[USER_VOLUME_ACCUMULATOR_SEED, user.as_ref()], &PUMP_FUN_PROGRAM).0
+}
+
+/// Fee tier config of the pump.fun program, owned by the fee program
+pub fn fee_config_address() -> Pubkey {
+    Pubkey::find_program_address(&[FEE_CONFIG_SEED, PUMP_FUN_PROGRAM.as_ref()], &PUMP_FEE_PROGRAM).0
+}
+
+/// Pump.fun `BondingCurve` account
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BondingCurve {
+    pub virtual_token_reserves: u64,
+    pub virtual_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub token_total_supply: u64,
+    pub complete: bool, // Curve filled and migrated; swaps are rejected from then on
+    pub creator: Option<Pubkey>, // Only present on curves created after creator fees were introduced
+}
+
+impl BondingCurve {
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        if data.len() < 8 || data[..8] != BONDING_CURVE_DISCRIMINATOR {
+            return Err(anyhow!("Account is not a pump.fun BondingCurve"));
+        }
+        let body = &data[8..];
+        let field = |offset: usize| {
+            transaction_parser::parse_u64(body, offset).ok_or_else(|| anyhow!("BondingCurve account is truncated"))
+        };
+        Ok(Self {
+            virtual_token_reserves: field(0)?,
+            virtual_sol_reserves: field(8)?,
+            real_token_reserves: field(16)?,
+            real_sol_reserves: field(24)?,
+            token_total_supply: field(32)?,
+            complete: transaction_parser::parse_u8(body, 40)
+                .ok_or_else(|| anyhow!("BondingCurve account is truncated"))? != 0,
+            creator: transaction_parser::parse_public_key(body, 41).filter(|creator| *creator != Pubkey::default()),
+        })
+    }
+
+    /// Curve state after a streamed trade; `None` when the trade carried no reserves
+    pub fn from_trade_info(trade_info: &TradeInfoFromToken) -> Option<Self> {
+        if trade_info.dex_type != DexType::PumpFun
+            || trade_info.virtual_sol_reserves == 0
+            || trade_info.virtual_token_reserves == 0
+        {
+            return None;
+        }
+        Some(Self {
+            virtual_token_reserves: trade_info.virtual_token_reserves,
+            virtual_sol_reserves: trade_info.virtual_sol_reserves,
+            real_token_reserves: trade_info.real_token_reserves,
+            real_sol_reserves: trade_info.real_sol_reserves,
+            token_total_supply: 0,
+            complete: false,
+            creator: trade_info.coin_creator.as_deref().and_then(|creator| Pubkey::from_str(creator).ok()),
+        })
+    }
+
+    /// Tokens bought with `sol_in` lamports reaching the curve (fees already deducted)
+    pub fn buy_exact_in(&self, sol_in: u64) -> u64 {
+        let denominator = self.virtual_sol_reserves as u128 + sol_in as u128;
+        if sol_in == 0 || denominator == 0 {
+            return 0;
+        }
+        let tokens_out = self.virtual_token_reserves as u128 * sol_in as u128 / denominator;
+        (tokens_out as u64).min(self.real_token_reserves)
+    }
+
+    /// Lamports the program charges, before fees, for exactly `tokens_out` tokens
+    pub fn buy_exact_out(&self, tokens_out: u64) -> Option<u64> {
+        if tokens_out == 0 || tokens_out > self.real_token_reserves || tokens_out >= self.virtual_token_reserves {
+            return None;
+        }
+        // The program rounds the cost down and adds one lamport
+        let cost = self.virtual_sol_reserves as u128 * tokens_out as u128
+            / (self.virtual_token_reserves - tokens_out) as u128
+            + 1;
+        u64::try_from(cost).ok()
+    }
+
+    /// Lamports, before fees, paid out for selling `tokens_in` tokens
+    pub fn sell_exact_in(&self, tokens_in: u64) -> u64 {
+        let denominator = self.virtual_token_reserves as u128 + tokens_in as u128;
+        if tokens_in == 0 || denominator == 0 {
+            return 0;
+        }
+        let sol_out = self.virtual_sol_reserves as u128 * tokens_in as u128 / denominator;
+        (sol_out as u64).min(self.real_sol_reserves)
+    }
+
+    /// Tokens to sell for the curve to pay out `sol_out` lamports before fees
+    pub fn sell_exact_out(&self, sol_out: u64) -> Option<u64> {
+        if sol_out == 0 || sol_out > self.real_sol_reserves || sol_out >= self.virtual_sol_reserves {
+            return None;
+        }
+        let numerator = self.virtual_token_reserves as u128 * sol_out as u128;
+        let tokens_in = numerator.div_ceil((self.virtual_sol_reserves - sol_out) as u128);
+        u64::try_from(tokens_in).ok()
+    }
+
+    /// Spot price in lamports per whole token
+    pub fn price(&self) -> f64 {
+        calculate_price(self.virtual_sol_reserves, self.virtual_token_reserves)
+    }
+
+    /// Spot price in SOL per whole token
+    pub fn spot_price(&self) -> Option<f64> {
+        if self.virtual_token_reserves == 0 {
+            return None;
+        }
+        Some(self.price() / 1_000_000_000.0)
+    }
+}
+
+/// Protocol and creator fees, charged on the SOL side of every trade
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PumpFunFees {
+    pub fee_basis_points: u64,
+    pub creator_fee_basis_points: u64,
+}
+
+impl Default for PumpFunFees {
+    fn default() -> Self {
+        Self {
+            fee_basis_points: DEFAULT_FEE_BASIS_POINTS,
+            creator_fee_basis_points: DEFAULT_CREATOR_FEE_BASIS_POINTS,
+        }
+    }
+}
+
+impl PumpFunFees {
+    /// Recover the rates from the fees charged in a streamed trade. Small trades are ignored,
+    /// since per-component rounding makes their rates unreliable.
+    pub fn from_trade_info(trade_info: &TradeInfoFromToken) -> Option<Self> {
+        let total_fee = trade_info.protocol_fee + trade_info.creator_fee;
+        // Fees are added to the curve input on buys and taken from the curve output on sells
+        let curve_sol = if trade_info.is_buy {
+            trade_info.amount_in.saturating_sub(total_fee)
+        } else {
+            trade_info.amount_out.saturating_add(total_fee)
+        };
+        if trade_info.dex_type != DexType::PumpFun || curve_sol < BASIS_POINTS * 1_000 {
+            return None;
+        }
+        let rate = |fee: u64| (fee as u128 * BASIS_POINTS as u128 / curve_sol as u128) as u64;
+        Some(Self {
+            fee_basis_points: rate(trade_info.protocol_fee),
+            creator_fee_basis_points: rate(trade_info.creator_fee),
+        })
+    }
+
+    /// Total fee charged on `sol_amount` lamports moved on the curve; each component rounds up
+    pub fn total_fee(&self, sol_amount: u64) -> u64 {
+        [self.fee_basis_points, self.creator_fee_basis_points]
+            .iter()
+            .map(|bps| (sol_amount as u128 * *bps as u128).div_ceil(BASIS_POINTS as u128) as u64)
+            .sum()
+    }
+
+    /// Largest curve amount whose cost including fees fits in `gross` lamports
+    pub fn net_of(&self, gross: u64) -> u64 {
+        let total_bps = self.fee_basis_points + self.creator_fee_basis_points;
+        let mut net = (gross as u128 * BASIS_POINTS as u128 / (BASIS_POINTS + total_bps) as u128) as u64;
+        while net > 0 && net + self.total_fee(net) > gross {
+            net -= 1;
+        }
+        net
+    }
+
+    /// Smallest curve output that still leaves `net_amount` once fees are deducted
+    pub fn gross_up(&self, net_amount: u64) -> Option<u64> {
+        let total_bps = self.fee_basis_points + self.creator_fee_basis_points;
+        if total_bps >= BASIS_POINTS {
+            return None;
+        }
+        let estimate = (net_amount as u128 * BASIS_POINTS as u128).div_ceil((BASIS_POINTS - total_bps) as u128);
+        let mut gross = u64::try_from(estimate).ok()?;
+        while gross.saturating_sub(self.total_fee(gross)) < net_amount {
+            gross = gross.checked_add(1)?;
+        }
+        Some(gross)
+    }
+}
+
+/// Quote a buy spending `sol_in` lamports including fees. Pump.fun buys name the token amount,
+/// so slippage is expressed as the extra SOL the buy may cost.
+pub fn quote_buy_exact_in(sol_in: u64, curve: &BondingCurve, fees: &PumpFunFees, slippage_bps: u64) -> SwapQuote {
+    let curve_sol_in = fees.net_of(sol_in);
+    let amount_out = curve.buy_exact_in(curve_sol_in);
+    SwapQuote {
+        amount_in: sol_in,
+        amount_out,
+        fee: fees.total_fee(curve_sol_in),
+        minimum_amount_out: amount_out,
+        maximum_amount_in: apply_max_slippage(sol_in, slippage_bps),
+    }
+}
+
+/// Quote a buy of exactly `tokens_out` raw tokens; fees are added on top of the curve cost
+pub fn quote_buy_exact_out(tokens_out: u64, curve: &BondingCurve, fees: &PumpFunFees, slippage_bps: u64) -> Option<SwapQuote> {
+    let cost = curve.buy_exact_out(tokens_out)?;
+    let fee = fees.total_fee(cost);
+    Some(SwapQuote {
+        amount_in: cost + fee,
+        amount_out: tokens_out,
+        fee,
+        minimum_amount_out: tokens_out,
+        maximum_amount_in: apply_max_slippage(cost + fee, slippage_bps),
+    })
+}
+
+/// Quote a sell of `tokens_in` raw tokens: fees come off the SOL the curve pays out
+pub fn quote_sell_exact_in(tokens_in: u64, curve: &BondingCurve, fees: &PumpFunFees, slippage_bps: u64) -> SwapQuote {
+    let gross_amount_out = curve.sell_exact_in(tokens_in);
+    let fee = fees.total_fee(gross_amount_out);
+    let amount_out = gross_amount_out.saturating_sub(fee);
+    SwapQuote {
+        amount_in: tokens_in,
+        amount_out,
+        fee,
+        minimum_amount_out: apply_slippage(amount_out, slippage_bps),
+        maximum_amount_in: tokens_in,
+    }
+}
+
+/// Quote a sell receiving `sol_out` lamports after fees. Sells name the token amount, so the
+/// slippage allowance goes on the tokens offered and `sol_out` is the floor.
+pub fn quote_sell_exact_out(sol_out: u64, curve: &BondingCurve, fees: &PumpFunFees, slippage_bps: u64) -> Option<SwapQuote> {
+    let gross_amount_out = fees.gross_up(sol_out)?;
+    let amount_in = curve.sell_exact_out(gross_amount_out)?;
+    Some(SwapQuote {
+        amount_in,
+        amount_out: sol_out,
+        fee: fees.total_fee(gross_amount_out),
+        minimum_amount_out: sol_out,
+        maximum_amount_in: apply_max_slippage(amount_in, slippage_bps),
+    })
+}
+
+/// Pump.fun TradeEvent, Borsh layout as emitted by the program
+#[derive(Clone, Debug)]
+pub struct TradeEvent {
+    pub mint: Pubkey,
+    pub sol_amount: u64, // Lamports moved on the curve, fees excluded
+    pub token_amount: u64,
+    pub is_buy: bool,
+    pub user: Pubkey,
+    pub timestamp: i64,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub fee_basis_points: u64,
+    pub fee: u64,
+    pub creator: Option<Pubkey>,
+    pub creator_fee_basis_points: u64,
+    pub creator_fee: u64,
+}
+
+impl TradeEvent {
+    /// Spot price after the trade in lamports per whole token
+    pub fn price(&self) -> f64 {
+        calculate_price(self.virtual_sol_reserves, self.virtual_token_reserves)
+    }
+
+    pub fn bonding_curve_info(&self) -> BondingCurveInfo {
+        BondingCurveInfo {
+            bonding_curve: bonding_curve_address(&self.mint),
+            new_virtual_sol_reserve: self.virtual_sol_reserves,
+            new_virtual_token_reserve: self.virtual_token_reserves,
+        }
+    }
+}
+
+/// Decode a pump.fun TradeEvent from CPI event data or a base64 decoded `Program data:` log.
+/// The discriminator is shared with the launchpad, so only pass data emitted by the pump.fun program.
+pub fn decode_trade_event(data: &[u8]) -> Option<TradeEvent> {
+    let payload = transaction_parser::event_payload(data);
+    if payload.len() < 8 + TRADE_EVENT_LEGACY_LEN || payload[..8] != TRADE_EVENT_DISCRIMINATOR {
+        return None;
+    }
+    let body = &payload[8..];
+    let has_fees = body.len() >= TRADE_EVENT_FEES_LEN;
+    let optional_u64 = |offset: usize| if has_fees { transaction_parser::parse_u64(body, offset) } else { Some(0) };
+
+    Some(TradeEvent {
+        mint: transaction_parser::parse_public_key(body, 0)?,
+        sol_amount: transaction_parser::parse_u64(body, 32)?,
+        token_amount: transaction_parser::parse_u64(body, 40)?,
+        is_buy: transaction_parser::parse_u8(body, 48)? != 0,
+        user: transaction_parser::parse_public_key(body, 49)?,
+        timestamp: transaction_parser::parse_u64(body, 81)? as i64,
+        virtual_sol_reserves: transaction_parser::parse_u64(body, 89)?,
+        virtual_token_reserves: transaction_parser::parse_u64(body, 97)?,
+        real_sol_reserves: transaction_parser::parse_u64(body, 105)?,
+        real_token_reserves: transaction_parser::parse_u64(body, 113)?,
+        fee_basis_points: optional_u64(153)?,
+        fee: optional_u64(161)?,
+        creator: if has_fees { transaction_parser::parse_public_key(body, 169) } else { None },
+        creator_fee_basis_points: optional_u64(201)?,
+        creator_fee: optional_u64(209)?,
+    })
+}
+
+/// Pump.fun trades in execution order. Self-CPI event data is preferred; the `Program data:`
+/// logs are used when it is absent.
+pub fn parse_trades(txn: &SubscribeUpdateTransaction, account_keys: &[Pubkey]) -> Vec<TradeInfoFromToken> {
+    let cpi_events: Vec<(usize, Option<usize>, TradeEvent)> =
+        transaction_parser::program_instructions(txn, account_keys, &PUMP_FUN_PROGRAM)
+            .into_iter()
+            .filter(|ix| ix.inner_index.is_some())
+            .filter_map(|ix| Some((ix.instruction_index, ix.inner_index, decode_trade_event(ix.data)?)))
+            .collect();
+    let events = if cpi_events.is_empty() {
+        transaction_parser::program_data_logs(txn, &PUMP_FUN_PROGRAM)
+            .into_iter()
+            .filter_map(|(index, data)| Some((index, None, decode_trade_event(&data)?)))
+            .collect()
+    } else {
+        cpi_events
+    };
+
+    events
+        .into_iter()
+        .map(|(instruction_index, inner_index, event)| build_trade_info(txn, instruction_index, inner_index, &event))
+        .collect()
+}
+
+/// Curve completion, seen as the buy that takes the last token off the curve. Pump.fun migrates
+/// completed curves into the canonical PumpSwap pool, so the new pool is known up front.
+pub fn parse_migration_event(txn: &SubscribeUpdateTransaction) -> Option<MigrationEvent> {
+    let trade = parse_trades(txn, &transaction_parser::transaction_account_keys(txn))
+        .into_iter()
+        .find(|trade| trade.is_buy && trade.real_token_reserves == 0 && trade.virtual_token_reserves > 0)?;
+    let mint = Pubkey::from_str(&trade.mint).ok()?;
+    Some(MigrationEvent {
+        slot: trade.slot,
+        signature: trade.signature,
+        timestamp: trade.timestamp,
+        mint: trade.mint,
+        launchpad_pool_id: trade.pool_id,
+        new_pool_id: Some(pump_swap::canonical_pool_address(&mint).to_string()),
+        venue: MigrationVenue::PumpSwap,
+        final_base_reserve: trade.real_token_reserves,
+        final_quote_reserve: trade.real_sol_reserves,
+    })
+}
+
+fn build_trade_info(
+    txn: &SubscribeUpdateTransaction,
+    instruction_index: usize,
+    inner_index: Option<usize>,
+    event: &TradeEvent,
+) -> TradeInfoFromToken {
+    let signature = txn.transaction
+        .as_ref()
+        .map(|tx_inner| bs58::encode(&tx_inner.signature).into_string())
+        .unwrap_or_default();
+
+    // Match the launchpad convention: buys report the SOL spent including fees, sells the SOL received
+    let total_fee = event.fee + event.creator_fee;
+    let (amount_in, amount_out) = if event.is_buy {
+        (event.sol_amount + total_fee, event.token_amount)
+    } else {
+        (event.token_amount, event.sol_amount.saturating_sub(total_fee))
+    };
+    let token_scale = 10f64.powi(LAUNCHPAD_TOKEN_DECIMALS as i32);
+    let (sol_change, token_change) = if event.is_buy {
+        (-(amount_in as f64) / 1_000_000_000.0, amount_out as f64 / token_scale)
+    } else {
+        (amount_out as f64 / 1_000_000_000.0, -(amount_in as f64) / token_scale)
+    };
+
+    TradeInfoFromToken {
+        dex_type: DexType::PumpFun,
+        slot: txn.slot,
+        signature,
+        pool_id: bonding_curve_address(&event.mint).to_string(),
+        mint: event.mint.to_string(),
+        quote_mint: SOL_MINT.to_string(),
+        timestamp: event.timestamp.max(0) as u64,
+        is_buy: event.is_buy,
+        price: event.price(),
+        is_reverse: false,
+        coin_creator: event.creator.map(|creator| creator.to_string()),
+        sol_change,
+        token_change,
+        liquidity: event.real_sol_reserves as f64 / 1_000_000_000.0,
+        virtual_sol_reserves: event.virtual_sol_reserves,
+        virtual_token_reserves: event.virtual_token_reserves,
+        amount_in,
+        amount_out,
+        real_sol_reserves: event.real_sol_reserves,
+        real_token_reserves: event.real_token_reserves,
+        curve_virtual_base: 0, // Launchpad only
+        curve_virtual_quote: 0,
+        total_base_sell: 0,
+        protocol_fee: event.fee,
+        platform_fee: 0,
+        creator_fee: event.creator_fee,
+        share_fee: 0,
+        instruction_index,
+        inner_instruction_index: inner_index,
+        user: event.user.to_string(),
+        base_vault: String::new(),
+        quote_vault: String::new(),
+    }
+}
+
+pub struct PumpFun {
+    pub keypair: Arc<Keypair>,
+    pub rpc_client: Option<Arc<solana_client::rpc_client::RpcClient>>,
+    pub rpc_nonblocking_client: Option<Arc<solana_client::nonblocking::rpc_client::RpcClient>>,
+}
+
+impl PumpFun {
+    pub fn new(
+        keypair: Arc<Keypair>,
+        rpc_client: Option<Arc<solana_client::rpc_client::RpcClient>>,
+        rpc_nonblocking_client: Option<Arc<solana_client::nonblocking::rpc_client::RpcClient>>,
+    ) -> Self {
+        Self {
+            keypair,
+            rpc_client,
+            rpc_nonblocking_client,
+        }
+    }
+
+    fn client(&self) -> Result<&Arc<solana_client::nonblocking::rpc_client::RpcClient>> {
+        self.rpc_nonblocking_client.as_ref()
+            .ok_or_else(|| anyhow!("RPC client not initialized"))
+    }
+
+    /// Live curve state, read from chain rather than derived from the transaction stream
+    pub async fn get_bonding_curve(&self, mint: &Pubkey) -> Result<BondingCurve> {
+        let bonding_curve = bonding_curve_address(mint);
+        let account = if let Some(client) = &self.rpc_nonblocking_client {
+            client.get_account(&bonding_curve).await
+        } else if let Some(client) = &self.rpc_client {
+            client.get_account(&bonding_curve)
+        } else {
+            return Err(anyhow!("RPC client not initialized"));
+        };
+        let account = account.map_err(|e| anyhow!("Bonding curve for {} not found: {}", mint, e))?;
+        BondingCurve::decode(&account.data)
+    }
+
+    /// Spot price in SOL per whole token, read from the live curve
+    pub async fn get_token_price(&self, mint_str: &str) -> Result<f64> {
+        let mint = Pubkey::from_str(mint_str).map_err(|_| anyhow!("Invalid mint address"))?;
+        let curve = self.get_bonding_curve(&mint).await?;
+        if curve.complete {
+            return Err(anyhow!("Bonding curve for {} is complete, price is no longer on the curve", mint_str));
+        }
+        curve.spot_price().ok_or_else(|| anyhow!("Bonding curve for {} has no token reserve", mint_str))
+    }
+
+    /// Curve for quoting: the streamed reserves when the trade carries them and the creator is
+    /// known, otherwise the account read from chain
+    async fn get_curve(&self, trade_info: &TradeInfoFromToken, mint: &Pubkey) -> Result<BondingCurve> {
+        let curve = match BondingCurve::from_trade_info(trade_info) {
+            Some(curve) if curve.creator.is_some() => curve,
+            _ => self.get_bonding_curve(mint).await?,
+        };
+        if curve.complete {
+            return Err(anyhow!("Bonding curve for {} is complete", mint));
+        }
+        Ok(curve)
+    }
+
+    /// Quote a swap of `amount` raw units against the curve. For exact-out swaps `amount` is
+    /// the output; otherwise it is the input.
+    fn quote_swap(
+        trade_info: &TradeInfoFromToken,
+        curve: &BondingCurve,
+        swap_direction: &SwapDirection,
+        exact_out: bool,
+        amount: u64,
+        slippage_bps: u64,
+    ) -> Result<SwapQuote> {
+        let fees = PumpFunFees::from_trade_info(trade_info).unwrap_or_default();
+        let quote = match (swap_direction, exact_out) {
+            (SwapDirection::Buy, false) => Some(quote_buy_exact_in(amount, curve, &fees, slippage_bps)),
+            (SwapDirection::Buy, true) => quote_buy_exact_out(amount, curve, &fees, slippage_bps),
+            (SwapDirection::Sell, false) => Some(quote_sell_exact_in(amount, curve, &fees, slippage_bps)),
+            (SwapDirection::Sell, true) => quote_sell_exact_out(amount, curve, &fees, slippage_bps),
+        };
+        quote.ok_or_else(|| anyhow!("Curve for {} cannot fill an exact output of {}", trade_info.mint, amount))
+    }
+
+    async fn get_token_balance(&self, token_account: &Pubkey, mint: &Pubkey) -> Result<u64> {
+        let client = self.rpc_nonblocking_client.as_ref()
+            .ok_or_else(|| anyhow!("No RPC client available to fetch token balance"))?;
+        client.get_token_account(token_account).await
+            .map_err(|e| anyhow!("Failed to get token account balance: {}", e))?
+            .ok_or_else(|| anyhow!("Token account does not exist for mint {}", mint))?
+            .token_amount
+            .amount
+            .parse::<u64>()
+            .map_err(|_| anyhow!("Failed to parse token balance for mint {}", mint))
+    }
+
+    pub async fn build_swap_from_parsed_data(
+        &self,
+        trade_info: &TradeInfoFromToken,
+        swap_config: SwapConfig,
+    ) -> Result<SwapInstructions> {
+        let owner = self.keypair.pubkey();
+        let mint = Pubkey::from_str(&trade_info.mint)?;
+        let token_program = token::get_mint_token_program(self.client()?.clone(), &mint).await?;
+        let token_ata = token::get_associated_token_address_for_program(&owner, &mint, &token_program);
+
+        let mut instructions = Vec::with_capacity(2);
+        if swap_config.swap_direction == SwapDirection::Buy && !WALLET_TOKEN_ACCOUNTS.contains(&token_ata) {
+            let logger = Logger::new("[PUMPFUN-ATA-CREATE] => ".yellow().to_string());
+            logger.log(format!("Creating token ATA for mint {} at address {}", mint, token_ata));
+            instructions.push(create_associated_token_account_idempotent(&owner, &owner, &mint, &token_program));
+            WALLET_TOKEN_ACCOUNTS.insert(token_ata);
+        }
+
+        // Buy: SOL to spend, or tokens to receive for ExactOut.
+        // Sell: tokens from the wallet balance (qty/pct), or SOL to receive for ExactOut.
+        let exact_out = swap_config.in_type == SwapInType::ExactOut;
+        let mut token_balance = None;
+        let amount = match swap_config.swap_direction {
+            SwapDirection::Buy if exact_out => ui_amount_to_amount(swap_config.amount_in, LAUNCHPAD_TOKEN_DECIMALS as u8),
+            SwapDirection::Buy => ui_amount_to_amount(swap_config.amount_in, 9),
+            SwapDirection::Sell => {
+                let balance = self.get_token_balance(&token_ata, &mint).await?;
+                token_balance = Some(balance);
+                match swap_config.in_type {
+                    SwapInType::Qty => ui_amount_to_amount(swap_config.amount_in, LAUNCHPAD_TOKEN_DECIMALS as u8),
+                    SwapInType::Pct => ((swap_config.amount_in.min(1.0) * balance as f64) as u64).max(1),
+                    SwapInType::ExactOut => ui_amount_to_amount(swap_config.amount_in, 9),
+                }
+            }
+        };
+
+        let curve = self.get_curve(trade_info, &mint).await?;
+        priority_fees::track_fee_account(&bonding_curve_address(&mint));
+        let creator = curve.creator
+            .ok_or_else(|| anyhow!("Bonding curve for {} has no creator", mint))?;
+        let quote = Self::quote_swap(trade_info, &curve, &swap_config.swap_direction, exact_out, amount, swap_config.slippage)?;
+
+        // Both instructions name the token amount; the SOL side carries the slippage bound
+        let instruction = match swap_config.swap_direction {
+            SwapDirection::Buy => {
+                if quote.amount_out == 0 {
+                    return Err(anyhow!("Quote for {} returned no output (amount in {})", mint, amount));
+                }
+                create_buy_instruction(
+                    &mint,
+                    &owner,
+                    &token_ata,
+                    &creator,
+                    &token_program,
+                    quote.amount_out,
+                    quote.maximum_amount_in,
+                )
+            }
+            SwapDirection::Sell => {
+                let tokens_in = if exact_out { quote.maximum_amount_in } else { quote.amount_in };
+                if let Some(balance) = token_balance {
+                    if exact_out && quote.amount_in > balance {
+                        return Err(anyhow!(
+                            "Exact-out sell of {} needs {} tokens but the wallet holds {}",
+                            mint, quote.amount_in, balance
+                        ));
+                    }
+                }
+                let tokens_in = token_balance.map_or(tokens_in, |balance| tokens_in.min(balance));
+                if !exact_out && quote.minimum_amount_out == 0 {
+                    return Err(anyhow!("Quote for {} returned no output (amount in {})", mint, amount));
+                }
+                create_sell_instruction(
+                    &mint,
+                    &owner,
+                    &token_ata,
+                    &creator,
+                    &token_program,
+                    tokens_in,
+                    quote.minimum_amount_out,
+                )
+            }
+        };
+        instructions.push(instruction);
+
+        let price_in_sol = if trade_info.dex_type == DexType::PumpFun && trade_info.price > 0.0 {
+            trade_info.price / 1_000_000_000.0
+        } else {
+            curve.spot_price().unwrap_or_default()
+        };
+        Ok((self.keypair.clone(), instructions, price_in_sol))
+    }
+}
+
+#[async_trait]
+impl Dex for PumpFun {
+    fn protocol(&self) -> SwapProtocol {
+        SwapProtocol::PumpFun
+    }
+
+    fn program_id(&self) -> Pubkey {
+        PUMP_FUN_PROGRAM
+    }
+
+    async fn pool_for_mint(&self, mint: &str) -> Result<PoolKeys> {
+        let mint = Pubkey::from_str(mint).map_err(|_| anyhow!("Invalid mint address"))?;
+        let curve = self.get_bonding_curve(&mint).await?;
+        if curve.complete {
+            return Err(anyhow!("Bonding curve for {} is complete", mint));
+        }
+        let token_program = token::get_mint_token_program(self.client()?.clone(), &mint).await?;
+        let bonding_curve = bonding_curve_address(&mint);
+        Ok(PoolKeys {
+            pool_id: bonding_curve,
+            base_mint: mint,
+            quote_mint: SOL_MINT,
+            base_vault: token::get_associated_token_address_for_program(&bonding_curve, &mint, &token_program),
+            quote_vault: bonding_curve, // The curve account holds the SOL itself
+        })
+    }
+
+    async fn quote(&self, trade_info: &TradeInfoFromToken, swap_config: &SwapConfig, amount: u64) -> Result<SwapQuote> {
+        let mint = Pubkey::from_str(&trade_info.mint)?;
+        let curve = self.get_curve(trade_info, &mint).await?;
+        Self::quote_swap(
+            trade_info,
+            &curve,
+            &swap_config.swap_direction,
+            swap_config.in_type == SwapInType::ExactOut,
+            amount,
+            swap_config.slippage,
+        )
+    }
+
+    async fn build_buy(&self, trade_info: &TradeInfoFromToken, mut swap_config: SwapConfig) -> Result<SwapInstructions> {
+        swap_config.swap_direction = SwapDirection::Buy;
+        self.build_swap_from_parsed_data(trade_info, swap_config).await
+    }
+
+    async fn build_sell(&self, trade_info: &TradeInfoFromToken, mut swap_config: SwapConfig) -> Result<SwapInstructions> {
+        swap_config.swap_direction = SwapDirection::Sell;
+        self.build_swap_from_parsed_data(trade_info, swap_config).await
+    }
+
+    async fn token_price(&self, mint: &str) -> Result<f64> {
+        self.get_token_price(mint).await
+    }
+
+    fn price_from_trade(&self, trade_info: &TradeInfoFromToken) -> f64 {
+        // The parser prices pump.fun trades in lamports per whole token, like launchpad trades
+        trade_info.price / 1_000_000_000.0
+    }
+}
+
+/// `buy`: receive exactly `tokens_out`, paying at most `max_sol_cost` lamports including fees
+pub fn create_buy_instruction(
+    mint: &Pubkey,
+    user: &Pubkey,
+    user_token_account: &Pubkey,
+    creator: &Pubkey,
+    token_program: &Pubkey,
+    tokens_out: u64,
+    max_sol_cost: u64,
+) -> Instruction {
+    let bonding_curve = bonding_curve_address(mint);
+    let accounts = vec![
+        AccountMeta::new_readonly(PUMP_FUN_GLOBAL, false),
+        AccountMeta::new(PUMP_FUN_FEE_RECIPIENT, false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new(bonding_curve, false),
+        AccountMeta::new(token::get_associated_token_address_for_program(&bonding_curve, mint, token_program), false),
+        AccountMeta::new(*user_token_account, false),
+        AccountMeta::new(*user, true),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new(creator_vault_address(creator), false),
+        AccountMeta::new_readonly(PUMP_FUN_EVENT_AUTHORITY, false),
+        AccountMeta::new_readonly(PUMP_FUN_PROGRAM, false),
+        AccountMeta::new(global_volume_accumulator_address(), false),
+        AccountMeta::new(user_volume_accumulator_address(user), false),
+        AccountMeta::new_readonly(fee_config_address(), false),
+        AccountMeta::new_readonly(PUMP_FEE_PROGRAM, false),
+    ];
+
+    let mut data = Vec::with_capacity(25);
+    data.extend_from_slice(&BUY_DISCRIMINATOR);
+    data.extend_from_slice(&tokens_out.to_le_bytes());
+    data.extend_from_slice(&max_sol_cost.to_le_bytes());
+    data.push(0); // track_volume: volume rewards are not claimed by the bot
+
+    Instruction { program_id: PUMP_FUN_PROGRAM, accounts, data }
+}
+
+/// `sell`: sell exactly `tokens_in`, receiving at least `min_sol_output` lamports after fees
+pub fn create_sell_instruction(
+    mint: &Pubkey,
+    user: &Pubkey,
+    user_token_account: &Pubkey,
+    creator: &Pubkey,
+    token_program: &Pubkey,
+    tokens_in: u64,
+    min_sol_output: u64,
+) -> Instruction {
+    let bonding_curve = bonding_curve_address(mint);
+    let accounts = vec![
+        AccountMeta::new_readonly(PUMP_FUN_GLOBAL, false),
+        AccountMeta::new(PUMP_FUN_FEE_RECIPIENT, false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new(bonding_curve, false),
+        AccountMeta::new(token::get_associated_token_address_for_program(&bonding_curve, mint, token_program), false),
+        AccountMeta::new(*user_token_account, false),
+        AccountMeta::new(*user, true),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new(creator_vault_address(creator), false),
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new_readonly(PUMP_FUN_EVENT_AUTHORITY, false),
+        AccountMeta::new_readonly(PUMP_FUN_PROGRAM, false),
+        AccountMeta::new_readonly(fee_config_address(), false),
+        AccountMeta::new_readonly(PUMP_FEE_PROGRAM, false),
+    ];
+
+    let mut data = Vec::with_capacity(24);
+    data.extend_from_slice(&SELL_DISCRIMINATOR);
+    data.extend_from_slice(&tokens_in.to_le_bytes());
+    data.extend_from_slice(&min_sol_output.to_le_bytes());
+
+    Instruction { program_id: PUMP_FUN_PROGRAM, accounts, data }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh pump.fun bonding curve
+    fn fresh_curve() -> BondingCurve {
+        BondingCurve {
+            virtual_token_reserves: 1_073_000_000_000_000,
+            virtual_sol_reserves: 30_000_000_000,
+            real_token_reserves: 793_100_000_000_000,
+            real_sol_reserves: 0,
+            token_total_supply: 1_000_000_000_000_000,
+            complete: false,
+            creator: None,
+        }
+    }
+
+    /// The same curve after 10 SOL of buys, so there is SOL to sell into
+    fn traded_curve() -> BondingCurve {
+        let curve = fresh_curve();
+        let tokens_out = curve.buy_exact_in(10_000_000_000);
+        BondingCurve {
+            virtual_token_reserves: curve.virtual_token_reserves - tokens_out,
+            virtual_sol_reserves: curve.virtual_sol_reserves + 10_000_000_000,
+            real_token_reserves: curve.real_token_reserves - tokens_out,
+            real_sol_reserves: 10_000_000_000,
+            ..curve
+        }
+    }
+
+    #[test]
+    fn buying_out_the_curve_costs_the_migration_amount() {
+        let curve = fresh_curve();
+        assert_eq!(curve.buy_exact_out(curve.real_token_reserves), Some(85_005_359_057));
+        assert_eq!(curve.buy_exact_out(curve.real_token_reserves + 1), None);
+    }
+
+    #[test]
+    fn total_fee_rounds_each_component_up() {
+        let fees = PumpFunFees::default();
+        assert_eq!(fees.total_fee(1_000_000_000), 12_500_000);
+        assert_eq!(fees.total_fee(1), 2);
+        assert_eq!(fees.total_fee(0), 0);
+    }
+
+    #[test]
+    fn net_of_and_gross_up_are_tight() {
+        for fees in [
+            PumpFunFees::default(),
+            PumpFunFees { fee_basis_points: 93, creator_fee_basis_points: 0 },
+            PumpFunFees { fee_basis_points: 0, creator_fee_basis_points: 0 },
+        ] {
+            for amount in [0, 1, 7, 999, 1_000_000, 123_456_789, 85_000_000_000] {
+                let net = fees.net_of(amount);
+                assert!(net + fees.total_fee(net) <= amount, "{:?}: {} does not fit in {}", fees, net, amount);
+                assert!(net + 1 + fees.total_fee(net + 1) > amount, "{:?}: {} is not maximal for {}", fees, net, amount);
+
+                let gross = fees.gross_up(amount).unwrap();
+                assert!(gross - fees.total_fee(gross) >= amount, "{:?}: {} does not cover {}", fees, gross, amount);
+                if gross > 0 {
+                    let smaller = gross - 1;
+                    assert!(smaller.saturating_sub(fees.total_fee(smaller)) < amount, "{:?}: {} is not minimal for {}", fees, gross, amount);
+                }
+            }
+        }
+        let all_fees = PumpFunFees { fee_basis_points: BASIS_POINTS, creator_fee_basis_points: 0 };
+        assert_eq!(all_fees.gross_up(1), None);
+    }
+
+    #[test]
+    fn fee_rates_are_recovered_from_buys_and_sells() {
+        // Buys pay fees on top of the curve input
+        let buy = TradeInfoFromToken {
+            dex_type: DexType::PumpFun,
+            is_buy: true,
+            amount_in: 1_012_500_000,
+            protocol_fee: 9_500_000,
+            creator_fee: 3_000_000,
+            ..Default::default()
+        };
+        assert_eq!(PumpFunFees::from_trade_info(&buy), Some(PumpFunFees::default()));
+        // Sells report amount_out after the fees were taken from the curve output
+        let sell = TradeInfoFromToken { is_buy: false, amount_in: 0, amount_out: 987_500_000, ..buy.clone() };
+        assert_eq!(PumpFunFees::from_trade_info(&sell), Some(PumpFunFees::default()));
+        let dust = TradeInfoFromToken { amount_in: 1_000_000, protocol_fee: 9_500, creator_fee: 3_000, ..buy };
+        assert_eq!(PumpFunFees::from_trade_info(&dust), None);
+    }
+
+    #[test]
+    fn buys_pay_fees_on_top_of_the_curve_input() {
+        let curve = fresh_curve();
+        let fees = PumpFunFees::default();
+        let quote = quote_buy_exact_in(1_012_500_000, &curve, &fees, 0);
+        assert_eq!(quote.fee, 12_500_000);
+        assert_eq!(quote.amount_out, curve.buy_exact_in(1_000_000_000));
+        assert_eq!(quote.minimum_amount_out, quote.amount_out);
+        assert_eq!(quote.maximum_amount_in, 1_012_500_000);
+        assert_eq!(quote_buy_exact_in(1_012_500_000, &curve, &fees, 10_000).maximum_amount_in, 2_025_000_000);
+
+        let quote = quote_buy_exact_out(quote.amount_out, &curve, &fees, 0).unwrap();
+        let cost = curve.buy_exact_out(quote.amount_out).unwrap();
+        assert_eq!(quote.fee, fees.total_fee(cost));
+        assert_eq!(quote.amount_in, cost + quote.fee);
+        assert_eq!(quote.maximum_amount_in, quote.amount_in);
+        assert_eq!(quote_buy_exact_out(quote.amount_out, &curve, &fees, 10_000).unwrap().maximum_amount_in, quote.amount_in * 2);
+    }
+
+    #[test]
+    fn sells_pay_fees_on_the_curve_output() {
+        let curve = traded_curve();
+        let fees = PumpFunFees::default();
+        let tokens_in = curve.real_token_reserves / 10;
+        let gross = curve.sell_exact_in(tokens_in);
+        let quote = quote_sell_exact_in(tokens_in, &curve, &fees, 0);
+        assert_eq!(quote.fee, fees.total_fee(gross));
+        assert_eq!(quote.amount_out + quote.fee, gross);
+        assert_eq!(quote.minimum_amount_out, quote.amount_out);
+        assert_eq!(quote_sell_exact_in(tokens_in, &curve, &fees, 10_000).minimum_amount_out, 0);
+
+        let quote = quote_sell_exact_out(1_000_000_000, &curve, &fees, 0).unwrap();
+        let gross = curve.sell_exact_in(quote.amount_in);
+        assert!(gross - fees.total_fee(gross) >= 1_000_000_000);
+        assert_eq!(quote.maximum_amount_in, quote.amount_in);
+        assert_eq!(quote_sell_exact_out(1_000_000_000, &curve, &fees, 10_000).unwrap().maximum_amount_in, quote.amount_in * 2);
+        // More than the curve holds cannot be paid out
+        assert!(quote_sell_exact_out(10_000_000_000, &curve, &fees, 0).is_none());
+    }
+
+    /// TradeEvent body with every field set to a distinct value, with or without the fee fields
+    fn trade_event_body(with_fees: bool) -> Vec<u8> {
+        let mut body = Vec::new();
+        body.extend_from_slice(&[1; 32]); // mint
+        body.extend_from_slice(&1_000_000_000u64.to_le_bytes()); // sol_amount
+        body.extend_from_slice(&34_612_903_225_806u64.to_le_bytes()); // token_amount
+        body.push(1); // is_buy
+        body.extend_from_slice(&[2; 32]); // user
+        body.extend_from_slice(&1_760_000_000i64.to_le_bytes()); // timestamp
+        body.extend_from_slice(&31_000_000_000u64.to_le_bytes()); // virtual_sol_reserves
+        body.extend_from_slice(&1_038_387_096_774_194u64.to_le_bytes()); // virtual_token_reserves
+        body.extend_from_slice(&1_000_000_000u64.to_le_bytes()); // real_sol_reserves
+        body.extend_from_slice(&758_487_096_774_194u64.to_le_bytes()); // real_token_reserves
+        assert_eq!(body.len(), TRADE_EVENT_LEGACY_LEN);
+        if with_fees {
+            body.extend_from_slice(&[3; 32]); // fee_recipient
+            body.extend_from_slice(&95u64.to_le_bytes()); // fee_basis_points
+            body.extend_from_slice(&9_500_000u64.to_le_bytes()); // fee
+            body.extend_from_slice(&[4; 32]); // creator
+            body.extend_from_slice(&30u64.to_le_bytes()); // creator_fee_basis_points
+            body.extend_from_slice(&3_000_000u64.to_le_bytes()); // creator_fee
+            assert_eq!(body.len(), TRADE_EVENT_FEES_LEN);
+        }
+        body
+    }
+
+    #[test]
+    fn trade_events_decode_at_their_borsh_offsets() {
+        // Self-CPI event data carries the event tag, `Program data:` logs do not
+        let mut cpi_data = transaction_parser::EVENT_IX_TAG.to_vec();
+        cpi_data.extend_from_slice(&TRADE_EVENT_DISCRIMINATOR);
+        cpi_data.extend_from_slice(&trade_event_body(true));
+        for data in [cpi_data.clone(), cpi_data[8..].to_vec()] {
+            let event = decode_trade_event(&data).unwrap();
+            assert_eq!(event.mint, Pubkey::new_from_array([1; 32]));
+            assert_eq!(event.sol_amount, 1_000_000_000);
+            assert_eq!(event.token_amount, 34_612_903_225_806);
+            assert!(event.is_buy);
+            assert_eq!(event.user, Pubkey::new_from_array([2; 32]));
+            assert_eq!(event.timestamp, 1_760_000_000);
+            assert_eq!(event.virtual_sol_reserves, 31_000_000_000);
+            assert_eq!(event.virtual_token_reserves, 1_038_387_096_774_194);
+            assert_eq!(event.real_sol_reserves, 1_000_000_000);
+            assert_eq!(event.real_token_reserves, 758_487_096_774_194);
+            assert_eq!(event.fee_basis_points, 95);
+            assert_eq!(event.fee, 9_500_000);
+            assert_eq!(event.creator, Some(Pubkey::new_from_array([4; 32])));
+            assert_eq!(event.creator_fee_basis_points, 30);
+            assert_eq!(event.creator_fee, 3_000_000);
+        }
+
+        // Events from before the fee fields have no fees and no creator
+        let mut legacy = TRADE_EVENT_DISCRIMINATOR.to_vec();
+        legacy.extend_from_slice(&trade_event_body(false));
+        let event = decode_trade_event(&legacy).unwrap();
+        assert_eq!(event.real_token_reserves, 758_487_096_774_194);
+        assert_eq!((event.fee, event.creator_fee, event.creator), (0, 0, None));
+
+        assert!(decode_trade_event(&legacy[..legacy.len() - 1]).is_none());
+        let mut other = cpi_data;
+        other[8] ^= 1;
+        assert!(decode_trade_event(&other).is_none());
+    }
+}
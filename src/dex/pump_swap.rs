@@ -0,0 +1,1019 @@
+//! PumpSwap AMM venue, where pump.fun tokens trade once their bonding curve completed.
+//!
+//! Decodes the `Pool` and `GlobalConfig` accounts and the program's `BuyEvent` / `SellEvent`,
+//! and builds `buy` / `sell` instructions against the pool's constant-product reserves. Fees are
+//! charged on the quote (wrapped SOL) side, which the wallet spends from and is paid into.
+
+use std::{str::FromStr, sync::Arc};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use colored::Colorize;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use spl_token::ui_amount_to_amount;
+use tokio::sync::OnceCell;
+use yellowstone_grpc_proto::geyser::SubscribeUpdateTransaction;
+
+use crate::common::{cache::WALLET_TOKEN_ACCOUNTS, config::SwapConfig, logger::Logger};
+use crate::core::token;
+use crate::dex::constant_product;
+use crate::dex::pump_fun::{PUMP_FEE_PROGRAM, PUMP_FUN_PROGRAM};
+use crate::dex::raydium_launchpad::{apply_max_slippage, apply_slippage, ASSOCIATED_TOKEN_PROGRAM, SOL_MINT, TOKEN_PROGRAM};
+use crate::dex::venue::{Dex, PoolKeys, SwapInstructions, SwapQuote};
+use crate::engine::swap::{SwapDirection, SwapInType, SwapProtocol};
+use crate::engine::transaction_parser::{self, calculate_price, DexType, TradeInfoFromToken, LAUNCHPAD_TOKEN_DECIMALS};
+use crate::services::priority_fees;
+
+pub const PUMP_SWAP_PROGRAM: Pubkey = solana_sdk::pubkey!("pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA");
+pub const BUY_DISCRIMINATOR: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234]; // buy discriminator
+pub const SELL_DISCRIMINATOR: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173]; // sell discriminator
+pub const POOL_DISCRIMINATOR: [u8; 8] = [241, 154, 109, 4, 17, 177, 109, 188]; // account:Pool
+pub const GLOBAL_CONFIG_DISCRIMINATOR: [u8; 8] = [149, 8, 156, 202, 160, 252, 176, 217]; // account:GlobalConfig
+pub const BUY_EVENT_DISCRIMINATOR: [u8; 8] = [103, 244, 82, 31, 44, 245, 119, 119]; // event:BuyEvent
+pub const SELL_EVENT_DISCRIMINATOR: [u8; 8] = [62, 47, 55, 10, 165, 3, 220, 42]; // event:SellEvent
+
+pub const POOL_SEED: &[u8] = b"pool";
+pub const POOL_AUTHORITY_SEED: &[u8] = b"pool-authority"; // Pump.fun PDA that creates the canonical pool
+pub const GLOBAL_CONFIG_SEED: &[u8] = b"global_config";
+pub const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+pub const CREATOR_VAULT_SEED: &[u8] = b"creator_vault";
+pub const GLOBAL_VOLUME_ACCUMULATOR_SEED: &[u8] = b"global_volume_accumulator";
+pub const USER_VOLUME_ACCUMULATOR_SEED: &[u8] = b"user_volume_accumulator";
+pub const FEE_CONFIG_SEED: &[u8] = b"fee_config";
+
+pub const POOL_BASE_MINT_OFFSET: usize = 43;
+pub const POOL_QUOTE_MINT_OFFSET: usize = 75;
+const BASIS_POINTS: u64 = 10_000;
+
+// Buy/SellEvent body sizes: coin creator fields were appended after launch
+const TRADE_EVENT_LEGACY_LEN: usize = 304;
+const TRADE_EVENT_CREATOR_LEN: usize = 352;
+
+/// Canonical pool that pump.fun migrates a completed curve into
+pub fn canonical_pool_address(mint: &Pubkey) -> Pubkey {
+    let pool_authority = Pubkey::find_program_address(&[POOL_AUTHORITY_SEED, mint.as_ref()], &PUMP_FUN_PROGRAM).0;
+    Pubkey::find_program_address(
+        &[POOL_SEED, &0u16.to_le_bytes(), pool_authority.as_ref(), mint.as_ref(), SOL_MINT.as_ref()],
+        &PUMP_SWAP_PROGRAM,
+    ).0
+}
+
+pub fn global_config_address() -> Pubkey {
+    Pubkey::find_program_address(&[GLOBAL_CONFIG_SEED], &PUMP_SWAP_PROGRAM).0
+}
+
+pub fn event_authority_address() -> Pubkey {
+    Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &PUMP_SWAP_PROGRAM).0
+}
+
+/// Authority of the vault collecting the creator fee of every pool whose coin creator is `creator`
+pub fn creator_vault_authority_address(creator: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[CREATOR_VAULT_SEED, creator.as_ref()], &PUMP_SWAP_PROGRAM).0
+}
+
+pub fn global_volume_accumulator_address() -> Pubkey {
+    Pubkey::find_program_address(&[GLOBAL_VOLUME_ACCUMULATOR_SEED], &PUMP_SWAP_PROGRAM).0
+}
+
+pub fn user_volume_accumulator_address(user: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[USER_VOLUME_ACCUMULATOR_SEED, user.as_ref()], &PUMP_SWAP_PROGRAM).0
+}
+
+/// Fee tier config of the PumpSwap program, owned by the fee program
+pub fn fee_config_address() -> Pubkey {
+    Pubkey::find_program_address(&[FEE_CONFIG_SEED, PUMP_SWAP_PROGRAM.as_ref()], &PUMP_FEE_PROGRAM).0
+}
+
+/// PumpSwap `Pool` account
+#[derive(Debug, Clone, PartialEq)]
+pub struct PumpSwapPool {
+    pub index: u16,
+    pub creator: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub lp_mint: Pubkey,
+    pub pool_base_token_account: Pubkey,
+    pub pool_quote_token_account: Pubkey,
+    pub lp_supply: u64,
+    pub coin_creator: Pubkey, // Default key on pools without a creator fee
+}
+
+impl PumpSwapPool {
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        if data.len() < 8 || data[..8] != POOL_DISCRIMINATOR {
+            return Err(anyhow!("Account is not a PumpSwap Pool"));
+        }
+        let body = &data[8..];
+        let truncated = || anyhow!("PumpSwap Pool account is truncated");
+        let key = |offset: usize| transaction_parser::parse_public_key(body, offset).ok_or_else(truncated);
+        let index_bytes = body.get(1..3).ok_or_else(truncated)?;
+        Ok(Self {
+            index: u16::from_le_bytes([index_bytes[0], index_bytes[1]]),
+            creator: key(3)?,
+            base_mint: key(35)?,
+            quote_mint: key(67)?,
+            lp_mint: key(99)?,
+            pool_base_token_account: key(131)?,
+            pool_quote_token_account: key(163)?,
+            lp_supply: transaction_parser::parse_u64(body, 195).ok_or_else(truncated)?,
+            coin_creator: transaction_parser::parse_public_key(body, 203).unwrap_or_default(),
+        })
+    }
+}
+
+/// PumpSwap `GlobalConfig` account
+#[derive(Debug, Clone, PartialEq)]
+pub struct PumpSwapGlobalConfig {
+    pub lp_fee_basis_points: u64,
+    pub protocol_fee_basis_points: u64,
+    pub protocol_fee_recipients: Vec<Pubkey>,
+    pub coin_creator_fee_basis_points: u64,
+}
+
+impl PumpSwapGlobalConfig {
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        if data.len() < 8 || data[..8] != GLOBAL_CONFIG_DISCRIMINATOR {
+            return Err(anyhow!("Account is not a PumpSwap GlobalConfig"));
+        }
+        let body = &data[8..];
+        let truncated = || anyhow!("PumpSwap GlobalConfig account is truncated");
+        let protocol_fee_recipients = (0..8)
+            .filter_map(|slot| transaction_parser::parse_public_key(body, 49 + slot * 32))
+            .filter(|recipient| *recipient != Pubkey::default())
+            .collect();
+        Ok(Self {
+            lp_fee_basis_points: transaction_parser::parse_u64(body, 32).ok_or_else(truncated)?,
+            protocol_fee_basis_points: transaction_parser::parse_u64(body, 40).ok_or_else(truncated)?,
+            protocol_fee_recipients,
+            coin_creator_fee_basis_points: transaction_parser::parse_u64(body, 305).unwrap_or_default(),
+        })
+    }
+}
+
+/// LP, protocol and coin creator fees, each charged on the quote side and rounded up
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PumpSwapFees {
+    pub lp_fee_basis_points: u64,
+    pub protocol_fee_basis_points: u64,
+    pub coin_creator_fee_basis_points: u64,
+}
+
+impl PumpSwapFees {
+    pub fn from_config(config: &PumpSwapGlobalConfig, pool: &PumpSwapPool) -> Self {
+        Self {
+            lp_fee_basis_points: config.lp_fee_basis_points,
+            protocol_fee_basis_points: config.protocol_fee_basis_points,
+            coin_creator_fee_basis_points: if pool.coin_creator == Pubkey::default() {
+                0
+            } else {
+                config.coin_creator_fee_basis_points
+            },
+        }
+    }
+
+    /// Recover the rates from the fees charged in a streamed trade. Small trades are ignored,
+    /// since per-component rounding makes their rates unreliable.
+    pub fn from_trade_info(trade_info: &TradeInfoFromToken) -> Option<Self> {
+        let total_fee = trade_info.platform_fee + trade_info.protocol_fee + trade_info.creator_fee;
+        // Fees are added to the pool input on buys and taken from the pool output on sells
+        let pool_quote = if trade_info.is_buy {
+            trade_info.amount_in.saturating_sub(total_fee)
+        } else {
+            trade_info.amount_out.saturating_add(total_fee)
+        };
+        if trade_info.dex_type != DexType::PumpSwap || pool_quote < BASIS_POINTS * 1_000 {
+            return None;
+        }
+        let rate = |fee: u64| (fee as u128 * BASIS_POINTS as u128 / pool_quote as u128) as u64;
+        Some(Self {
+            lp_fee_basis_points: rate(trade_info.platform_fee),
+            protocol_fee_basis_points: rate(trade_info.protocol_fee),
+            coin_creator_fee_basis_points: rate(trade_info.creator_fee),
+        })
+    }
+
+    fn total_basis_points(&self) -> u64 {
+        self.lp_fee_basis_points + self.protocol_fee_basis_points + self.coin_creator_fee_basis_points
+    }
+
+    /// Total fee charged on `quote_amount` moved through the pool
+    pub fn total_fee(&self, quote_amount: u64) -> u64 {
+        [self.lp_fee_basis_points, self.protocol_fee_basis_points, self.coin_creator_fee_basis_points]
+            .iter()
+            .map(|bps| constant_product::fee(quote_amount, *bps, BASIS_POINTS))
+            .sum()
+    }
+
+    /// Largest pool input whose cost including fees fits in `gross` lamports
+    pub fn net_of(&self, gross: u64) -> u64 {
+        let mut net = (gross as u128 * BASIS_POINTS as u128 / (BASIS_POINTS + self.total_basis_points()) as u128) as u64;
+        while net > 0 && net + self.total_fee(net) > gross {
+            net -= 1;
+        }
+        net
+    }
+
+    /// Smallest pool output that still leaves `net_amount` once fees are deducted
+    pub fn gross_up(&self, net_amount: u64) -> Option<u64> {
+        let mut gross = constant_product::gross_up(net_amount, self.total_basis_points(), BASIS_POINTS)?;
+        while gross.saturating_sub(self.total_fee(gross)) < net_amount {
+            gross = gross.checked_add(1)?;
+        }
+        Some(gross)
+    }
+}
+
+/// Quote a buy spending `sol_in` lamports including fees. Buys name the token amount, so
+/// slippage is expressed as the extra SOL the buy may cost.
+pub fn quote_buy_exact_in(sol_in: u64, base_reserve: u64, quote_reserve: u64, fees: &PumpSwapFees, slippage_bps: u64) -> SwapQuote {
+    let pool_quote_in = fees.net_of(sol_in);
+    let amount_out = constant_product::amount_out(pool_quote_in, quote_reserve, base_reserve);
+    SwapQuote {
+        amount_in: sol_in,
+        amount_out,
+        fee: fees.total_fee(pool_quote_in),
+        minimum_amount_out: amount_out,
+        maximum_amount_in: apply_max_slippage(sol_in, slippage_bps),
+    }
+}
+
+/// Quote a buy of exactly `tokens_out` raw tokens; fees are added on top of the pool cost
+pub fn quote_buy_exact_out(tokens_out: u64, base_reserve: u64, quote_reserve: u64, fees: &PumpSwapFees, slippage_bps: u64) -> Option<SwapQuote> {
+    let cost = constant_product::amount_in(tokens_out, quote_reserve, base_reserve)?;
+    let fee = fees.total_fee(cost);
+    Some(SwapQuote {
+        amount_in: cost + fee,
+        amount_out: tokens_out,
+        fee,
+        minimum_amount_out: tokens_out,
+        maximum_amount_in: apply_max_slippage(cost + fee, slippage_bps),
+    })
+}
+
+/// Quote a sell of `tokens_in` raw tokens: fees come off the SOL the pool pays out
+pub fn quote_sell_exact_in(tokens_in: u64, base_reserve: u64, quote_reserve: u64, fees: &PumpSwapFees, slippage_bps: u64) -> SwapQuote {
+    let gross_amount_out = constant_product::amount_out(tokens_in, base_reserve, quote_reserve);
+    let fee = fees.total_fee(gross_amount_out);
+    let amount_out = gross_amount_out.saturating_sub(fee);
+    SwapQuote {
+        amount_in: tokens_in,
+        amount_out,
+        fee,
+        minimum_amount_out: apply_slippage(amount_out, slippage_bps),
+        maximum_amount_in: tokens_in,
+    }
+}
+
+/// Quote a sell receiving `sol_out` lamports after fees. Sells name the token amount, so the
+/// slippage allowance goes on the tokens offered and `sol_out` is the floor.
+pub fn quote_sell_exact_out(sol_out: u64, base_reserve: u64, quote_reserve: u64, fees: &PumpSwapFees, slippage_bps: u64) -> Option<SwapQuote> {
+    let gross_amount_out = fees.gross_up(sol_out)?;
+    let amount_in = constant_product::amount_in(gross_amount_out, base_reserve, quote_reserve)?;
+    Some(SwapQuote {
+        amount_in,
+        amount_out: sol_out,
+        fee: fees.total_fee(gross_amount_out),
+        minimum_amount_out: sol_out,
+        maximum_amount_in: apply_max_slippage(amount_in, slippage_bps),
+    })
+}
+
+/// PumpSwap BuyEvent or SellEvent; both share one Borsh layout with the amounts named per side
+#[derive(Clone, Debug)]
+pub struct PumpSwapTradeEvent {
+    pub is_buy: bool,
+    pub timestamp: i64,
+    pub base_amount: u64, // Tokens received on a buy, sold on a sell
+    pub pool_base_token_reserves: u64, // Reserves before the trade
+    pub pool_quote_token_reserves: u64,
+    pub quote_amount: u64, // Pool quote moved, fees excluded
+    pub lp_fee: u64,
+    pub protocol_fee: u64,
+    pub user_quote_amount: u64, // Quote paid including fees on a buy, received after fees on a sell
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub user_base_token_account: Pubkey,
+    pub user_quote_token_account: Pubkey,
+    pub coin_creator: Option<Pubkey>,
+    pub coin_creator_fee: u64,
+}
+
+impl PumpSwapTradeEvent {
+    /// (SOL reserve, token reserve) after the trade; the LP fee stays in the pool
+    pub fn reserves_after(&self) -> (u64, u64) {
+        if self.is_buy {
+            (
+                self.pool_quote_token_reserves + self.quote_amount + self.lp_fee,
+                self.pool_base_token_reserves.saturating_sub(self.base_amount),
+            )
+        } else {
+            (
+                self.pool_quote_token_reserves.saturating_sub(self.quote_amount.saturating_sub(self.lp_fee)),
+                self.pool_base_token_reserves + self.base_amount,
+            )
+        }
+    }
+}
+
+/// Decode a PumpSwap BuyEvent or SellEvent from CPI event data or a base64 decoded `Program data:` log
+pub fn decode_trade_event(data: &[u8]) -> Option<PumpSwapTradeEvent> {
+    let payload = transaction_parser::event_payload(data);
+    if payload.len() < 8 + TRADE_EVENT_LEGACY_LEN {
+        return None;
+    }
+    let is_buy = if payload[..8] == BUY_EVENT_DISCRIMINATOR {
+        true
+    } else if payload[..8] == SELL_EVENT_DISCRIMINATOR {
+        false
+    } else {
+        return None;
+    };
+    let body = &payload[8..];
+    let has_creator = body.len() >= TRADE_EVENT_CREATOR_LEN;
+    Some(PumpSwapTradeEvent {
+        is_buy,
+        timestamp: transaction_parser::parse_u64(body, 0)? as i64,
+        base_amount: transaction_parser::parse_u64(body, 8)?,
+        pool_base_token_reserves: transaction_parser::parse_u64(body, 40)?,
+        pool_quote_token_reserves: transaction_parser::parse_u64(body, 48)?,
+        quote_amount: transaction_parser::parse_u64(body, 56)?,
+        lp_fee: transaction_parser::parse_u64(body, 72)?,
+        protocol_fee: transaction_parser::parse_u64(body, 88)?,
+        user_quote_amount: transaction_parser::parse_u64(body, 104)?,
+        pool: transaction_parser::parse_public_key(body, 112)?,
+        user: transaction_parser::parse_public_key(body, 144)?,
+        user_base_token_account: transaction_parser::parse_public_key(body, 176)?,
+        user_quote_token_account: transaction_parser::parse_public_key(body, 208)?,
+        coin_creator: if has_creator {
+            transaction_parser::parse_public_key(body, 304).filter(|creator| *creator != Pubkey::default())
+        } else {
+            None
+        },
+        coin_creator_fee: if has_creator { transaction_parser::parse_u64(body, 344)? } else { 0 },
+    })
+}
+
+/// SOL-quoted PumpSwap trades in execution order. Self-CPI event data is preferred; the
+/// `Program data:` logs are used when it is absent. Mints come from the user's token balances.
+pub fn parse_trades(txn: &SubscribeUpdateTransaction, account_keys: &[Pubkey]) -> Vec<TradeInfoFromToken> {
+    let cpi_events: Vec<(usize, Option<usize>, PumpSwapTradeEvent)> =
+        transaction_parser::program_instructions(txn, account_keys, &PUMP_SWAP_PROGRAM)
+            .into_iter()
+            .filter(|ix| ix.inner_index.is_some())
+            .filter_map(|ix| Some((ix.instruction_index, ix.inner_index, decode_trade_event(ix.data)?)))
+            .collect();
+    let events = if cpi_events.is_empty() {
+        transaction_parser::program_data_logs(txn, &PUMP_SWAP_PROGRAM)
+            .into_iter()
+            .filter_map(|(index, data)| Some((index, None, decode_trade_event(&data)?)))
+            .collect()
+    } else {
+        cpi_events
+    };
+
+    events
+        .into_iter()
+        .filter_map(|(instruction_index, inner_index, event)| {
+            let mint = transaction_parser::token_account_mint(txn, account_keys, &event.user_base_token_account)?;
+            let quote_mint = transaction_parser::token_account_mint(txn, account_keys, &event.user_quote_token_account)?;
+            if quote_mint != SOL_MINT {
+                return None;
+            }
+            Some(build_trade_info(txn, instruction_index, inner_index, &mint, &event))
+        })
+        .collect()
+}
+
+fn build_trade_info(
+    txn: &SubscribeUpdateTransaction,
+    instruction_index: usize,
+    inner_index: Option<usize>,
+    mint: &Pubkey,
+    event: &PumpSwapTradeEvent,
+) -> TradeInfoFromToken {
+    let signature = txn.transaction
+        .as_ref()
+        .map(|tx_inner| bs58::encode(&tx_inner.signature).into_string())
+        .unwrap_or_default();
+
+    // Match the launchpad convention: buys report the SOL spent including fees, sells the SOL received
+    let (amount_in, amount_out) = if event.is_buy {
+        (event.user_quote_amount, event.base_amount)
+    } else {
+        (event.base_amount, event.user_quote_amount)
+    };
+    let token_scale = 10f64.powi(LAUNCHPAD_TOKEN_DECIMALS as i32);
+    let (sol_change, token_change) = if event.is_buy {
+        (-(amount_in as f64) / 1_000_000_000.0, amount_out as f64 / token_scale)
+    } else {
+        (amount_out as f64 / 1_000_000_000.0, -(amount_in as f64) / token_scale)
+    };
+    let (sol_reserve, token_reserve) = event.reserves_after();
+
+    TradeInfoFromToken {
+        dex_type: DexType::PumpSwap,
+        slot: txn.slot,
+        signature,
+        pool_id: event.pool.to_string(),
+        mint: mint.to_string(),
+        quote_mint: SOL_MINT.to_string(),
+        timestamp: event.timestamp.max(0) as u64,
+        is_buy: event.is_buy,
+        price: calculate_price(sol_reserve, token_reserve),
+        is_reverse: false,
+        coin_creator: event.coin_creator.map(|creator| creator.to_string()),
+        sol_change,
+        token_change,
+        liquidity: sol_reserve as f64 / 1_000_000_000.0,
+        virtual_sol_reserves: sol_reserve,
+        virtual_token_reserves: token_reserve,
+        amount_in,
+        amount_out,
+        real_sol_reserves: sol_reserve,
+        real_token_reserves: token_reserve,
+        curve_virtual_base: 0, // Launchpad only
+        curve_virtual_quote: 0,
+        total_base_sell: 0,
+        protocol_fee: event.protocol_fee,
+        platform_fee: event.lp_fee, // The LP fee is reported as the platform fee
+        creator_fee: event.coin_creator_fee,
+        share_fee: 0,
+        instruction_index,
+        inner_instruction_index: inner_index,
+        user: event.user.to_string(),
+        base_vault: String::new(),
+        quote_vault: String::new(),
+    }
+}
+
+pub struct PumpSwap {
+    pub keypair: Arc<Keypair>,
+    pub rpc_client: Option<Arc<solana_client::rpc_client::RpcClient>>,
+    pub rpc_nonblocking_client: Option<Arc<solana_client::nonblocking::rpc_client::RpcClient>>,
+    global_config: OnceCell<PumpSwapGlobalConfig>,
+}
+
+impl PumpSwap {
+    pub fn new(
+        keypair: Arc<Keypair>,
+        rpc_client: Option<Arc<solana_client::rpc_client::RpcClient>>,
+        rpc_nonblocking_client: Option<Arc<solana_client::nonblocking::rpc_client::RpcClient>>,
+    ) -> Self {
+        Self {
+            keypair,
+            rpc_client,
+            rpc_nonblocking_client,
+            global_config: OnceCell::new(),
+        }
+    }
+
+    fn client(&self) -> Result<&Arc<solana_client::nonblocking::rpc_client::RpcClient>> {
+        self.rpc_nonblocking_client.as_ref()
+            .ok_or_else(|| anyhow!("RPC client not initialized"))
+    }
+
+    /// Global config, read once; fee rates and recipients change rarely
+    async fn get_global_config(&self) -> Result<&PumpSwapGlobalConfig> {
+        self.global_config.get_or_try_init(|| async {
+            let account = self.client()?.get_account(&global_config_address()).await
+                .map_err(|e| anyhow!("PumpSwap global config not found: {}", e))?;
+            PumpSwapGlobalConfig::decode(&account.data)
+        }).await
+    }
+
+    /// Pool trading `mint` against SOL: the canonical migration pool, otherwise a program account scan
+    pub async fn find_pool(&self, mint: &Pubkey) -> Result<(Pubkey, PumpSwapPool)> {
+        let client = self.client()?;
+        let canonical_pool = canonical_pool_address(mint);
+        if let Ok(account) = client.get_account(&canonical_pool).await {
+            return Ok((canonical_pool, PumpSwapPool::decode(&account.data)?));
+        }
+
+        let logger = Logger::new("[PUMPSWAP-GET-POOL] => ".blue().to_string());
+        logger.log(format!("No canonical PumpSwap pool for {}, falling back to a program account scan", mint).yellow().to_string());
+        let accounts = client.get_program_accounts_with_config(
+            &PUMP_SWAP_PROGRAM,
+            RpcProgramAccountsConfig {
+                filters: Some(vec![
+                    RpcFilterType::Memcmp(Memcmp::new(POOL_BASE_MINT_OFFSET, MemcmpEncodedBytes::Base64(base64::encode(mint.to_bytes())))),
+                    RpcFilterType::Memcmp(Memcmp::new(POOL_QUOTE_MINT_OFFSET, MemcmpEncodedBytes::Base64(base64::encode(SOL_MINT.to_bytes())))),
+                ]),
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ).await
+        .map_err(|e| anyhow!("Failed to scan PumpSwap pools for {}: {}", mint, e))?;
+        accounts
+            .iter()
+            .find_map(|(pool_id, account)| Some((*pool_id, PumpSwapPool::decode(&account.data).ok()?)))
+            .ok_or_else(|| anyhow!("Failed to find PumpSwap pool for mint {}", mint))
+    }
+
+    /// Pool of the trade when it was parsed from this venue, otherwise the pool of its mint
+    async fn pool_for_trade(&self, trade_info: &TradeInfoFromToken, mint: &Pubkey) -> Result<(Pubkey, PumpSwapPool)> {
+        match Pubkey::from_str(&trade_info.pool_id) {
+            Ok(pool_id) if trade_info.dex_type == DexType::PumpSwap => {
+                let account = self.client()?.get_account(&pool_id).await
+                    .map_err(|e| anyhow!("PumpSwap pool {} not found: {}", pool_id, e))?;
+                Ok((pool_id, PumpSwapPool::decode(&account.data)?))
+            }
+            _ => self.find_pool(mint).await,
+        }
+    }
+
+    /// (base reserve, quote reserve) read from the pool token accounts
+    async fn get_reserves(&self, pool: &PumpSwapPool) -> Result<(u64, u64)> {
+        let vaults = self.client()?
+            .get_multiple_accounts(&[pool.pool_base_token_account, pool.pool_quote_token_account]).await
+            .map_err(|e| anyhow!("Failed to fetch PumpSwap pool token accounts: {}", e))?;
+        let amount = |index: usize| {
+            vaults.get(index)
+                .and_then(|vault| vault.as_ref())
+                .and_then(|vault| token::token_account_amount(&vault.data))
+                .ok_or_else(|| anyhow!("PumpSwap pool token account {} is missing", index))
+        };
+        Ok((amount(0)?, amount(1)?))
+    }
+
+    /// Spot price in SOL per whole token, read from the live pool
+    pub async fn get_token_price(&self, mint_str: &str) -> Result<f64> {
+        let mint = Pubkey::from_str(mint_str).map_err(|_| anyhow!("Invalid mint address"))?;
+        let (pool_id, pool) = self.find_pool(&mint).await?;
+        let (base_reserve, quote_reserve) = self.get_reserves(&pool).await?;
+        if base_reserve == 0 {
+            return Err(anyhow!("PumpSwap pool {} has no token reserve", pool_id));
+        }
+        Ok(calculate_price(quote_reserve, base_reserve) / 1_000_000_000.0)
+    }
+
+    /// Fee rates of the trade when it was streamed from this venue, otherwise the global config
+    async fn get_fees(&self, trade_info: &TradeInfoFromToken, pool: &PumpSwapPool) -> Result<PumpSwapFees> {
+        match PumpSwapFees::from_trade_info(trade_info) {
+            Some(fees) => Ok(fees),
+            None => Ok(PumpSwapFees::from_config(self.get_global_config().await?, pool)),
+        }
+    }
+
+    /// Quote a swap of `amount` raw units. For exact-out swaps `amount` is the output.
+    async fn quote_swap(
+        &self,
+        trade_info: &TradeInfoFromToken,
+        pool: &PumpSwapPool,
+        swap_direction: &SwapDirection,
+        exact_out: bool,
+        amount: u64,
+        slippage_bps: u64,
+    ) -> Result<SwapQuote> {
+        if pool.quote_mint != SOL_MINT {
+            return Err(anyhow!("PumpSwap pool for {} is not quoted in SOL", pool.base_mint));
+        }
+        let fees = self.get_fees(trade_info, pool).await?;
+        let (base_reserve, quote_reserve) = self.get_reserves(pool).await?;
+        let quote = match (swap_direction, exact_out) {
+            (SwapDirection::Buy, false) => Some(quote_buy_exact_in(amount, base_reserve, quote_reserve, &fees, slippage_bps)),
+            (SwapDirection::Buy, true) => quote_buy_exact_out(amount, base_reserve, quote_reserve, &fees, slippage_bps),
+            (SwapDirection::Sell, false) => Some(quote_sell_exact_in(amount, base_reserve, quote_reserve, &fees, slippage_bps)),
+            (SwapDirection::Sell, true) => quote_sell_exact_out(amount, base_reserve, quote_reserve, &fees, slippage_bps),
+        };
+        quote.ok_or_else(|| anyhow!("PumpSwap pool for {} cannot fill an exact output of {}", trade_info.mint, amount))
+    }
+
+    async fn get_token_balance(&self, token_account: &Pubkey, mint: &Pubkey) -> Result<u64> {
+        self.client()?.get_token_account(token_account).await
+            .map_err(|e| anyhow!("Failed to get token account balance: {}", e))?
+            .ok_or_else(|| anyhow!("Token account does not exist for mint {}", mint))?
+            .token_amount
+            .amount
+            .parse::<u64>()
+            .map_err(|_| anyhow!("Failed to parse token balance for mint {}", mint))
+    }
+
+    pub async fn build_swap_from_parsed_data(
+        &self,
+        trade_info: &TradeInfoFromToken,
+        swap_config: SwapConfig,
+    ) -> Result<SwapInstructions> {
+        let owner = self.keypair.pubkey();
+        let mint = Pubkey::from_str(&trade_info.mint)?;
+        let (pool_id, pool) = self.pool_for_trade(trade_info, &mint).await?;
+        priority_fees::track_fee_account(&pool_id);
+        let token_program = token::get_mint_token_program(self.client()?.clone(), &mint).await?;
+        let token_ata = token::get_associated_token_address_for_program(&owner, &mint, &token_program);
+        let wsol_ata = token::get_associated_token_address_for_program(&owner, &SOL_MINT, &TOKEN_PROGRAM);
+
+        let mut instructions = Vec::with_capacity(3);
+        let logger = Logger::new("[PUMPSWAP-ATA-CREATE] => ".yellow().to_string());
+        for (ata, ata_mint, ata_program) in [(token_ata, mint, token_program), (wsol_ata, SOL_MINT, TOKEN_PROGRAM)] {
+            if !WALLET_TOKEN_ACCOUNTS.contains(&ata) {
+                logger.log(format!("Creating token ATA for mint {} at address {}", ata_mint, ata));
+                instructions.push(create_associated_token_account_idempotent(&owner, &owner, &ata_mint, &ata_program));
+                WALLET_TOKEN_ACCOUNTS.insert(ata);
+            }
+        }
+
+        // Buy: SOL to spend, or tokens to receive for ExactOut.
+        // Sell: tokens from the wallet balance (qty/pct), or SOL to receive for ExactOut.
+        let exact_out = swap_config.in_type == SwapInType::ExactOut;
+        let mut token_balance = None;
+        let amount = match swap_config.swap_direction {
+            SwapDirection::Buy if exact_out => ui_amount_to_amount(swap_config.amount_in, LAUNCHPAD_TOKEN_DECIMALS as u8),
+            SwapDirection::Buy => ui_amount_to_amount(swap_config.amount_in, 9),
+            SwapDirection::Sell => {
+                let balance = self.get_token_balance(&token_ata, &mint).await?;
+                token_balance = Some(balance);
+                match swap_config.in_type {
+                    SwapInType::Qty => ui_amount_to_amount(swap_config.amount_in, LAUNCHPAD_TOKEN_DECIMALS as u8),
+                    SwapInType::Pct => ((swap_config.amount_in.min(1.0) * balance as f64) as u64).max(1),
+                    SwapInType::ExactOut => ui_amount_to_amount(swap_config.amount_in, 9),
+                }
+            }
+        };
+
+        let quote = self.quote_swap(trade_info, &pool, &swap_config.swap_direction, exact_out, amount, swap_config.slippage).await?;
+        let protocol_fee_recipient = *self.get_global_config().await?
+            .protocol_fee_recipients
+            .first()
+            .ok_or_else(|| anyhow!("PumpSwap global config lists no protocol fee recipient"))?;
+        let accounts = PumpSwapAccounts {
+            pool_id,
+            pool: &pool,
+            user: owner,
+            user_base_token_account: token_ata,
+            user_quote_token_account: wsol_ata,
+            base_token_program: token_program,
+            protocol_fee_recipient,
+        };
+
+        // Both instructions name the token amount; the SOL side carries the slippage bound
+        let instruction = match swap_config.swap_direction {
+            SwapDirection::Buy => {
+                if quote.amount_out == 0 {
+                    return Err(anyhow!("Quote for {} returned no output (amount in {})", mint, amount));
+                }
+                create_buy_instruction(&accounts, quote.amount_out, quote.maximum_amount_in)
+            }
+            SwapDirection::Sell => {
+                let tokens_in = if exact_out { quote.maximum_amount_in } else { quote.amount_in };
+                if let Some(balance) = token_balance {
+                    if exact_out && quote.amount_in > balance {
+                        return Err(anyhow!(
+                            "Exact-out sell of {} needs {} tokens but the wallet holds {}",
+                            mint, quote.amount_in, balance
+                        ));
+                    }
+                }
+                let tokens_in = token_balance.map_or(tokens_in, |balance| tokens_in.min(balance));
+                if !exact_out && quote.minimum_amount_out == 0 {
+                    return Err(anyhow!("Quote for {} returned no output (amount in {})", mint, amount));
+                }
+                create_sell_instruction(&accounts, tokens_in, quote.minimum_amount_out)
+            }
+        };
+        instructions.push(instruction);
+
+        let price_in_sol = if trade_info.dex_type == DexType::PumpSwap && trade_info.price > 0.0 {
+            trade_info.price / 1_000_000_000.0
+        } else {
+            self.get_token_price(&trade_info.mint).await.unwrap_or_default()
+        };
+        Ok((self.keypair.clone(), instructions, price_in_sol))
+    }
+}
+
+#[async_trait]
+impl Dex for PumpSwap {
+    fn protocol(&self) -> SwapProtocol {
+        SwapProtocol::PumpSwap
+    }
+
+    fn program_id(&self) -> Pubkey {
+        PUMP_SWAP_PROGRAM
+    }
+
+    fn discovers_launches(&self) -> bool {
+        false // Migrated tokens are followed by the per-token and target wallet streams
+    }
+
+    async fn pool_for_mint(&self, mint: &str) -> Result<PoolKeys> {
+        let mint = Pubkey::from_str(mint).map_err(|_| anyhow!("Invalid mint address"))?;
+        let (pool_id, pool) = self.find_pool(&mint).await?;
+        Ok(PoolKeys {
+            pool_id,
+            base_mint: pool.base_mint,
+            quote_mint: pool.quote_mint,
+            base_vault: pool.pool_base_token_account,
+            quote_vault: pool.pool_quote_token_account,
+        })
+    }
+
+    async fn quote(&self, trade_info: &TradeInfoFromToken, swap_config: &SwapConfig, amount: u64) -> Result<SwapQuote> {
+        let mint = Pubkey::from_str(&trade_info.mint)?;
+        let (_, pool) = self.pool_for_trade(trade_info, &mint).await?;
+        self.quote_swap(
+            trade_info,
+            &pool,
+            &swap_config.swap_direction,
+            swap_config.in_type == SwapInType::ExactOut,
+            amount,
+            swap_config.slippage,
+        ).await
+    }
+
+    async fn build_buy(&self, trade_info: &TradeInfoFromToken, mut swap_config: SwapConfig) -> Result<SwapInstructions> {
+        swap_config.swap_direction = SwapDirection::Buy;
+        self.build_swap_from_parsed_data(trade_info, swap_config).await
+    }
+
+    async fn build_sell(&self, trade_info: &TradeInfoFromToken, mut swap_config: SwapConfig) -> Result<SwapInstructions> {
+        swap_config.swap_direction = SwapDirection::Sell;
+        self.build_swap_from_parsed_data(trade_info, swap_config).await
+    }
+
+    async fn token_price(&self, mint: &str) -> Result<f64> {
+        self.get_token_price(mint).await
+    }
+
+    fn price_from_trade(&self, trade_info: &TradeInfoFromToken) -> f64 {
+        // The parser prices PumpSwap trades from the post-trade reserves, in lamports per whole token
+        trade_info.price / 1_000_000_000.0
+    }
+}
+
+/// Accounts shared by `buy` and `sell`
+pub struct PumpSwapAccounts<'a> {
+    pub pool_id: Pubkey,
+    pub pool: &'a PumpSwapPool,
+    pub user: Pubkey,
+    pub user_base_token_account: Pubkey,
+    pub user_quote_token_account: Pubkey,
+    pub base_token_program: Pubkey,
+    pub protocol_fee_recipient: Pubkey,
+}
+
+impl PumpSwapAccounts<'_> {
+    fn metas(&self) -> Vec<AccountMeta> {
+        let quote_mint = self.pool.quote_mint;
+        let coin_creator_vault_authority = creator_vault_authority_address(&self.pool.coin_creator);
+        vec![
+            AccountMeta::new(self.pool_id, false),
+            AccountMeta::new(self.user, true),
+            AccountMeta::new_readonly(global_config_address(), false),
+            AccountMeta::new_readonly(self.pool.base_mint, false),
+            AccountMeta::new_readonly(quote_mint, false),
+            AccountMeta::new(self.user_base_token_account, false),
+            AccountMeta::new(self.user_quote_token_account, false),
+            AccountMeta::new(self.pool.pool_base_token_account, false),
+            AccountMeta::new(self.pool.pool_quote_token_account, false),
+            AccountMeta::new_readonly(self.protocol_fee_recipient, false),
+            AccountMeta::new(
+                token::get_associated_token_address_for_program(&self.protocol_fee_recipient, &quote_mint, &TOKEN_PROGRAM),
+                false,
+            ),
+            AccountMeta::new_readonly(self.base_token_program, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM, false),
+            AccountMeta::new_readonly(event_authority_address(), false),
+            AccountMeta::new_readonly(PUMP_SWAP_PROGRAM, false),
+            AccountMeta::new(
+                token::get_associated_token_address_for_program(&coin_creator_vault_authority, &quote_mint, &TOKEN_PROGRAM),
+                false,
+            ),
+            AccountMeta::new_readonly(coin_creator_vault_authority, false),
+        ]
+    }
+}
+
+/// `buy`: receive exactly `tokens_out`, paying at most `max_quote_in` lamports including fees
+pub fn create_buy_instruction(accounts: &PumpSwapAccounts, tokens_out: u64, max_quote_in: u64) -> Instruction {
+    let mut metas = accounts.metas();
+    metas.extend([
+        AccountMeta::new(global_volume_accumulator_address(), false),
+        AccountMeta::new(user_volume_accumulator_address(&accounts.user), false),
+        AccountMeta::new_readonly(fee_config_address(), false),
+        AccountMeta::new_readonly(PUMP_FEE_PROGRAM, false),
+    ]);
+
+    let mut data = Vec::with_capacity(25);
+    data.extend_from_slice(&BUY_DISCRIMINATOR);
+    data.extend_from_slice(&tokens_out.to_le_bytes());
+    data.extend_from_slice(&max_quote_in.to_le_bytes());
+    data.push(0); // track_volume: volume rewards are not claimed by the bot
+
+    Instruction { program_id: PUMP_SWAP_PROGRAM, accounts: metas, data }
+}
+
+/// `sell`: sell exactly `tokens_in`, receiving at least `min_quote_out` lamports after fees
+pub fn create_sell_instruction(accounts: &PumpSwapAccounts, tokens_in: u64, min_quote_out: u64) -> Instruction {
+    let mut metas = accounts.metas();
+    metas.extend([
+        AccountMeta::new_readonly(fee_config_address(), false),
+        AccountMeta::new_readonly(PUMP_FEE_PROGRAM, false),
+    ]);
+
+    let mut data = Vec::with_capacity(24);
+    data.extend_from_slice(&SELL_DISCRIMINATOR);
+    data.extend_from_slice(&tokens_in.to_le_bytes());
+    data.extend_from_slice(&min_quote_out.to_le_bytes());
+
+    Instruction { program_id: PUMP_SWAP_PROGRAM, accounts: metas, data }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE_RESERVE: u64 = 206_900_000_000_000;
+    const QUOTE_RESERVE: u64 = 84_990_359_057;
+
+    /// 0.2% LP, 0.05% protocol and 0.05% coin creator fee
+    fn fees() -> PumpSwapFees {
+        PumpSwapFees { lp_fee_basis_points: 20, protocol_fee_basis_points: 5, coin_creator_fee_basis_points: 5 }
+    }
+
+    /// Account or event data: `discriminator` followed by a zeroed body of `len` bytes with
+    /// `fields` written at their body offsets
+    fn account_data(discriminator: [u8; 8], len: usize, fields: &[(usize, &[u8])]) -> Vec<u8> {
+        let mut data = discriminator.to_vec();
+        data.resize(8 + len, 0);
+        for (offset, bytes) in fields {
+            data[8 + offset..8 + offset + bytes.len()].copy_from_slice(bytes);
+        }
+        data
+    }
+
+    #[test]
+    fn pool_and_global_config_decode_at_their_offsets() {
+        let base_mint = Pubkey::new_from_array([2; 32]);
+        let data = account_data(POOL_DISCRIMINATOR, 235, &[
+            (1, &7u16.to_le_bytes()),
+            (3, &[1; 32]), // creator
+            (POOL_BASE_MINT_OFFSET - 8, base_mint.as_ref()),
+            (POOL_QUOTE_MINT_OFFSET - 8, SOL_MINT.as_ref()),
+            (99, &[3; 32]), // lp_mint
+            (131, &[4; 32]), // pool_base_token_account
+            (163, &[5; 32]), // pool_quote_token_account
+            (195, &1_000_000u64.to_le_bytes()),
+            (203, &[6; 32]), // coin_creator
+        ]);
+        let pool = PumpSwapPool::decode(&data).unwrap();
+        assert_eq!(pool, PumpSwapPool {
+            index: 7,
+            creator: Pubkey::new_from_array([1; 32]),
+            base_mint,
+            quote_mint: SOL_MINT,
+            lp_mint: Pubkey::new_from_array([3; 32]),
+            pool_base_token_account: Pubkey::new_from_array([4; 32]),
+            pool_quote_token_account: Pubkey::new_from_array([5; 32]),
+            lp_supply: 1_000_000,
+            coin_creator: Pubkey::new_from_array([6; 32]),
+        });
+        // Pools from before creator fees have no coin creator and pay no creator fee
+        let legacy = PumpSwapPool::decode(&data[..8 + 203]).unwrap();
+        assert_eq!(legacy.coin_creator, Pubkey::default());
+        assert!(PumpSwapPool::decode(&data[..8 + 202]).is_err());
+
+        let recipient = Pubkey::new_from_array([9; 32]);
+        let data = account_data(GLOBAL_CONFIG_DISCRIMINATOR, 313, &[
+            (32, &20u64.to_le_bytes()),
+            (40, &5u64.to_le_bytes()),
+            (49 + 32, recipient.as_ref()), // Second of eight recipient slots
+            (305, &5u64.to_le_bytes()),
+        ]);
+        let config = PumpSwapGlobalConfig::decode(&data).unwrap();
+        assert_eq!(config.protocol_fee_recipients, vec![recipient]);
+        assert_eq!(PumpSwapFees::from_config(&config, &pool), fees());
+        assert_eq!(PumpSwapFees::from_config(&config, &legacy).coin_creator_fee_basis_points, 0);
+        assert!(PumpSwapGlobalConfig::decode(&account_data(POOL_DISCRIMINATOR, 313, &[])).is_err());
+    }
+
+    #[test]
+    fn trade_events_decode_at_their_borsh_offsets() {
+        let fields: [(usize, &[u8]); 13] = [
+            (0, &1_760_000_000i64.to_le_bytes()),
+            (8, &2_400_000_000_000u64.to_le_bytes()), // base_amount_out
+            (40, &BASE_RESERVE.to_le_bytes()),
+            (48, &QUOTE_RESERVE.to_le_bytes()),
+            (56, &1_000_000_000u64.to_le_bytes()), // quote_amount_in
+            (72, &2_000_000u64.to_le_bytes()), // lp_fee
+            (88, &500_000u64.to_le_bytes()), // protocol_fee
+            (104, &1_003_000_000u64.to_le_bytes()), // user_quote_amount_in
+            (112, &[1; 32]), // pool
+            (144, &[2; 32]), // user
+            (176, &[3; 32]), // user_base_token_account
+            (208, &[4; 32]), // user_quote_token_account
+            (304, &[5; 32]), // coin_creator
+        ];
+        let mut event_data = transaction_parser::EVENT_IX_TAG.to_vec();
+        event_data.extend(account_data(BUY_EVENT_DISCRIMINATOR, TRADE_EVENT_CREATOR_LEN, &fields));
+        event_data[8 + 8 + 344..].copy_from_slice(&500_000u64.to_le_bytes());
+
+        // Self-CPI event data carries the event tag, `Program data:` logs do not
+        for data in [&event_data[..], &event_data[8..]] {
+            let event = decode_trade_event(data).unwrap();
+            assert!(event.is_buy);
+            assert_eq!(event.timestamp, 1_760_000_000);
+            assert_eq!(event.base_amount, 2_400_000_000_000);
+            assert_eq!((event.pool_base_token_reserves, event.pool_quote_token_reserves), (BASE_RESERVE, QUOTE_RESERVE));
+            assert_eq!((event.quote_amount, event.lp_fee, event.protocol_fee), (1_000_000_000, 2_000_000, 500_000));
+            assert_eq!(event.user_quote_amount, 1_003_000_000);
+            assert_eq!((event.pool, event.user), (Pubkey::new_from_array([1; 32]), Pubkey::new_from_array([2; 32])));
+            assert_eq!(event.user_base_token_account, Pubkey::new_from_array([3; 32]));
+            assert_eq!(event.user_quote_token_account, Pubkey::new_from_array([4; 32]));
+            assert_eq!((event.coin_creator, event.coin_creator_fee), (Some(Pubkey::new_from_array([5; 32])), 500_000));
+            // The LP fee stays in the pool
+            assert_eq!(event.reserves_after(), (QUOTE_RESERVE + 1_002_000_000, BASE_RESERVE - 2_400_000_000_000));
+        }
+
+        // Sells share the layout; events from before creator fees have no coin creator
+        let mut sell = SELL_EVENT_DISCRIMINATOR.to_vec();
+        sell.extend_from_slice(&event_data[16..16 + TRADE_EVENT_LEGACY_LEN]);
+        let event = decode_trade_event(&sell).unwrap();
+        assert!(!event.is_buy);
+        assert_eq!((event.coin_creator, event.coin_creator_fee), (None, 0));
+        assert_eq!(event.reserves_after(), (QUOTE_RESERVE - 998_000_000, BASE_RESERVE + 2_400_000_000_000));
+        assert!(decode_trade_event(&sell[..sell.len() - 1]).is_none());
+    }
+
+    #[test]
+    fn net_of_and_gross_up_are_tight() {
+        for fees in [fees(), PumpSwapFees::default()] {
+            for amount in [0, 1, 7, 999, 1_000_000, 123_456_789, 85_000_000_000] {
+                let net = fees.net_of(amount);
+                assert!(net + fees.total_fee(net) <= amount, "{:?}: {} does not fit in {}", fees, net, amount);
+                assert!(net + 1 + fees.total_fee(net + 1) > amount, "{:?}: {} is not maximal for {}", fees, net, amount);
+
+                let gross = fees.gross_up(amount).unwrap();
+                assert!(gross - fees.total_fee(gross) >= amount, "{:?}: {} does not cover {}", fees, gross, amount);
+                if gross > 0 {
+                    let smaller = gross - 1;
+                    assert!(smaller.saturating_sub(fees.total_fee(smaller)) < amount, "{:?}: {} is not minimal for {}", fees, gross, amount);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fee_rates_are_recovered_from_buys_and_sells() {
+        let buy = TradeInfoFromToken {
+            dex_type: DexType::PumpSwap,
+            is_buy: true,
+            amount_in: 1_003_000_000,
+            platform_fee: 2_000_000,
+            protocol_fee: 500_000,
+            creator_fee: 500_000,
+            ..Default::default()
+        };
+        assert_eq!(PumpSwapFees::from_trade_info(&buy), Some(fees()));
+        let sell = TradeInfoFromToken { is_buy: false, amount_in: 0, amount_out: 997_000_000, ..buy.clone() };
+        assert_eq!(PumpSwapFees::from_trade_info(&sell), Some(fees()));
+        let pump_fun = TradeInfoFromToken { dex_type: DexType::PumpFun, ..buy };
+        assert_eq!(PumpSwapFees::from_trade_info(&pump_fun), None);
+    }
+
+    #[test]
+    fn buys_pay_fees_on_top_of_the_pool_input() {
+        let fees = fees();
+        let quote = quote_buy_exact_in(1_003_000_000, BASE_RESERVE, QUOTE_RESERVE, &fees, 0);
+        assert_eq!(quote.fee, 3_000_000);
+        assert_eq!(quote.amount_out, constant_product::amount_out(1_000_000_000, QUOTE_RESERVE, BASE_RESERVE));
+        assert_eq!(quote.maximum_amount_in, 1_003_000_000);
+        assert_eq!(quote_buy_exact_in(1_003_000_000, BASE_RESERVE, QUOTE_RESERVE, &fees, 10_000).maximum_amount_in, 2_006_000_000);
+
+        let exact_out = quote_buy_exact_out(quote.amount_out, BASE_RESERVE, QUOTE_RESERVE, &fees, 0).unwrap();
+        let cost = constant_product::amount_in(quote.amount_out, QUOTE_RESERVE, BASE_RESERVE).unwrap();
+        assert_eq!(exact_out.amount_in, cost + fees.total_fee(cost));
+        assert!(exact_out.amount_in <= 1_003_000_000);
+        assert!(quote_buy_exact_out(BASE_RESERVE, BASE_RESERVE, QUOTE_RESERVE, &fees, 0).is_none());
+    }
+
+    #[test]
+    fn sells_pay_fees_on_the_pool_output() {
+        let fees = fees();
+        let tokens_in = 2_400_000_000_000;
+        let gross = constant_product::amount_out(tokens_in, BASE_RESERVE, QUOTE_RESERVE);
+        let quote = quote_sell_exact_in(tokens_in, BASE_RESERVE, QUOTE_RESERVE, &fees, 100);
+        assert_eq!(quote.fee, fees.total_fee(gross));
+        assert_eq!(quote.amount_out + quote.fee, gross);
+        assert_eq!(quote.minimum_amount_out, apply_slippage(quote.amount_out, 100));
+
+        let quote = quote_sell_exact_out(500_000_000, BASE_RESERVE, QUOTE_RESERVE, &fees, 0).unwrap();
+        let gross = constant_product::amount_out(quote.amount_in, BASE_RESERVE, QUOTE_RESERVE);
+        assert!(gross - fees.total_fee(gross) >= 500_000_000);
+        assert_eq!(quote.minimum_amount_out, 500_000_000);
+        assert_eq!(quote_sell_exact_out(500_000_000, BASE_RESERVE, QUOTE_RESERVE, &fees, 10_000).unwrap().maximum_amount_in, quote.amount_in * 2);
+        assert!(quote_sell_exact_out(QUOTE_RESERVE, BASE_RESERVE, QUOTE_RESERVE, &fees, 0).is_none());
+    }
+}
@@ -0,0 +1,141 @@
+//! Quote mints launchpad pools trade against.
+//!
+//! Let's Bonk pools are quoted in SOL or in a stablecoin. Amounts in a pool's quote mint are
+//! converted to SOL with a cached rate read from the quote mint's Raydium CPMM pool against SOL,
+//! so prices, liquidity and PnL stay denominated in SOL whatever the pool is quoted in.
+
+use std::time::{Duration, Instant};
+use anyhow::{anyhow, Result};
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::dex::raydium_cpmm::RaydiumCpmm;
+use crate::dex::raydium_launchpad::SOL_MINT;
+
+pub const USD1_MINT: Pubkey = solana_sdk::pubkey!("USD1ttGY1N17NEEHLmELoaybftRBUSErhqYiQzvEmuB");
+pub const USDC_MINT: Pubkey = solana_sdk::pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+pub const KNOWN_QUOTE_MINTS: [Pubkey; 3] = [SOL_MINT, USD1_MINT, USDC_MINT];
+pub const DEFAULT_QUOTE_DECIMALS: u8 = 6; // Stablecoin quotes; unknown mints learn theirs from the CPMM pool
+pub const QUOTE_RATE_MAX_AGE: Duration = Duration::from_secs(60); // Older rates are re-read before trading
+
+lazy_static! {
+    /// SOL per whole quote unit, with the time it was read
+    static ref QUOTE_RATES: DashMap<Pubkey, (f64, Instant)> = DashMap::new();
+    static ref QUOTE_DECIMALS: DashMap<Pubkey, u8> = DashMap::new();
+}
+
+pub fn is_sol(mint: &Pubkey) -> bool {
+    *mint == SOL_MINT
+}
+
+/// Parse a quote mint as carried by parsed trades; empty or invalid strings are SOL
+pub fn parse_quote_mint(mint: &str) -> Pubkey {
+    mint.parse().unwrap_or(SOL_MINT)
+}
+
+pub fn quote_decimals(mint: &Pubkey) -> u8 {
+    if is_sol(mint) {
+        return 9;
+    }
+    QUOTE_DECIMALS.get(mint).map_or(DEFAULT_QUOTE_DECIMALS, |decimals| *decimals)
+}
+
+/// Cached SOL per whole quote unit; always 1 for SOL, `None` until a non-SOL rate is read
+pub fn sol_per_quote(mint: &Pubkey) -> Option<f64> {
+    if is_sol(mint) {
+        return Some(1.0);
+    }
+    QUOTE_RATES.get(mint).map(|rate| rate.0).filter(|rate| *rate > 0.0)
+}
+
+pub fn set_sol_per_quote(mint: &Pubkey, rate: f64) {
+    if !is_sol(mint) && rate.is_finite() && rate > 0.0 {
+        QUOTE_RATES.insert(*mint, (rate, Instant::now()));
+    }
+}
+
+/// Register a quote mint seen in a trade so the rate updater keeps its rate fresh
+pub fn track_quote_mint(mint: &Pubkey) {
+    if !is_sol(mint) && !QUOTE_RATES.contains_key(mint) {
+        QUOTE_RATES.insert(*mint, (0.0, Instant::now())); // A zero rate is never used
+    }
+}
+
+/// Quote mints with a cached or pending rate, plus the known stablecoins
+pub fn tracked_quote_mints() -> Vec<Pubkey> {
+    let mut mints: Vec<Pubkey> = QUOTE_RATES.iter().map(|entry| *entry.key()).collect();
+    for mint in KNOWN_QUOTE_MINTS.iter().filter(|mint| !is_sol(mint)) {
+        if !mints.contains(mint) {
+            mints.push(*mint);
+        }
+    }
+    mints
+}
+
+/// Raw quote units in SOL
+pub fn quote_to_sol(mint: &Pubkey, amount: u64) -> Option<f64> {
+    let rate = sol_per_quote(mint)?;
+    Some(amount as f64 / 10f64.powi(quote_decimals(mint) as i32) * rate)
+}
+
+/// Raw quote units in lamports
+pub fn quote_to_lamports(mint: &Pubkey, amount: u64) -> Option<u64> {
+    if is_sol(mint) {
+        return Some(amount);
+    }
+    quote_to_sol(mint, amount).map(|sol| (sol * 1_000_000_000.0) as u64)
+}
+
+/// Price in raw quote units per whole token converted to lamports per whole token
+pub fn quote_price_to_lamports(mint: &Pubkey, price: f64) -> Option<f64> {
+    if is_sol(mint) {
+        return Some(price);
+    }
+    let rate = sol_per_quote(mint)?;
+    Some(price / 10f64.powi(quote_decimals(mint) as i32) * rate * 1_000_000_000.0)
+}
+
+/// Lamports in raw quote units
+pub fn lamports_to_quote(mint: &Pubkey, lamports: u64) -> Option<u64> {
+    if is_sol(mint) {
+        return Some(lamports);
+    }
+    let rate = sol_per_quote(mint)?;
+    Some((lamports as f64 / 1_000_000_000.0 / rate * 10f64.powi(quote_decimals(mint) as i32)) as u64)
+}
+
+/// Read the rate of `mint` from its CPMM pool against SOL and cache it
+pub async fn refresh_rate(cpmm: &RaydiumCpmm, mint: &Pubkey) -> Result<f64> {
+    if is_sol(mint) {
+        return Ok(1.0);
+    }
+    let pool_id = cpmm.find_pool(mint).await?;
+    let pool = cpmm.load_pool(&pool_id).await?;
+    let decimals = if pool.state.token_0_mint == *mint {
+        pool.state.mint_0_decimals
+    } else {
+        pool.state.mint_1_decimals
+    };
+    QUOTE_DECIMALS.insert(*mint, decimals);
+    let (sol_reserve, quote_reserve) = pool.sol_and_token_reserves()?;
+    if quote_reserve == 0 {
+        return Err(anyhow!("CPMM pool {} has no {} reserve", pool_id, mint));
+    }
+    let rate = (sol_reserve as f64 / 1_000_000_000.0) / (quote_reserve as f64 / 10f64.powi(decimals as i32));
+    set_sol_per_quote(mint, rate);
+    Ok(rate)
+}
+
+/// Cached rate when it is fresh, otherwise a rate read from chain
+pub async fn ensure_rate(cpmm: &RaydiumCpmm, mint: &Pubkey) -> Result<f64> {
+    if is_sol(mint) {
+        return Ok(1.0);
+    }
+    if let Some(rate) = QUOTE_RATES.get(mint) {
+        if rate.0 > 0.0 && rate.1.elapsed() < QUOTE_RATE_MAX_AGE {
+            return Ok(rate.0);
+        }
+    }
+    refresh_rate(cpmm, mint).await
+}
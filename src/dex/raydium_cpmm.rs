@@ -0,0 +1,1042 @@
+//! Raydium CPMM (CP-Swap) venue, where launchpad tokens trade once their curve migrated.
+//!
+//! Decodes `PoolState` and `AmmConfig`, quotes against the vault balances net of accrued fees,
+//! and builds `swap_base_input` / `swap_base_output` instructions. Pools trade the token against
+//! wrapped SOL, so buys spend from and sells pay into the wallet's WSOL account.
+
+use std::{str::FromStr, sync::Arc};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use colored::Colorize;
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use spl_token::ui_amount_to_amount;
+use yellowstone_grpc_proto::geyser::SubscribeUpdateTransaction;
+
+use crate::common::{cache::WALLET_TOKEN_ACCOUNTS, config::SwapConfig, logger::Logger};
+use crate::core::token;
+use crate::dex::constant_product;
+use crate::dex::raydium_launchpad::{apply_max_slippage, apply_slippage, FEE_RATE_DENOMINATOR, SOL_MINT, TOKEN_PROGRAM};
+use crate::dex::venue::{Dex, PoolKeys, SwapInstructions, SwapQuote};
+use crate::engine::swap::{SwapDirection, SwapInType, SwapProtocol};
+use crate::engine::transaction_parser::{
+    self, calculate_price, DexType, TradeInfoFromToken, LAUNCHPAD_TOKEN_DECIMALS,
+};
+use crate::services::priority_fees;
+
+pub const RAYDIUM_CPMM_PROGRAM: Pubkey = solana_sdk::pubkey!("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C");
+pub const RAYDIUM_CPMM_AUTHORITY: Pubkey = solana_sdk::pubkey!("GpMZbSM2GgvTKHJirzeGfMFoaZ8UR2X7F4v8vHTvxFbL");
+pub const SWAP_BASE_INPUT_DISCRIMINATOR: [u8; 8] = [143, 190, 90, 218, 196, 30, 51, 222]; // swap_base_input discriminator
+pub const SWAP_BASE_OUTPUT_DISCRIMINATOR: [u8; 8] = [55, 217, 98, 86, 163, 74, 180, 173]; // swap_base_output discriminator
+pub const POOL_STATE_DISCRIMINATOR: [u8; 8] = [247, 237, 227, 245, 215, 195, 222, 70]; // account:PoolState
+pub const AMM_CONFIG_DISCRIMINATOR: [u8; 8] = [218, 244, 33, 104, 203, 203, 43, 111]; // account:AmmConfig
+pub const SWAP_EVENT_DISCRIMINATOR: [u8; 8] = [64, 198, 205, 232, 38, 8, 113, 226]; // event:SwapEvent
+
+// Account positions in swap_base_input / swap_base_output (both share the same accounts)
+pub const SWAP_ACCOUNT_PAYER: usize = 0;
+pub const SWAP_ACCOUNT_POOL_STATE: usize = 3;
+pub const SWAP_ACCOUNT_INPUT_VAULT: usize = 6;
+pub const SWAP_ACCOUNT_OUTPUT_VAULT: usize = 7;
+pub const SWAP_ACCOUNT_INPUT_MINT: usize = 10;
+pub const SWAP_ACCOUNT_OUTPUT_MINT: usize = 11;
+pub const SWAP_ACCOUNTS_LEN: usize = 13;
+
+pub const POOL_STATE_TOKEN_0_MINT_OFFSET: usize = 168;
+pub const POOL_STATE_TOKEN_1_MINT_OFFSET: usize = 200;
+const POOL_STATUS_SWAP_DISABLED: u8 = 1 << 2;
+
+// SwapEvent body sizes: mints and fee fields were appended after launch
+const SWAP_EVENT_LEGACY_LEN: usize = 81;
+const SWAP_EVENT_LEN: usize = 162;
+
+lazy_static! {
+    /// CPMM pool of each token the bot tracks or looked up, learned from migrations, streamed
+    /// trades and program account scans
+    pub static ref CPMM_POOLS: DashMap<String, Pubkey> = DashMap::new();
+}
+
+/// Remember the pool a token trades in, so lookups skip the program account scan
+pub fn record_pool(mint: &str, pool_id: &Pubkey) {
+    CPMM_POOLS.insert(mint.to_string(), *pool_id);
+}
+
+/// Index the pool of a streamed CPMM trade; trades on other venues are ignored
+pub fn record_trade(trade_info: &TradeInfoFromToken) {
+    if trade_info.dex_type != DexType::RaydiumCpmm {
+        return;
+    }
+    if let Ok(pool_id) = Pubkey::from_str(&trade_info.pool_id) {
+        record_pool(&trade_info.mint, &pool_id);
+    }
+}
+
+/// Token whose fees pay the pool creator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CreatorFeeOn {
+    BothToken, // Charged on whichever token is the input
+    OnlyToken0,
+    OnlyToken1,
+}
+
+/// CP-Swap `PoolState` account
+#[derive(Debug, Clone, PartialEq)]
+pub struct CpmmPoolState {
+    pub amm_config: Pubkey,
+    pub token_0_vault: Pubkey,
+    pub token_1_vault: Pubkey,
+    pub token_0_mint: Pubkey,
+    pub token_1_mint: Pubkey,
+    pub token_0_program: Pubkey,
+    pub token_1_program: Pubkey,
+    pub observation_key: Pubkey,
+    pub status: u8,
+    pub mint_0_decimals: u8,
+    pub mint_1_decimals: u8,
+    pub protocol_fees_token_0: u64,
+    pub protocol_fees_token_1: u64,
+    pub fund_fees_token_0: u64,
+    pub fund_fees_token_1: u64,
+    pub open_time: u64,
+    pub creator_fee_on: CreatorFeeOn,
+    pub enable_creator_fee: bool,
+    pub creator_fees_token_0: u64,
+    pub creator_fees_token_1: u64,
+}
+
+impl CpmmPoolState {
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        if data.len() < 8 || data[..8] != POOL_STATE_DISCRIMINATOR {
+            return Err(anyhow!("Account is not a CPMM PoolState"));
+        }
+        let body = &data[8..];
+        let truncated = || anyhow!("CPMM PoolState account is truncated");
+        let key = |offset: usize| transaction_parser::parse_public_key(body, offset).ok_or_else(truncated);
+        let byte = |offset: usize| transaction_parser::parse_u8(body, offset).ok_or_else(truncated);
+        let field = |offset: usize| transaction_parser::parse_u64(body, offset).ok_or_else(truncated);
+        // Creator fee fields replaced padding, so pools created before them read as zero
+        let optional_field = |offset: usize| transaction_parser::parse_u64(body, offset).unwrap_or_default();
+        Ok(Self {
+            amm_config: key(0)?,
+            token_0_vault: key(64)?,
+            token_1_vault: key(96)?,
+            token_0_mint: key(160)?,
+            token_1_mint: key(192)?,
+            token_0_program: key(224)?,
+            token_1_program: key(256)?,
+            observation_key: key(288)?,
+            status: byte(321)?,
+            mint_0_decimals: byte(323)?,
+            mint_1_decimals: byte(324)?,
+            protocol_fees_token_0: field(333)?,
+            protocol_fees_token_1: field(341)?,
+            fund_fees_token_0: field(349)?,
+            fund_fees_token_1: field(357)?,
+            open_time: field(365)?,
+            creator_fee_on: match transaction_parser::parse_u8(body, 381).unwrap_or_default() {
+                1 => CreatorFeeOn::OnlyToken0,
+                2 => CreatorFeeOn::OnlyToken1,
+                _ => CreatorFeeOn::BothToken,
+            },
+            enable_creator_fee: transaction_parser::parse_u8(body, 382).unwrap_or_default() != 0,
+            creator_fees_token_0: optional_field(389),
+            creator_fees_token_1: optional_field(397),
+        })
+    }
+
+    pub fn is_swap_enabled(&self) -> bool {
+        self.status & POOL_STATUS_SWAP_DISABLED == 0
+    }
+
+    /// Whether token 0 is the traded token, i.e. token 1 is wrapped SOL; `None` for non-SOL pairs
+    pub fn token_is_0(&self) -> Option<bool> {
+        if self.token_1_mint == SOL_MINT {
+            Some(true)
+        } else if self.token_0_mint == SOL_MINT {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Tradable reserves: vault balances less the protocol, fund and creator fees not yet collected
+    pub fn reserves(&self, vault_0_amount: u64, vault_1_amount: u64) -> (u64, u64) {
+        (
+            vault_0_amount
+                .saturating_sub(self.protocol_fees_token_0)
+                .saturating_sub(self.fund_fees_token_0)
+                .saturating_sub(self.creator_fees_token_0),
+            vault_1_amount
+                .saturating_sub(self.protocol_fees_token_1)
+                .saturating_sub(self.fund_fees_token_1)
+                .saturating_sub(self.creator_fees_token_1),
+        )
+    }
+
+    /// Whether the creator fee of a swap spending token 0 (or token 1) comes off the input
+    pub fn is_creator_fee_on_input(&self, input_is_token_0: bool) -> bool {
+        match self.creator_fee_on {
+            CreatorFeeOn::BothToken => true,
+            CreatorFeeOn::OnlyToken0 => input_is_token_0,
+            CreatorFeeOn::OnlyToken1 => !input_is_token_0,
+        }
+    }
+}
+
+/// CP-Swap `AmmConfig` account; rates are over `FEE_RATE_DENOMINATOR`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AmmConfig {
+    pub trade_fee_rate: u64,
+    pub protocol_fee_rate: u64, // Share of the trade fee, does not change what the trader pays
+    pub fund_fee_rate: u64, // Share of the trade fee, does not change what the trader pays
+    pub creator_fee_rate: u64,
+}
+
+impl AmmConfig {
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        if data.len() < 8 || data[..8] != AMM_CONFIG_DISCRIMINATOR {
+            return Err(anyhow!("Account is not a CPMM AmmConfig"));
+        }
+        let body = &data[8..];
+        let field = |offset: usize| {
+            transaction_parser::parse_u64(body, offset).ok_or_else(|| anyhow!("CPMM AmmConfig account is truncated"))
+        };
+        Ok(Self {
+            trade_fee_rate: field(4)?,
+            protocol_fee_rate: field(12)?,
+            fund_fee_rate: field(20)?,
+            creator_fee_rate: transaction_parser::parse_u64(body, 100).unwrap_or_default(),
+        })
+    }
+}
+
+/// Fee rates charged on one swap direction of a pool
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CpmmFees {
+    pub trade_fee_rate: u64,
+    pub creator_fee_rate: u64,
+    pub creator_fee_on_input: bool,
+}
+
+impl CpmmFees {
+    pub fn new(pool: &CpmmPoolState, config: &AmmConfig, input_is_token_0: bool) -> Self {
+        Self {
+            trade_fee_rate: config.trade_fee_rate,
+            creator_fee_rate: if pool.enable_creator_fee { config.creator_fee_rate } else { 0 },
+            creator_fee_on_input: pool.is_creator_fee_on_input(input_is_token_0),
+        }
+    }
+
+    /// Fees taken from `amount_in` before it reaches the curve
+    fn input_fee(&self, amount_in: u64) -> u64 {
+        let creator_fee = if self.creator_fee_on_input {
+            constant_product::fee(amount_in, self.creator_fee_rate, FEE_RATE_DENOMINATOR)
+        } else {
+            0
+        };
+        constant_product::fee(amount_in, self.trade_fee_rate, FEE_RATE_DENOMINATOR) + creator_fee
+    }
+
+    /// Creator fee taken from the curve output
+    fn output_fee(&self, amount_out: u64) -> u64 {
+        if self.creator_fee_on_input {
+            return 0;
+        }
+        constant_product::fee(amount_out, self.creator_fee_rate, FEE_RATE_DENOMINATOR)
+    }
+}
+
+/// Quote `swap_base_input`: spend exactly `amount_in`
+pub fn quote_swap_base_input(amount_in: u64, reserve_in: u64, reserve_out: u64, fees: &CpmmFees, slippage_bps: u64) -> SwapQuote {
+    let input_fee = fees.input_fee(amount_in);
+    let curve_out = constant_product::amount_out(amount_in.saturating_sub(input_fee), reserve_in, reserve_out);
+    let output_fee = fees.output_fee(curve_out);
+    let amount_out = curve_out.saturating_sub(output_fee);
+    SwapQuote {
+        amount_in,
+        amount_out,
+        fee: input_fee + output_fee,
+        minimum_amount_out: apply_slippage(amount_out, slippage_bps),
+        maximum_amount_in: amount_in,
+    }
+}
+
+/// Quote `swap_base_output`: receive exactly `amount_out`
+pub fn quote_swap_base_output(amount_out: u64, reserve_in: u64, reserve_out: u64, fees: &CpmmFees, slippage_bps: u64) -> Option<SwapQuote> {
+    let curve_out = if fees.creator_fee_on_input {
+        amount_out
+    } else {
+        constant_product::gross_up(amount_out, fees.creator_fee_rate, FEE_RATE_DENOMINATOR)?
+    };
+    let curve_in = constant_product::amount_in(curve_out, reserve_in, reserve_out)?;
+    let input_rate = fees.trade_fee_rate + if fees.creator_fee_on_input { fees.creator_fee_rate } else { 0 };
+    let mut amount_in = constant_product::gross_up(curve_in, input_rate, FEE_RATE_DENOMINATOR)?;
+    // Each fee component rounds up on its own
+    while amount_in.saturating_sub(fees.input_fee(amount_in)) < curve_in {
+        amount_in = amount_in.checked_add(1)?;
+    }
+    Some(SwapQuote {
+        amount_in,
+        amount_out,
+        fee: fees.input_fee(amount_in) + (curve_out - amount_out),
+        minimum_amount_out: amount_out,
+        maximum_amount_in: apply_max_slippage(amount_in, slippage_bps),
+    })
+}
+
+/// A pool with its config and tradable reserves, read from chain
+#[derive(Debug, Clone)]
+pub struct CpmmPool {
+    pub pool_id: Pubkey,
+    pub state: CpmmPoolState,
+    pub config: AmmConfig,
+    pub reserve_0: u64,
+    pub reserve_1: u64,
+}
+
+impl CpmmPool {
+    fn token_is_0(&self) -> Result<bool> {
+        self.state.token_is_0()
+            .ok_or_else(|| anyhow!("CPMM pool {} does not trade against SOL", self.pool_id))
+    }
+
+    /// (token mint, token vault, token program, SOL vault)
+    pub fn token_side(&self) -> Result<(Pubkey, Pubkey, Pubkey, Pubkey)> {
+        let state = &self.state;
+        Ok(if self.token_is_0()? {
+            (state.token_0_mint, state.token_0_vault, state.token_0_program, state.token_1_vault)
+        } else {
+            (state.token_1_mint, state.token_1_vault, state.token_1_program, state.token_0_vault)
+        })
+    }
+
+    /// (SOL reserve, token reserve)
+    pub fn sol_and_token_reserves(&self) -> Result<(u64, u64)> {
+        Ok(if self.token_is_0()? {
+            (self.reserve_1, self.reserve_0)
+        } else {
+            (self.reserve_0, self.reserve_1)
+        })
+    }
+
+    /// Spot price in SOL per whole token
+    pub fn spot_price(&self) -> Result<f64> {
+        let (sol_reserve, token_reserve) = self.sol_and_token_reserves()?;
+        if token_reserve == 0 {
+            return Err(anyhow!("CPMM pool {} has no token reserve", self.pool_id));
+        }
+        Ok(calculate_price(sol_reserve, token_reserve) / 1_000_000_000.0)
+    }
+
+    /// Quote a swap of `amount` raw units. For exact-out swaps `amount` is the output.
+    pub fn quote(&self, swap_direction: &SwapDirection, exact_out: bool, amount: u64, slippage_bps: u64) -> Result<SwapQuote> {
+        let token_is_0 = self.token_is_0()?;
+        let (sol_reserve, token_reserve) = self.sol_and_token_reserves()?;
+        // Buys spend SOL, sells spend the token
+        let (reserve_in, reserve_out, input_is_token_0) = match swap_direction {
+            SwapDirection::Buy => (sol_reserve, token_reserve, !token_is_0),
+            SwapDirection::Sell => (token_reserve, sol_reserve, token_is_0),
+        };
+        let fees = CpmmFees::new(&self.state, &self.config, input_is_token_0);
+        if exact_out {
+            quote_swap_base_output(amount, reserve_in, reserve_out, &fees, slippage_bps)
+                .ok_or_else(|| anyhow!("CPMM pool {} cannot fill an exact output of {}", self.pool_id, amount))
+        } else {
+            Ok(quote_swap_base_input(amount, reserve_in, reserve_out, &fees, slippage_bps))
+        }
+    }
+}
+
+/// CP-Swap SwapEvent, Borsh layout as emitted by the program
+#[derive(Clone, Debug)]
+pub struct SwapEvent {
+    pub pool_id: Pubkey,
+    pub input_vault_before: u64,
+    pub output_vault_before: u64,
+    pub input_amount: u64,
+    pub output_amount: u64,
+    pub input_transfer_fee: u64,
+    pub output_transfer_fee: u64,
+    pub base_input: bool,
+    pub input_mint: Option<Pubkey>, // Absent from events of the launch layout
+    pub output_mint: Option<Pubkey>,
+    pub trade_fee: u64,
+    pub creator_fee: u64,
+}
+
+/// Decode a CP-Swap SwapEvent from CPI event data or a base64 decoded `Program data:` log
+pub fn decode_swap_event(data: &[u8]) -> Option<SwapEvent> {
+    let payload = transaction_parser::event_payload(data);
+    if payload.len() < 8 + SWAP_EVENT_LEGACY_LEN || payload[..8] != SWAP_EVENT_DISCRIMINATOR {
+        return None;
+    }
+    let body = &payload[8..];
+    let has_mints = body.len() >= SWAP_EVENT_LEN;
+    Some(SwapEvent {
+        pool_id: transaction_parser::parse_public_key(body, 0)?,
+        input_vault_before: transaction_parser::parse_u64(body, 32)?,
+        output_vault_before: transaction_parser::parse_u64(body, 40)?,
+        input_amount: transaction_parser::parse_u64(body, 48)?,
+        output_amount: transaction_parser::parse_u64(body, 56)?,
+        input_transfer_fee: transaction_parser::parse_u64(body, 64)?,
+        output_transfer_fee: transaction_parser::parse_u64(body, 72)?,
+        base_input: transaction_parser::parse_u8(body, 80)? != 0,
+        input_mint: if has_mints { transaction_parser::parse_public_key(body, 81) } else { None },
+        output_mint: if has_mints { transaction_parser::parse_public_key(body, 113) } else { None },
+        trade_fee: if has_mints { transaction_parser::parse_u64(body, 145)? } else { 0 },
+        creator_fee: if has_mints { transaction_parser::parse_u64(body, 153)? } else { 0 },
+    })
+}
+
+/// Accounts of a swap_base_input / swap_base_output instruction
+#[derive(Clone, Debug)]
+pub struct CpmmSwapInstruction {
+    pub instruction_index: usize,
+    pub inner_index: Option<usize>,
+    pub payer: Pubkey,
+    pub pool_state: Pubkey,
+    pub input_vault: Pubkey,
+    pub output_vault: Pubkey,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+}
+
+/// All CPMM swap instructions in execution order
+pub fn find_swap_instructions(txn: &SubscribeUpdateTransaction, account_keys: &[Pubkey]) -> Vec<CpmmSwapInstruction> {
+    transaction_parser::program_instructions(txn, account_keys, &RAYDIUM_CPMM_PROGRAM)
+        .into_iter()
+        .filter(|ix| {
+            ix.data.len() >= 8
+                && ix.accounts.len() >= SWAP_ACCOUNTS_LEN
+                && (ix.data[..8] == SWAP_BASE_INPUT_DISCRIMINATOR || ix.data[..8] == SWAP_BASE_OUTPUT_DISCRIMINATOR)
+        })
+        .filter_map(|ix| {
+            let account = |position: usize| account_keys.get(ix.accounts[position] as usize).copied();
+            Some(CpmmSwapInstruction {
+                instruction_index: ix.instruction_index,
+                inner_index: ix.inner_index,
+                payer: account(SWAP_ACCOUNT_PAYER)?,
+                pool_state: account(SWAP_ACCOUNT_POOL_STATE)?,
+                input_vault: account(SWAP_ACCOUNT_INPUT_VAULT)?,
+                output_vault: account(SWAP_ACCOUNT_OUTPUT_VAULT)?,
+                input_mint: account(SWAP_ACCOUNT_INPUT_MINT)?,
+                output_mint: account(SWAP_ACCOUNT_OUTPUT_MINT)?,
+            })
+        })
+        .collect()
+}
+
+/// SOL-paired CPMM trades in execution order. Each SwapEvent is matched to the first swap of the
+/// same outer instruction and pool; events without a swap are kept when they name their mints.
+pub fn parse_trades(txn: &SubscribeUpdateTransaction, account_keys: &[Pubkey]) -> Vec<TradeInfoFromToken> {
+    let mut swaps: Vec<Option<CpmmSwapInstruction>> = find_swap_instructions(txn, account_keys)
+        .into_iter()
+        .map(Some)
+        .collect();
+    let cpi_events: Vec<(usize, SwapEvent)> =
+        transaction_parser::program_instructions(txn, account_keys, &RAYDIUM_CPMM_PROGRAM)
+            .into_iter()
+            .filter(|ix| ix.inner_index.is_some())
+            .filter_map(|ix| Some((ix.instruction_index, decode_swap_event(ix.data)?)))
+            .collect();
+    let events = if cpi_events.is_empty() {
+        transaction_parser::program_data_logs(txn, &RAYDIUM_CPMM_PROGRAM)
+            .into_iter()
+            .filter_map(|(index, data)| Some((index, decode_swap_event(&data)?)))
+            .collect()
+    } else {
+        cpi_events
+    };
+
+    events
+        .into_iter()
+        .filter_map(|(instruction_index, event)| {
+            let swap = swaps
+                .iter_mut()
+                .find(|slot| {
+                    slot.as_ref().is_some_and(|swap| {
+                        swap.instruction_index == instruction_index && swap.pool_state == event.pool_id
+                    })
+                })
+                .and_then(|slot| slot.take());
+            build_trade_info(txn, instruction_index, swap.as_ref(), &event)
+        })
+        .collect()
+}
+
+fn build_trade_info(
+    txn: &SubscribeUpdateTransaction,
+    instruction_index: usize,
+    swap: Option<&CpmmSwapInstruction>,
+    event: &SwapEvent,
+) -> Option<TradeInfoFromToken> {
+    let (input_mint, output_mint) = match swap {
+        Some(swap) => (swap.input_mint, swap.output_mint),
+        None => (event.input_mint?, event.output_mint?),
+    };
+    // Only SOL pairs are traded; buys spend SOL for the token
+    let (is_buy, mint) = if input_mint == SOL_MINT {
+        (true, output_mint)
+    } else if output_mint == SOL_MINT {
+        (false, input_mint)
+    } else {
+        return None;
+    };
+    let signature = txn.transaction
+        .as_ref()
+        .map(|tx_inner| bs58::encode(&tx_inner.signature).into_string())
+        .unwrap_or_default();
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    // Reserves after the trade, from the vault balances (accrued fees included)
+    let input_vault_after = (event.input_vault_before + event.input_amount).saturating_sub(event.input_transfer_fee);
+    let output_vault_after = event.output_vault_before.saturating_sub(event.output_amount);
+    let (sol_reserve, token_reserve) = if is_buy {
+        (input_vault_after, output_vault_after)
+    } else {
+        (output_vault_after, input_vault_after)
+    };
+    let amount_in = event.input_amount;
+    let amount_out = event.output_amount.saturating_sub(event.output_transfer_fee);
+    let token_scale = 10f64.powi(LAUNCHPAD_TOKEN_DECIMALS as i32);
+    let (sol_change, token_change) = if is_buy {
+        (-(amount_in as f64) / 1_000_000_000.0, amount_out as f64 / token_scale)
+    } else {
+        (amount_out as f64 / 1_000_000_000.0, -(amount_in as f64) / token_scale)
+    };
+    let (token_vault, sol_vault) = match swap {
+        Some(swap) if is_buy => (swap.output_vault.to_string(), swap.input_vault.to_string()),
+        Some(swap) => (swap.input_vault.to_string(), swap.output_vault.to_string()),
+        None => (String::new(), String::new()),
+    };
+
+    Some(TradeInfoFromToken {
+        dex_type: DexType::RaydiumCpmm,
+        slot: txn.slot,
+        signature,
+        pool_id: event.pool_id.to_string(),
+        mint: mint.to_string(),
+        quote_mint: SOL_MINT.to_string(),
+        timestamp,
+        is_buy,
+        price: calculate_price(sol_reserve, token_reserve),
+        is_reverse: false,
+        coin_creator: None,
+        sol_change,
+        token_change,
+        liquidity: sol_reserve as f64 / 1_000_000_000.0,
+        virtual_sol_reserves: sol_reserve,
+        virtual_token_reserves: token_reserve,
+        amount_in,
+        amount_out,
+        real_sol_reserves: sol_reserve,
+        real_token_reserves: token_reserve,
+        curve_virtual_base: 0, // Launchpad only
+        curve_virtual_quote: 0,
+        total_base_sell: 0,
+        protocol_fee: event.trade_fee,
+        platform_fee: 0,
+        creator_fee: event.creator_fee,
+        share_fee: 0,
+        instruction_index: swap.map_or(instruction_index, |swap| swap.instruction_index),
+        inner_instruction_index: swap.and_then(|swap| swap.inner_index),
+        user: swap.map(|swap| swap.payer.to_string()).unwrap_or_default(),
+        base_vault: token_vault,
+        quote_vault: sol_vault,
+    })
+}
+
+pub struct RaydiumCpmm {
+    pub keypair: Arc<Keypair>,
+    pub rpc_client: Option<Arc<solana_client::rpc_client::RpcClient>>,
+    pub rpc_nonblocking_client: Option<Arc<solana_client::nonblocking::rpc_client::RpcClient>>,
+    amm_configs: DashMap<Pubkey, AmmConfig>,
+}
+
+impl RaydiumCpmm {
+    pub fn new(
+        keypair: Arc<Keypair>,
+        rpc_client: Option<Arc<solana_client::rpc_client::RpcClient>>,
+        rpc_nonblocking_client: Option<Arc<solana_client::nonblocking::rpc_client::RpcClient>>,
+    ) -> Self {
+        Self {
+            keypair,
+            rpc_client,
+            rpc_nonblocking_client,
+            amm_configs: DashMap::new(),
+        }
+    }
+
+    fn client(&self) -> Result<&Arc<solana_client::nonblocking::rpc_client::RpcClient>> {
+        self.rpc_nonblocking_client.as_ref()
+            .ok_or_else(|| anyhow!("RPC client not initialized"))
+    }
+
+    /// Pool trading `mint` against SOL: the recorded pool, otherwise a program account scan
+    /// with the token on either side of the pair
+    pub async fn find_pool(&self, mint: &Pubkey) -> Result<Pubkey> {
+        if let Some(pool_id) = CPMM_POOLS.get(&mint.to_string()) {
+            return Ok(*pool_id);
+        }
+        let logger = Logger::new("[RAYDIUM-CPMM-GET-POOL] => ".blue().to_string());
+        logger.log(format!("CPMM pool for {} not recorded, falling back to a program account scan", mint).yellow().to_string());
+
+        let client = self.client()?;
+        let orders = [
+            (POOL_STATE_TOKEN_0_MINT_OFFSET, POOL_STATE_TOKEN_1_MINT_OFFSET),
+            (POOL_STATE_TOKEN_1_MINT_OFFSET, POOL_STATE_TOKEN_0_MINT_OFFSET),
+        ];
+        for (token_offset, sol_offset) in orders {
+            let accounts = client.get_program_accounts_with_config(
+                &RAYDIUM_CPMM_PROGRAM,
+                RpcProgramAccountsConfig {
+                    filters: Some(vec![
+                        RpcFilterType::Memcmp(Memcmp::new(token_offset, MemcmpEncodedBytes::Base64(base64::encode(mint.to_bytes())))),
+                        RpcFilterType::Memcmp(Memcmp::new(sol_offset, MemcmpEncodedBytes::Base64(base64::encode(SOL_MINT.to_bytes())))),
+                    ]),
+                    account_config: RpcAccountInfoConfig {
+                        encoding: Some(UiAccountEncoding::Base64),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+            ).await
+            .map_err(|e| anyhow!("Failed to scan CPMM pools for {}: {}", mint, e))?;
+            if let Some((pool_id, _)) = accounts.iter().find(|(_, account)| CpmmPoolState::decode(&account.data).is_ok()) {
+                record_pool(&mint.to_string(), pool_id);
+                return Ok(*pool_id);
+            }
+        }
+        Err(anyhow!("Failed to find CPMM pool for mint {}", mint))
+    }
+
+    async fn get_amm_config(&self, address: &Pubkey) -> Result<AmmConfig> {
+        if let Some(config) = self.amm_configs.get(address) {
+            return Ok(*config);
+        }
+        let account = self.client()?.get_account(address).await
+            .map_err(|e| anyhow!("CPMM config {} not found: {}", address, e))?;
+        let config = AmmConfig::decode(&account.data)?;
+        self.amm_configs.insert(*address, config);
+        Ok(config)
+    }
+
+    /// Pool state, config and reserves, read from chain
+    pub async fn load_pool(&self, pool_id: &Pubkey) -> Result<CpmmPool> {
+        let client = self.client()?;
+        let account = client.get_account(pool_id).await
+            .map_err(|e| anyhow!("CPMM pool {} not found: {}", pool_id, e))?;
+        let state = CpmmPoolState::decode(&account.data)?;
+        let vaults = client.get_multiple_accounts(&[state.token_0_vault, state.token_1_vault]).await
+            .map_err(|e| anyhow!("Failed to fetch vaults of CPMM pool {}: {}", pool_id, e))?;
+        let vault_amount = |index: usize| {
+            vaults.get(index)
+                .and_then(|vault| vault.as_ref())
+                .and_then(|vault| token::token_account_amount(&vault.data))
+                .ok_or_else(|| anyhow!("Vault {} of CPMM pool {} is missing", index, pool_id))
+        };
+        let (reserve_0, reserve_1) = state.reserves(vault_amount(0)?, vault_amount(1)?);
+        let config = self.get_amm_config(&state.amm_config).await?;
+        Ok(CpmmPool { pool_id: *pool_id, state, config, reserve_0, reserve_1 })
+    }
+
+    /// Pool of the trade when it was parsed from this venue, otherwise the pool of its mint
+    async fn pool_for_trade(&self, trade_info: &TradeInfoFromToken, mint: &Pubkey) -> Result<CpmmPool> {
+        let pool_id = match Pubkey::from_str(&trade_info.pool_id) {
+            Ok(pool_id) if trade_info.dex_type == DexType::RaydiumCpmm => pool_id,
+            _ => self.find_pool(mint).await?,
+        };
+        let pool = self.load_pool(&pool_id).await?;
+        if !pool.state.is_swap_enabled() {
+            return Err(anyhow!("Swaps are disabled on CPMM pool {}", pool_id));
+        }
+        Ok(pool)
+    }
+
+    /// Spot price in SOL per whole token, read from the live pool
+    pub async fn get_token_price(&self, mint_str: &str) -> Result<f64> {
+        let mint = Pubkey::from_str(mint_str).map_err(|_| anyhow!("Invalid mint address"))?;
+        let pool_id = self.find_pool(&mint).await?;
+        self.load_pool(&pool_id).await?.spot_price()
+    }
+
+    async fn get_token_balance(&self, token_account: &Pubkey, mint: &Pubkey) -> Result<u64> {
+        self.client()?.get_token_account(token_account).await
+            .map_err(|e| anyhow!("Failed to get token account balance: {}", e))?
+            .ok_or_else(|| anyhow!("Token account does not exist for mint {}", mint))?
+            .token_amount
+            .amount
+            .parse::<u64>()
+            .map_err(|_| anyhow!("Failed to parse token balance for mint {}", mint))
+    }
+
+    pub async fn build_swap_from_parsed_data(
+        &self,
+        trade_info: &TradeInfoFromToken,
+        swap_config: SwapConfig,
+    ) -> Result<SwapInstructions> {
+        let owner = self.keypair.pubkey();
+        let mint = Pubkey::from_str(&trade_info.mint)?;
+        let pool = self.pool_for_trade(trade_info, &mint).await?;
+        priority_fees::track_fee_account(&pool.pool_id);
+        let (_, token_vault, token_program, sol_vault) = pool.token_side()?;
+        let token_ata = token::get_associated_token_address_for_program(&owner, &mint, &token_program);
+        let wsol_ata = token::get_associated_token_address_for_program(&owner, &SOL_MINT, &TOKEN_PROGRAM);
+
+        let mut instructions = Vec::with_capacity(3);
+        let logger = Logger::new("[RAYDIUM-CPMM-ATA-CREATE] => ".yellow().to_string());
+        for (ata, ata_mint, ata_program) in [(token_ata, mint, token_program), (wsol_ata, SOL_MINT, TOKEN_PROGRAM)] {
+            if !WALLET_TOKEN_ACCOUNTS.contains(&ata) {
+                logger.log(format!("Creating token ATA for mint {} at address {}", ata_mint, ata));
+                instructions.push(create_associated_token_account_idempotent(&owner, &owner, &ata_mint, &ata_program));
+                WALLET_TOKEN_ACCOUNTS.insert(ata);
+            }
+        }
+
+        // Buy: SOL to spend, or tokens to receive for ExactOut.
+        // Sell: tokens from the wallet balance (qty/pct), or SOL to receive for ExactOut.
+        let exact_out = swap_config.in_type == SwapInType::ExactOut;
+        let mut token_balance = None;
+        let amount = match swap_config.swap_direction {
+            SwapDirection::Buy if exact_out => ui_amount_to_amount(swap_config.amount_in, LAUNCHPAD_TOKEN_DECIMALS as u8),
+            SwapDirection::Buy => ui_amount_to_amount(swap_config.amount_in, 9),
+            SwapDirection::Sell => {
+                let balance = self.get_token_balance(&token_ata, &mint).await?;
+                token_balance = Some(balance);
+                match swap_config.in_type {
+                    SwapInType::Qty => ui_amount_to_amount(swap_config.amount_in, LAUNCHPAD_TOKEN_DECIMALS as u8),
+                    SwapInType::Pct => ((swap_config.amount_in.min(1.0) * balance as f64) as u64).max(1),
+                    SwapInType::ExactOut => ui_amount_to_amount(swap_config.amount_in, 9),
+                }
+            }
+        };
+
+        let quote = pool.quote(&swap_config.swap_direction, exact_out, amount, swap_config.slippage)?;
+        let (discriminator, amount, other_amount_threshold) = if exact_out {
+            if let Some(balance) = token_balance {
+                if quote.amount_in > balance {
+                    return Err(anyhow!(
+                        "Exact-out sell of {} needs {} tokens but the wallet holds {}",
+                        mint, quote.amount_in, balance
+                    ));
+                }
+            }
+            let maximum_amount_in = token_balance.map_or(quote.maximum_amount_in, |balance| quote.maximum_amount_in.min(balance));
+            (SWAP_BASE_OUTPUT_DISCRIMINATOR, quote.amount_out, maximum_amount_in)
+        } else {
+            if quote.minimum_amount_out == 0 {
+                return Err(anyhow!("Quote for {} returned no output (amount in {})", mint, amount));
+            }
+            (SWAP_BASE_INPUT_DISCRIMINATOR, amount, quote.minimum_amount_out)
+        };
+
+        let token = (token_ata, token_vault, token_program, mint);
+        let sol = (wsol_ata, sol_vault, TOKEN_PROGRAM, SOL_MINT);
+        let (input, output) = match swap_config.swap_direction {
+            SwapDirection::Buy => (sol, token),
+            SwapDirection::Sell => (token, sol),
+        };
+        instructions.push(create_swap_instruction(
+            discriminator,
+            &owner,
+            &pool,
+            input,
+            output,
+            amount,
+            other_amount_threshold,
+        ));
+
+        let price_in_sol = if trade_info.dex_type == DexType::RaydiumCpmm && trade_info.price > 0.0 {
+            trade_info.price / 1_000_000_000.0
+        } else {
+            pool.spot_price().unwrap_or_default()
+        };
+        Ok((self.keypair.clone(), instructions, price_in_sol))
+    }
+}
+
+#[async_trait]
+impl Dex for RaydiumCpmm {
+    fn protocol(&self) -> SwapProtocol {
+        SwapProtocol::RaydiumCpmm
+    }
+
+    fn program_id(&self) -> Pubkey {
+        RAYDIUM_CPMM_PROGRAM
+    }
+
+    fn discovers_launches(&self) -> bool {
+        false // Migrated tokens are followed by the per-token and target wallet streams
+    }
+
+    async fn pool_for_mint(&self, mint: &str) -> Result<PoolKeys> {
+        let mint = Pubkey::from_str(mint).map_err(|_| anyhow!("Invalid mint address"))?;
+        let pool_id = self.find_pool(&mint).await?;
+        let account = self.client()?.get_account(&pool_id).await
+            .map_err(|e| anyhow!("CPMM pool {} not found: {}", pool_id, e))?;
+        let state = CpmmPoolState::decode(&account.data)?;
+        let pool = CpmmPool { pool_id, state, config: AmmConfig::default(), reserve_0: 0, reserve_1: 0 };
+        let (_, token_vault, _, sol_vault) = pool.token_side()?;
+        Ok(PoolKeys {
+            pool_id,
+            base_mint: mint,
+            quote_mint: SOL_MINT,
+            base_vault: token_vault,
+            quote_vault: sol_vault,
+        })
+    }
+
+    async fn quote(&self, trade_info: &TradeInfoFromToken, swap_config: &SwapConfig, amount: u64) -> Result<SwapQuote> {
+        let mint = Pubkey::from_str(&trade_info.mint)?;
+        let pool = self.pool_for_trade(trade_info, &mint).await?;
+        pool.quote(
+            &swap_config.swap_direction,
+            swap_config.in_type == SwapInType::ExactOut,
+            amount,
+            swap_config.slippage,
+        )
+    }
+
+    async fn build_buy(&self, trade_info: &TradeInfoFromToken, mut swap_config: SwapConfig) -> Result<SwapInstructions> {
+        swap_config.swap_direction = SwapDirection::Buy;
+        self.build_swap_from_parsed_data(trade_info, swap_config).await
+    }
+
+    async fn build_sell(&self, trade_info: &TradeInfoFromToken, mut swap_config: SwapConfig) -> Result<SwapInstructions> {
+        swap_config.swap_direction = SwapDirection::Sell;
+        self.build_swap_from_parsed_data(trade_info, swap_config).await
+    }
+
+    async fn token_price(&self, mint: &str) -> Result<f64> {
+        self.get_token_price(mint).await
+    }
+
+    fn price_from_trade(&self, trade_info: &TradeInfoFromToken) -> f64 {
+        // The parser prices CPMM trades from the post-trade vault balances, in lamports per whole token
+        trade_info.price / 1_000_000_000.0
+    }
+}
+
+/// `swap_base_input` / `swap_base_output`. Each side is (user token account, pool vault, token
+/// program, mint). `amount` is the exact input (or output) and `other_amount_threshold` the
+/// minimum output (or maximum input).
+pub fn create_swap_instruction(
+    discriminator: [u8; 8],
+    payer: &Pubkey,
+    pool: &CpmmPool,
+    input: (Pubkey, Pubkey, Pubkey, Pubkey),
+    output: (Pubkey, Pubkey, Pubkey, Pubkey),
+    amount: u64,
+    other_amount_threshold: u64,
+) -> Instruction {
+    let (input_account, input_vault, input_program, input_mint) = input;
+    let (output_account, output_vault, output_program, output_mint) = output;
+    let accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(RAYDIUM_CPMM_AUTHORITY, false),
+        AccountMeta::new_readonly(pool.state.amm_config, false),
+        AccountMeta::new(pool.pool_id, false),
+        AccountMeta::new(input_account, false),
+        AccountMeta::new(output_account, false),
+        AccountMeta::new(input_vault, false),
+        AccountMeta::new(output_vault, false),
+        AccountMeta::new_readonly(input_program, false),
+        AccountMeta::new_readonly(output_program, false),
+        AccountMeta::new_readonly(input_mint, false),
+        AccountMeta::new_readonly(output_mint, false),
+        AccountMeta::new(pool.state.observation_key, false),
+    ];
+
+    let mut data = Vec::with_capacity(24);
+    data.extend_from_slice(&discriminator);
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.extend_from_slice(&other_amount_threshold.to_le_bytes());
+
+    Instruction { program_id: RAYDIUM_CPMM_PROGRAM, accounts, data }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESERVE_IN: u64 = 250_000_000_000;
+    const RESERVE_OUT: u64 = 180_000_000_000_000;
+
+    /// 0.25% trade fee and a 0.1% creator fee on the given side
+    fn fees(creator_fee_on_input: bool) -> CpmmFees {
+        CpmmFees { trade_fee_rate: 2_500, creator_fee_rate: 1_000, creator_fee_on_input }
+    }
+
+    #[test]
+    fn creator_fee_on_input_comes_off_the_amount_in() {
+        let fees = fees(true);
+        let quote = quote_swap_base_input(1_000_000_000, RESERVE_IN, RESERVE_OUT, &fees, 0);
+        assert_eq!(quote.fee, 3_500_000);
+        assert_eq!(quote.amount_out, constant_product::amount_out(996_500_000, RESERVE_IN, RESERVE_OUT));
+        assert_eq!(quote.minimum_amount_out, quote.amount_out);
+        assert_eq!(quote.maximum_amount_in, 1_000_000_000);
+    }
+
+    #[test]
+    fn creator_fee_on_output_comes_off_the_curve_output() {
+        let fees = fees(false);
+        let quote = quote_swap_base_input(1_000_000_000, RESERVE_IN, RESERVE_OUT, &fees, 0);
+        let curve_out = constant_product::amount_out(997_500_000, RESERVE_IN, RESERVE_OUT);
+        let creator_fee = constant_product::fee(curve_out, 1_000, FEE_RATE_DENOMINATOR);
+        assert_eq!(quote.amount_out, curve_out - creator_fee);
+        assert_eq!(quote.fee, 2_500_000 + creator_fee);
+    }
+
+    #[test]
+    fn base_output_quotes_the_smallest_sufficient_input() {
+        for fees in [fees(true), fees(false), CpmmFees::default()] {
+            for amount_out in [1, 1_000, 1_000_000_007, 50_000_000_000_000] {
+                let quote = quote_swap_base_output(amount_out, RESERVE_IN, RESERVE_OUT, &fees, 0).unwrap();
+                assert_eq!(quote.amount_out, amount_out);
+                assert_eq!(quote.maximum_amount_in, quote.amount_in);
+                let received = quote_swap_base_input(quote.amount_in, RESERVE_IN, RESERVE_OUT, &fees, 0).amount_out;
+                assert!(received >= amount_out, "{:?}: {} in pays {} < {}", fees, quote.amount_in, received, amount_out);
+                let short = quote_swap_base_input(quote.amount_in - 1, RESERVE_IN, RESERVE_OUT, &fees, 0).amount_out;
+                assert!(short < amount_out, "{:?}: {} is not the smallest input for {}", fees, quote.amount_in, amount_out);
+            }
+        }
+        assert!(quote_swap_base_output(RESERVE_OUT, RESERVE_IN, RESERVE_OUT, &fees(true), 0).is_none());
+    }
+
+    #[test]
+    fn slippage_applies_to_the_unfixed_side() {
+        let fees = fees(true);
+        assert_eq!(quote_swap_base_input(1_000_000_000, RESERVE_IN, RESERVE_OUT, &fees, 10_000).minimum_amount_out, 0);
+        let quote = quote_swap_base_input(1_000_000_000, RESERVE_IN, RESERVE_OUT, &fees, 100);
+        assert_eq!(quote.minimum_amount_out, apply_slippage(quote.amount_out, 100));
+        assert_eq!(quote.maximum_amount_in, 1_000_000_000);
+
+        let exact = quote_swap_base_output(1_000_000, RESERVE_IN, RESERVE_OUT, &fees, 0).unwrap();
+        let loose = quote_swap_base_output(1_000_000, RESERVE_IN, RESERVE_OUT, &fees, 10_000).unwrap();
+        assert_eq!(loose.amount_in, exact.amount_in);
+        assert_eq!(loose.maximum_amount_in, exact.amount_in * 2);
+        assert_eq!(loose.minimum_amount_out, 1_000_000);
+    }
+
+    /// Account or event data: `discriminator` followed by a zeroed body of `len` bytes with
+    /// `fields` written at their body offsets
+    fn account_data(discriminator: [u8; 8], len: usize, fields: &[(usize, &[u8])]) -> Vec<u8> {
+        let mut data = discriminator.to_vec();
+        data.resize(8 + len, 0);
+        for (offset, bytes) in fields {
+            data[8 + offset..8 + offset + bytes.len()].copy_from_slice(bytes);
+        }
+        data
+    }
+
+    #[test]
+    fn pool_state_decodes_at_its_offsets() {
+        let token_mint = Pubkey::new_from_array([5; 32]);
+        let data = account_data(POOL_STATE_DISCRIMINATOR, 629, &[
+            (0, &[1; 32]), // amm_config
+            (64, &[2; 32]), // token_0_vault
+            (96, &[3; 32]), // token_1_vault
+            (POOL_STATE_TOKEN_0_MINT_OFFSET - 8, token_mint.as_ref()),
+            (POOL_STATE_TOKEN_1_MINT_OFFSET - 8, SOL_MINT.as_ref()),
+            (224, TOKEN_PROGRAM.as_ref()),
+            (256, TOKEN_PROGRAM.as_ref()),
+            (288, &[6; 32]), // observation_key
+            (321, &[POOL_STATUS_SWAP_DISABLED]),
+            (323, &[6, 9]), // mint decimals
+            (333, &11u64.to_le_bytes()),
+            (341, &12u64.to_le_bytes()),
+            (349, &13u64.to_le_bytes()),
+            (357, &14u64.to_le_bytes()),
+            (365, &1_760_000_000u64.to_le_bytes()),
+            (381, &[2, 1]), // creator_fee_on OnlyToken1, enable_creator_fee
+            (389, &15u64.to_le_bytes()),
+            (397, &16u64.to_le_bytes()),
+        ]);
+        let state = CpmmPoolState::decode(&data).unwrap();
+        assert_eq!(state.amm_config, Pubkey::new_from_array([1; 32]));
+        assert_eq!((state.token_0_vault, state.token_1_vault), (Pubkey::new_from_array([2; 32]), Pubkey::new_from_array([3; 32])));
+        assert_eq!((state.token_0_mint, state.token_1_mint), (token_mint, SOL_MINT));
+        assert_eq!((state.token_0_program, state.token_1_program), (TOKEN_PROGRAM, TOKEN_PROGRAM));
+        assert_eq!(state.observation_key, Pubkey::new_from_array([6; 32]));
+        assert_eq!((state.mint_0_decimals, state.mint_1_decimals), (6, 9));
+        assert_eq!(state.open_time, 1_760_000_000);
+        assert_eq!(state.creator_fee_on, CreatorFeeOn::OnlyToken1);
+        assert!(state.enable_creator_fee);
+        assert!(!state.is_swap_enabled());
+        assert_eq!(state.token_is_0(), Some(true));
+        // Uncollected protocol, fund and creator fees are not tradable
+        assert_eq!(state.reserves(1_000, 2_000), (1_000 - 11 - 13 - 15, 2_000 - 12 - 14 - 16));
+        assert!(!state.is_creator_fee_on_input(true));
+        assert!(state.is_creator_fee_on_input(false));
+
+        // Pools from before the creator fee fields read the padding as zero
+        let legacy = CpmmPoolState::decode(&data[..8 + 389]).unwrap();
+        assert_eq!((legacy.creator_fees_token_0, legacy.creator_fees_token_1), (0, 0));
+        assert!(CpmmPoolState::decode(&data[..8 + 372]).is_err());
+        assert!(CpmmPoolState::decode(&account_data(AMM_CONFIG_DISCRIMINATOR, 629, &[])).is_err());
+    }
+
+    #[test]
+    fn amm_config_decodes_at_its_offsets() {
+        let data = account_data(AMM_CONFIG_DISCRIMINATOR, 228, &[
+            (4, &2_500u64.to_le_bytes()),
+            (12, &120_000u64.to_le_bytes()),
+            (20, &40_000u64.to_le_bytes()),
+            (100, &1_000u64.to_le_bytes()),
+        ]);
+        let config = AmmConfig::decode(&data).unwrap();
+        assert_eq!(config, AmmConfig { trade_fee_rate: 2_500, protocol_fee_rate: 120_000, fund_fee_rate: 40_000, creator_fee_rate: 1_000 });
+        assert_eq!(AmmConfig::decode(&data[..8 + 100]).unwrap().creator_fee_rate, 0);
+        assert!(AmmConfig::decode(&data[..8 + 20]).is_err());
+    }
+
+    #[test]
+    fn swap_events_decode_at_their_borsh_offsets() {
+        let token_mint = Pubkey::new_from_array([5; 32]);
+        let fields: [(usize, &[u8]); 11] = [
+            (0, &[7; 32]), // pool_id
+            (32, &250_000_000_000u64.to_le_bytes()),
+            (40, &180_000_000_000_000u64.to_le_bytes()),
+            (48, &1_000_000_000u64.to_le_bytes()),
+            (56, &714_000_000_000u64.to_le_bytes()),
+            (64, &0u64.to_le_bytes()),
+            (72, &0u64.to_le_bytes()),
+            (80, &[1]), // base_input
+            (81, SOL_MINT.as_ref()),
+            (113, token_mint.as_ref()),
+            (145, &2_500_000u64.to_le_bytes()),
+        ];
+        let mut event_data = transaction_parser::EVENT_IX_TAG.to_vec();
+        event_data.extend(account_data(SWAP_EVENT_DISCRIMINATOR, SWAP_EVENT_LEN, &fields));
+        event_data[8 + 8 + 153..8 + 8 + 161].copy_from_slice(&1_000_000u64.to_le_bytes());
+
+        // Self-CPI event data carries the event tag, `Program data:` logs do not
+        for data in [&event_data[..], &event_data[8..]] {
+            let event = decode_swap_event(data).unwrap();
+            assert_eq!(event.pool_id, Pubkey::new_from_array([7; 32]));
+            assert_eq!((event.input_vault_before, event.output_vault_before), (250_000_000_000, 180_000_000_000_000));
+            assert_eq!((event.input_amount, event.output_amount), (1_000_000_000, 714_000_000_000));
+            assert!(event.base_input);
+            assert_eq!((event.input_mint, event.output_mint), (Some(SOL_MINT), Some(token_mint)));
+            assert_eq!((event.trade_fee, event.creator_fee), (2_500_000, 1_000_000));
+        }
+
+        // Events of the launch layout end after base_input
+        let legacy = decode_swap_event(&event_data[8..8 + 8 + SWAP_EVENT_LEGACY_LEN]).unwrap();
+        assert_eq!(legacy.output_amount, 714_000_000_000);
+        assert_eq!((legacy.input_mint, legacy.trade_fee, legacy.creator_fee), (None, 0, 0));
+        assert!(decode_swap_event(&event_data[8..8 + 8 + SWAP_EVENT_LEGACY_LEN - 1]).is_none());
+    }
+}
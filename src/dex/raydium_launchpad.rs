@@ -1,5 +1,4 @@
-use std::{str::FromStr, sync::Arc, time::Instant};
-use solana_program_pack::Pack;
+use std::{str::FromStr, sync::Arc};
 use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
 use solana_client::rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType};
 use solana_account_decoder::UiAccountEncoding;
@@ -9,21 +8,30 @@ use solana_sdk::{
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     signature::Keypair,
-    system_program,
     signer::Signer,
 };
-use crate::engine::transaction_parser::DexType;
+use crate::engine::transaction_parser::{PoolStatus, TradeInfoFromToken};
+use crate::dex::launchpad_curve::{CurveType, LaunchpadCurve, POOL_CURVE_TYPES};
+use crate::dex::pool_index::{derive_pool_id, derive_pool_vault, PoolIndexEntry, POOL_INDEX};
+use crate::dex::quote_mint::{self, KNOWN_QUOTE_MINTS};
+use crate::dex::raydium_cpmm::{self, RaydiumCpmm};
+use crate::dex::venue::{Dex, PoolKeys, SwapInstructions, SwapQuote};
+use async_trait::async_trait;
+use borsh::BorshDeserialize;
+use dashmap::{DashMap, DashSet};
 use spl_associated_token_account::{
     get_associated_token_address,
     instruction::create_associated_token_account_idempotent
 };
 use spl_token::ui_amount_to_amount;
+use spl_token_2022::{extension::StateWithExtensionsOwned, state::Mint};
 
 
 use crate::{
     common::{config::SwapConfig, logger::Logger, cache::WALLET_TOKEN_ACCOUNTS},
     core::token,
-    engine::swap::{SwapDirection, SwapInType},
+    engine::swap::{SwapDirection, SwapInType, SwapProtocol},
+    services::priority_fees,
 };
 
 pub const TOKEN_PROGRAM: Pubkey = solana_sdk::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
@@ -37,10 +45,223 @@ pub const EVENT_AUTHORITY: Pubkey = solana_sdk::pubkey!("2DPAtwB8L12vrMRExbLuyGn
 pub const SOL_MINT: Pubkey = solana_sdk::pubkey!("So11111111111111111111111111111111111111112");
 pub const BUY_DISCRIMINATOR: [u8; 8] = [250, 234, 13, 123, 213, 156, 19, 236]; // buy_exact_in discriminator
 pub const SELL_DISCRIMINATOR: [u8; 8] = [149, 39, 222, 155, 211, 124, 152, 26]; // sell_exact_in discriminator
+pub const BUY_EXACT_OUT_DISCRIMINATOR: [u8; 8] = [24, 211, 116, 40, 105, 3, 153, 56]; // buy_exact_out discriminator
+pub const SELL_EXACT_OUT_DISCRIMINATOR: [u8; 8] = [95, 200, 71, 34, 8, 9, 11, 166]; // sell_exact_out discriminator
+
+// Account positions shared by all swap instructions (see create_buy_accounts / create_sell_accounts)
+pub const SWAP_ACCOUNT_USER: usize = 0;
+pub const SWAP_ACCOUNT_POOL_STATE: usize = 4;
+pub const SWAP_ACCOUNT_USER_BASE_TOKEN: usize = 5;
+pub const SWAP_ACCOUNT_USER_QUOTE_TOKEN: usize = 6;
+pub const SWAP_ACCOUNT_BASE_VAULT: usize = 7;
+pub const SWAP_ACCOUNT_QUOTE_VAULT: usize = 8;
+pub const SWAP_ACCOUNT_BASE_MINT: usize = 9;
+pub const SWAP_ACCOUNT_QUOTE_MINT: usize = 10;
+pub const SWAP_ACCOUNTS_LEN: usize = 15;
+
+pub const INITIALIZE_DISCRIMINATOR: [u8; 8] = [175, 175, 109, 31, 13, 152, 155, 237]; // initialize discriminator
+
+// Account positions in the initialize instruction
+pub const INITIALIZE_ACCOUNT_PAYER: usize = 0;
+pub const INITIALIZE_ACCOUNT_CREATOR: usize = 1;
+pub const INITIALIZE_ACCOUNT_GLOBAL_CONFIG: usize = 2;
+pub const INITIALIZE_ACCOUNT_PLATFORM_CONFIG: usize = 3;
+pub const INITIALIZE_ACCOUNT_POOL_STATE: usize = 5;
+pub const INITIALIZE_ACCOUNT_BASE_MINT: usize = 6;
+pub const INITIALIZE_ACCOUNT_QUOTE_MINT: usize = 7;
+pub const INITIALIZE_ACCOUNT_BASE_VAULT: usize = 8;
+pub const INITIALIZE_ACCOUNT_QUOTE_VAULT: usize = 9;
+pub const INITIALIZE_ACCOUNTS_LEN: usize = 18;
+
+pub const MIGRATE_TO_AMM_DISCRIMINATOR: [u8; 8] = [207, 82, 192, 145, 254, 207, 145, 223]; // migrate_to_amm discriminator
+pub const MIGRATE_TO_CPSWAP_DISCRIMINATOR: [u8; 8] = [136, 92, 200, 103, 28, 218, 144, 140]; // migrate_to_cpswap discriminator
+
+// Account positions in the migrate_to_amm instruction
+pub const MIGRATE_TO_AMM_ACCOUNT_BASE_MINT: usize = 1;
+pub const MIGRATE_TO_AMM_ACCOUNT_QUOTE_MINT: usize = 2;
+pub const MIGRATE_TO_AMM_ACCOUNT_AMM_POOL: usize = 13;
+pub const MIGRATE_TO_AMM_ACCOUNT_POOL_STATE: usize = 23;
+pub const MIGRATE_TO_AMM_ACCOUNT_BASE_VAULT: usize = 25;
+pub const MIGRATE_TO_AMM_ACCOUNT_QUOTE_VAULT: usize = 26;
+pub const MIGRATE_TO_AMM_ACCOUNTS_LEN: usize = 32;
+
+// Account positions in the migrate_to_cpswap instruction
+pub const MIGRATE_TO_CPSWAP_ACCOUNT_BASE_MINT: usize = 1;
+pub const MIGRATE_TO_CPSWAP_ACCOUNT_QUOTE_MINT: usize = 2;
+pub const MIGRATE_TO_CPSWAP_ACCOUNT_CPSWAP_POOL: usize = 5;
+pub const MIGRATE_TO_CPSWAP_ACCOUNT_POOL_STATE: usize = 17;
+pub const MIGRATE_TO_CPSWAP_ACCOUNT_BASE_VAULT: usize = 19;
+pub const MIGRATE_TO_CPSWAP_ACCOUNT_QUOTE_VAULT: usize = 20;
+pub const MIGRATE_TO_CPSWAP_ACCOUNTS_LEN: usize = 28;
 
 const TEN_THOUSAND: u64 = 10000;
-const POOL_VAULT_SEED: &[u8] = b"pool_vault";
 
+pub const POOL_STATE_DISCRIMINATOR: [u8; 8] = [247, 237, 227, 245, 215, 195, 222, 70]; // account:PoolState
+pub const GLOBAL_CONFIG_DISCRIMINATOR: [u8; 8] = [149, 8, 156, 202, 160, 252, 176, 217]; // account:GlobalConfig
+pub const PLATFORM_CONFIG_DISCRIMINATOR: [u8; 8] = [160, 78, 128, 0, 248, 83, 230, 160]; // account:PlatformConfig
+pub const POOL_STATE_LEN: u64 = 429;
+pub const POOL_STATE_BASE_MINT_OFFSET: usize = 205;
+/// Fee rates in the launchpad configs are expressed over this denominator
+pub const FEE_RATE_DENOMINATOR: u64 = 1_000_000;
+
+/// Creator vesting schedule stored in the pool
+#[derive(Debug, Clone, Default, BorshDeserialize)]
+pub struct VestingSchedule {
+    pub total_locked_amount: u64,
+    pub cliff_period: u64,
+    pub unlock_period: u64,
+    pub start_time: u64,
+    pub allocated_share_amount: u64,
+}
+
+/// Launchpad `PoolState` account (429 bytes including the Anchor discriminator)
+#[derive(Debug, Clone, BorshDeserialize)]
+pub struct PoolState {
+    pub epoch: u64,
+    pub auth_bump: u8,
+    pub status: u8, // 0 Fund, 1 Migrate, 2 Trade
+    pub base_decimals: u8,
+    pub quote_decimals: u8,
+    pub migrate_type: u8, // 0 AMM, 1 CPMM
+    pub supply: u64,
+    pub total_base_sell: u64,
+    pub virtual_base: u64,
+    pub virtual_quote: u64,
+    pub real_base: u64,
+    pub real_quote: u64,
+    pub total_quote_fund_raising: u64,
+    pub quote_protocol_fee: u64,
+    pub platform_fee: u64,
+    pub migrate_fee: u64,
+    pub vesting_schedule: VestingSchedule,
+    pub global_config: Pubkey,
+    pub platform_config: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub base_vault: Pubkey,
+    pub quote_vault: Pubkey,
+    pub creator: Pubkey,
+    pub padding: [u64; 8],
+}
+
+impl PoolState {
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        decode_account(data, &POOL_STATE_DISCRIMINATOR, "PoolState")
+    }
+
+    pub fn pool_status(&self) -> Option<PoolStatus> {
+        PoolStatus::from_u8(self.status)
+    }
+
+    /// The curve is complete once the pool left the Fund status; swaps are rejected from then on
+    pub fn is_migrated(&self) -> bool {
+        !matches!(self.pool_status(), Some(PoolStatus::Fund))
+    }
+
+    /// Spot price in quote units per whole base token (SOL per token for SOL pools)
+    pub fn spot_price(&self, curve_type: u8) -> Result<f64> {
+        let curve_type = CurveType::from_u8(curve_type)
+            .ok_or_else(|| anyhow!("Unknown launchpad curve type {}", curve_type))?;
+        let raw_price = LaunchpadCurve::from_pool_state(self, curve_type)
+            .spot_price()
+            .ok_or_else(|| anyhow!("Pool has no base reserve left"))?;
+        Ok(raw_price * 10f64.powi(self.base_decimals as i32) / 10f64.powi(self.quote_decimals as i32))
+    }
+}
+
+/// Launchpad `GlobalConfig` account: curve type, trade fee and migration thresholds
+#[derive(Debug, Clone, BorshDeserialize)]
+pub struct GlobalConfig {
+    pub epoch: u64,
+    pub curve_type: u8, // 0 constant product, 1 fixed price, 2 linear price
+    pub index: u16,
+    pub migrate_fee: u64,
+    pub trade_fee_rate: u64,
+    pub max_share_fee_rate: u64,
+    pub min_base_supply: u64,
+    pub max_lock_rate: u64,
+    pub min_base_sell_rate: u64,
+    pub min_base_migrate_rate: u64,
+    pub min_quote_fund_raising: u64,
+    pub quote_mint: Pubkey,
+    pub protocol_fee_owner: Pubkey,
+    pub migrate_fee_owner: Pubkey,
+    pub migrate_to_amm_wallet: Pubkey,
+    pub migrate_to_cpswap_wallet: Pubkey,
+    pub padding: [u64; 16],
+}
+
+impl GlobalConfig {
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        decode_account(data, &GLOBAL_CONFIG_DISCRIMINATOR, "GlobalConfig")
+    }
+}
+
+/// Launchpad `PlatformConfig` account: platform fee rate and display metadata
+#[derive(Debug, Clone, BorshDeserialize)]
+pub struct PlatformConfig {
+    pub epoch: u64,
+    pub platform_fee_wallet: Pubkey,
+    pub platform_nft_wallet: Pubkey,
+    pub platform_scale: u64,
+    pub creator_scale: u64,
+    pub burn_scale: u64,
+    pub fee_rate: u64,
+    pub name: [u8; 64],
+    pub web: [u8; 256],
+    pub img: [u8; 256],
+    /// Only present on accounts created after creator fees were introduced
+    #[borsh(skip)]
+    pub cpswap_config: Option<Pubkey>,
+    #[borsh(skip)]
+    pub creator_fee_rate: u64,
+}
+
+impl PlatformConfig {
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let mut config: Self = decode_account(data, &PLATFORM_CONFIG_DISCRIMINATOR, "PlatformConfig")?;
+        // Fields appended in later program versions follow the fixed layout
+        let tail_offset = 8 + 8 + 32 + 32 + 8 * 4 + 64 + 256 + 256;
+        if let Some(tail) = data.get(tail_offset..tail_offset + 40) {
+            config.cpswap_config = Pubkey::try_from(&tail[..32]).ok();
+            config.creator_fee_rate = u64::from_le_bytes(tail[32..40].try_into()?);
+        }
+        Ok(config)
+    }
+
+    pub fn name(&self) -> String {
+        String::from_utf8_lossy(&self.name).trim_end_matches('\0').to_string()
+    }
+}
+
+/// Check the Anchor discriminator and Borsh-decode the account body, ignoring trailing padding
+fn decode_account<T: BorshDeserialize>(data: &[u8], discriminator: &[u8; 8], name: &str) -> Result<T> {
+    if data.len() < 8 || data[..8] != discriminator[..] {
+        return Err(anyhow!("Account is not a launchpad {}", name));
+    }
+    T::deserialize(&mut &data[8..]).map_err(|e| anyhow!("Failed to decode {}: {}", name, e))
+}
+
+/// A launchpad pool with the configs that govern its curve and fees
+#[derive(Debug, Clone)]
+pub struct LaunchpadPoolInfo {
+    pub pool_id: Pubkey,
+    pub state: PoolState,
+    pub global_config: GlobalConfig,
+    pub platform_config: PlatformConfig,
+}
+
+impl LaunchpadPoolInfo {
+    pub fn curve(&self) -> Result<LaunchpadCurve> {
+        let curve_type = CurveType::from_u8(self.global_config.curve_type)
+            .ok_or_else(|| anyhow!("Unknown launchpad curve type {}", self.global_config.curve_type))?;
+        Ok(LaunchpadCurve::from_pool_state(&self.state, curve_type))
+    }
+
+    pub fn fee_rates(&self) -> FeeRates {
+        FeeRates::from_configs(&self.global_config, &self.platform_config)
+    }
+}
 
 
 /// A struct to represent the Raydium pool which uses constant product AMM
@@ -57,6 +278,11 @@ pub struct Raydium {
     pub keypair: Arc<Keypair>,
     pub rpc_client: Option<Arc<solana_client::rpc_client::RpcClient>>,
     pub rpc_nonblocking_client: Option<Arc<solana_client::nonblocking::rpc_client::RpcClient>>,
+    quote_router: RaydiumCpmm, // Swaps SOL to and from the quote of pools not quoted in SOL
+    pool_configs: DashMap<Pubkey, (Pubkey, Pubkey)>, // Global and platform config of non-SOL pools
+    pub share_fee: Option<ShareFee>,
+    max_share_fee_rates: DashMap<Pubkey, u64>, // Per global config, read on first use
+    share_fee_accounts: DashSet<Pubkey>, // Receiver token accounts known to exist
 }
 
 impl Raydium {
@@ -64,8 +290,14 @@ impl Raydium {
         keypair: Arc<Keypair>,
         rpc_client: Option<Arc<solana_client::rpc_client::RpcClient>>,
         rpc_nonblocking_client: Option<Arc<solana_client::nonblocking::rpc_client::RpcClient>>,
+        share_fee: Option<ShareFee>,
     ) -> Self {
         Self {
+            quote_router: RaydiumCpmm::new(keypair.clone(), rpc_client.clone(), rpc_nonblocking_client.clone()),
+            pool_configs: DashMap::new(),
+            share_fee: share_fee.filter(|share_fee| share_fee.rate > 0),
+            max_share_fee_rates: DashMap::new(),
+            share_fee_accounts: DashSet::new(),
             keypair,
             rpc_client,
             rpc_nonblocking_client,
@@ -77,22 +309,157 @@ impl Raydium {
         mint_str: &str,
     ) -> Result<RaydiumPool> {
         let mint = Pubkey::from_str(mint_str).map_err(|_| anyhow!("Invalid mint address"))?;
-        let rpc_client = self.rpc_client.clone()
-            .ok_or_else(|| anyhow!("RPC client not initialized"))?;
-        get_pool_info(rpc_client, mint).await
+        get_pool_info(self.client()?.clone(), mint).await
+    }
+
+    fn client(&self) -> Result<&Arc<solana_client::nonblocking::rpc_client::RpcClient>> {
+        self.rpc_nonblocking_client.as_ref()
+            .ok_or_else(|| anyhow!("RPC client not initialized"))
+    }
+
+    /// Fetch raw account data, preferring the nonblocking client
+    async fn get_account_data(&self, keys: &[Pubkey]) -> Result<Vec<Option<Vec<u8>>>> {
+        let accounts = if let Some(client) = &self.rpc_nonblocking_client {
+            client.get_multiple_accounts(keys).await
+        } else if let Some(client) = &self.rpc_client {
+            client.get_multiple_accounts(keys)
+        } else {
+            return Err(anyhow!("RPC client not initialized"));
+        };
+        let accounts = accounts.map_err(|e| anyhow!("Failed to fetch accounts: {}", e))?;
+        Ok(accounts.into_iter().map(|account| account.map(|account| account.data)).collect())
+    }
+
+    async fn get_single_account_data(&self, key: &Pubkey) -> Result<Vec<u8>> {
+        self.get_account_data(std::slice::from_ref(key))
+            .await?
+            .pop()
+            .flatten()
+            .ok_or_else(|| anyhow!("Account {} not found", key))
+    }
+
+    /// Live pool state, read from chain rather than derived from the transaction stream
+    pub async fn get_pool_state(&self, pool_id: &Pubkey) -> Result<PoolState> {
+        PoolState::decode(&self.get_single_account_data(pool_id).await?)
+    }
+
+    pub async fn get_global_config(&self, global_config: &Pubkey) -> Result<GlobalConfig> {
+        GlobalConfig::decode(&self.get_single_account_data(global_config).await?)
+    }
+
+    pub async fn get_platform_config(&self, platform_config: &Pubkey) -> Result<PlatformConfig> {
+        PlatformConfig::decode(&self.get_single_account_data(platform_config).await?)
+    }
+
+    /// Global and platform config a pool's swaps pass. SOL pools use the Let's Bonk configs;
+    /// other quote mints have their own global config, read once from the pool.
+    async fn get_pool_configs(&self, pool_info: &RaydiumPool) -> Result<(Pubkey, Pubkey)> {
+        if quote_mint::is_sol(&pool_info.quote_mint) {
+            return Ok((RAYDIUM_GLOBAL_CONFIG, RAYDIUM_PLATFORM_CONFIG));
+        }
+        if let Some(configs) = self.pool_configs.get(&pool_info.pool_id) {
+            return Ok(*configs);
+        }
+        let state = self.get_pool_state(&pool_info.pool_id).await?;
+        let configs = (state.global_config, state.platform_config);
+        self.pool_configs.insert(pool_info.pool_id, configs);
+        Ok(configs)
+    }
+
+    /// Share fee rate and receiver account for a swap on `pool_info`. `None` when no share fee is
+    /// configured, the receiver holds another mint, or the rate exceeds the pool's platform maximum.
+    async fn share_fee_for(&self, pool_info: &RaydiumPool, quote_program: &Pubkey) -> Result<Option<(u64, Pubkey)>> {
+        let Some(share_fee) = self.share_fee else {
+            return Ok(None);
+        };
+        let Some(receiver) = share_fee.receiver_account(&pool_info.quote_mint, quote_program) else {
+            return Ok(None);
+        };
+        let (global_config, _) = self.get_pool_configs(pool_info).await?;
+        let max_share_fee_rate = match self.max_share_fee_rates.get(&global_config) {
+            Some(rate) => *rate,
+            None => {
+                let rate = self.get_global_config(&global_config).await?.max_share_fee_rate;
+                self.max_share_fee_rates.insert(global_config, rate);
+                rate
+            }
+        };
+        if share_fee.rate > max_share_fee_rate {
+            let logger = Logger::new("[RAYDIUM-SHARE-FEE] => ".yellow().to_string());
+            logger.log(format!(
+                "Share fee rate {} exceeds the maximum of {} under global config {}, swapping without it",
+                share_fee.rate, max_share_fee_rate, global_config
+            ));
+            return Ok(None);
+        }
+        Ok(Some((share_fee.rate, receiver)))
+    }
+
+    /// Create the share fee receiver's associated token account when the receiver is a wallet
+    /// without one for the pool's quote mint. The account is only remembered once it is seen on
+    /// chain, so a swap that fails to land doesn't stop the next one from creating it.
+    async fn push_create_share_fee_account(&self, instructions: &mut Vec<Instruction>, receiver_account: &Pubkey, quote_mint: &Pubkey, quote_program: &Pubkey) -> Result<()> {
+        let Some(share_fee) = self.share_fee.filter(|share_fee| share_fee.receiver_mint.is_none()) else {
+            return Ok(());
+        };
+        if self.share_fee_accounts.contains(receiver_account) {
+            return Ok(());
+        }
+        let client = self.client()?;
+        match client.get_account_with_commitment(receiver_account, client.commitment()).await {
+            Ok(response) if response.value.is_some() => {
+                self.share_fee_accounts.insert(*receiver_account);
+            }
+            // Missing, or unknown because the lookup failed; the create is idempotent either way
+            _ => instructions.push(create_associated_token_account_idempotent(
+                &self.keypair.pubkey(),
+                &share_fee.receiver,
+                quote_mint,
+                quote_program,
+            )),
+        }
+        Ok(())
     }
 
+    /// Decode a pool and both of its configs; the configs are fetched together in one request
+    pub async fn get_launchpad_pool_by_id(&self, pool_id: &Pubkey) -> Result<LaunchpadPoolInfo> {
+        let state = self.get_pool_state(pool_id).await?;
+        let configs = self.get_account_data(&[state.global_config, state.platform_config]).await?;
+        let [global_config, platform_config]: [Option<Vec<u8>>; 2] = configs
+            .try_into()
+            .map_err(|_| anyhow!("Unexpected number of config accounts"))?;
+        let global_config = global_config
+            .ok_or_else(|| anyhow!("Global config {} not found", state.global_config))?;
+        let platform_config = platform_config
+            .ok_or_else(|| anyhow!("Platform config {} not found", state.platform_config))?;
+
+        let global_config = GlobalConfig::decode(&global_config)?;
+        if let Some(curve_type) = CurveType::from_u8(global_config.curve_type) {
+            POOL_CURVE_TYPES.insert(pool_id.to_string(), curve_type);
+        }
+
+        Ok(LaunchpadPoolInfo {
+            pool_id: *pool_id,
+            global_config,
+            platform_config: PlatformConfig::decode(&platform_config)?,
+            state,
+        })
+    }
+
+    pub async fn get_launchpad_pool(&self, mint_str: &str) -> Result<LaunchpadPoolInfo> {
+        let pool = self.get_raydium_pool(mint_str).await?;
+        self.get_launchpad_pool_by_id(&pool.pool_id).await
+    }
+
+    /// Spot price in SOL per whole token, read from the live pool state
     pub async fn get_token_price(&self, mint_str: &str) -> Result<f64> {
-        // For Raydium Launchpad, this method is mainly used for standalone price queries
-        // Since we're now using trade_info.price directly in the main flow,
-        // this fallback method returns a placeholder value
-        let _pool = self.get_raydium_pool(mint_str).await?;
-        
-        // Return a placeholder price since the real price should come from trade_info
-        // This method is rarely used in the main trading flow
-        // Note: The correct Raydium Launchpad price formula is:
-        // Price = (virtual_quote_reserve - real_quote_after) / (virtual_base_reserve - real_base_after)
-        Ok(0.0001) // Placeholder price in SOL
+        let pool = self.get_launchpad_pool(mint_str).await?;
+        if pool.state.is_migrated() {
+            return Err(anyhow!("Launchpad pool for {} has migrated, price is no longer on the curve", mint_str));
+        }
+        // The curve prices in the pool's quote mint
+        let sol_per_quote = quote_mint::ensure_rate(&self.quote_router, &pool.state.quote_mint).await?;
+        Ok(pool.state.spot_price(pool.global_config.curve_type)? * sol_per_quote)
     }
 
     async fn get_or_fetch_pool_info(
@@ -101,55 +468,207 @@ impl Raydium {
         mint: Pubkey
     ) -> Result<RaydiumPool> {
         // Use pool_id from trade_info instead of fetching it dynamically
-        let pool_id = Pubkey::from_str(&trade_info.pool_id)
-            .map_err(|e| anyhow!("Invalid pool_id in trade_info: {}", e))?;
-        
-        // For Raydium Launchpad, derive pool vault addresses using PDA (Program Derived Address)
-        let pump_program = RAYDIUM_LAUNCHPAD_PROGRAM;
-        let sol_mint = SOL_MINT;
-        
-        // Derive pool vault addresses using PDA with specific seeds
-        let base_seeds = [POOL_VAULT_SEED, pool_id.as_ref(), mint.as_ref()];
-        let (pool_base_account, _) = Pubkey::find_program_address(&base_seeds, &pump_program);
-        
-        let quote_seeds = [POOL_VAULT_SEED, pool_id.as_ref(), sol_mint.as_ref()];
-        let (pool_quote_account, _) = Pubkey::find_program_address(&quote_seeds, &pump_program);
-        
-        let pool_info = RaydiumPool {
+        let pool_id = match Pubkey::from_str(&trade_info.pool_id) {
+            Ok(pool_id) => pool_id,
+            Err(_) => return self.get_raydium_pool(&mint.to_string()).await,
+        };
+        // Trades built without a quote mint take it from the index, or from the pool itself
+        let quote_mint = if !trade_info.quote_mint.is_empty() {
+            POOL_INDEX.record_trade(trade_info);
+            trade_info.quote_mint_pubkey()
+        } else {
+            match POOL_INDEX.get(&mint.to_string()).filter(|pool| pool.pool_id == pool_id) {
+                Some(pool) => pool.quote_mint,
+                None => self.get_pool_state(&pool_id).await?.quote_mint,
+            }
+        };
+
+        // Prefer the vaults resolved from the observed swap instruction, otherwise derive the PDAs
+        let pool_base_account = Pubkey::from_str(&trade_info.base_vault)
+            .unwrap_or_else(|_| derive_pool_vault(&pool_id, &mint));
+        let pool_quote_account = Pubkey::from_str(&trade_info.quote_vault)
+            .unwrap_or_else(|_| derive_pool_vault(&pool_id, &quote_mint));
+
+        Ok(RaydiumPool {
             pool_id,
             base_mint: mint,
-            quote_mint: sol_mint,
+            quote_mint,
             pool_base_account,
             pool_quote_account,
-        };
-        
-
-            
-        Ok(pool_info)
+        })
     }
     
-    // Helper method to determine the correct token program for a mint
-    async fn get_token_program(&self, mint: &Pubkey) -> Result<Pubkey> {
-        if let Some(rpc_client) = &self.rpc_client {
-            match rpc_client.get_account(mint) {
-                Ok(account) => {
-                    if account.owner == TOKEN_2022_PROGRAM {
-                        Ok(TOKEN_2022_PROGRAM)
-                    } else {
-                        Ok(TOKEN_PROGRAM)
-                    }
-                },
-                Err(_) => {
-                    // Default to TOKEN_PROGRAM if we can't fetch the account
-                    Ok(TOKEN_PROGRAM)
+    /// Decimals of a mint, read from chain the first time it is seen
+    async fn get_mint_decimals(&self, mint: &Pubkey) -> Result<u8> {
+        let mint_info = token::get_mint_info(self.client()?.clone(), self.keypair.clone(), *mint)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch mint {}: {}", mint, e))?;
+        Ok(mint_info.base.decimals)
+    }
+
+    /// Mint state for Token-2022 mints, needed for transfer fees; `None` for legacy mints
+    async fn get_token_2022_mint(&self, mint: &Pubkey, token_program: &Pubkey) -> Result<Option<StateWithExtensionsOwned<Mint>>> {
+        if *token_program != TOKEN_2022_PROGRAM {
+            return Ok(None);
+        }
+        let client = self.rpc_nonblocking_client.as_ref()
+            .ok_or_else(|| anyhow!("RPC client not initialized"))?;
+        let mint_info = token::get_mint_info(client.clone(), self.keypair.clone(), *mint)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch Token-2022 mint {}: {}", mint, e))?;
+        Ok(Some(mint_info))
+    }
+
+    /// Quote a swap of `amount` raw units against the pool. For exact-out swaps `amount` is the
+    /// output; otherwise it is the input. Token-2022 transfer fees are included in the result.
+    #[allow(clippy::too_many_arguments)]
+    async fn quote_swap(
+        &self,
+        trade_info: &TradeInfoFromToken,
+        pool_info: &RaydiumPool,
+        token_program: &Pubkey,
+        swap_direction: &SwapDirection,
+        exact_out: bool,
+        amount: u64,
+        slippage_bps: u64,
+        share_fee_rate: u64,
+    ) -> Result<SwapQuote> {
+        let mint = pool_info.base_mint;
+
+        // Quote against the streamed reserves when the pool's curve is known, otherwise read the pool from chain
+        let streamed_curve = POOL_CURVE_TYPES
+            .get(&trade_info.pool_id)
+            .and_then(|curve_type| LaunchpadCurve::from_trade_info(trade_info, *curve_type));
+        let (curve, fees) = match streamed_curve {
+            Some(curve) => (curve, FeeRates::from_trade_info(trade_info).unwrap_or_default()),
+            None => {
+                let launchpad_pool = self.get_launchpad_pool_by_id(&pool_info.pool_id).await?;
+                if launchpad_pool.state.is_migrated() {
+                    return Err(anyhow!("Launchpad pool for {} has migrated", mint));
                 }
+                (launchpad_pool.curve()?, launchpad_pool.fee_rates())
             }
+        };
+        // The share fee is set by the swap, not the pool
+        let fees = FeeRates { share_fee_rate, ..fees };
+        // Token-2022 transfer fees apply to the base leg: the pool receives less than a seller
+        // sends, and a buyer receives less than the pool sends
+        let mint_2022 = self.get_token_2022_mint(&mint, token_program).await?;
+        let transfer_fee = |amount: u64| mint_2022.as_ref().map_or(0, |mint| token::transfer_fee(mint, amount));
+        if exact_out {
+            match swap_direction {
+                SwapDirection::Buy => {
+                    let base_out = mint_2022.as_ref()
+                        .map_or(amount, |mint| token::amount_before_transfer_fee(mint, amount));
+                    quote_buy_exact_out(base_out, &curve, &fees, slippage_bps)
+                }
+                SwapDirection::Sell => quote_sell_exact_out(amount, &curve, &fees, slippage_bps)
+                    .map(|mut quote| {
+                        if let Some(mint) = mint_2022.as_ref() {
+                            quote.amount_in = token::amount_before_transfer_fee(mint, quote.amount_in);
+                            quote.maximum_amount_in = token::amount_before_transfer_fee(mint, quote.maximum_amount_in);
+                        }
+                        quote
+                    }),
+            }
+            .ok_or_else(|| anyhow!("Curve for {} cannot fill an exact output of {}", mint, amount))
+        } else {
+            let quote = match swap_direction {
+                SwapDirection::Buy => {
+                    let mut quote = quote_buy_exact_in(amount, &curve, &fees, slippage_bps);
+                    quote.amount_out = quote.amount_out.saturating_sub(transfer_fee(quote.amount_out));
+                    quote.minimum_amount_out = apply_slippage(quote.amount_out, slippage_bps);
+                    quote
+                }
+                SwapDirection::Sell => {
+                    let base_received = amount.saturating_sub(transfer_fee(amount));
+                    SwapQuote {
+                        amount_in: amount,
+                        maximum_amount_in: amount,
+                        ..quote_sell_exact_in(base_received, &curve, &fees, slippage_bps)
+                    }
+                }
+            };
+            Ok(quote)
+        }
+    }
+
+    /// Create `ata` for `mint` in the same transaction unless the cache knows it. The create is
+    /// idempotent, so an account that already exists costs no RPC round trip on the hot path.
+    fn push_create_ata_if_missing(&self, instructions: &mut Vec<Instruction>, ata: &Pubkey, mint: &Pubkey, token_program: &Pubkey) {
+        if WALLET_TOKEN_ACCOUNTS.contains(ata) {
+            return;
+        }
+        let logger = Logger::new("[RAYDIUM-ATA-CREATE] => ".yellow().to_string());
+        logger.log(format!("Creating token ATA for mint {} at address {}", mint, ata));
+        instructions.push(create_associated_token_account_idempotent(
+            &self.keypair.pubkey(),
+            &self.keypair.pubkey(),
+            mint,
+            token_program,
+        ));
+        // Cache the account, it exists or is created by this transaction
+        WALLET_TOKEN_ACCOUNTS.insert(*ata);
+    }
+
+    async fn get_token_balance(&self, token_account: &Pubkey, mint: &Pubkey) -> Result<u64> {
+        let account = if let Some(client) = &self.rpc_nonblocking_client {
+            client.get_token_account(token_account).await
+        } else if let Some(client) = &self.rpc_client {
+            // Fallback to blocking client
+            client.get_token_account(token_account)
         } else {
-            // Default to TOKEN_PROGRAM if no RPC client
-            Ok(TOKEN_PROGRAM)
+            return Err(anyhow!("No RPC client available to fetch token balance"));
+        };
+        match account {
+            Ok(Some(account)) => account.token_amount.amount.parse::<u64>()
+                .map_err(|_| anyhow!("Failed to parse token balance for mint {}", mint)),
+            Ok(None) => Err(anyhow!("Token account does not exist for mint {}", mint)),
+            Err(e) => Err(anyhow!("Failed to get token account balance: {}", e)),
         }
     }
-    
+
+    /// SOL <-> quote swap on the quote mint's CPMM pool, funding buys and settling sells on pools
+    /// not quoted in SOL. `direction` is relative to the quote mint: a buy spends SOL.
+    #[allow(clippy::too_many_arguments)]
+    async fn quote_route_instruction(
+        &self,
+        quote_mint: &Pubkey,
+        quote_ata: &Pubkey,
+        wsol_ata: &Pubkey,
+        direction: SwapDirection,
+        exact_out: bool,
+        amount: u64,
+        slippage_bps: u64,
+    ) -> Result<(Instruction, SwapQuote)> {
+        let pool_id = self.quote_router.find_pool(quote_mint).await
+            .map_err(|e| anyhow!("No SOL route for quote mint {}: {}", quote_mint, e))?;
+        let pool = self.quote_router.load_pool(&pool_id).await?;
+        let quote = pool.quote(&direction, exact_out, amount, slippage_bps)?;
+        let (_, quote_vault, quote_program, sol_vault) = pool.token_side()?;
+        let quote_side = (*quote_ata, quote_vault, quote_program, *quote_mint);
+        let sol_side = (*wsol_ata, sol_vault, TOKEN_PROGRAM, SOL_MINT);
+        let (input, output) = match direction {
+            SwapDirection::Buy => (sol_side, quote_side),
+            SwapDirection::Sell => (quote_side, sol_side),
+        };
+        let (discriminator, amount, other_amount_threshold) = if exact_out {
+            (raydium_cpmm::SWAP_BASE_OUTPUT_DISCRIMINATOR, quote.amount_out, quote.maximum_amount_in)
+        } else {
+            (raydium_cpmm::SWAP_BASE_INPUT_DISCRIMINATOR, quote.amount_in, quote.minimum_amount_out)
+        };
+        let instruction = raydium_cpmm::create_swap_instruction(
+            discriminator,
+            &self.keypair.pubkey(),
+            &pool,
+            input,
+            output,
+            amount,
+            other_amount_threshold,
+        );
+        Ok((instruction, quote))
+    }
+
     // Highly optimized build_swap_from_parsed_data
     pub async fn build_swap_from_parsed_data(
         &self,
@@ -160,187 +679,192 @@ impl Raydium {
         let mint = Pubkey::from_str(&trade_info.mint)?;
         
         // Get token program for the mint
-        let token_program = self.get_token_program(&mint).await?;
+        let token_program = token::get_mint_token_program(self.client()?.clone(), &mint).await?;
+        let pool_info = self.get_or_fetch_pool_info(trade_info, mint).await?;
+        let quote_mint = pool_info.quote_mint;
+        let quote_is_sol = quote_mint::is_sol(&quote_mint);
         
         // Prepare swap parameters
-        let (_token_in, _token_out, discriminator) = match swap_config.swap_direction {
-            SwapDirection::Buy => (SOL_MINT, mint, BUY_DISCRIMINATOR),
-            SwapDirection::Sell => (mint, SOL_MINT, SELL_DISCRIMINATOR),
+        let discriminator = match swap_config.swap_direction {
+            SwapDirection::Buy => BUY_DISCRIMINATOR,
+            SwapDirection::Sell => SELL_DISCRIMINATOR,
         };
         
-        let mut instructions = Vec::with_capacity(3); // Pre-allocate for typical case
+        let mut instructions = Vec::with_capacity(5); // Pre-allocate for the routed case
         
-        // Check and create token accounts if needed
-        let token_ata = get_associated_token_address(&owner, &mint);
+        // Check and create token accounts if needed, under the mint's owning program.
+        // WSOL is always needed: it is the quote, or it funds and settles the quote route.
+        let token_ata = token::get_associated_token_address_for_program(&owner, &mint, &token_program);
         let wsol_ata = get_associated_token_address(&owner, &SOL_MINT);
+        self.push_create_ata_if_missing(&mut instructions, &token_ata, &mint, &token_program);
+        self.push_create_ata_if_missing(&mut instructions, &wsol_ata, &SOL_MINT, &TOKEN_PROGRAM); // WSOL always uses legacy token program
+        let quote_program = if quote_is_sol { TOKEN_PROGRAM } else { token::get_mint_token_program(self.client()?.clone(), &quote_mint).await? };
+        let quote_ata = if quote_is_sol {
+            wsol_ata
+        } else {
+            let quote_ata = token::get_associated_token_address_for_program(&owner, &quote_mint, &quote_program);
+            self.push_create_ata_if_missing(&mut instructions, &quote_ata, &quote_mint, &quote_program);
+            quote_ata
+        };
+        let (global_config, platform_config) = self.get_pool_configs(&pool_info).await?;
+        priority_fees::track_fee_account(&pool_info.pool_id);
         
-        // Check if token account exists and create if needed
-        if !WALLET_TOKEN_ACCOUNTS.contains(&token_ata) {
-            // Double-check with RPC to see if the account actually exists
-            let account_exists = if let Some(rpc_client) = &self.rpc_client {
-                match rpc_client.get_account(&token_ata) {
-                    Ok(_) => {
-                        // Account exists, add to cache
-                        WALLET_TOKEN_ACCOUNTS.insert(token_ata);
-                        true
-                    },
-                    Err(_) => false
-                }
-            } else {
-                false // No RPC client, assume account doesn't exist
-            };
-            
-            if !account_exists {
-                let logger = Logger::new("[RAYDIUM-ATA-CREATE] => ".yellow().to_string());
-                logger.log(format!("Creating token ATA for mint {} at address {}", mint, token_ata));
-                
-                instructions.push(create_associated_token_account_idempotent(
-                    &owner,
-                    &owner,
-                    &mint,
-                    &TOKEN_PROGRAM, // Always use legacy token program for ATA creation
-                ));
-                
-                // Cache the account since we're creating it
-                WALLET_TOKEN_ACCOUNTS.insert(token_ata);
-            }
-        }
-        
-        // Check if WSOL account exists and create if needed
-        if !WALLET_TOKEN_ACCOUNTS.contains(&wsol_ata) {
-            // Double-check with RPC to see if the account actually exists
-            let account_exists = if let Some(rpc_client) = &self.rpc_client {
-                match rpc_client.get_account(&wsol_ata) {
-                    Ok(_) => {
-                        // Account exists, add to cache
-                        WALLET_TOKEN_ACCOUNTS.insert(wsol_ata);
-                        true
-                    },
-                    Err(_) => false
-                }
-            } else {
-                false // No RPC client, assume account doesn't exist
-            };
-            
-            if !account_exists {
-                let logger = Logger::new("[RAYDIUM-WSOL-CREATE] => ".yellow().to_string());
-                logger.log(format!("Creating WSOL ATA at address {}", wsol_ata));
-                
-                instructions.push(create_associated_token_account_idempotent(
-                    &owner,
-                    &owner,
-                    &SOL_MINT,
-                    &TOKEN_PROGRAM, // WSOL always uses legacy token program
-                ));
-                
-                // Cache the account since we're creating it
-                WALLET_TOKEN_ACCOUNTS.insert(wsol_ata);
-            }
-        }
-        
-        // Convert amount_in to lamports/token units
+        // Convert amount_in to raw units
         // For Raydium Launchpad:
-        // - Buy: amount_in is SOL amount (convert to lamports)
-        // - Sell: amount_in is token amount (fetch actual balance and apply qty/pct logic)
-        let amount_in = match swap_config.swap_direction {
+        // - Buy: amount_in is SOL amount (converted to the quote mint), or tokens to receive for ExactOut
+        // - Sell: amount_in is token amount (fetch actual balance and apply qty/pct logic),
+        //   or SOL to receive for ExactOut
+        let exact_out = swap_config.in_type == SwapInType::ExactOut;
+        let mut token_balance = None;
+        // Quote held in the wallet; a non-SOL buy spends it before routing SOL through CPMM
+        let held_quote = if quote_is_sol || swap_config.swap_direction == SwapDirection::Sell {
+            0
+        } else {
+            self.get_token_balance(&quote_ata, &quote_mint).await.unwrap_or_default()
+        };
+        let amount = match swap_config.swap_direction {
             SwapDirection::Buy => {
-                // For buy: amount_in is SOL amount, convert to lamports
-                ui_amount_to_amount(swap_config.amount_in, 9)
+                if exact_out {
+                    ui_amount_to_amount(swap_config.amount_in, self.get_mint_decimals(&mint).await?)
+                } else {
+                    // For buy: amount_in is SOL amount, convert to lamports
+                    let lamports = ui_amount_to_amount(swap_config.amount_in, 9);
+                    if quote_is_sol {
+                        lamports
+                    } else {
+                        quote_mint::ensure_rate(&self.quote_router, &quote_mint).await?;
+                        let quote_amount = quote_mint::lamports_to_quote(&quote_mint, lamports)
+                            .ok_or_else(|| anyhow!("No SOL rate for quote mint {}", quote_mint))?;
+                        if held_quote >= quote_amount {
+                            quote_amount
+                        } else {
+                            // Swap the SOL into the quote first and buy with what that swap guarantees
+                            let (instruction, route_quote) = self.quote_route_instruction(
+                                &quote_mint, &quote_ata, &wsol_ata, SwapDirection::Buy, false, lamports, swap_config.slippage,
+                            ).await?;
+                            instructions.push(instruction);
+                            route_quote.minimum_amount_out
+                        }
+                    }
+                }
             },
             SwapDirection::Sell => {
                 // For sell: need to get actual token balance first
-                let token_ata = get_associated_token_address(&owner, &mint);
-                
-                // Get actual token balance from blockchain
-                let actual_token_balance = if let Some(client) = &self.rpc_nonblocking_client {
-                    match client.get_token_account(&token_ata).await {
-                        Ok(Some(account)) => {
-                            // Parse the amount string to get actual balance
-                            match account.token_amount.amount.parse::<u64>() {
-                                Ok(balance) => balance,
-                                Err(_) => {
-                                    return Err(anyhow!("Failed to parse token balance for mint {}", mint));
-                                }
-                            }
-                        },
-                        Ok(None) => {
-                            return Err(anyhow!("Token account does not exist for mint {}", mint));
-                        },
-                        Err(e) => {
-                            return Err(anyhow!("Failed to get token account balance: {}", e));
-                        }
-                    }
-                } else if let Some(client) = &self.rpc_client {
-                    // Fallback to blocking client
-                    match client.get_token_account(&token_ata) {
-                        Ok(Some(account)) => {
-                            match account.token_amount.amount.parse::<u64>() {
-                                Ok(balance) => balance,
-                                Err(_) => {
-                                    return Err(anyhow!("Failed to parse token balance for mint {}", mint));
-                                }
-                            }
-                        },
-                        Ok(None) => {
-                            return Err(anyhow!("Token account does not exist for mint {}", mint));
-                        },
-                        Err(e) => {
-                            return Err(anyhow!("Failed to get token account balance: {}", e));
-                        }
-                    }
-                } else {
-                    return Err(anyhow!("No RPC client available to fetch token balance"));
-                };
+                let actual_token_balance = self.get_token_balance(&token_ata, &mint).await?;
+                token_balance = Some(actual_token_balance);
                 
                 // Apply swap logic based on in_type
                 match swap_config.in_type {
                     SwapInType::Qty => {
                         // Use specified quantity (convert from UI amount to token units)
-                        let decimals = 6; // All tokens are 6 decimals
-                        ui_amount_to_amount(swap_config.amount_in, decimals)
+                        ui_amount_to_amount(swap_config.amount_in, self.get_mint_decimals(&mint).await?)
                     },
                     SwapInType::Pct => {
                         // Use percentage of actual balance
                         let percentage = swap_config.amount_in.min(1.0); // Cap at 100%
                         ((percentage * actual_token_balance as f64) as u64).max(1) // Ensure at least 1 token unit
+                    },
+                    SwapInType::ExactOut => {
+                        // SOL to receive, in lamports
+                        ui_amount_to_amount(swap_config.amount_in, 9)
                     }
                 }
             }
         };
-        
-        // Calculate the actual quote amount using virtual reserves from trade_info
-        let minimum_amount_out: u64 = 1; // to ignore slippage
-        
+
+        // An exact-out sell on a non-SOL pool receives the quote the settlement swap needs
+        let mut settlement = None;
+        let amount = if exact_out && swap_config.swap_direction == SwapDirection::Sell && !quote_is_sol {
+            let (instruction, route_quote) = self.quote_route_instruction(
+                &quote_mint, &quote_ata, &wsol_ata, SwapDirection::Sell, true, amount, swap_config.slippage,
+            ).await?;
+            settlement = Some(instruction);
+            route_quote.maximum_amount_in
+        } else {
+            amount
+        };
+
+        let share_fee = self.share_fee_for(&pool_info, &quote_program).await?;
+        if let Some((_, receiver_account)) = share_fee {
+            self.push_create_share_fee_account(&mut instructions, &receiver_account, &quote_mint, &quote_program).await?;
+        }
+        let quote = self.quote_swap(
+            trade_info,
+            &pool_info,
+            &token_program,
+            &swap_config.swap_direction,
+            exact_out,
+            amount,
+            swap_config.slippage,
+            share_fee.map_or(0, |(rate, _)| rate),
+        ).await?;
+        let (discriminator, amount, other_amount_threshold) = if exact_out {
+            // Never offer more tokens than the wallet holds
+            if let Some(balance) = token_balance {
+                if quote.amount_in > balance {
+                    return Err(anyhow!(
+                        "Exact-out sell of {} needs {} tokens but the wallet holds {}",
+                        mint, quote.amount_in, balance
+                    ));
+                }
+            }
+            let maximum_amount_in = token_balance.map_or(quote.maximum_amount_in, |balance| quote.maximum_amount_in.min(balance));
+            // An exact-out buy on a non-SOL pool swaps SOL for the quote it is short of
+            if swap_config.swap_direction == SwapDirection::Buy && !quote_is_sol && held_quote < maximum_amount_in {
+                let (instruction, _) = self.quote_route_instruction(
+                    &quote_mint, &quote_ata, &wsol_ata, SwapDirection::Buy, true, maximum_amount_in - held_quote, swap_config.slippage,
+                ).await?;
+                instructions.push(instruction);
+            }
+            let discriminator = match swap_config.swap_direction {
+                SwapDirection::Buy => BUY_EXACT_OUT_DISCRIMINATOR,
+                SwapDirection::Sell => SELL_EXACT_OUT_DISCRIMINATOR,
+            };
+            (discriminator, quote.amount_out, maximum_amount_in)
+        } else {
+            if quote.minimum_amount_out == 0 {
+                return Err(anyhow!("Quote for {} returned no output (amount in {})", mint, amount));
+            }
+            // Sell proceeds on a non-SOL pool are swapped back to SOL
+            if swap_config.swap_direction == SwapDirection::Sell && !quote_is_sol {
+                let (instruction, _) = self.quote_route_instruction(
+                    &quote_mint, &quote_ata, &wsol_ata, SwapDirection::Sell, false, quote.minimum_amount_out, swap_config.slippage,
+                ).await?;
+                settlement = Some(instruction);
+            }
+            (discriminator, amount, quote.minimum_amount_out)
+        };
+
         // Create accounts based on swap direction
         let accounts = match swap_config.swap_direction {
             SwapDirection::Buy => {
-                // For buy, we need pool info for accounts
-                let pool_info = self.get_or_fetch_pool_info(trade_info, mint).await?;
                 create_buy_accounts(
                     pool_info.pool_id,
                     owner,
+                    (global_config, platform_config),
                     mint,
-                    SOL_MINT,
+                    quote_mint,
                     token_ata,
-                    wsol_ata,
+                    quote_ata,
                     pool_info.pool_base_account,
                     pool_info.pool_quote_account,
                     &token_program,
+                    &quote_program,
                 )?
             },
             SwapDirection::Sell => {
-                // For sell, we need pool info for accounts
-                let pool_info = self.get_or_fetch_pool_info(trade_info, mint).await?;
                 create_sell_accounts(
                     pool_info.pool_id,
                     owner,
+                    (global_config, platform_config),
                     mint,
-                    SOL_MINT,
+                    quote_mint,
                     token_ata,
-                    wsol_ata,
+                    quote_ata,
                     pool_info.pool_base_account,
                     pool_info.pool_quote_account,
                     &token_program,
+                    &quote_program,
                 )?
             }
         };
@@ -348,13 +872,15 @@ impl Raydium {
         instructions.push(create_swap_instruction(
             RAYDIUM_LAUNCHPAD_PROGRAM,
             discriminator,
-            amount_in,
-            minimum_amount_out,
+            amount,
+            other_amount_threshold,
             accounts,
+            share_fee,
         ));
+        instructions.extend(settlement);
         
         // Return the actual price from trade_info (convert from lamports to SOL)
-        let price_in_sol = trade_info.price as f64 / 1_000_000_000.0;
+        let price_in_sol = trade_info.price / 1_000_000_000.0;
         
         Ok((self.keypair.clone(), instructions, price_in_sol))
     }
@@ -362,19 +888,123 @@ impl Raydium {
 
 }
 
-/// Get the Raydium pool information for a specific token mint
+#[async_trait]
+impl Dex for Raydium {
+    fn protocol(&self) -> SwapProtocol {
+        SwapProtocol::RaydiumLaunchpad
+    }
+
+    fn program_id(&self) -> Pubkey {
+        RAYDIUM_LAUNCHPAD_PROGRAM
+    }
+
+    async fn pool_for_mint(&self, mint: &str) -> Result<PoolKeys> {
+        self.get_raydium_pool(mint).await.map(PoolKeys::from)
+    }
+
+    async fn quote(&self, trade_info: &TradeInfoFromToken, swap_config: &SwapConfig, amount: u64) -> Result<SwapQuote> {
+        let mint = Pubkey::from_str(&trade_info.mint)?;
+        let token_program = token::get_mint_token_program(self.client()?.clone(), &mint).await?;
+        let pool_info = self.get_or_fetch_pool_info(trade_info, mint).await?;
+        let quote_program = if quote_mint::is_sol(&pool_info.quote_mint) {
+            TOKEN_PROGRAM
+        } else {
+            token::get_mint_token_program(self.client()?.clone(), &pool_info.quote_mint).await?
+        };
+        let share_fee = self.share_fee_for(&pool_info, &quote_program).await?;
+        self.quote_swap(
+            trade_info,
+            &pool_info,
+            &token_program,
+            &swap_config.swap_direction,
+            swap_config.in_type == SwapInType::ExactOut,
+            amount,
+            swap_config.slippage,
+            share_fee.map_or(0, |(rate, _)| rate),
+        ).await
+    }
+
+    async fn build_buy(&self, trade_info: &TradeInfoFromToken, mut swap_config: SwapConfig) -> Result<SwapInstructions> {
+        swap_config.swap_direction = SwapDirection::Buy;
+        self.build_swap_from_parsed_data(trade_info, swap_config).await
+    }
+
+    async fn build_sell(&self, trade_info: &TradeInfoFromToken, mut swap_config: SwapConfig) -> Result<SwapInstructions> {
+        swap_config.swap_direction = SwapDirection::Sell;
+        self.build_swap_from_parsed_data(trade_info, swap_config).await
+    }
+
+    async fn token_price(&self, mint: &str) -> Result<f64> {
+        self.get_token_price(mint).await
+    }
+
+    fn price_from_trade(&self, trade_info: &TradeInfoFromToken) -> f64 {
+        // The parser prices launchpad trades in lamports per whole token
+        trade_info.price / 1_000_000_000.0
+    }
+}
+
+impl From<RaydiumPool> for PoolKeys {
+    fn from(pool: RaydiumPool) -> Self {
+        Self {
+            pool_id: pool.pool_id,
+            base_mint: pool.base_mint,
+            quote_mint: pool.quote_mint,
+            base_vault: pool.pool_base_account,
+            quote_vault: pool.pool_quote_account,
+        }
+    }
+}
+
+/// Get the Raydium pool information for a specific token mint.
+/// Lookup order: the pool index, the pool PDA for the mint and each known quote mint, then a
+/// getProgramAccounts scan.
 pub async fn get_pool_info(
-    rpc_client: Arc<solana_client::rpc_client::RpcClient>,
+    rpc_client: Arc<solana_client::nonblocking::rpc_client::RpcClient>,
     mint: Pubkey,
 ) -> Result<RaydiumPool> {
     let logger = Logger::new("[RAYDIUM-GET-POOL-INFO] => ".blue().to_string());
-    
+
+    if let Some(pool) = POOL_INDEX.get(&mint.to_string()) {
+        return Ok(pool);
+    }
+
+    // Pools are PDAs of the mint pair, so one account read usually finds them
+    let derived_pool_ids: Vec<Pubkey> = KNOWN_QUOTE_MINTS.iter().map(|quote_mint| derive_pool_id(&mint, quote_mint)).collect();
+    if let Ok(accounts) = rpc_client.get_multiple_accounts(&derived_pool_ids).await {
+        for (derived_pool_id, account) in derived_pool_ids.iter().zip(accounts) {
+            let Some(state) = account.and_then(|account| PoolState::decode(&account.data).ok()) else {
+                continue;
+            };
+            if state.base_mint == mint {
+                let pool = RaydiumPool {
+                    pool_id: *derived_pool_id,
+                    base_mint: mint,
+                    quote_mint: state.quote_mint,
+                    pool_base_account: state.base_vault,
+                    pool_quote_account: state.quote_vault,
+                };
+                POOL_INDEX.insert(PoolIndexEntry {
+                    pool_id: pool.pool_id.to_string(),
+                    base_mint: mint.to_string(),
+                    quote_mint: pool.quote_mint.to_string(),
+                    base_vault: pool.pool_base_account.to_string(),
+                    quote_vault: pool.pool_quote_account.to_string(),
+                    slot: POOL_INDEX.latest_slot(), // Seen now; the pool account stores no slot
+                });
+                return Ok(pool);
+            }
+        }
+    }
+
+    logger.log(format!("Pool for {} not indexed, falling back to a program account scan", mint).yellow().to_string());
+
     // Initialize
-    let sol_mint = SOL_MINT;
     let pump_program = RAYDIUM_LAUNCHPAD_PROGRAM;
     
     // Use getProgramAccounts with config for better efficiency
     let mut pool_id = Pubkey::default();
+    let mut quote_mint = SOL_MINT;
     let mut retry_count = 0;
     let max_retries = 2;
     
@@ -384,8 +1014,11 @@ pub async fn get_pool_info(
             &pump_program,
             RpcProgramAccountsConfig {
                 filters: Some(vec![
-                    RpcFilterType::DataSize(300),
-                    RpcFilterType::Memcmp(Memcmp::new(43, MemcmpEncodedBytes::Base64(base64::encode(mint.to_bytes())))),
+                    RpcFilterType::DataSize(POOL_STATE_LEN),
+                    RpcFilterType::Memcmp(Memcmp::new(
+                        POOL_STATE_BASE_MINT_OFFSET,
+                        MemcmpEncodedBytes::Base64(base64::encode(mint.to_bytes())),
+                    )),
                 ]),
                 account_config: RpcAccountInfoConfig {
                     encoding: Some(UiAccountEncoding::Base64),
@@ -393,16 +1026,16 @@ pub async fn get_pool_info(
                 },
                 ..Default::default()
             },
-        ) {
+        ).await {
             Ok(accounts) => {
                 for (pubkey, account) in accounts.iter() {
-                    if account.data.len() >= 75 {
-                        if let Ok(pubkey_from_data) = Pubkey::try_from(&account.data[43..75]) {
-                            if pubkey_from_data == mint {
-                                pool_id = *pubkey;
-                                break;
-                            }
+                    match PoolState::decode(&account.data) {
+                        Ok(state) if state.base_mint == mint => {
+                            pool_id = *pubkey;
+                            quote_mint = state.quote_mint;
+                            break;
                         }
+                        _ => {}
                     }
                 }
                 
@@ -420,7 +1053,7 @@ pub async fn get_pool_info(
         
         retry_count += 1;
         if retry_count < max_retries {
-            std::thread::sleep(std::time::Duration::from_millis(500));
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
         }
     }
     
@@ -428,135 +1061,458 @@ pub async fn get_pool_info(
         return Err(anyhow!("Failed to find Raydium pool for mint {}", mint));
     }
     
-    // Derive pool vault addresses using PDA
-    let base_seeds = [POOL_VAULT_SEED, pool_id.as_ref(), mint.as_ref()];
-    let (pool_base_account, _) = Pubkey::find_program_address(&base_seeds, &pump_program);
-    
-    let quote_seeds = [POOL_VAULT_SEED, pool_id.as_ref(), sol_mint.as_ref()];
-    let (pool_quote_account, _) = Pubkey::find_program_address(&quote_seeds, &pump_program);
-    
-    Ok(RaydiumPool {
-        pool_id,
-        base_mint: mint,
-        quote_mint: sol_mint,
-        pool_base_account,
-        pool_quote_account,
-    })
+    let entry = PoolIndexEntry::derived(&pool_id, &mint, &quote_mint, POOL_INDEX.latest_slot());
+    let pool = entry.to_raydium_pool()
+        .ok_or_else(|| anyhow!("Invalid pool index entry for mint {}", mint))?;
+    POOL_INDEX.insert(entry);
+    Ok(pool)
 }
 
 // Optimized account creation with const pubkeys
-fn create_buy_accounts(
+// Order and flags follow idl/raydium_launchpad.json, checked by tests/launchpad_idl.rs
+#[allow(clippy::too_many_arguments)]
+pub fn create_buy_accounts(
     pool_id: Pubkey,
     user: Pubkey,
+    (global_config, platform_config): (Pubkey, Pubkey),
     base_mint: Pubkey,
     quote_mint: Pubkey,
     user_base_token_account: Pubkey,
-    wsol_account: Pubkey,
+    user_quote_token_account: Pubkey,
     pool_base_token_account: Pubkey,
     pool_quote_token_account: Pubkey,
     token_program: &Pubkey,
+    quote_token_program: &Pubkey,
 ) -> Result<Vec<AccountMeta>> {
     
     Ok(vec![
-        AccountMeta::new(user, true),
+        AccountMeta::new_readonly(user, true), // Writable anyway as the fee payer
         AccountMeta::new_readonly(RAYDIUM_LAUNCHPAD_AUTHORITY, false),
-        AccountMeta::new_readonly(RAYDIUM_GLOBAL_CONFIG, false),
-        AccountMeta::new_readonly(RAYDIUM_PLATFORM_CONFIG, false),
+        AccountMeta::new_readonly(global_config, false),
+        AccountMeta::new_readonly(platform_config, false),
         AccountMeta::new(pool_id, false),
         AccountMeta::new(user_base_token_account, false),
-        AccountMeta::new(wsol_account, false),
+        AccountMeta::new(user_quote_token_account, false),
         AccountMeta::new(pool_base_token_account, false),
         AccountMeta::new(pool_quote_token_account, false),
         AccountMeta::new_readonly(base_mint, false),
         AccountMeta::new_readonly(quote_mint, false),
         AccountMeta::new_readonly(*token_program, false), // Use detected token program for base mint
-        AccountMeta::new_readonly(TOKEN_PROGRAM, false), // Use legacy token program for WSOL
+        AccountMeta::new_readonly(*quote_token_program, false), // Legacy token program for WSOL
         AccountMeta::new_readonly(EVENT_AUTHORITY, false),
         AccountMeta::new_readonly(RAYDIUM_LAUNCHPAD_PROGRAM, false),
         ])
 }
 
 // Similar optimization for sell accounts
-fn create_sell_accounts(
+#[allow(clippy::too_many_arguments)]
+pub fn create_sell_accounts(
     pool_id: Pubkey,
     user: Pubkey,
+    (global_config, platform_config): (Pubkey, Pubkey),
     base_mint: Pubkey,
     quote_mint: Pubkey,
     user_base_token_account: Pubkey,
-    wsol_account: Pubkey,
+    user_quote_token_account: Pubkey,
     pool_base_token_account: Pubkey,
     pool_quote_token_account: Pubkey,
     token_program: &Pubkey,
+    quote_token_program: &Pubkey,
 ) -> Result<Vec<AccountMeta>> {
 
 
     Ok(vec![
-        AccountMeta::new(user, true),
+        AccountMeta::new_readonly(user, true), // Writable anyway as the fee payer
         AccountMeta::new_readonly(RAYDIUM_LAUNCHPAD_AUTHORITY, false),
-        AccountMeta::new_readonly(RAYDIUM_GLOBAL_CONFIG, false),
-        AccountMeta::new_readonly(RAYDIUM_PLATFORM_CONFIG, false),
+        AccountMeta::new_readonly(global_config, false),
+        AccountMeta::new_readonly(platform_config, false),
         AccountMeta::new(pool_id, false),
         AccountMeta::new(user_base_token_account, false),
-        AccountMeta::new(wsol_account, false),
+        AccountMeta::new(user_quote_token_account, false),
         AccountMeta::new(pool_base_token_account, false),
         AccountMeta::new(pool_quote_token_account, false),
         AccountMeta::new_readonly(base_mint, false),
         AccountMeta::new_readonly(quote_mint, false),
         AccountMeta::new_readonly(*token_program, false), // Use detected token program for base mint
-        AccountMeta::new_readonly(TOKEN_PROGRAM, false), // Use legacy token program for WSOL
+        AccountMeta::new_readonly(*quote_token_program, false), // Legacy token program for WSOL
         AccountMeta::new_readonly(EVENT_AUTHORITY, false),
         AccountMeta::new_readonly(RAYDIUM_LAUNCHPAD_PROGRAM, false),
 ])
 }
 
-#[inline]
-fn calculate_raydium_sell_amount_out(
-    base_amount_in: u64,
-    virtual_base_reserve: u64, 
-    virtual_quote_reserve: u64,
-    real_base_reserve: u64,
-    real_quote_reserve: u64
-) -> u64 {
-    if base_amount_in == 0 || virtual_base_reserve == 0 || virtual_quote_reserve == 0 {
-        return 0;
+/// Referral cut the launchpad routes to a partner on every swap the bot sends, configured
+/// with `SHARE_FEE_RATE` and `SHARE_FEE_RECEIVER`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShareFee {
+    pub rate: u64, // Over FEE_RATE_DENOMINATOR, capped by the global config's max_share_fee_rate
+    pub receiver: Pubkey,
+    /// Mint of `receiver` when it is a token account; `None` when it is a wallet, in which case
+    /// the fee goes to its associated token account for the pool's quote mint
+    pub receiver_mint: Option<Pubkey>,
+}
+
+impl ShareFee {
+    /// Reject rates the program would refuse under `global_config`
+    pub fn validate(&self, global_config: &GlobalConfig) -> Result<()> {
+        if self.rate > global_config.max_share_fee_rate {
+            return Err(anyhow!(
+                "Share fee rate {} exceeds the platform maximum of {} (over {})",
+                self.rate, global_config.max_share_fee_rate, FEE_RATE_DENOMINATOR
+            ));
+        }
+        Ok(())
     }
-    
-    // Raydium Launchpad constant product formula for selling:
-    // input_reserve = virtual_base - real_base  
-    // output_reserve = virtual_quote + real_quote
-    // amount_out = (amount_in * output_reserve) / (input_reserve + amount_in)
-    
-    let input_reserve = virtual_base_reserve.saturating_sub(real_base_reserve);
-    let output_reserve = virtual_quote_reserve.saturating_add(real_quote_reserve);
-    
-    if input_reserve == 0 || input_reserve > virtual_base_reserve {
-        return 0;
+
+    /// Token account receiving the fee on a pool quoted in `quote_mint`; `None` when the
+    /// configured receiver holds another mint
+    pub fn receiver_account(&self, quote_mint: &Pubkey, quote_token_program: &Pubkey) -> Option<Pubkey> {
+        match self.receiver_mint {
+            Some(receiver_mint) => (receiver_mint == *quote_mint).then_some(self.receiver),
+            None => Some(token::get_associated_token_address_for_program(&self.receiver, quote_mint, quote_token_program)),
+        }
     }
-    
-    let numerator = (base_amount_in as u128).saturating_mul(output_reserve as u128);
-    let denominator = (input_reserve as u128).saturating_add(base_amount_in as u128);
-    
+
+    /// Fraction of the quote amount paid as share fee
+    pub fn fraction(&self) -> f64 {
+        self.rate as f64 / FEE_RATE_DENOMINATOR as f64
+    }
+}
+
+/// Fee rates charged on the quote side of a swap, over `FEE_RATE_DENOMINATOR`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FeeRates {
+    pub trade_fee_rate: u64,
+    pub platform_fee_rate: u64,
+    pub creator_fee_rate: u64,
+    pub share_fee_rate: u64,
+}
+
+impl Default for FeeRates {
+    /// Let's Bonk rates: 0.25% protocol, 1% platform, no creator fee
+    fn default() -> Self {
+        Self {
+            trade_fee_rate: 2_500,
+            platform_fee_rate: 10_000,
+            creator_fee_rate: 0,
+            share_fee_rate: 0,
+        }
+    }
+}
+
+impl FeeRates {
+    pub fn from_configs(global_config: &GlobalConfig, platform_config: &PlatformConfig) -> Self {
+        Self {
+            trade_fee_rate: global_config.trade_fee_rate,
+            platform_fee_rate: platform_config.fee_rate,
+            creator_fee_rate: platform_config.creator_fee_rate,
+            share_fee_rate: 0,
+        }
+    }
+
+    /// Recover the rates from the fees charged in a streamed trade. Rates are rounded up so
+    /// the resulting quote errs on the low side.
+    pub fn from_trade_info(trade_info: &TradeInfoFromToken) -> Option<Self> {
+        let fees = [trade_info.protocol_fee, trade_info.platform_fee, trade_info.creator_fee, trade_info.share_fee];
+        let total_fee: u64 = fees.iter().sum();
+        // Fees are taken from the quote input on buys and from the gross quote output on sells
+        let gross_quote = if trade_info.is_buy {
+            trade_info.amount_in
+        } else {
+            trade_info.amount_out.saturating_add(total_fee)
+        };
+        if gross_quote == 0 {
+            return None;
+        }
+        let rate = |fee: u64| ceil_div(fee as u128 * FEE_RATE_DENOMINATOR as u128, gross_quote as u128) as u64;
+        Some(Self {
+            trade_fee_rate: rate(fees[0]),
+            platform_fee_rate: rate(fees[1]),
+            creator_fee_rate: rate(fees[2]),
+            share_fee_rate: rate(fees[3]),
+        })
+    }
+
+    /// Total fee charged on `amount`; each component is rounded up like the program does
+    pub fn total_fee(&self, amount: u64) -> u64 {
+        [self.trade_fee_rate, self.platform_fee_rate, self.creator_fee_rate, self.share_fee_rate]
+            .iter()
+            .map(|rate| ceil_div(amount as u128 * *rate as u128, FEE_RATE_DENOMINATOR as u128) as u64)
+            .sum()
+    }
+
+    /// Smallest gross amount that still leaves `net_amount` once fees are deducted
+    pub fn gross_up(&self, net_amount: u64) -> Option<u64> {
+        let total_rate = self.trade_fee_rate + self.platform_fee_rate + self.creator_fee_rate + self.share_fee_rate;
+        if total_rate >= FEE_RATE_DENOMINATOR {
+            return None;
+        }
+        let estimate = ceil_div(
+            net_amount as u128 * FEE_RATE_DENOMINATOR as u128,
+            (FEE_RATE_DENOMINATOR - total_rate) as u128,
+        );
+        let mut gross = u64::try_from(estimate).ok()?;
+        // Per-component rounding can leave the estimate a few units short
+        while gross.saturating_sub(self.total_fee(gross)) < net_amount {
+            gross = gross.checked_add(1)?;
+        }
+        Some(gross)
+    }
+}
+
+#[inline]
+fn ceil_div(numerator: u128, denominator: u128) -> u128 {
     if denominator == 0 {
         return 0;
     }
-    
-    numerator.checked_div(denominator).unwrap_or(0) as u64
+    numerator.div_ceil(denominator)
+}
+
+/// Apply a slippage tolerance in basis points to an expected output
+pub fn apply_slippage(amount_out: u64, slippage_bps: u64) -> u64 {
+    let slippage_bps = slippage_bps.min(TEN_THOUSAND);
+    (amount_out as u128 * (TEN_THOUSAND - slippage_bps) as u128 / TEN_THOUSAND as u128) as u64
+}
+
+/// Apply a slippage tolerance in basis points to an expected input
+pub fn apply_max_slippage(amount_in: u64, slippage_bps: u64) -> u64 {
+    let maximum = ceil_div(amount_in as u128 * (TEN_THOUSAND + slippage_bps) as u128, TEN_THOUSAND as u128);
+    maximum.min(u64::MAX as u128) as u64
+}
+
+/// Quote a buy of `quote_amount_in` lamports: fees come off the input before it hits the curve
+pub fn quote_buy_exact_in(
+    quote_amount_in: u64,
+    curve: &LaunchpadCurve,
+    fees: &FeeRates,
+    slippage_bps: u64,
+) -> SwapQuote {
+    let fee = fees.total_fee(quote_amount_in);
+    let amount_out = curve.buy_exact_in(quote_amount_in.saturating_sub(fee));
+    SwapQuote {
+        amount_in: quote_amount_in,
+        amount_out,
+        fee,
+        minimum_amount_out: apply_slippage(amount_out, slippage_bps),
+        maximum_amount_in: quote_amount_in,
+    }
+}
+
+/// Quote a sell of `base_amount_in` raw tokens: fees come off the quote the curve pays out
+pub fn quote_sell_exact_in(
+    base_amount_in: u64,
+    curve: &LaunchpadCurve,
+    fees: &FeeRates,
+    slippage_bps: u64,
+) -> SwapQuote {
+    let gross_amount_out = curve.sell_exact_in(base_amount_in);
+    let fee = fees.total_fee(gross_amount_out);
+    let amount_out = gross_amount_out.saturating_sub(fee);
+    SwapQuote {
+        amount_in: base_amount_in,
+        amount_out,
+        fee,
+        minimum_amount_out: apply_slippage(amount_out, slippage_bps),
+        maximum_amount_in: base_amount_in,
+    }
+}
+
+/// Quote a buy of exactly `base_amount_out` raw tokens; the fees are added on top of the curve input
+pub fn quote_buy_exact_out(
+    base_amount_out: u64,
+    curve: &LaunchpadCurve,
+    fees: &FeeRates,
+    slippage_bps: u64,
+) -> Option<SwapQuote> {
+    let amount_in = fees.gross_up(curve.buy_exact_out(base_amount_out)?)?;
+    Some(SwapQuote {
+        amount_in,
+        amount_out: base_amount_out,
+        fee: fees.total_fee(amount_in),
+        minimum_amount_out: base_amount_out,
+        maximum_amount_in: apply_max_slippage(amount_in, slippage_bps),
+    })
+}
+
+/// Quote a sell that receives exactly `quote_amount_out` lamports after fees
+pub fn quote_sell_exact_out(
+    quote_amount_out: u64,
+    curve: &LaunchpadCurve,
+    fees: &FeeRates,
+    slippage_bps: u64,
+) -> Option<SwapQuote> {
+    let gross_amount_out = fees.gross_up(quote_amount_out)?;
+    let amount_in = curve.sell_exact_out(gross_amount_out)?;
+    Some(SwapQuote {
+        amount_in,
+        amount_out: quote_amount_out,
+        fee: fees.total_fee(gross_amount_out),
+        minimum_amount_out: quote_amount_out,
+        maximum_amount_in: apply_max_slippage(amount_in, slippage_bps),
+    })
 }
 
 // Optimized instruction creation
-fn create_swap_instruction(
+// Exact-in: (amount_in, minimum_amount_out); exact-out: (amount_out, maximum_amount_in)
+// The share fee receiver, when there is one, follows the fixed accounts
+pub fn create_swap_instruction(
     program_id: Pubkey,
     discriminator: [u8; 8],
-    amount_in: u64,
-    minimum_amount_out: u64,
-    accounts: Vec<AccountMeta>,
+    amount: u64,
+    other_amount_threshold: u64,
+    mut accounts: Vec<AccountMeta>,
+    share_fee: Option<(u64, Pubkey)>,
 ) -> Instruction {
-    let mut data = Vec::with_capacity(24);
-    let share_fee_rate = 0_u64;
+    let mut data = Vec::with_capacity(32);
+    let share_fee_rate = match share_fee {
+        Some((rate, receiver)) => {
+            accounts.push(AccountMeta::new(receiver, false));
+            rate
+        }
+        None => 0,
+    };
     data.extend_from_slice(&discriminator);
-    data.extend_from_slice(&amount_in.to_le_bytes());
-    data.extend_from_slice(&minimum_amount_out.to_le_bytes());
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.extend_from_slice(&other_amount_threshold.to_le_bytes());
     data.extend_from_slice(&share_fee_rate.to_le_bytes());
     
     Instruction { program_id, accounts, data }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh Let's Bonk pool
+    fn bonk_curve() -> LaunchpadCurve {
+        LaunchpadCurve {
+            curve_type: CurveType::ConstantProduct,
+            virtual_base: 1_073_025_605_596_382,
+            virtual_quote: 30_000_852_951,
+            real_base: 0,
+            real_quote: 0,
+            total_base_sell: Some(793_100_000_000_000),
+        }
+    }
+
+    /// The same pool after 10 SOL of buys, so there is quote to sell into
+    fn traded_curve() -> LaunchpadCurve {
+        let real_base = bonk_curve().buy_exact_in(10_000_000_000);
+        LaunchpadCurve { real_base, real_quote: 10_000_000_000, ..bonk_curve() }
+    }
+
+    #[test]
+    fn total_fee_rounds_each_component_up() {
+        let fees = FeeRates::default();
+        assert_eq!(fees.total_fee(1_000_000_000), 12_500_000);
+        assert_eq!(fees.total_fee(1), 2);
+        assert_eq!(fees.total_fee(0), 0);
+        let fees = FeeRates { creator_fee_rate: 5_000, share_fee_rate: 1_000, ..fees };
+        assert_eq!(fees.total_fee(1_000_000_000), 18_500_000);
+    }
+
+    #[test]
+    fn gross_up_returns_the_smallest_sufficient_amount() {
+        for fees in [
+            FeeRates::default(),
+            FeeRates { creator_fee_rate: 5_000, share_fee_rate: 3_333, ..FeeRates::default() },
+            FeeRates { trade_fee_rate: 0, platform_fee_rate: 0, creator_fee_rate: 0, share_fee_rate: 0 },
+        ] {
+            for net in [0, 1, 7, 999, 1_000_000, 123_456_789, 85_000_000_000] {
+                let gross = fees.gross_up(net).unwrap();
+                assert!(gross - fees.total_fee(gross) >= net, "{:?}: {} does not cover {}", fees, gross, net);
+                if gross > 0 {
+                    let smaller = gross - 1;
+                    assert!(smaller.saturating_sub(fees.total_fee(smaller)) < net, "{:?}: {} is not minimal for {}", fees, gross, net);
+                }
+            }
+        }
+        let all_fees = FeeRates { trade_fee_rate: FEE_RATE_DENOMINATOR, ..FeeRates::default() };
+        assert_eq!(all_fees.gross_up(1), None);
+        assert_eq!(FeeRates::default().gross_up(u64::MAX), None);
+    }
+
+    #[test]
+    fn fee_rates_are_recovered_from_buys_and_sells() {
+        // Buys pay fees out of amount_in
+        let buy = TradeInfoFromToken {
+            is_buy: true,
+            amount_in: 1_000_000_000,
+            protocol_fee: 2_500_000,
+            platform_fee: 10_000_000,
+            ..Default::default()
+        };
+        assert_eq!(FeeRates::from_trade_info(&buy), Some(FeeRates::default()));
+        // Sells report amount_out after the fees were taken from the curve output
+        let sell = TradeInfoFromToken {
+            is_buy: false,
+            amount_in: 5_000_000_000_000,
+            amount_out: 987_500_000,
+            protocol_fee: 2_500_000,
+            platform_fee: 10_000_000,
+            ..Default::default()
+        };
+        assert_eq!(FeeRates::from_trade_info(&sell), Some(FeeRates::default()));
+        assert_eq!(FeeRates::from_trade_info(&TradeInfoFromToken::default()), None);
+    }
+
+    #[test]
+    fn slippage_bounds_round_against_the_trader() {
+        assert_eq!(apply_slippage(1_000_000, 0), 1_000_000);
+        assert_eq!(apply_slippage(1_000_000, 100), 990_000);
+        assert_eq!(apply_slippage(999, 100), 989);
+        assert_eq!(apply_slippage(1_000_000, 10_000), 0);
+        assert_eq!(apply_slippage(1_000_000, 20_000), 0);
+        assert_eq!(apply_slippage(u64::MAX, 0), u64::MAX);
+
+        assert_eq!(apply_max_slippage(1_000_000, 0), 1_000_000);
+        assert_eq!(apply_max_slippage(1_000_000, 100), 1_010_000);
+        assert_eq!(apply_max_slippage(999, 100), 1_009);
+        assert_eq!(apply_max_slippage(1_000_000, 10_000), 2_000_000);
+        assert_eq!(apply_max_slippage(u64::MAX, 1), u64::MAX);
+    }
+
+    #[test]
+    fn buys_pay_fees_on_the_quote_input() {
+        let curve = bonk_curve();
+        let fees = FeeRates::default();
+        let quote = quote_buy_exact_in(1_000_000_000, &curve, &fees, 0);
+        assert_eq!(quote.fee, 12_500_000);
+        assert_eq!(quote.amount_out, curve.buy_exact_in(987_500_000));
+        assert_eq!(quote.minimum_amount_out, quote.amount_out);
+        assert_eq!(quote.maximum_amount_in, 1_000_000_000);
+        assert_eq!(quote_buy_exact_in(1_000_000_000, &curve, &fees, 10_000).minimum_amount_out, 0);
+
+        let quote = quote_buy_exact_out(quote.amount_out, &curve, &fees, 0).unwrap();
+        assert_eq!(quote.fee, fees.total_fee(quote.amount_in));
+        assert!(quote.amount_in - quote.fee >= curve.buy_exact_out(quote.amount_out).unwrap());
+        // Buying back what 1 SOL bought costs at most 1 SOL
+        assert!(quote.amount_in <= 1_000_000_000);
+        assert_eq!(quote.maximum_amount_in, quote.amount_in);
+        let quote_max = quote_buy_exact_out(quote.amount_out, &curve, &fees, 10_000).unwrap();
+        assert_eq!(quote_max.maximum_amount_in, quote.amount_in * 2);
+        assert!(quote_buy_exact_out(800_000_000_000_000, &curve, &fees, 0).is_none());
+    }
+
+    #[test]
+    fn sells_pay_fees_on_the_quote_output() {
+        let curve = traded_curve();
+        let fees = FeeRates::default();
+        let base_in = curve.real_base / 2;
+        let gross = curve.sell_exact_in(base_in);
+        let quote = quote_sell_exact_in(base_in, &curve, &fees, 0);
+        assert_eq!(quote.fee, fees.total_fee(gross));
+        assert_eq!(quote.amount_out + quote.fee, gross);
+        assert_eq!(quote.minimum_amount_out, quote.amount_out);
+        assert_eq!(quote_sell_exact_in(base_in, &curve, &fees, 10_000).minimum_amount_out, 0);
+
+        let quote = quote_sell_exact_out(quote.amount_out, &curve, &fees, 0).unwrap();
+        let gross = curve.sell_exact_in(quote.amount_in);
+        assert!(gross - fees.total_fee(gross) >= quote.amount_out);
+        assert!(quote.amount_in <= base_in);
+        assert_eq!(quote.maximum_amount_in, quote.amount_in);
+        let quote_max = quote_sell_exact_out(quote.amount_out, &curve, &fees, 10_000).unwrap();
+        assert_eq!(quote_max.maximum_amount_in, quote.amount_in * 2);
+        // More than the pool raised cannot be paid out
+        assert!(quote_sell_exact_out(10_000_000_000, &curve, &fees, 0).is_none());
+    }
+}
@@ -0,0 +1,157 @@
+//! Venue abstraction.
+//!
+//! Every venue the bot trades on implements `Dex`, and the engine reaches venues only through a
+//! `DexRegistry` keyed by `SwapProtocol`. Adding a venue means adding a module with a `Dex`
+//! implementation and registering it in `DexRegistry::new`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use anyhow::Result;
+use async_trait::async_trait;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Keypair};
+
+use crate::common::config::SwapConfig;
+use crate::dex::pump_fun::PumpFun;
+use crate::dex::pump_swap::PumpSwap;
+use crate::dex::raydium_cpmm::RaydiumCpmm;
+use crate::dex::raydium_launchpad::{Raydium, ShareFee};
+use crate::engine::swap::SwapProtocol;
+use crate::engine::transaction_parser::TradeInfoFromToken;
+
+/// Accounts identifying a pool on any venue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolKeys {
+    pub pool_id: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub base_vault: Pubkey,
+    pub quote_vault: Pubkey,
+}
+
+/// Result of quoting a swap, in raw units
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SwapQuote {
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee: u64,
+    pub minimum_amount_out: u64,
+    pub maximum_amount_in: u64,
+}
+
+/// Signer, instructions and the trade price in SOL per token, as returned by the builders
+pub type SwapInstructions = (Arc<Keypair>, Vec<Instruction>, f64);
+
+/// A trading venue
+#[async_trait]
+pub trait Dex: Send + Sync {
+    /// Protocol this venue is registered under
+    fn protocol(&self) -> SwapProtocol;
+
+    /// Program id, used for stream subscriptions
+    fn program_id(&self) -> Pubkey;
+
+    /// Whether launches are discovered on this venue, so the DEX stream follows its whole
+    /// program. Post-migration AMMs are streamed per token instead.
+    fn discovers_launches(&self) -> bool {
+        true
+    }
+
+    /// Pool trading `mint` against its quote mint, SOL everywhere but on some launchpad pools
+    async fn pool_for_mint(&self, mint: &str) -> Result<PoolKeys>;
+
+    /// Quote a swap of `amount` raw units on the pool of `trade_info`. The amount is the input,
+    /// or the output when `swap_config.in_type` is exact-out.
+    async fn quote(&self, trade_info: &TradeInfoFromToken, swap_config: &SwapConfig, amount: u64) -> Result<SwapQuote>;
+
+    /// Buy instructions for the pool of `trade_info`
+    async fn build_buy(&self, trade_info: &TradeInfoFromToken, swap_config: SwapConfig) -> Result<SwapInstructions>;
+
+    /// Sell instructions for the pool of `trade_info`
+    async fn build_sell(&self, trade_info: &TradeInfoFromToken, swap_config: SwapConfig) -> Result<SwapInstructions>;
+
+    /// Spot price in SOL per whole token, read from chain
+    async fn token_price(&self, mint: &str) -> Result<f64>;
+
+    /// Price of a parsed trade in SOL per whole token
+    fn price_from_trade(&self, trade_info: &TradeInfoFromToken) -> f64;
+}
+
+/// Venues keyed by protocol; `Auto` and `Unknown` resolve to the default venue
+pub struct DexRegistry {
+    venues: HashMap<SwapProtocol, Arc<dyn Dex>>,
+    default_protocol: SwapProtocol,
+}
+
+impl DexRegistry {
+    /// Registry with every venue the bot supports
+    pub fn new(
+        wallet: Arc<Keypair>,
+        rpc_client: Arc<solana_client::rpc_client::RpcClient>,
+        rpc_nonblocking_client: Arc<solana_client::nonblocking::rpc_client::RpcClient>,
+        share_fee: Option<ShareFee>,
+    ) -> Self {
+        let mut registry = Self::empty(SwapProtocol::RaydiumLaunchpad);
+        registry.register(Arc::new(Raydium::new(
+            wallet.clone(),
+            Some(rpc_client.clone()),
+            Some(rpc_nonblocking_client.clone()),
+            share_fee,
+        )));
+        registry.register(Arc::new(PumpFun::new(
+            wallet.clone(),
+            Some(rpc_client.clone()),
+            Some(rpc_nonblocking_client.clone()),
+        )));
+        registry.register(Arc::new(RaydiumCpmm::new(
+            wallet.clone(),
+            Some(rpc_client.clone()),
+            Some(rpc_nonblocking_client.clone()),
+        )));
+        registry.register(Arc::new(PumpSwap::new(
+            wallet,
+            Some(rpc_client),
+            Some(rpc_nonblocking_client),
+        )));
+        registry
+    }
+
+    pub fn empty(default_protocol: SwapProtocol) -> Self {
+        Self {
+            venues: HashMap::new(),
+            default_protocol,
+        }
+    }
+
+    /// Register a venue under its protocol, replacing any venue already registered there
+    pub fn register(&mut self, dex: Arc<dyn Dex>) {
+        self.venues.insert(dex.protocol(), dex);
+    }
+
+    pub fn get(&self, protocol: &SwapProtocol) -> Option<Arc<dyn Dex>> {
+        let protocol = match protocol {
+            SwapProtocol::Auto | SwapProtocol::Unknown => &self.default_protocol,
+            protocol => protocol,
+        };
+        self.venues.get(protocol).cloned()
+    }
+
+    /// Program ids of all registered venues
+    pub fn program_ids(&self) -> Vec<Pubkey> {
+        let mut program_ids: Vec<Pubkey> = self.venues.values().map(|dex| dex.program_id()).collect();
+        program_ids.sort();
+        program_ids.dedup();
+        program_ids
+    }
+
+    /// Program ids of the venues launches are discovered on
+    pub fn discovery_program_ids(&self) -> Vec<Pubkey> {
+        let mut program_ids: Vec<Pubkey> = self.venues
+            .values()
+            .filter(|dex| dex.discovers_launches())
+            .map(|dex| dex.program_id())
+            .collect();
+        program_ids.sort();
+        program_ids.dedup();
+        program_ids
+    }
+}
@@ -3,5 +3,6 @@ pub mod monitor;
 pub mod risk_management;
 pub mod selling_strategy;
 pub mod swap;
+pub mod transaction_fixture;
 pub mod transaction_parser;
 pub mod transaction_retry;
@@ -1,5 +1,4 @@
 use crate::common::config::import_env_var;
-use crate::engine::monitor::PoolInfo;
 use solana_sdk::signature::Signer;
 use std::collections::{HashSet, VecDeque};
 use std::str::FromStr;
@@ -10,24 +9,26 @@ use anchor_client::solana_sdk::{hash::Hash, instruction::Instruction, pubkey::Pu
 use colored::Colorize;
 use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
-use spl_associated_token_account::get_associated_token_address;
+use crate::core::token::get_wallet_token_address;
 use dashmap::DashMap;
 use solana_program_pack::Pack;
 
 use crate::common::{
-    config::{AppState, SwapConfig},
+    config::{AppState, SwapConfig, TransactionLandingMode},
     logger::Logger,
-    cache::WALLET_TOKEN_ACCOUNTS,
 };
-use crate::engine::transaction_parser::{TradeInfoFromToken, DexType};
+use crate::engine::transaction_parser::{TradeInfoFromToken, DexType, MigrationEvent, MigrationVenue};
 use crate::engine::swap::{SwapDirection, SwapProtocol, SwapInType};
-use crate::dex::raydium_launchpad::RaydiumLaunchpad;
+use crate::dex::quote_mint::KNOWN_QUOTE_MINTS;
 
 // Implement conversion from SwapProtocol to DexType
 impl From<SwapProtocol> for DexType {
     fn from(protocol: SwapProtocol) -> Self {
         match protocol {
             SwapProtocol::RaydiumLaunchpad => DexType::RaydiumLaunchpad,
+            SwapProtocol::PumpFun => DexType::PumpFun,
+            SwapProtocol::RaydiumCpmm => DexType::RaydiumCpmm,
+            SwapProtocol::PumpSwap => DexType::PumpSwap,
             SwapProtocol::Auto | SwapProtocol::Unknown => DexType::Unknown,
         }
     }
@@ -42,6 +43,49 @@ lazy_static! {
         map.insert((), VecDeque::with_capacity(100));
         map
     });
+    // Tokens whose launchpad curve completed, keyed by mint; sells are routed by `route_sell`
+    pub static ref MIGRATED_TOKENS: Arc<DashMap<String, MigrationEvent>> = Arc::new(DashMap::new());
+}
+
+/// Venue and trade info to sell `trade_info.mint` with. A mint that completed its launchpad
+/// curve is sold on the pool it migrated to; when that venue has no adapter (or is not known
+/// yet) this fails, so callers fall back to Jupiter instead of swapping against the drained curve.
+pub fn route_sell(trade_info: &TradeInfoFromToken, protocol: &SwapProtocol) -> Result<(SwapProtocol, TradeInfoFromToken)> {
+    let Some(migration) = MIGRATED_TOKENS.get(&trade_info.mint).map(|entry| entry.clone()) else {
+        return Ok((protocol.clone(), trade_info.clone()));
+    };
+    let venue_protocol = SwapProtocol::from_migration_venue(&migration.venue)
+        .ok_or_else(|| anyhow!("Token {} migrated to {:?}, which has no venue adapter", migration.mint, migration.venue))?;
+    let migrated_trade_info = TradeInfoFromToken {
+        slot: trade_info.slot,
+        timestamp: trade_info.timestamp,
+        token_change: trade_info.token_change,
+        ..migrated_trade_info(&migration, venue_protocol.clone(), &trade_info.signature)
+    };
+    Ok((venue_protocol, migrated_trade_info))
+}
+
+/// PnL in percent of a round trip from `entry_price` to `exit_price`, net of a share fee of
+/// `share_fee_fraction` paid on the quote side of both legs
+pub fn net_pnl_percentage(entry_price: f64, exit_price: f64, share_fee_fraction: f64) -> f64 {
+    let entry_cost = entry_price * (1.0 + share_fee_fraction);
+    if entry_cost <= 0.0 {
+        return 0.0;
+    }
+    (exit_price * (1.0 - share_fee_fraction) - entry_cost) / entry_cost * 100.0
+}
+
+/// Sell-side trade info on the pool `migration` moved to; without a pool id the venue looks the
+/// pool up by mint
+fn migrated_trade_info(migration: &MigrationEvent, protocol: SwapProtocol, signature: &str) -> TradeInfoFromToken {
+    TradeInfoFromToken {
+        dex_type: DexType::from(protocol),
+        signature: signature.to_string(),
+        pool_id: migration.new_pool_id.clone().unwrap_or_default(),
+        mint: migration.mint.clone(),
+        is_buy: false,
+        ..Default::default()
+    }
 }
 
 /// Token metrics for selling strategy
@@ -85,6 +129,8 @@ pub struct TradeExecutionRecord {
     pub timestamp: u64,
     pub amount_sold: f64,
     pub protocol: String,
+    #[serde(default)]
+    pub share_fee: f64, // SOL paid to the share fee receiver on the buy and the sell
 }
 
 /// Market condition enum for dynamic strategy adjustment
@@ -453,6 +499,12 @@ pub struct TokenManager {
     logger: Logger,
 }
 
+impl Default for TokenManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TokenManager {
     pub fn new() -> Self {
         Self {
@@ -571,11 +623,10 @@ impl TokenManager {
     ) -> Result<bool> {
         use solana_sdk::pubkey::Pubkey;
         use std::str::FromStr;
-        use spl_associated_token_account::get_associated_token_address;
         
         if let Ok(wallet_pubkey) = app_state.wallet.try_pubkey() {
             if let Ok(token_pubkey) = Pubkey::from_str(token_mint) {
-                let ata = get_associated_token_address(&wallet_pubkey, &token_pubkey);
+                let ata = get_wallet_token_address(app_state.rpc_nonblocking_client.clone(), &wallet_pubkey, &token_pubkey).await?;
                 
                 match app_state.rpc_nonblocking_client.get_token_account(&ata).await {
                     Ok(account_result) => {
@@ -636,7 +687,7 @@ impl TokenManager {
                 metrics.amount_held,
                 metrics.entry_price,
                 metrics.current_price,
-                format!("{:.2}%", current_pnl).color(pnl_color).to_string()
+                format!("{:.2}%", current_pnl).color(pnl_color)
             ));
         }
     }
@@ -660,8 +711,8 @@ impl TokenManager {
 
             // Check if we should sell this token
             match engine.evaluate_sell_conditions(&token_mint).await {
-                Ok((should_sell, use_whale_emergency)) => {
-                    if should_sell {
+                Ok((sell_reason, use_whale_emergency)) => {
+                    if let Some(reason) = sell_reason {
                         if use_whale_emergency {
                             // Spawn whale emergency sell task
                             tokio::spawn(async move {
@@ -676,7 +727,7 @@ impl TokenManager {
                                         };
                                         
                                         // Use unified emergency sell for whale emergency selling
-                                        if let Err(e) = engine_clone.unified_emergency_sell(&token_mint_clone, true, Some(&trade_info), Some(protocol)).await {
+                                        if let Err(e) = engine_clone.unified_emergency_sell(&token_mint_clone, reason, true, Some(&trade_info), Some(protocol)).await {
                                             let logger = Logger::new("[TOKEN-MANAGER-WHALE] => ".red().to_string());
                                             logger.log(format!("Failed to whale emergency sell token {}: {}", token_mint_clone, e));
                                         }
@@ -700,7 +751,7 @@ impl TokenManager {
                                             SwapProtocol::RaydiumLaunchpad
                                         };
                                         
-                                        if let Err(e) = engine_clone.unified_emergency_sell(&token_mint_clone, false, Some(&trade_info), Some(protocol)).await {
+                                        if let Err(e) = engine_clone.unified_emergency_sell(&token_mint_clone, reason, false, Some(&trade_info), Some(protocol)).await {
                                             let logger = Logger::new("[TOKEN-MANAGER-EMERGENCY] => ".red().to_string());
                                             logger.log(format!("Failed to emergency sell token {}: {}", token_mint_clone, e));
                                         }
@@ -822,8 +873,8 @@ impl SellingEngine {
                 let token_mint = parsed_account.mint.to_string();
                 let token_amount = parsed_account.amount as f64 / 10f64.powi(9); // Assume 9 decimals for simplicity
                 
-                // Skip WSOL and very small amounts
-                if parsed_account.mint == spl_token::native_mint::id() || token_amount <= 0.000001 {
+                // Skip quote balances (WSOL and the stablecoins buys are funded from) and very small amounts
+                if KNOWN_QUOTE_MINTS.contains(&parsed_account.mint) || token_amount <= 0.000001 {
                     continue;
                 }
                 
@@ -837,8 +888,8 @@ impl SellingEngine {
                 // Create basic token metrics for existing balance (use current price as entry price approximation)
                 let current_price = match self.get_current_price(&token_mint).await {
                     Ok(price) => price,
-                    Err(_) => {
-                        self.logger.log(format!("⚠️  Could not get price for {}, skipping", token_mint).yellow().to_string());
+                    Err(e) => {
+                        self.logger.log(format!("⚠️  Could not get price for {}, skipping: {}", token_mint, e).yellow().to_string());
                         continue;
                     }
                 };
@@ -926,7 +977,7 @@ impl SellingEngine {
         // Get token account to determine actual balance
         let token_pubkey = Pubkey::from_str(token_mint)
             .map_err(|e| anyhow!("Invalid token mint address: {}", e))?;
-        let ata = get_associated_token_address(&wallet_pubkey, &token_pubkey);
+        let ata = get_wallet_token_address(self.app_state.rpc_nonblocking_client.clone(), &wallet_pubkey, &token_pubkey).await?;
 
         // Get current token balance
         let actual_token_balance = match self.app_state.rpc_nonblocking_client.get_token_account(&ata).await {
@@ -941,22 +992,9 @@ impl SellingEngine {
         
         // Calculate price using the same logic as transaction_parser.rs
         let price = match trade_info.dex_type {
-            DexType::RaydiumLaunchpad => {
-                // Raydium Launchpad: virtual_sol_reserves / virtual_token_reserves
-                if trade_info.virtual_token_reserves > 0 {
-                    (trade_info.virtual_sol_reserves as f64 * 1_000_000_000.0) / 
-                    (trade_info.virtual_token_reserves as f64) / 1_000_000_000.0
-                } else {
-                    0.0
-                }
-            },
-            DexType::RaydiumLaunchpad => {
-                // Raydium Launchpad: use the price from trade_info (already calculated correctly)
-                trade_info.price as f64 / 1_000_000_000.0
-            },
-            DexType::RaydiumLaunchpad => {
-                // Use the price calculated by the parser (already scaled correctly)
-                trade_info.price as f64 / 1_000_000_000.0
+            DexType::RaydiumLaunchpad | DexType::PumpFun | DexType::RaydiumCpmm | DexType::PumpSwap => {
+                // Reserve ratio decoded by the parser in f64 (lamports per whole token)
+                trade_info.price / 1_000_000_000.0
             },
             _ => {
                 // Fallback to simple calculation if virtual reserves not available
@@ -976,20 +1014,9 @@ impl SellingEngine {
 
         // Get current liquidity based on protocol
         let current_liquidity = match self.app_state.protocol_preference {
-            SwapProtocol::RaydiumLaunchpad => {
-                let raydium = RaydiumLaunchpad::new(
-                    self.app_state.wallet.clone(),
-                    Some(self.app_state.rpc_client.clone()),
-                    Some(self.app_state.rpc_nonblocking_client.clone()),
-                );
-                match raydium.get_pool_liquidity(token_mint).await {
-                    Ok(liquidity) => liquidity,
-                    Err(_) => 0.0,
-                }
-            },
-            SwapProtocol::RaydiumLaunchpad => {
-                // For Raydium Launchpad, use virtual SOL reserves as proxy for liquidity
-                trade_info.virtual_sol_reserves as f64 / 1e9 // Convert lamports to SOL
+            SwapProtocol::RaydiumLaunchpad | SwapProtocol::PumpFun | SwapProtocol::RaydiumCpmm | SwapProtocol::PumpSwap => {
+                // Virtual quote reserves on the curves, the SOL reserve on the AMMs, in SOL
+                trade_info.virtual_quote_reserve_sol()
             },
             _ => 0.0,
         };
@@ -1108,11 +1135,8 @@ impl SellingEngine {
         let liquidity = trade_info.liquidity;
 
         // Determine protocol from trade info
-        let protocol = match trade_info.dex_type {
-            DexType::RaydiumLaunchpad => SwapProtocol::RaydiumLaunchpad,
-            DexType::RaydiumLaunchpad => SwapProtocol::RaydiumLaunchpad,
-            _ => self.app_state.protocol_preference.clone(),
-        };
+        let protocol = SwapProtocol::from_dex_type(&trade_info.dex_type)
+            .unwrap_or_else(|| self.app_state.protocol_preference.clone());
 
         // Create or update token metrics
         let metrics = TokenMetrics {
@@ -1154,27 +1178,27 @@ impl SellingEngine {
     /// This method combines all selling conditions from the enhanced decision framework
     /// into a single evaluation, providing a comprehensive analysis of when to exit a position.
     /// 
-    /// Returns: (should_sell, use_whale_emergency) where:
-    /// - should_sell: true if any sell condition is met
+    /// Returns: (sell_reason, use_whale_emergency) where:
+    /// - sell_reason: the condition that was met, `None` when the position should be held
     /// - use_whale_emergency: true if should use emergency zeroslot selling for whale transactions
     /// 
     /// Note: Bot always sells all tokens when sell conditions are met
-    pub async fn evaluate_sell_conditions(&self, token_mint: &str) -> Result<(bool, bool)> {
+    pub async fn evaluate_sell_conditions(&self, token_mint: &str) -> Result<(Option<&'static str>, bool)> {
         // Get metrics for the token using DashMap's get() method
         let metrics = match TOKEN_METRICS.get(token_mint) {
             Some(metrics) => metrics.clone(),
-            None => return Ok((false, false)), // No metrics, so nothing to sell
+            None => return Ok((None, false)), // No metrics, so nothing to sell
         };
         
         // Calculate time held
         let time_held = metrics.last_update.elapsed().as_secs();
         
-        // Calculate percentage gain from entry (PNL)
-        let pnl = if metrics.entry_price > 0.0 {
-            (metrics.current_price - metrics.entry_price) / metrics.entry_price * 100.0
-        } else {
-            0.0
-        };
+        // Calculate percentage gain from entry (PNL), net of share fees
+        let pnl = net_pnl_percentage(
+            metrics.entry_price,
+            metrics.current_price,
+            self.app_state.share_fee_fraction(&metrics.protocol),
+        );
         
         // Calculate percentage change from highest price
         let retracement = if metrics.highest_price > 0.0 {
@@ -1203,21 +1227,21 @@ impl SellingEngine {
                 pnl, whale_threshold.pnl_threshold, whale_threshold.whale_limit_sol
             ).cyan().bold().to_string());
             
-            return Ok((true, whale_threshold.use_emergency_zeroslot));
+            return Ok((Some("WHALE_PNL_THRESHOLD"), whale_threshold.use_emergency_zeroslot));
         }
         
         // Max Hold Time: Sell after 1 hour regardless of performance
         if time_held > self.config.max_hold_time {
             self.logger.log(format!("⏰ Selling due to max hold time exceeded: {}s > {}s (1 hour)", 
                              time_held, self.config.max_hold_time).yellow().to_string());
-            return Ok((true, false)); // Not whale emergency
+            return Ok((Some("MAX_HOLD_TIME"), false)); // Not whale emergency
         }
         
         // Check if we've hit stop loss
         if pnl <= self.config.stop_loss {
             self.logger.log(format!("🛑 Selling due to stop loss triggered: {:.2}% <= {:.2}%", 
                              pnl, self.config.stop_loss).red().to_string());
-            return Ok((true, false));
+            return Ok((Some("STOP_LOSS"), false));
         }
         
         // Retracement logic: Apply when price drops from highest point (only if still profitable)
@@ -1228,14 +1252,14 @@ impl SellingEngine {
                 retracement, self.config.dynamic_whale_selling.retracement_percentage, 
                 pnl
             ).yellow().to_string());
-            return Ok((true, false));
+            return Ok((Some("RETRACEMENT"), false));
         }
         
         // Standard take profit (fallback for lower PNL levels)
         if pnl >= self.config.take_profit {
             self.logger.log(format!("🎯 Selling due to take profit reached: {:.2}% >= {:.2}%", 
                              pnl, self.config.take_profit).green().to_string());
-            return Ok((true, false));
+            return Ok((Some("TAKE_PROFIT"), false));
         }
 
         // Enhanced liquidity monitoring
@@ -1246,69 +1270,35 @@ impl SellingEngine {
             if metrics.liquidity_at_current < self.config.liquidity_monitor.min_absolute_liquidity {
                 self.logger.log(format!("💧 Selling due to low absolute liquidity: {:.2} SOL < {:.2} SOL", 
                                  metrics.liquidity_at_current, self.config.liquidity_monitor.min_absolute_liquidity).red().to_string());
-                return Ok((true, false));
+                return Ok((Some("LOW_LIQUIDITY"), false));
             }
             
             // Check liquidity drop percentage
             if liquidity_drop >= self.config.liquidity_monitor.max_acceptable_drop * 100.0 {
                 self.logger.log(format!("💧 Selling due to liquidity drop: {:.2}% >= {:.2}%", 
                                  liquidity_drop, self.config.liquidity_monitor.max_acceptable_drop * 100.0).red().to_string());
-                return Ok((true, false));
+                return Ok((Some("LIQUIDITY_DROP"), false));
             }
         }
         
         // If we've reached here, no sell conditions met
-        Ok((false, false))
+        Ok((None, false))
     }
     
 
     
     /// Get the current price of a token
     async fn get_current_price(&self, token_mint: &str) -> Result<f64> {
-        // Get the token metrics to determine which protocol to use
-        let protocol = if let Some(metrics) = TOKEN_METRICS.get(token_mint) {
-            metrics.protocol.clone()
-        } else {
-            self.app_state.protocol_preference.clone()
-        };
-
-        match protocol {
-            SwapProtocol::RaydiumLaunchpad => {
-                // Use cached current price from TOKEN_METRICS instead of RPC call
-                if let Some(metrics) = TOKEN_METRICS.get(token_mint) {
-                    Ok(metrics.current_price)
-                } else {
-                    Err(anyhow!("No metrics available for Raydium Launchpad token"))
-                }
-            },
-            SwapProtocol::RaydiumLaunchpad => {
-                let raydium = RaydiumLaunchpad::new(
-                    self.app_state.wallet.clone(),
-                    Some(self.app_state.rpc_client.clone()),
-                    Some(self.app_state.rpc_nonblocking_client.clone()),
-                );
-                
-                raydium.get_token_price(token_mint).await
-            },
-            SwapProtocol::RaydiumLaunchpad => {
-                // For RaydiumLaunchpad, fall back to stored metrics price
-                if let Some(metrics) = TOKEN_METRICS.get(token_mint) {
-                    Ok(metrics.current_price)
-                } else {
-                    Err(anyhow!("No metrics available for Raydium token"))
-                }
-            },
-            SwapProtocol::Auto | SwapProtocol::Unknown => {
-                self.logger.log("Auto/Unknown protocol in get_current_price, using cached metrics".yellow().to_string());
-                
-                // Fall back to stored metrics for Auto/Unknown protocols
-                if let Some(metrics) = TOKEN_METRICS.get(token_mint) {
-                    Ok(metrics.current_price)
-                } else {
-                    Err(anyhow!("Unable to get price for Auto/Unknown protocol"))
-                }
-            }
+        // Use cached current price from TOKEN_METRICS
+        if let Some(metrics) = TOKEN_METRICS.get(token_mint) {
+            return Ok(metrics.current_price);
         }
+
+        // Untracked token: read the price from chain on the preferred venue
+        let protocol = &self.app_state.protocol_preference;
+        let dex = self.app_state.dex_registry.get(protocol)
+            .ok_or_else(|| anyhow!("No venue registered for protocol {:?}", protocol))?;
+        dex.token_price(token_mint).await
     }
     
     /// Check if this might be wash trading (self-trading, circular trades)
@@ -1410,17 +1400,17 @@ impl SellingEngine {
             .unwrap_or(0.0);
         
         // Get current price
-        let exit_price = match self.get_current_price(mint).await {
-            Ok(price) => price,
-            Err(_) => 0.0,
-        };
+        let exit_price = self.get_current_price(mint).await.unwrap_or(0.0);
         
-        // Calculate PNL
-        let pnl = if entry_price > 0.0 {
-            ((exit_price - entry_price) / entry_price) * 100.0
-        } else {
-            0.0
+        // Launchpad swaps pay the configured share fee on the quote side of both legs
+        let share_fee_fraction = match protocol {
+            "RaydiumLaunchpad" => self.app_state.share_fee_fraction(&SwapProtocol::RaydiumLaunchpad),
+            _ => 0.0,
         };
+        let share_fee = amount_sold * (entry_price + exit_price) * share_fee_fraction;
+        
+        // Calculate PNL, net of share fees
+        let pnl = net_pnl_percentage(entry_price, exit_price, share_fee_fraction);
         
         // Create record
         let record = TradeExecutionRecord {
@@ -1432,12 +1422,13 @@ impl SellingEngine {
             timestamp,
             amount_sold,
             protocol: protocol.to_string(),
+            share_fee,
         };
         
         // Log record
         self.logger.log(format!(
-            "Trade execution recorded: {} sold at {:.8} SOL (PNL: {:.2}%)",
-            mint, exit_price, pnl
+            "Trade execution recorded: {} sold at {:.8} SOL (PNL: {:.2}%, share fee: {:.6} SOL)",
+            mint, exit_price, pnl, share_fee
         ).green().to_string());
         
         // Add to history using entry API
@@ -1475,120 +1466,16 @@ impl SellingEngine {
         // Convert to raw amount (assuming 9 decimals)
         let _raw_token_amount = (token_amount * 1_000_000_000.0) as u64;
         
-        // Create a DexType based on protocol
-        let dex_type = match protocol_to_use {
-            SwapProtocol::RaydiumLaunchpad => DexType::RaydiumLaunchpad,
-            SwapProtocol::RaydiumLaunchpad => DexType::RaydiumLaunchpad,
-            SwapProtocol::RaydiumLaunchpad => DexType::RaydiumLaunchpad,
-            SwapProtocol::Auto => {
-                // For Auto protocol, default to Raydium Launchpad as it's most common
-                self.logger.log("Auto protocol detected, defaulting to Raydium Launchpad".yellow().to_string());
-                DexType::RaydiumLaunchpad
-            },
-            SwapProtocol::Unknown => {
-                self.logger.log("Unknown protocol detected, defaulting to Raydium Launchpad".yellow().to_string());
-                DexType::RaydiumLaunchpad
-            },
-        };
-
-        // Get pool and necessary reserves information from the blockchain
-        let (
-            pool,
-            _pool_info,
-            pool_base_token_reserves,
-            pool_quote_token_reserves,
-            _sol_amount,
-            coin_creator
-        ) = match protocol_to_use {
-            SwapProtocol::RaydiumLaunchpad => {
-                let raydium = RaydiumLaunchpad::new(
-                    self.app_state.wallet.clone(),
-                    Some(self.app_state.rpc_client.clone()),
-                    Some(self.app_state.rpc_nonblocking_client.clone()),
-                );
-                match raydium.get_pool_info(token_mint).await {
-                    Ok((pool_id, base_mint, quote_mint, base_reserve, quote_reserve)) => {
-                        // Convert price to estimated SOL amount
-                        let est_sol_amount = (metrics.current_price * token_amount * 1_000_000_000.0) as u64;
-                        
-                        // Use default coin creator since we're selling (not needed for selling operations)
-                        let default_coin_creator = Pubkey::default();
-
-                        (
-                            Some(pool_id.to_string()),
-                            Some(PoolInfo {
-                                pool_id,
-                                base_mint,
-                                quote_mint,
-                                base_reserve,
-                                quote_reserve,
-                                coin_creator: default_coin_creator,
-                            }),
-                            Some(base_reserve),
-                            Some(quote_reserve),
-                            Some(est_sol_amount),
-                            Some(default_coin_creator.to_string())
-                        )
-                    },
-                    Err(e) => {
-                        self.logger.log(format!("Failed to get pool info: {}", e).red().to_string());
-                        (None, None, None, None, None, None)
-                    }
-                }
-            },
-            SwapProtocol::RaydiumLaunchpad => {
-                // For Raydium Launchpad, use cached metrics instead of RPC calls
-                // Calculate estimated SOL amount and use reasonable defaults for reserves
-                let est_sol_amount = (metrics.current_price * token_amount * 1_000_000_000.0) as u64;
-                
-                // Since we don't have actual reserves data, use reasonable defaults
-                let virtual_token_reserves = 1_000_000_000_000; // 1 trillion token units
-                let virtual_sol_reserves = (virtual_token_reserves as f64 * metrics.current_price) as u64;
-                
-                (
-                    None, // Raydium Launchpad doesn't use pool field
-                    None, // No pool_info for Raydium Launchpad
-                    Some(virtual_token_reserves),
-                    Some(virtual_sol_reserves),
-                    Some(est_sol_amount),
-                    None  // We don't have creator info
-                )
-            },
-            SwapProtocol::RaydiumLaunchpad => {
-                // For RaydiumLaunchpad, we don't have a direct method to get pool info
-                // Use reasonable defaults based on current metrics
-                let est_sol_amount = (metrics.current_price * token_amount * 1_000_000_000.0) as u64;
-                
-                // Use defaults for Raydium Launchpad
-                let virtual_token_reserves = 1_000_000_000_000; // 1 trillion token units
-                let virtual_sol_reserves = (virtual_token_reserves as f64 * metrics.current_price) as u64;
-                
-                (
-                    None, // No pool_id for Raydium Launchpad
-                    None, // No pool_info for Raydium Launchpad
-                    Some(virtual_token_reserves),
-                    Some(virtual_sol_reserves),
-                    Some(est_sol_amount),
-                    None  // We don't have creator info
-                )
-            },
-            SwapProtocol::Auto | SwapProtocol::Unknown => {
-                // For Auto/Unknown protocols, use Raydium Launchpad defaults
-                self.logger.log("Using Raydium Launchpad defaults for Auto/Unknown protocol".yellow().to_string());
-                
-                let est_sol_amount = (metrics.current_price * token_amount * 1_000_000_000.0) as u64;
-                let virtual_token_reserves = 1_000_000_000_000; // 1 trillion token units
-                let virtual_sol_reserves = (virtual_token_reserves as f64 * metrics.current_price) as u64;
-                
-                (
-                    None, // Raydium Launchpad doesn't use pool field
-                    None, // No pool_info for Raydium Launchpad
-                    Some(virtual_token_reserves),
-                    Some(virtual_sol_reserves),
-                    Some(est_sol_amount),
-                    None  // We don't have creator info
-                )
-            },
+        // Resolve the pool through the venue for the stored protocol
+        let dex = self.app_state.dex_registry.get(&protocol_to_use)
+            .ok_or_else(|| anyhow!("No venue registered for protocol {:?}", protocol_to_use))?;
+        let dex_type = DexType::from(dex.protocol());
+        let pool = match dex.pool_for_mint(token_mint).await {
+            Ok(pool) => Some(pool),
+            Err(e) => {
+                self.logger.log(format!("Failed to get pool info: {}", e).red().to_string());
+                None
+            }
         };
         
         // Get user wallet to set as target
@@ -1599,18 +1486,18 @@ impl SellingEngine {
             dex_type,
             slot: 0, // Not critical for selling
             signature: "metrics_to_trade_info".to_string(),
-            pool_id: pool.unwrap_or_default(),
+            pool_id: pool.map(|pool| pool.pool_id.to_string()).unwrap_or_default(),
+            base_vault: pool.map(|pool| pool.base_vault.to_string()).unwrap_or_default(),
+            quote_vault: pool.map(|pool| pool.quote_vault.to_string()).unwrap_or_default(),
             mint: token_mint.to_string(),
+            quote_mint: pool.map(|pool| pool.quote_mint.to_string()).unwrap_or_default(),
             timestamp,
             is_buy: false, // We're analyzing for sell
-            price: (metrics.current_price * 1_000_000_000.0) as u64, // Convert to lamports
-            is_reverse: false,
-            coin_creator,
+            price: metrics.current_price * 1_000_000_000.0, // Convert to lamports
             sol_change: 0.0,
             token_change: token_amount,
-            liquidity: pool_quote_token_reserves.unwrap_or(0) as f64 / 1_000_000_000.0,
-            virtual_sol_reserves: pool_quote_token_reserves.unwrap_or(0),
-            virtual_token_reserves: pool_base_token_reserves.unwrap_or(0),
+            // Reserves are left empty so swap building reads the live pool state
+            ..Default::default()
         })
     }
 
@@ -1734,6 +1621,7 @@ impl SellingEngine {
     /// 
     /// # Parameters
     /// - `token_mint`: The token to sell
+    /// - `reason`: Why the position is sold, recorded with the trade
     /// - `is_whale_emergency`: Whether to use whale emergency selling (higher slippage, faster execution)
     /// - `parsed_data`: Optional transaction data
     /// - `protocol`: Optional protocol preference
@@ -1741,7 +1629,7 @@ impl SellingEngine {
     /// # Returns
     /// - `Ok(signature)`: Transaction signature on success
     /// - `Err(error)`: Error message on failure
-    pub async fn unified_emergency_sell(&self, token_mint: &str, is_whale_emergency: bool, parsed_data: Option<&TradeInfoFromToken>, protocol: Option<SwapProtocol>) -> Result<String> {
+    pub async fn unified_emergency_sell(&self, token_mint: &str, reason: &str, is_whale_emergency: bool, parsed_data: Option<&TradeInfoFromToken>, protocol: Option<SwapProtocol>) -> Result<String> {
         // Add timeout to prevent hanging
         use tokio::time::{timeout, Duration};
         
@@ -1751,7 +1639,7 @@ impl SellingEngine {
             Duration::from_secs(60) // Standard timeout for regular sells
         };
         
-        let result = timeout(timeout_duration, self.execute_emergency_sell_internal(token_mint, is_whale_emergency, parsed_data, protocol)).await;
+        let result = timeout(timeout_duration, self.execute_emergency_sell_internal(token_mint, reason, is_whale_emergency, parsed_data, protocol)).await;
         
         match result {
             Ok(inner_result) => inner_result,
@@ -1763,7 +1651,7 @@ impl SellingEngine {
     }
     
     /// Internal implementation of emergency sell without timeout wrapper
-    async fn execute_emergency_sell_internal(&self, token_mint: &str, is_whale_emergency: bool, parsed_data: Option<&TradeInfoFromToken>, protocol: Option<SwapProtocol>) -> Result<String> {
+    async fn execute_emergency_sell_internal(&self, token_mint: &str, reason: &str, is_whale_emergency: bool, parsed_data: Option<&TradeInfoFromToken>, protocol: Option<SwapProtocol>) -> Result<String> {
         // Log the type of emergency sell
         if is_whale_emergency {
             self.logger.log(format!("🐋 WHALE EMERGENCY SELL triggered for token: {}", token_mint).red().bold().to_string());
@@ -1778,7 +1666,7 @@ impl SellingEngine {
         // Get token account to determine how much we own
         let token_pubkey = Pubkey::from_str(token_mint)
             .map_err(|e| anyhow!("Invalid token mint address: {}", e))?;
-        let ata = get_associated_token_address(&wallet_pubkey, &token_pubkey);
+        let ata = get_wallet_token_address(self.app_state.rpc_nonblocking_client.clone(), &wallet_pubkey, &token_pubkey).await?;
 
         // Get current token balance
         let token_amount = match self.app_state.rpc_nonblocking_client.get_token_account(&ata).await {
@@ -1802,6 +1690,40 @@ impl SellingEngine {
             return Ok("no_tokens_to_sell".to_string());
         }
 
+        // The launchpad rejects swaps on a completed curve, sell on the migrated venue instead
+        if let Some(migration) = MIGRATED_TOKENS.get(token_mint).map(|entry| entry.clone()) {
+            self.logger.log(format!(
+                "Token {} migrated to {:?} (pool: {}), skipping launchpad sell",
+                token_mint,
+                migration.venue,
+                migration.new_pool_id.as_deref().unwrap_or("pending")
+            ).yellow().to_string());
+
+            // Sell directly on the new pool when the venue has an adapter, through Jupiter otherwise
+            let venue_result = match SwapProtocol::from_migration_venue(&migration.venue) {
+                Some(venue_protocol) => self.sell_on_migrated_venue(&migration, venue_protocol, is_whale_emergency).await,
+                None => Err(anyhow!("No venue adapter for {:?}", migration.venue)),
+            };
+            let (result, method) = match venue_result {
+                Ok(signature) => (Ok(signature), format!("{:?}", migration.venue)),
+                Err(e) => {
+                    self.logger.log(format!("Direct sell on migrated venue failed: {}, falling back to Jupiter", e).yellow().to_string());
+                    (self.try_jupiter_fallback_sell(token_mint, token_amount).await, "Jupiter".to_string())
+                }
+            };
+            if result.is_ok() {
+                if let Err(e) = self.record_trade_execution(
+                    token_mint,
+                    reason,
+                    token_amount,
+                    &method
+                ).await {
+                    self.logger.log(format!("Failed to record emergency trade execution: {}", e).red().to_string());
+                }
+            }
+            return result;
+        }
+
         // Determine protocol - use provided protocol or get from metrics
         let sell_protocol = protocol.unwrap_or_else(|| {
             if let Some(metrics) = crate::engine::selling_strategy::TOKEN_METRICS.get(token_mint) {
@@ -1823,12 +1745,7 @@ impl SellingEngine {
         let emergency_trade_info = if let Some(data) = parsed_data {
             // Use provided parsed data
             TradeInfoFromToken {
-                dex_type: match sell_protocol {
-                    SwapProtocol::RaydiumLaunchpad => crate::engine::transaction_parser::DexType::RaydiumLaunchpad,
-                    SwapProtocol::RaydiumLaunchpad => crate::engine::transaction_parser::DexType::RaydiumLaunchpad,
-                    SwapProtocol::RaydiumLaunchpad => crate::engine::transaction_parser::DexType::RaydiumLaunchpad,
-                    _ => crate::engine::transaction_parser::DexType::Unknown,
-                },
+                dex_type: DexType::from(sell_protocol.clone()),
                 slot: data.slot,
                 signature: if is_whale_emergency { "whale_emergency_sell" } else { "regular_emergency_sell" }.to_string(),
                 pool_id: data.pool_id.clone(),
@@ -1843,6 +1760,7 @@ impl SellingEngine {
                 liquidity: data.liquidity,
                 virtual_sol_reserves: data.virtual_sol_reserves,
                 virtual_token_reserves: data.virtual_token_reserves,
+                ..data.clone()
             }
         } else {
             // Create trade info from metrics (for execute_emergency_sell_via_engine replacement)
@@ -1863,21 +1781,18 @@ impl SellingEngine {
         // Protocol string for logging
         let protocol_str = match sell_protocol {
             SwapProtocol::RaydiumLaunchpad => "RaydiumLaunchpad",
+            SwapProtocol::PumpFun => "PumpFun",
+            SwapProtocol::RaydiumCpmm => "RaydiumCpmm",
+            SwapProtocol::PumpSwap => "PumpSwap",
             _ => "Unknown",
         };
 
-        // Execute emergency sell based on protocol
-        let result = match sell_protocol {
-            SwapProtocol::RaydiumLaunchpad => {
-                self.logger.log("Using Raydium Launchpad protocol for emergency sell".red().to_string());
-                
-                let pump = crate::dex::raydium_launchpad::RaydiumLaunchpad::new(
-                    self.app_state.rpc_nonblocking_client.clone(),
-                    self.app_state.rpc_client.clone(),
-                    self.app_state.wallet.clone(),
-                );
+        // Execute emergency sell on the venue for the protocol
+        let result = match self.app_state.dex_registry.get(&sell_protocol) {
+            Some(dex) => {
+                self.logger.log(format!("Using {:?} venue for emergency sell", dex.protocol()).red().to_string());
                 
-                match pump.build_swap_from_parsed_data(&emergency_trade_info, emergency_config).await {
+                match dex.build_sell(&emergency_trade_info, emergency_config).await {
                     Ok((keypair, instructions, price)) => {
                         // Get recent blockhash from the processor
                         let recent_blockhash = match crate::services::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
@@ -1887,14 +1802,16 @@ impl SellingEngine {
                                 return Err(anyhow!("Failed to get recent blockhash"));
                             }
                         };
-                        self.logger.log(format!("Generated emergency Raydium Launchpad sell instruction at price: {}", price));
-                        // Execute with zeroslot for copy selling
-                        match crate::core::tx::new_signed_and_send_zeroslot(
-                            self.app_state.zeroslot_rpc_client.clone(),
+                        self.logger.log(format!("Generated emergency {} sell instruction at price: {}", protocol_str, price));
+                        // Execute with zeroslot for copy selling, or a Jito bundle for whale dumps
+                        match crate::core::tx::new_signed_and_send_with_landing_mode(
+                            self.emergency_landing_mode(is_whale_emergency),
+                            &self.app_state,
                             recent_blockhash,
                             &keypair,
                             instructions,
                             &self.logger,
+                            crate::services::priority_fees::FeeIntent::EmergencySell,
                         ).await {
                             Ok(signatures) => {
                                 if signatures.is_empty() {
@@ -1902,7 +1819,7 @@ impl SellingEngine {
                                 }
                                 
                                 let signature = &signatures[0];
-                                self.logger.log(format!("Emergency Raydium Launchpad sell transaction sent: {}", signature).green().to_string());
+                                self.logger.log(format!("Emergency {} sell transaction sent: {}", protocol_str, signature).green().to_string());
                                 
                                 Ok(signature.to_string())
                             },
@@ -1913,159 +1830,12 @@ impl SellingEngine {
                         }
                     },
                     Err(e) => {
-                        self.logger.log(format!("Failed to build emergency Raydium Launchpad sell instruction: {}", e).red().to_string());
-                        Err(anyhow!("Failed to build emergency sell instruction: {}", e))
-                    }
-                }
-            },
-            SwapProtocol::RaydiumLaunchpad => {
-                self.logger.log("Using Raydium Launchpad protocol for emergency sell".red().to_string());
-                
-                let raydium = crate::dex::raydium_launchpad::RaydiumLaunchpad::new(
-                    self.app_state.wallet.clone(),
-                    Some(self.app_state.rpc_client.clone()),
-                    Some(self.app_state.rpc_nonblocking_client.clone()),
-                );
-                
-                match raydium.build_swap_from_parsed_data(&emergency_trade_info, emergency_config).await {
-                    Ok((keypair, instructions, price)) => {
-                        // Get recent blockhash from the processor
-                        let recent_blockhash = match crate::services::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
-                            Some(hash) => hash,
-                            None => {
-                                self.logger.log("Failed to get recent blockhash".red().to_string());
-                                return Err(anyhow!("Failed to get recent blockhash"));
-                            }
-                        };
-                        self.logger.log(format!("Generated emergency Raydium Launchpad sell instruction at price: {}", price));
-                        // Execute with zeroslot for copy selling
-                        match crate::core::tx::new_signed_and_send_zeroslot(
-                            self.app_state.zeroslot_rpc_client.clone(),
-                            recent_blockhash,
-                            &keypair,
-                            instructions,
-                            &self.logger,
-                        ).await {
-                            Ok(signatures) => {
-                                if signatures.is_empty() {
-                                    return Err(anyhow!("No transaction signature returned"));
-                                }
-                                
-                                let signature = &signatures[0];
-                                self.logger.log(format!("Emergency Raydium Launchpad sell transaction sent: {}", signature).green().to_string());
-                                
-                                Ok(signature.to_string())
-                            },
-                            Err(e) => {
-                                self.logger.log(format!("Emergency sell transaction failed: {}", e).red().to_string());
-                                Err(anyhow!("Failed to send emergency sell transaction: {}", e))
-                            }
-                        }
-                    },
-                    Err(e) => {
-                        self.logger.log(format!("Failed to build emergency Raydium Launchpad sell instruction: {}", e).red().to_string());
-                        Err(anyhow!("Failed to build emergency sell instruction: {}", e))
-                    }
-                }
-            },
-            SwapProtocol::RaydiumLaunchpad => {
-                self.logger.log("Using Raydium protocol for emergency sell".red().to_string());
-                
-                let raydium = crate::dex::raydium_launchpad::Raydium::new(
-                    self.app_state.wallet.clone(),
-                    Some(self.app_state.rpc_client.clone()),
-                    Some(self.app_state.rpc_nonblocking_client.clone()),
-                );
-                
-                match raydium.build_swap_from_parsed_data(&emergency_trade_info, emergency_config).await {
-                    Ok((keypair, instructions, price)) => {
-                        // Get recent blockhash from the processor
-                        let recent_blockhash = match crate::services::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
-                            Some(hash) => hash,
-                            None => {
-                                self.logger.log("Failed to get recent blockhash".red().to_string());
-                                return Err(anyhow!("Failed to get recent blockhash"));
-                            }
-                        };
-                        self.logger.log(format!("Generated emergency Raydium sell instruction at price: {}", price));
-                        // Execute with zeroslot for copy selling
-                        match crate::core::tx::new_signed_and_send_zeroslot(
-                            self.app_state.zeroslot_rpc_client.clone(),
-                            recent_blockhash,
-                            &keypair,
-                            instructions,
-                            &self.logger,
-                        ).await {
-                            Ok(signatures) => {
-                                if signatures.is_empty() {
-                                    return Err(anyhow!("No transaction signature returned"));
-                                }
-                                
-                                let signature = &signatures[0];
-                                self.logger.log(format!("Emergency Raydium sell transaction sent: {}", signature).green().to_string());
-                                
-                                Ok(signature.to_string())
-                            },
-                            Err(e) => {
-                                self.logger.log(format!("Emergency sell transaction failed: {}", e).red().to_string());
-                                Err(anyhow!("Failed to send emergency sell transaction: {}", e))
-                            }
-                        }
-                    },
-                    Err(e) => {
-                        self.logger.log(format!("Failed to build emergency Raydium sell instruction: {}", e).red().to_string());
-                        Err(anyhow!("Failed to build emergency sell instruction: {}", e))
-                    }
-                }
-            },
-            SwapProtocol::Auto | SwapProtocol::Unknown => {
-                self.logger.log("Auto/Unknown protocol detected, defaulting to Raydium Launchpad for emergency sell".yellow().to_string());
-                
-                let pump = crate::dex::raydium_launchpad::RaydiumLaunchpad::new(
-                    self.app_state.rpc_nonblocking_client.clone(),
-                    self.app_state.rpc_client.clone(),
-                    self.app_state.wallet.clone(),
-                );
-                
-                match pump.build_swap_from_parsed_data(&emergency_trade_info, emergency_config).await {
-                    Ok((keypair, instructions, price)) => {
-                        let recent_blockhash = match crate::services::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
-                            Some(hash) => hash,
-                            None => {
-                                self.logger.log("Failed to get recent blockhash".red().to_string());
-                                return Err(anyhow!("Failed to get recent blockhash"));
-                            }
-                        };
-                        self.logger.log(format!("Generated emergency Raydium Launchpad sell instruction at price: {}", price));
-                        match crate::core::tx::new_signed_and_send_zeroslot(
-                            self.app_state.zeroslot_rpc_client.clone(),
-                            recent_blockhash,
-                            &keypair,
-                            instructions,
-                            &self.logger,
-                        ).await {
-                            Ok(signatures) => {
-                                if signatures.is_empty() {
-                                    return Err(anyhow!("No transaction signature returned"));
-                                }
-                                
-                                let signature = &signatures[0];
-                                self.logger.log(format!("Emergency Raydium Launchpad (Auto/Unknown) sell transaction sent: {}", signature).green().to_string());
-                                
-                                Ok(signature.to_string())
-                            },
-                            Err(e) => {
-                                self.logger.log(format!("Emergency sell transaction failed: {}", e).red().to_string());
-                                Err(anyhow!("Failed to send emergency sell transaction: {}", e))
-                            }
-                        }
-                    },
-                    Err(e) => {
-                        self.logger.log(format!("Failed to build emergency Raydium Launchpad sell instruction: {}", e).red().to_string());
+                        self.logger.log(format!("Failed to build emergency {} sell instruction: {}", protocol_str, e).red().to_string());
                         Err(anyhow!("Failed to build emergency sell instruction: {}", e))
                     }
                 }
             },
+            None => Err(anyhow!("No venue registered for protocol {:?}", sell_protocol)),
         };
 
         // If DEX selling failed, try Jupiter API as fallback
@@ -2099,7 +1869,7 @@ impl SellingEngine {
             // Record the emergency trade execution
             if let Err(e) = self.record_trade_execution(
                 token_mint,
-                "EMERGENCY_STOP_LOSS",
+                reason,
                 token_amount,
                 protocol_str
             ).await {
@@ -2110,6 +1880,76 @@ impl SellingEngine {
         final_result
     }
 
+    /// Record a launchpad curve migration and re-point the position to the new venue.
+    /// A concrete venue is never downgraded back to Pending by a late TradeEvent.
+    pub fn handle_migration(&self, migration: &MigrationEvent) {
+        if let Some(existing) = MIGRATED_TOKENS.get(&migration.mint) {
+            if existing.venue != MigrationVenue::Pending && migration.venue == MigrationVenue::Pending {
+                return;
+            }
+        }
+        MIGRATED_TOKENS.insert(migration.mint.clone(), migration.clone());
+
+        self.logger.log(format!(
+            "🔀 Token {} completed its curve: {} -> {:?} {} (final reserves: {} base / {} quote)",
+            migration.mint,
+            migration.launchpad_pool_id,
+            migration.venue,
+            migration.new_pool_id.as_deref().unwrap_or("pending"),
+            migration.final_base_reserve,
+            migration.final_quote_reserve
+        ).magenta().to_string());
+
+        if let Some(mut metrics) = TOKEN_METRICS.get_mut(&migration.mint) {
+            // Route later sells and price reads to the new venue when it has an adapter
+            if let Some(protocol) = SwapProtocol::from_migration_venue(&migration.venue) {
+                metrics.protocol = protocol;
+            }
+            self.logger.log(format!(
+                "Open position in {} will now be sold on the migrated venue", migration.mint
+            ).yellow().to_string());
+        }
+    }
+
+    /// Emergency sells go through zeroslot; whale dumps use an atomic Jito bundle when the
+    /// landing mode is Jito, so a sell that would revert costs no tip
+    fn emergency_landing_mode(&self, is_whale_emergency: bool) -> TransactionLandingMode {
+        match self.app_state.transaction_landing_mode {
+            TransactionLandingMode::Jito if is_whale_emergency => TransactionLandingMode::Jito,
+            _ => TransactionLandingMode::Zeroslot,
+        }
+    }
+
+    /// Sell the whole balance on the venue a token migrated to, in the pool named by the migration
+    async fn sell_on_migrated_venue(&self, migration: &MigrationEvent, protocol: SwapProtocol, is_whale_emergency: bool) -> Result<String> {
+        let dex = self.app_state.dex_registry.get(&protocol)
+            .ok_or_else(|| anyhow!("No venue registered for protocol {:?}", protocol))?;
+        let trade_info = migrated_trade_info(migration, protocol, "migrated_emergency_sell");
+
+        let mut sell_config = (*self.swap_config).clone();
+        sell_config.swap_direction = SwapDirection::Sell;
+        sell_config.in_type = SwapInType::Pct;
+        sell_config.amount_in = 1.0;
+        sell_config.slippage = if is_whale_emergency { 1500 } else { 1000 };
+
+        let (keypair, instructions, _price) = dex.build_sell(&trade_info, sell_config).await?;
+        let recent_blockhash = crate::services::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await
+            .ok_or_else(|| anyhow!("Failed to get recent blockhash"))?;
+        let signatures = crate::core::tx::new_signed_and_send_with_landing_mode(
+            self.emergency_landing_mode(is_whale_emergency),
+            &self.app_state,
+            recent_blockhash,
+            &keypair,
+            instructions,
+            &self.logger,
+            crate::services::priority_fees::FeeIntent::EmergencySell,
+        ).await?;
+        let signature = signatures.into_iter().next()
+            .ok_or_else(|| anyhow!("No transaction signature returned"))?;
+        self.logger.log(format!("Migrated {:?} sell transaction sent: {}", dex.protocol(), signature).green().to_string());
+        Ok(signature)
+    }
+
     /// Try Jupiter API as fallback when DEX selling fails
     async fn try_jupiter_fallback_sell(&self, token_mint: &str, token_amount: f64) -> Result<String> {
         self.logger.log(format!("🌌 Attempting Jupiter API fallback sell for {} tokens of {}", token_amount, token_mint).cyan().to_string());
@@ -2124,7 +1964,7 @@ impl SellingEngine {
             .map_err(|e| anyhow!("Failed to get wallet pubkey: {}", e))?;
         let token_pubkey = Pubkey::from_str(token_mint)
             .map_err(|e| anyhow!("Invalid token mint address: {}", e))?;
-        let ata = get_associated_token_address(&wallet_pubkey, &token_pubkey);
+        let ata = get_wallet_token_address(self.app_state.rpc_nonblocking_client.clone(), &wallet_pubkey, &token_pubkey).await?;
 
         // Get current token balance in raw units for Jupiter
         let raw_token_amount = match self.app_state.rpc_nonblocking_client.get_token_account(&ata).await {
@@ -2192,12 +2032,12 @@ impl SellingEngine {
             ));
         }
         
-        // Calculate current PNL
-        let pnl = if metrics.entry_price > 0.0 {
-            ((metrics.current_price - metrics.entry_price) / metrics.entry_price) * 100.0
-        } else {
-            0.0
-        };
+        // Calculate current PNL, net of share fees
+        let pnl = net_pnl_percentage(
+            metrics.entry_price,
+            metrics.current_price,
+            self.app_state.share_fee_fraction(&metrics.protocol),
+        );
         
         // Check min profit time only for profitable positions
         if pnl > 0.0 && time_held_seconds < self.config.time_based.min_profit_time_secs {
@@ -2211,24 +2051,16 @@ impl SellingEngine {
     pub fn calculate_current_price(&self, trade_info: &TradeInfoFromToken) -> Option<f64> {
         // For RaydiumLaunchpad and other DEXes with pre-calculated prices, use the parser's calculation
         match trade_info.dex_type {
-            DexType::RaydiumLaunchpad => {
+            DexType::RaydiumLaunchpad | DexType::PumpFun | DexType::RaydiumCpmm | DexType::PumpSwap => {
                 // Use the price calculated by the parser (already scaled correctly)
-                if trade_info.price > 0 {
-                    Some(trade_info.price as f64 / 1_000_000_000.0)
+                if trade_info.price > 0.0 {
+                    Some(trade_info.price / 1_000_000_000.0)
                 } else {
                     None
                 }
             },
-            DexType::RaydiumLaunchpad => {
-                // Use the price calculated by the parser
-                if trade_info.price > 0 {
-                    Some(trade_info.price as f64 / 1_000_000_000.0)
-                } else {
-                    None
-                }
-            },
-            DexType::RaydiumLaunchpad | _ => {
-                // For Raydium Launchpad and others, fall back to virtual reserves calculation
+            _ => {
+                // For other venues, fall back to virtual reserves calculation
                 let virtual_sol = trade_info.virtual_sol_reserves;
                 let virtual_token = trade_info.virtual_token_reserves;
                 
@@ -2277,8 +2109,8 @@ impl SellingEngine {
         let dynamic_trail_percentage = self.config.trailing_stop.get_trailing_stop_for_pnl(current_pnl);
         
         // Apply dynamic trailing stop if we're in profit and above activation threshold
-        if current_pnl >= self.config.trailing_stop.activation_percentage {
-            if retracement >= dynamic_trail_percentage {
+        if current_pnl >= self.config.trailing_stop.activation_percentage
+            && retracement >= dynamic_trail_percentage {
                 self.logger.log(format!(
                     "🎯 Dynamic trailing stop triggered: PnL {:.2}% → Trail {:.2}% → Retracement {:.2}%",
                     current_pnl, dynamic_trail_percentage, retracement
@@ -2286,7 +2118,6 @@ impl SellingEngine {
                 return Some(format!("Dynamic trailing stop: {:.2}% retracement (trail: {:.2}%)", 
                            retracement, dynamic_trail_percentage));
             }
-        }
         
         // Traditional retracement check (fallback)
         if retracement > self.config.retracement_threshold {
@@ -2306,8 +2137,7 @@ impl SellingEngine {
     }
 
     pub fn calculate_liquidity(&self, trade_info: &TradeInfoFromToken) -> Option<f64> {
-        let sol_reserves = trade_info.virtual_sol_reserves;
-        Some(sol_reserves as f64 / 1e9) // Convert lamports to SOL
+        Some(trade_info.virtual_quote_reserve_sol())
     }
 
     pub async fn get_average_volume(&self, token_mint: &str) -> Option<f64> {
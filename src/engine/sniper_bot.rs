@@ -1,6 +1,5 @@
 use std::collections::{HashSet, VecDeque};
 use std::str::FromStr;
-use bs58;
 use std::sync::Arc;
 use std::time::Duration;
 use std::time::Instant;
@@ -12,27 +11,28 @@ use anyhow::Result;
 /// Extract the signer (fee payer) from a yellowstone grpc transaction
 /// Returns the first signer which is typically the transaction fee payer
 fn extract_signer_from_transaction(txn: &SubscribeUpdateTransaction) -> Option<String> {
-    if let Some(transaction_info) = &txn.transaction {
-        if let Some(transaction) = &transaction_info.transaction {
-            if let Some(message) = &transaction.message {
-                // Get account keys - the first one is typically the signer/fee payer
-                if !message.account_keys.is_empty() {
-                    // Convert the first account key (signer) to string
-                    return Some(bs58::encode(&message.account_keys[0]).into_string());
-                }
-            }
-        }
+    // The first account key is the signer/fee payer; it is always a static key, but resolve
+    // through the full list so v0 transactions are handled the same way as legacy ones
+    transaction_parser::transaction_account_keys(txn)
+        .first()
+        .map(|signer| signer.to_string())
+}
+
+/// The wallet behind a parsed trade: the swap instruction's user account when it was resolved,
+/// otherwise the fee payer (bundled transactions are often paid for by another wallet)
+fn extract_trader(parsed_data: &transaction_parser::TradeInfoFromToken, txn: &SubscribeUpdateTransaction) -> Option<String> {
+    if !parsed_data.user.is_empty() {
+        return Some(parsed_data.user.clone());
     }
-    None
+    extract_signer_from_transaction(txn)
 }
 use anchor_client::solana_sdk::{pubkey::Pubkey, signature::Signature};
 use solana_sdk::signature::Signer;
-use spl_associated_token_account::get_associated_token_address;
+use crate::core::token::get_wallet_token_address;
 use colored::Colorize;
 use tokio::time;
 use tokio::time::sleep;
 use tokio_util::sync::CancellationToken;
-use solana_sdk::signer::keypair::Keypair;
 use futures_util::stream::StreamExt;
 use futures_util::{SinkExt, Sink};
 use yellowstone_grpc_client::{ClientTlsConfig, GeyserGrpcClient};
@@ -46,15 +46,15 @@ use crate::common::{
     config::{Config, AppState, SwapConfig},
     logger::Logger,
     cache::WALLET_TOKEN_ACCOUNTS,
-    constants::WHALE_SELLING_AMOUNT_FOR_SELLING_TRIGGER,
 };
 use crate::engine::swap::{SwapDirection, SwapProtocol, SwapInType};
-use crate::engine::transaction_parser::{DexType, TradeInfoFromToken};
-use crate::engine::selling_strategy::{TokenTrackingInfo as SellingTokenTrackingInfo, TokenMetrics};
 use crate::engine::transaction_retry;
 use dashmap::DashMap;
-use crate::dex::raydium_launchpad::RAYDIUM_LAUNCHPAD_PROGRAM;
-use chrono::Timelike;
+use crate::dex::launchpad_curve;
+use crate::dex::pool_index::POOL_INDEX;
+use crate::dex::quote_mint;
+use crate::dex::raydium_cpmm;
+use crate::dex::venue::Dex;
 
 // Enum for different selling actions
 #[derive(Debug, Clone)]
@@ -67,17 +67,18 @@ pub enum SellingAction {
 #[derive(Clone, Debug)]
 pub struct BoughtTokenInfo {
     pub token_mint: String,
-    pub entry_price: u64,
-    pub current_price: u64,
-    pub highest_price: u64,
-    pub lowest_price_after_highest: u64,
+    pub entry_price: f64, // Lamports per whole token, like TradeInfoFromToken::price
+    pub current_price: f64,
+    pub highest_price: f64,
+    pub lowest_price_after_highest: f64,
     pub initial_amount: f64, // Amount of SOL initially spent
     pub current_amount: f64, // Current token amount held
     pub buy_timestamp: Instant,
     pub protocol: SwapProtocol,
     pub trade_info: transaction_parser::TradeInfoFromToken,
-    pub pnl_percentage: f64,
+    pub pnl_percentage: f64, // Net of share fees
     pub highest_pnl_percentage: f64,
+    pub share_fee_fraction: f64, // Share fee paid on each leg, as a fraction of the quote amount
     pub trailing_stop_percentage: f64,
     pub selling_time_seconds: u64, // SELLING_TIME in seconds
     pub last_price_update: Instant,
@@ -87,7 +88,7 @@ pub struct BoughtTokenInfo {
 impl BoughtTokenInfo {
     pub fn new(
         token_mint: String,
-        entry_price: u64,
+        entry_price: f64,
         initial_amount: f64,
         current_amount: f64,
         protocol: SwapProtocol,
@@ -107,6 +108,7 @@ impl BoughtTokenInfo {
             trade_info,
             pnl_percentage: 0.0,
             highest_pnl_percentage: 0.0,
+            share_fee_fraction: 0.0,
             trailing_stop_percentage: 1.0, // Start with 1% trailing stop
             selling_time_seconds,
             last_price_update: Instant::now(),
@@ -114,7 +116,7 @@ impl BoughtTokenInfo {
         }
     }
 
-    pub fn update_price(&mut self, new_price: u64) {
+    pub fn update_price(&mut self, new_price: f64) {
         self.current_price = new_price;
         self.last_price_update = Instant::now();
         
@@ -126,12 +128,12 @@ impl BoughtTokenInfo {
             self.lowest_price_after_highest = new_price;
         }
         
-        // Calculate PnL percentage - prevent division by zero
-        self.pnl_percentage = if self.entry_price > 0 {
-            ((new_price as f64 - self.entry_price as f64) / self.entry_price as f64) * 100.0
-        } else {
-            0.0 // No PnL calculation if entry_price is not set
-        };
+        // Calculate PnL percentage net of share fees; zero while entry_price is not set
+        self.pnl_percentage = crate::engine::selling_strategy::net_pnl_percentage(
+            self.entry_price,
+            new_price,
+            self.share_fee_fraction,
+        );
         
         // Debug logging for price calculations
         if self.pnl_percentage.abs() > 1000.0 { // Log if PnL is unusually high
@@ -178,18 +180,18 @@ impl BoughtTokenInfo {
     
     pub fn should_sell_due_to_trailing_stop(&self) -> bool {
         // Don't trigger trailing stop if entry_price is not set (buy not processed yet)
-        if self.entry_price == 0 || self.highest_price == 0 {
+        if self.entry_price <= 0.0 || self.highest_price <= 0.0 {
             return false;
         }
         
-        let drop_from_highest = ((self.highest_price as f64 - self.current_price as f64) / self.highest_price as f64) * 100.0;
+        let drop_from_highest = ((self.highest_price - self.current_price) / self.highest_price) * 100.0;
         drop_from_highest >= self.trailing_stop_percentage
     }
     
     /// Determine selling action based on comprehensive rules
     pub fn get_selling_action(&self) -> SellingAction {
         // CRITICAL: Don't sell if entry_price is 0 (buy transaction not yet processed)
-        if self.entry_price == 0 {
+        if self.entry_price <= 0.0 {
             return SellingAction::Hold;
         }
         
@@ -218,21 +220,6 @@ impl BoughtTokenInfo {
         // Trailing stop logic is disabled
         SellingAction::Hold
     }
-    
-
-    
-    /// Check trailing stop with specific percentage
-    fn should_sell_due_to_trailing_stop_with_percentage(&self, trailing_stop_percentage: f64) -> bool {
-        // Don't trigger trailing stop if entry_price is not set (buy not processed yet)
-        if self.entry_price == 0 || self.highest_price == 0 {
-            return false;
-        }
-        
-        let drop_from_highest = ((self.highest_price as f64 - self.current_price as f64) / self.highest_price as f64) * 100.0;
-        drop_from_highest >= trailing_stop_percentage
-    }
-    
-
 }
 
 // Global state for sniper bot
@@ -255,8 +242,13 @@ lazy_static::lazy_static! {
     pub static ref FOCUS_TOKEN_LIST: Arc<DashMap<String, FocusTokenInfo>> = Arc::new(DashMap::new());
     // SNIPER BOT: Price monitoring tasks for focus tokens
     static ref PRICE_MONITORING_TASKS: Arc<DashMap<String, CancellationToken>> = Arc::new(DashMap::new());
+    // SNIPER BOT: Recently launched Let's Bonk pools keyed by base mint
+    pub static ref RECENT_LAUNCHES: Arc<DashMap<String, transaction_parser::LaunchEvent>> = Arc::new(DashMap::new());
 }
 
+// Maximum number of launches kept in RECENT_LAUNCHES
+const MAX_RECENT_LAUNCHES: usize = 1000;
+
 // Initialize the global counters with default values
 fn init_global_state() {
     COUNTER.insert((), 0);
@@ -349,13 +341,13 @@ async fn cancel_token_monitoring(token_mint: &str, logger: &Logger) -> Result<()
     }
     
     // Check if all tokens are sold and stop streaming if needed
-    check_and_stop_streaming_if_all_sold(&logger).await;
+    check_and_stop_streaming_if_all_sold(logger).await;
     
     Ok(())
 }
 
 /// Check if all tokens are sold and stop GRPC streaming to prevent connection accumulation
-pub async fn check_and_stop_streaming_if_all_sold(logger: &Logger) {
+pub async fn check_and_stop_streaming_if_all_sold(_logger: &Logger) {
     let active_tokens_count = BOUGHT_TOKEN_LIST.len();
     let active_monitoring_count = MONITORING_TASKS.len();
     let active_tracking_count = TOKEN_TRACKING.len();
@@ -548,11 +540,10 @@ pub async fn start_dex_monitoring(config: SniperConfig) -> Result<(), String> {
     let subscribe_tx = Arc::new(tokio::sync::Mutex::new(subscribe_tx));
 
     // Create config for subscription
-    let dexs = vec![
-        PUMP_FUN_PROGRAM.to_string(),
-        PUMP_SWAP_PROGRAM.to_string(),
-        RAYDIUM_LAUNCHPAD_PROGRAM.to_string(),
-    ];
+    let dexs: Vec<String> = config.app_state.dex_registry.discovery_program_ids()
+        .iter()
+        .map(|program_id| program_id.to_string())
+        .collect();
     // Set up subscription
     let subscription_request = SubscribeRequest {
         transactions: maplit::hashmap! {
@@ -656,23 +647,19 @@ async fn verify_transaction(
         // Check transaction status
         match app_state.rpc_nonblocking_client.get_signature_statuses(&[signature]).await {
             Ok(result) => {
-                if let Some(status_opt) = result.value.get(0) {
-                    if let Some(status) = status_opt {
-                        if status.err.is_some() {
-                            // Transaction failed
-                            return Err(format!("Transaction failed: {:?}", status.err));
-                        } else if let Some(conf_status) = &status.confirmation_status {
-                            if matches!(conf_status, TransactionConfirmationStatus::Finalized | 
-                                                      TransactionConfirmationStatus::Confirmed) {
-                                return Ok(true);
-                            } else {
-                                logger.log(format!("Transaction not yet confirmed (status: {:?}), retrying...", 
-                                         conf_status).yellow().to_string());
-                            }
+                if let Some(Some(status)) = result.value.first() {
+                    if status.err.is_some() {
+                        // Transaction failed
+                        return Err(format!("Transaction failed: {:?}", status.err));
+                    } else if let Some(conf_status) = &status.confirmation_status {
+                        if matches!(conf_status, TransactionConfirmationStatus::Finalized | 
+                                                  TransactionConfirmationStatus::Confirmed) {
+                            return Ok(true);
                         } else {
+                            logger.log(format!("Transaction not yet confirmed (status: {:?}), retrying...", 
+                                     conf_status).yellow().to_string());
                         }
-                    } else {
-                    }
+                    } 
                 }
             },
             Err(e) => {
@@ -692,6 +679,87 @@ async fn verify_transaction(
     Err("Transaction verification failed after retries".to_string())
 }
 
+/// Venue registered for `protocol`
+fn dex_for(app_state: &AppState, protocol: &SwapProtocol) -> Result<Arc<dyn Dex>, String> {
+    app_state.dex_registry.get(protocol)
+        .ok_or_else(|| format!("No venue registered for protocol {:?}", protocol))
+}
+
+/// Build a sell on the venue for `protocol` (or the one the token migrated to) and send it through
+/// every fan-out relay when relays are configured, otherwise with zeroslot or normal RPC.
+async fn execute_dex_sell(
+    trade_info: &transaction_parser::TradeInfoFromToken,
+    sell_config: SwapConfig,
+    app_state: Arc<AppState>,
+    protocol: &SwapProtocol,
+    method: &str,
+    logger: &Logger,
+) -> Result<(), String> {
+    let (protocol, trade_info) = crate::engine::selling_strategy::route_sell(trade_info, protocol)
+        .map_err(|e| e.to_string())?;
+    let dex = dex_for(&app_state, &protocol)?;
+
+    match dex.build_sell(&trade_info, sell_config).await {
+        Ok((keypair, instructions, price)) => {
+            logger.log(format!("Generated {:?} sell instruction at price: {}", dex.protocol(), price));
+
+            let recent_blockhash = match crate::services::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
+                Some(hash) => hash,
+                None => {
+                    return Err("Failed to get recent blockhash".to_string());
+                }
+            };
+
+            let sent = if let Some(relay_fanout) = &app_state.relay_fanout {
+                crate::core::tx::new_signed_and_send_fanout(
+                    relay_fanout,
+                    recent_blockhash,
+                    &keypair,
+                    instructions,
+                    logger,
+                    crate::services::priority_fees::FeeIntent::Sell,
+                ).await
+            } else if method == "zeroslot" {
+                crate::core::tx::new_signed_and_send_zeroslot(
+                    app_state.zeroslot_rpc_client.clone(),
+                    recent_blockhash,
+                    &keypair,
+                    instructions,
+                    logger,
+                    crate::services::priority_fees::FeeIntent::Sell,
+                ).await
+            } else {
+                crate::core::tx::new_signed_and_send_normal(
+                    app_state.rpc_nonblocking_client.clone(),
+                    recent_blockhash,
+                    &keypair,
+                    instructions,
+                    logger,
+                    crate::services::priority_fees::FeeIntent::Sell,
+                ).await
+            };
+
+            match sent {
+                Ok(signatures) => {
+                    if signatures.is_empty() {
+                        return Err("No transaction signature returned".to_string());
+                    }
+
+                    let signature = &signatures[0];
+                    logger.log(format!("{} sell transaction sent: {}", method.to_uppercase(), signature));
+
+                    verify_transaction(&signature.to_string(), app_state.clone(), logger).await
+                        .map_err(|e| format!("Transaction verification error: {}", e))?;
+
+                    Ok(())
+                },
+                Err(e) => Err(format!("Transaction error: {}", e)),
+            }
+        },
+        Err(e) => Err(format!("Failed to build {:?} sell instruction: {}", dex.protocol(), e)),
+    }
+}
+
 /// Execute buy operation based on detected transaction
 pub async fn execute_buy(
     trade_info: transaction_parser::TradeInfoFromToken,
@@ -712,12 +780,18 @@ pub async fn execute_buy(
     let mut buy_config = (*swap_config).clone();
     buy_config.swap_direction = SwapDirection::Buy;
     
-    // Store the amount_in before potential moves
-    let amount_in = buy_config.amount_in;
+    // SOL committed to the buy; an exact-out buy names the tokens to receive instead
+    let amount_in = match buy_config.in_type {
+        SwapInType::ExactOut => buy_config.amount_in * trade_info.price / 1_000_000_000.0,
+        _ => buy_config.amount_in,
+    };
     
     // Get token amount and SOL cost from trade_info
     let (_amount_in, _token_amount) = match trade_info.dex_type {
-        transaction_parser::DexType::RaydiumLaunchpad => {
+        transaction_parser::DexType::RaydiumLaunchpad
+        | transaction_parser::DexType::PumpFun
+        | transaction_parser::DexType::RaydiumCpmm
+        | transaction_parser::DexType::PumpSwap => {
             let sol_amount = trade_info.sol_change.abs();
             let token_amount = trade_info.token_change.abs();
             (sol_amount, token_amount)
@@ -727,449 +801,112 @@ pub async fn execute_buy(
         }
     };
     
-    // Protocol string for notifications
-    let _protocol_str = match protocol {
-        SwapProtocol::RaydiumLaunchpad => "RaydiumLaunchpad",
-        _ => "Unknown",
-    };
-    
-    // Send notification that we're attempting to copy the trade
-    
-    // Execute based on protocol
-    let result = match protocol {
-        SwapProtocol::PumpFun => {
-            logger.log("Using PumpFun protocol for buy".to_string());
-            
-            // Create the PumpFun instance
-            let pump = crate::dex::pump_fun::Pump::new(
-                app_state.rpc_nonblocking_client.clone(),
-                app_state.rpc_client.clone(),
-                app_state.wallet.clone(),
-            );
-            // Build swap instructions from parsed data
-            match pump.build_swap_from_parsed_data(&trade_info, buy_config.clone()).await {
-                Ok((keypair, instructions, price)) => {
-                    logger.log(format!("Generated PumpFun buy instruction at price: {}", price));
-                    logger.log(format!("copy transaction {}", trade_info.signature));
-                    let start_time = Instant::now();
-                    // Get real-time blockhash from processor
-                    let recent_blockhash = match crate::services::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
-                        Some(hash) => hash,
-                        None => {
-                            logger.log("Failed to get real-time blockhash, skipping transaction".red().to_string());
-                            return Err("Failed to get real-time blockhash".to_string());
-                        }
-                    };
-                    println!("time taken for get_latest_blockhash: {:?}", start_time.elapsed());
-                    println!("using zeroslot for buy transaction >>>>>>>>");
-                    // Execute the transaction using zeroslot for buying
-                    match crate::core::tx::new_signed_and_send_zeroslot(
-                        app_state.zeroslot_rpc_client.clone(),
-                        recent_blockhash,
-                        &keypair,
-                        instructions,
-                        &logger,
-                    ).await {
-                        Ok(signatures) => {
-                            if signatures.is_empty() {
-                                return Err("No transaction signature returned".to_string());
-                            }
-                            
-                            let signature = &signatures[0];
-                            logger.log(format!("Buy transaction sent: {}", signature));
-                            
-                            
-                            // Verify transaction
-                            match verify_transaction(&signature.to_string(), app_state.clone(), &logger).await {
-                                Ok(verified) => {
-                                    if verified {
-                                        logger.log("Buy transaction verified successfully".to_string());
-                                        
-                                        // Add token account to our global list and tracking
-                                        if let Ok(wallet_pubkey) = app_state.wallet.try_pubkey() {
-                                            let token_mint = Pubkey::from_str(&trade_info.mint)
-                                                .map_err(|_| "Invalid token mint".to_string())?;
-                                            let token_ata = get_associated_token_address(&wallet_pubkey, &token_mint);
-                                            WALLET_TOKEN_ACCOUNTS.insert(token_ata);
-                                            logger.log(format!("Added token account {} to global list", token_ata));
-                                            
-                                            // Add to enhanced tracking system for PumpFun
-                                            let bought_token_info = BoughtTokenInfo::new(
-                                                trade_info.mint.clone(),
-                                                trade_info.price, // Use price directly from TradeInfoFromToken (already scaled)
-                                                amount_in,
-                                                _token_amount,
-                                                protocol.clone(),
-                                                trade_info.clone(),
-                                                3, // 3 seconds selling time
-                                            );
-                                            BOUGHT_TOKEN_LIST.insert(trade_info.mint.clone(), bought_token_info);
-                                            logger.log(format!("Added {} to enhanced tracking system (PumpFun)", trade_info.mint));
-                                            
-                                            // Add to permanent blacklist (never rebuy this token)
-                                            let timestamp = std::time::SystemTime::now()
-                                                .duration_since(std::time::UNIX_EPOCH)
-                                                .unwrap_or_default()
-                                                .as_secs();
-                                            BOUGHT_TOKENS_BLACKLIST.insert(trade_info.mint.clone(), timestamp);
-                                            logger.log(format!("🚫 Added {} to permanent blacklist", trade_info.mint));
-                                            
-                                            // CRITICAL FIX: Update selling strategy with actual token balance after successful buy
-                                            let selling_engine = crate::engine::selling_strategy::SellingEngine::new(
-                                                app_state.clone(),
-                                                Arc::new(buy_config.clone()),
-                                                crate::engine::selling_strategy::SellingConfig::default()
-                                            );
-                                            if let Err(e) = selling_engine.update_metrics(&trade_info.mint, &trade_info).await {
-                                                logger.log(format!("Warning: Failed to update token metrics after buy: {}", e).yellow().to_string());
-                                            }
-                                            
-                                            // Selling instructions are now built on-demand, no caching needed
-                                            logger.log(format!("✅ Buy transaction completed for token: {}", trade_info.mint).green().to_string());
-                                        }
-                                        
-                                        
-                                        Ok(())
-                                    } else {
-                                        
-                                        Err("Buy transaction verification failed".to_string())
-                                    }
-                                },
-                                Err(e) => {
-                                    Err(format!("Transaction verification error: {}", e))
-                                },
-                            }
-                        },
-                        Err(e) => {
-                            Err(format!("Transaction error: {}", e))
-                        },
-                    }
-                },
-                Err(e) => {
-                    Err(format!("Failed to build PumpFun buy instruction: {}", e))
+    // Resolve the venue for this protocol
+    let dex = dex_for(&app_state, &protocol)?;
+    logger.log(format!("Using {:?} venue for buy", dex.protocol()));
+
+    let result = match dex.build_buy(&trade_info, buy_config.clone()).await {
+        Ok((keypair, instructions, price)) => {
+            logger.log(format!("Generated {:?} buy instruction at price: {}", dex.protocol(), price));
+            logger.log(format!("copy transaction {}", trade_info.signature));
+
+            // Get real-time blockhash from processor
+            let recent_blockhash = match crate::services::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
+                Some(hash) => hash,
+                None => {
+                    logger.log("Failed to get real-time blockhash, skipping transaction".red().to_string());
+                    return Err("Failed to get real-time blockhash".to_string());
                 }
-            }
-        },
-        SwapProtocol::PumpSwap => {
-            logger.log("Using PumpSwap protocol for buy".to_string());
-            
-            // Create the PumpSwap instance
-            let pump_swap = crate::dex::pump_swap::PumpSwap::new(
-                app_state.wallet.clone(),
-                Some(app_state.rpc_client.clone()),
-                Some(app_state.rpc_nonblocking_client.clone()),
-            );
-            
-            // Build swap instructions from parsed data for buy
-            match pump_swap.build_swap_from_parsed_data(&trade_info, buy_config.clone()).await {
-                Ok((keypair, instructions, price)) => {
-                    logger.log(format!("Generated PumpSwap buy instruction at price: {}", price));
-                    logger.log(format!("copy transaction {}", trade_info.signature));
-                    
-                    // Get real-time blockhash from processor
-                    let recent_blockhash = match crate::services::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
-                        Some(hash) => hash,
-                        None => {
-                            logger.log("Failed to get real-time blockhash, skipping transaction".red().to_string());
-                            return Err("Failed to get real-time blockhash".to_string());
-                        }
-                    };
+            };
 
-                    println!("using zeroslot for buy transaction >>>>>>>>");
-                    // Execute the transaction using zeroslot for buying
-                    match crate::core::tx::new_signed_and_send_zeroslot(
-                        app_state.zeroslot_rpc_client.clone(),
-                        recent_blockhash,
-                        &keypair,
-                        instructions,
-                        &logger,
-                    ).await {
-                        Ok(signatures) => {
-                            if signatures.is_empty() {
-                                return Err("No transaction signature returned".to_string());
-                            }
-                            
-                            let signature = &signatures[0];
-                            logger.log(format!("Buy transaction sent: {}", signature));
-                            
-                            // Verify transaction
-                            match verify_transaction(&signature.to_string(), app_state.clone(), &logger).await {
-                                Ok(verified) => {
-                                    if verified {
-                                        logger.log("Buy transaction verified successfully".to_string());
-                                        
-                                        // Add token account to our global list and tracking
-                                        if let Ok(wallet_pubkey) = app_state.wallet.try_pubkey() {
-                                            let token_mint = Pubkey::from_str(&trade_info.mint)
-                                                .map_err(|_| "Invalid token mint".to_string())?;
-                                            let token_ata = get_associated_token_address(&wallet_pubkey, &token_mint);
-                                            WALLET_TOKEN_ACCOUNTS.insert(token_ata);
-                                            logger.log(format!("Added token account {} to global list", token_ata));
-                                            
-                                            // Add to enhanced tracking system for PumpSwap
-                                            let bought_token_info = BoughtTokenInfo::new(
-                                                trade_info.mint.clone(),
-                                                trade_info.price, // Use price directly from TradeInfoFromToken (already scaled)
-                                                amount_in,
-                                                _token_amount,
-                                                protocol.clone(),
-                                                trade_info.clone(),
-                                                3, // 3 seconds selling time
-                                            );
-                                            BOUGHT_TOKEN_LIST.insert(trade_info.mint.clone(), bought_token_info);
-                                            logger.log(format!("Added {} to enhanced tracking system (PumpSwap)", trade_info.mint));
-                                            
-                                            // Add to permanent blacklist (never rebuy this token)
-                                            let timestamp = std::time::SystemTime::now()
-                                                .duration_since(std::time::UNIX_EPOCH)
-                                                .unwrap_or_default()
-                                                .as_secs();
-                                            BOUGHT_TOKENS_BLACKLIST.insert(trade_info.mint.clone(), timestamp);
-                                            logger.log(format!("🚫 Added {} to permanent blacklist", trade_info.mint));
-                                            
-                                            // CRITICAL FIX: Update selling strategy with actual token balance after successful buy
-                                            let selling_engine = crate::engine::selling_strategy::SellingEngine::new(
-                                                app_state.clone(),
-                                                Arc::new(buy_config.clone()),
-                                                crate::engine::selling_strategy::SellingConfig::default()
-                                            );
-                                            if let Err(e) = selling_engine.update_metrics(&trade_info.mint, &trade_info).await {
-                                                logger.log(format!("Warning: Failed to update token metrics after buy: {}", e).yellow().to_string());
-                                            }
-                                        }
-                                        
-                                        Ok(())
-                                    } else {
-                                        Err("Buy transaction verification failed".to_string())
-                                    }
-                                },
-                                Err(e) => {
-                                    Err(format!("Transaction verification error: {}", e))
-                                },
-                            }
-                        },
-                        Err(e) => {
-                            Err(format!("Transaction error: {}", e))
-                        },
+            // Execute the transaction using zeroslot for buying
+            match crate::core::tx::new_signed_and_send_zeroslot(
+                app_state.zeroslot_rpc_client.clone(),
+                recent_blockhash,
+                &keypair,
+                instructions,
+                &logger,
+                crate::services::priority_fees::FeeIntent::Buy,
+            ).await {
+                Ok(signatures) => {
+                    if signatures.is_empty() {
+                        return Err("No transaction signature returned".to_string());
                     }
-                },
-                Err(e) => {
-                    Err(format!("Failed to build PumpSwap buy instruction: {}", e))
-                },
-            }
-        },
-                    SwapProtocol::RaydiumLaunchpad => {
-                logger.log("Using RaydiumLaunchpad protocol for buy".to_string());
-                
-                // Create the Raydium instance
-                let raydium = crate::dex::raydium_launchpad::Raydium::new(
-                app_state.wallet.clone(),
-                Some(app_state.rpc_client.clone()),
-                Some(app_state.rpc_nonblocking_client.clone()),
-            );
-            
-            // Build swap instructions from parsed data for buy
-            match raydium.build_swap_from_parsed_data(&trade_info, buy_config.clone()).await {
-                Ok((keypair, instructions, _price)) => {
-                    
-                    // Get real-time blockhash from processor
-                    let recent_blockhash = match crate::services::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
-                        Some(hash) => hash,
-                        None => {
-                            logger.log("Failed to get real-time blockhash, skipping transaction".red().to_string());
-                            return Err("Failed to get real-time blockhash".to_string());
-                        }
-                    };
-                    
-                    // Execute the transaction using zeroslot for buying
-                    match crate::core::tx::new_signed_and_send_zeroslot(
-                        app_state.zeroslot_rpc_client.clone(),
-                        recent_blockhash,
-                        &keypair,
-                        instructions,
-                        &logger,
-                    ).await {
-                        Ok(signatures) => {
-                            if signatures.is_empty() {
-                                return Err("No transaction signature returned".to_string());
-                            }
-                            
-                            let signature = &signatures[0];
-                            logger.log(format!("Buy transaction sent: {}", signature));
-                            
-                            // Verify transaction
-                            match verify_transaction(&signature.to_string(), app_state.clone(), &logger).await {
-                                Ok(verified) => {
-                                    if verified {
-                                        logger.log("Buy transaction verified successfully".to_string());
-                                        
-                                        // Add token account to our global list and tracking
-                                        if let Ok(wallet_pubkey) = app_state.wallet.try_pubkey() {
-                                            let token_mint = Pubkey::from_str(&trade_info.mint)
-                                                .map_err(|_| "Invalid token mint".to_string())?;
-                                            let token_ata = get_associated_token_address(&wallet_pubkey, &token_mint);
-                                            WALLET_TOKEN_ACCOUNTS.insert(token_ata);
-                                            logger.log(format!("Added token account {} to global list", token_ata));
-                                            
-                                            // Add to enhanced tracking system for Raydium
-                                            let bought_token_info = BoughtTokenInfo::new(
-                                                trade_info.mint.clone(),
-                                                trade_info.price, // Use price directly from TradeInfoFromToken (already scaled)
-                                                amount_in,
-                                                _token_amount,
-                                                protocol.clone(),
-                                                trade_info.clone(),
-                                                3, // 3 seconds selling time
-                                            );
-                                            BOUGHT_TOKEN_LIST.insert(trade_info.mint.clone(), bought_token_info);
-                                            logger.log(format!("Added {} to enhanced tracking system (Raydium)", trade_info.mint));
-                                            
-                                            // Add to permanent blacklist (never rebuy this token)
-                                            let timestamp = std::time::SystemTime::now()
-                                                .duration_since(std::time::UNIX_EPOCH)
-                                                .unwrap_or_default()
-                                                .as_secs();
-                                            BOUGHT_TOKENS_BLACKLIST.insert(trade_info.mint.clone(), timestamp);
-                                            logger.log(format!("🚫 Added {} to permanent blacklist", trade_info.mint));
-                                            
-                                            // CRITICAL FIX: Update selling strategy with actual token balance after successful buy
-                                            let selling_engine = crate::engine::selling_strategy::SellingEngine::new(
-                                                app_state.clone(),
-                                                Arc::new(buy_config.clone()),
-                                                crate::engine::selling_strategy::SellingConfig::default()
-                                            );
-                                            if let Err(e) = selling_engine.update_metrics(&trade_info.mint, &trade_info).await {
-                                                logger.log(format!("Warning: Failed to update token metrics after buy: {}", e).yellow().to_string());
-                                            }
-                                        }
-                                        
-                                        Ok(())
+
+                    let signature = &signatures[0];
+                    logger.log(format!("Buy transaction sent: {}", signature));
+
+                    // Verify transaction
+                    match verify_transaction(&signature.to_string(), app_state.clone(), &logger).await {
+                        Ok(verified) => {
+                            if verified {
+                                logger.log("Buy transaction verified successfully".to_string());
+
+                                // Add token account to our global list and tracking
+                                if let Ok(wallet_pubkey) = app_state.wallet.try_pubkey() {
+                                    let token_mint = Pubkey::from_str(&trade_info.mint)
+                                        .map_err(|_| "Invalid token mint".to_string())?;
+                                    let token_ata = get_wallet_token_address(app_state.rpc_nonblocking_client.clone(), &wallet_pubkey, &token_mint)
+                                        .await
+                                        .map_err(|e| e.to_string())?;
+                                    WALLET_TOKEN_ACCOUNTS.insert(token_ata);
+                                    logger.log(format!("Added token account {} to global list", token_ata));
+
+                                    // Add to enhanced tracking system, only with a valid entry price
+                                    let mut bought_token_info = BoughtTokenInfo::new(
+                                        trade_info.mint.clone(),
+                                        trade_info.price, // Use price directly from TradeInfoFromToken (already scaled)
+                                        amount_in,
+                                        _token_amount,
+                                        dex.protocol(),
+                                        trade_info.clone(),
+                                        std::env::var("SELLING_TIME").unwrap_or_else(|_| "300".to_string()).parse().unwrap_or(300),
+                                    );
+                                    bought_token_info.share_fee_fraction = app_state.share_fee_fraction(&dex.protocol());
+                                    if bought_token_info.entry_price > 0.0 {
+                                        BOUGHT_TOKEN_LIST.insert(trade_info.mint.clone(), bought_token_info);
+                                        logger.log(format!("Added {} to enhanced tracking system ({:?})", trade_info.mint, dex.protocol()));
                                     } else {
-                                        Err("Buy transaction verification failed".to_string())
+                                        logger.log(format!("Refusing to track token {} with entry_price = 0", trade_info.mint).yellow().to_string());
                                     }
-                                },
-                                Err(e) => {
-                                    Err(format!("Transaction verification error: {}", e))
-                                },
-                            }
-                        },
-                        Err(e) => {
-                            Err(format!("Transaction error: {}", e))
-                        },
-                    }
-                },
-                Err(e) => {
-                    Err(format!("Failed to build RaydiumLaunchpad buy instruction: {}", e))
-                },
-            }
-        },
-        SwapProtocol::Auto | SwapProtocol::Unknown => {
-            logger.log("Auto/Unknown protocol detected, defaulting to PumpFun for buy".yellow().to_string());
-            
-            // Create the PumpFun instance
-            let pump = crate::dex::pump_fun::Pump::new(
-                app_state.rpc_nonblocking_client.clone(),
-                app_state.rpc_client.clone(),
-                app_state.wallet.clone(),
-            );
-            // Build swap instructions from parsed data
-            match pump.build_swap_from_parsed_data(&trade_info, buy_config.clone()).await {
-                Ok((keypair, instructions, price)) => {
-                    logger.log(format!("Generated PumpFun buy instruction at price: {}", price));
-                    logger.log(format!("copy transaction {}", trade_info.signature));
-                    let start_time = Instant::now();
-                    // Get real-time blockhash from processor
-                    let recent_blockhash = match crate::services::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
-                        Some(hash) => hash,
-                        None => {
-                            logger.log("Failed to get real-time blockhash, skipping transaction".red().to_string());
-                            return Err("Failed to get real-time blockhash".to_string());
-                        }
-                    };
-                    println!("time taken for get_latest_blockhash: {:?}", start_time.elapsed());
-                    println!("using zeroslot for buy transaction >>>>>>>>");
-                    // Execute the transaction using zeroslot for buying
-                    match crate::core::tx::new_signed_and_send_zeroslot(
-                        app_state.zeroslot_rpc_client.clone(),
-                        recent_blockhash,
-                        &keypair,
-                        instructions,
-                        &logger,
-                    ).await {
-                        Ok(signatures) => {
-                            if signatures.is_empty() {
-                                return Err("No transaction signature returned".to_string());
-                            }
-                            
-                            let signature = &signatures[0];
-                            logger.log(format!("Buy transaction sent: {}", signature));
-                            
-                            // Verify transaction
-                            match verify_transaction(&signature.to_string(), app_state.clone(), &logger).await {
-                                Ok(verified) => {
-                                    if verified {
-                                        logger.log("Buy transaction verified successfully".to_string());
-                                        
-                                        // Add token account to our global list and tracking
-                                        if let Ok(wallet_pubkey) = app_state.wallet.try_pubkey() {
-                                            let token_mint = Pubkey::from_str(&trade_info.mint)
-                                                .map_err(|_| "Invalid token mint".to_string())?;
-                                            let token_ata = get_associated_token_address(&wallet_pubkey, &token_mint);
-                                            WALLET_TOKEN_ACCOUNTS.insert(token_ata);
-                                            logger.log(format!("Added token account {} to global list", token_ata));
-                                            
-                                            // Add to enhanced tracking system for PumpFun
-                                            let bought_token_info = BoughtTokenInfo::new(
-                                                trade_info.mint.clone(),
-                                                trade_info.price, // Use price directly from TradeInfoFromToken (already scaled)
-                                                amount_in,
-                                                _token_amount,
-                                                SwapProtocol::PumpFun, // Use PumpFun as the fallback protocol
-                                                trade_info.clone(),
-                                                import_env_var("SELLING_TIME").parse::<u64>().unwrap_or(600),
-                                            );
-                                            
-                                            // Insert the token into the bought tokens map for monitoring
-                                            BOUGHT_TOKEN_LIST.insert(trade_info.mint.clone(), bought_token_info);
-                                            logger.log(format!("Added {} to bought tokens tracking", trade_info.mint));
-                                            
-                                            // Add to permanent blacklist (never rebuy this token)
-                                            let timestamp = std::time::SystemTime::now()
-                                                .duration_since(std::time::UNIX_EPOCH)
-                                                .unwrap_or_default()
-                                                .as_secs();
-                                            BOUGHT_TOKENS_BLACKLIST.insert(trade_info.mint.clone(), timestamp);
-                                            logger.log(format!("🚫 Added {} to permanent blacklist", trade_info.mint));
-                                            
-                                            // Start enhanced selling monitor for this token
-                                            let app_state_clone = app_state.clone();
-                                            let swap_config_clone = swap_config.clone();
-                                            let _token_mint = trade_info.mint.clone();
-                                            tokio::spawn(async move {
-                                                start_enhanced_selling_monitor(app_state_clone, swap_config_clone).await;
-                                            });
-                                        }
-                                        
-                                        Ok(())
-                                    } else {
-                                        Err("Buy transaction verification failed".to_string())
+
+                                    // Add to permanent blacklist (never rebuy this token)
+                                    let timestamp = std::time::SystemTime::now()
+                                        .duration_since(std::time::UNIX_EPOCH)
+                                        .unwrap_or_default()
+                                        .as_secs();
+                                    BOUGHT_TOKENS_BLACKLIST.insert(trade_info.mint.clone(), timestamp);
+                                    logger.log(format!("🚫 Added {} to permanent blacklist", trade_info.mint));
+
+                                    // CRITICAL FIX: Update selling strategy with actual token balance after successful buy
+                                    let selling_engine = crate::engine::selling_strategy::SellingEngine::new(
+                                        app_state.clone(),
+                                        Arc::new(buy_config.clone()),
+                                        crate::engine::selling_strategy::SellingConfig::default()
+                                    );
+                                    if let Err(e) = selling_engine.update_metrics(&trade_info.mint, &trade_info).await {
+                                        logger.log(format!("Warning: Failed to update token metrics after buy: {}", e).yellow().to_string());
                                     }
-                                },
-                                Err(e) => {
-                                    Err(format!("Transaction verification error: {}", e))
-                                },
+                                }
+
+                                Ok(())
+                            } else {
+                                Err("Buy transaction verification failed".to_string())
                             }
                         },
                         Err(e) => {
-                            Err(format!("Transaction error: {}", e))
+                            Err(format!("Transaction verification error: {}", e))
                         },
                     }
                 },
                 Err(e) => {
-                    Err(format!("Failed to build PumpFun buy instruction: {}", e))
+                    Err(format!("Transaction error: {}", e))
                 },
             }
         },
+        Err(e) => {
+            Err(format!("Failed to build {:?} buy instruction: {}", dex.protocol(), e))
+        },
     };
     
     // Log execution time
@@ -1186,37 +923,7 @@ pub async fn execute_buy(
         };
         logger.log(format!("Total bought: {}", bought_count));
         
-        // Add token to bought token list for comprehensive tracking
-        let bought_token_info = BoughtTokenInfo::new(
-            trade_info.mint.clone(),
-            trade_info.price, // Use price directly from TradeInfoFromToken (already scaled)
-            amount_in, // SOL amount spent (using stored value)
-            trade_info.token_change.abs(), // Token amount received
-            protocol.clone(),
-            trade_info.clone(),
-            std::env::var("SELLING_TIME").unwrap_or_else(|_| "300".to_string()).parse().unwrap_or(300),
-        );
-        
-        // Debug logging for token tracking
-        println!("DEBUG TRACKING: Adding token {} to BOUGHT_TOKEN_LIST with entry_price: {}", 
-            trade_info.mint, bought_token_info.entry_price);
-        
-        // Only add to tracking if entry_price is valid
-        if bought_token_info.entry_price > 0 {
-            BOUGHT_TOKEN_LIST.insert(trade_info.mint.clone(), bought_token_info);
-            
-            // Add to permanent blacklist (never rebuy this token)
-            let timestamp = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs();
-            BOUGHT_TOKENS_BLACKLIST.insert(trade_info.mint.clone(), timestamp);
-            logger.log(format!("🚫 Added {} to permanent blacklist", trade_info.mint));
-        } else {
-            println!("WARNING: Refusing to track token {} with entry_price = 0", trade_info.mint);
-        }
-        
-        // Token added to selling system via the selling_engine.update_metrics call above
+        // Token added to BOUGHT_TOKEN_LIST and the selling system after verification above
         
         // Legacy tracking for compatibility
         TOKEN_TRACKING.entry(trade_info.mint.clone()).or_insert(TokenTrackingInfo {
@@ -1236,525 +943,59 @@ pub async fn execute_buy(
     result
 }
 
-/// Internal wallet monitoring function using second GRPC stream
-async fn start_wallet_monitoring_internal(app_state: Arc<AppState>) -> Result<(), String> {
-    use futures_util::stream::StreamExt;
-    use futures_util::{SinkExt, Sink};
-    use yellowstone_grpc_client::{ClientTlsConfig, GeyserGrpcClient};
-    use yellowstone_grpc_proto::geyser::{
-        subscribe_update::UpdateOneof, CommitmentLevel, SubscribeRequest, SubscribeRequestPing,
-        SubscribeRequestFilterTransactions, SubscribeUpdate,
+/// Enhanced sell execution with comprehensive selling logic
+pub async fn execute_enhanced_sell(
+    token_mint: String,
+    app_state: Arc<AppState>,
+    swap_config: Arc<SwapConfig>,
+) -> Result<(), String> {
+    let logger = Logger::new("[ENHANCED-SELL] => ".green().to_string());
+    
+    // Get token info from global tracking
+    let mut token_info = match BOUGHT_TOKEN_LIST.get_mut(&token_mint) {
+        Some(info) => info,
+        None => {
+            return Err(format!("Token {} not found in tracking list", token_mint));
+        }
     };
     
-    let logger = Logger::new("[WALLET-MONITOR-INTERNAL] => ".purple().bold().to_string());
+    // Get selling action based on comprehensive rules
+    let selling_action = token_info.get_selling_action();
     
-    let second_grpc_http = std::env::var("SECOND_YELLOWSTONE_GRPC_HTTP")
-        .map_err(|_| "SECOND_YELLOWSTONE_GRPC_HTTP not set".to_string())?;
-    let second_grpc_token = std::env::var("SECOND_YELLOWSTONE_GRPC_TOKEN")
-        .map_err(|_| "SECOND_YELLOWSTONE_GRPC_TOKEN not set".to_string())?;
-    let wallet_pubkey = app_state.wallet.pubkey().to_string();
+    // Debug logging for tokens with invalid entry price
+    if token_info.entry_price <= 0.0 {
+        logger.log(format!("WARNING: Token {} has entry_price = 0, buy transaction may not be processed yet", token_mint).yellow().to_string());
+    }
     
+    match selling_action {
+        SellingAction::Hold => {
+            logger.log(format!("Holding token {}", token_mint));
+            Ok(())
+        },
+        SellingAction::SellAll(reason) => {
+            logger.log(format!("Selling ALL of token {} - Reason: {}", token_mint, reason));
+            execute_sell_all_enhanced(&token_mint, &mut token_info, app_state, swap_config).await
+        }
+    }
+}
 
+/// Execute sell all with zeroslot for maximum speed
+async fn execute_sell_all_enhanced(
+    token_mint: &str,
+    token_info: &mut BoughtTokenInfo,
+    app_state: Arc<AppState>,
+    swap_config: Arc<SwapConfig>,
+) -> Result<(), String> {
+    let logger = Logger::new("[SELL-ALL-ENHANCED] => ".red().to_string());
     
-    // Connect to second Yellowstone gRPC
-    let mut client = GeyserGrpcClient::build_from_shared(second_grpc_http)
-        .map_err(|e| format!("Failed to build second client: {}", e))?
-        .x_token::<String>(Some(second_grpc_token))
-        .map_err(|e| format!("Failed to set x_token: {}", e))?
-        .tls_config(ClientTlsConfig::new().with_native_roots())
-        .map_err(|e| format!("Failed to set tls config: {}", e))?
-        .connect()
+    // Get current token balance
+    let wallet_pubkey = app_state.wallet.try_pubkey()
+        .map_err(|e| format!("Failed to get wallet pubkey: {}", e))?;
+    let token_pubkey = Pubkey::from_str(token_mint)
+        .map_err(|e| format!("Invalid token mint: {}", e))?;
+    let ata = get_wallet_token_address(app_state.rpc_nonblocking_client.clone(), &wallet_pubkey, &token_pubkey)
         .await
-        .map_err(|e| format!("Failed to connect to second gRPC: {}", e))?;
-
-    // Set up subscribe
-    let (subscribe_tx, mut stream) = client.subscribe().await
-        .map_err(|e| format!("Failed to subscribe to second gRPC: {}", e))?;
-    let subscribe_tx = Arc::new(tokio::sync::Mutex::new(subscribe_tx));
-
-    // Set up wallet-specific subscription
-    let subscription_request = SubscribeRequest {
-        transactions: maplit::hashmap! {
-            "WalletMonitor".to_owned() => SubscribeRequestFilterTransactions {
-                vote: Some(false),
-                failed: Some(false),
-                signature: None,
-                account_include: vec![wallet_pubkey.clone()],
-                account_exclude: vec![],
-                account_required: Vec::<String>::new(),
-            }
-        },
-        commitment: Some(CommitmentLevel::Processed as i32),
-        ..Default::default()
-    };
-    
-    subscribe_tx.lock().await.send(subscription_request).await
-        .map_err(|e| format!("Failed to send wallet subscription: {}", e))?;
-
-        // Process wallet transactions
-    while SHOULD_CONTINUE_STREAMING.load(Ordering::SeqCst) {
-        match stream.next().await {
-            Some(msg_result) => {
-                match msg_result {
-                    Ok(msg) => {
-                        if let Some(UpdateOneof::Transaction(txn)) = &msg.update_oneof {
-                            if let Some(transaction) = &txn.transaction {
-                                let signature_bytes = &transaction.signature;
-                                let signature = bs58::encode(signature_bytes).into_string();
-                                    
-                                if let Some(meta) = &transaction.meta {
-                                    let is_buy = meta.log_messages.iter().any(|log| {
-                                        log.contains("Program log: Instruction: Buy") || log.contains("MintTo")
-                                    });
-                                    
-                                    let is_sell = meta.log_messages.iter().any(|log| {
-                                        log.contains("Program log: Instruction: Sell")
-                                    });
-                                    
-                                    if is_sell {
-                                        // Try to extract token mint and remove from bought list
-                                        for token_balance in &meta.post_token_balances {
-                                            if token_balance.ui_token_amount.as_ref().map(|ui| ui.ui_amount).unwrap_or(0.0) == 0.0 {
-                                                // This token was sold completely
-                                                BOUGHT_TOKEN_LIST.remove(&token_balance.mint);
-                                                // Remove token from the global tracking system
-                                                crate::engine::selling_strategy::TOKEN_METRICS.remove(&token_balance.mint);
-                                                crate::engine::selling_strategy::TOKEN_TRACKING.remove(&token_balance.mint);
-                                                
-                                                // Check if all tokens are sold
-                                                check_and_stop_streaming_if_all_sold(&logger).await;
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    },
-                    Err(e) => {
-                        logger.log(format!("Wallet monitor stream error: {:?}", e).red().to_string());
-                        // Check if it's a connection limit error
-                        if format!("{:?}", e).contains("Maximum connection count reached") {
-                            logger.log("🚫 Wallet monitor connection limit reached - closing stream".red().bold().to_string());
-                        }
-                        return Err(format!("Wallet monitor stream error: {:?}", e));
-                    },
-                }
-            },
-            None => {
-                logger.log("Wallet monitor stream ended".yellow().to_string());
-                break;
-            }
-        }
-    }
-    
-    if !SHOULD_CONTINUE_STREAMING.load(Ordering::SeqCst) {
-    }
-    
-    // Explicitly drop the stream and client to close connections
-    drop(stream);
-    drop(subscribe_tx);
-    drop(client);
-    
-
-    
-    Ok(())
-}
-
-
-/// Execute whale emergency sell with comprehensive multi-fallback system
-/// Execute PumpFun emergency sell with zeroslot
-async fn execute_pumpfun_emergency_sell_with_zeroslot(
-    trade_info: &transaction_parser::TradeInfoFromToken,
-    sell_config: SwapConfig,
-    app_state: Arc<AppState>,
-    logger: &Logger,
-) -> Result<(), String> {
-    let pump = crate::dex::pump_fun::Pump::new(
-        app_state.rpc_nonblocking_client.clone(),
-        app_state.rpc_client.clone(),
-        app_state.wallet.clone(),
-    );
-    
-    match pump.build_swap_from_parsed_data(trade_info, sell_config).await {
-        Ok((keypair, instructions, price)) => {
-            logger.log(format!("🐋 Generated PumpFun whale emergency sell instruction at price: {}", price));
-            
-            let recent_blockhash = match crate::services::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
-                Some(hash) => hash,
-                None => {
-                    return Err("Failed to get recent blockhash".to_string());
-                }
-            };
-            
-            match crate::core::tx::new_signed_and_send_zeroslot(
-                app_state.zeroslot_rpc_client.clone(),
-                recent_blockhash,
-                &keypair,
-                instructions,
-                logger,
-            ).await {
-                Ok(signatures) => {
-                    if signatures.is_empty() {
-                        return Err("No transaction signature returned".to_string());
-                    }
-                    
-                    let signature = &signatures[0];
-                    logger.log(format!("🐋 ZEROSLOT whale emergency sell transaction sent: {}", signature));
-                    
-                    verify_transaction(&signature.to_string(), app_state.clone(), logger).await
-                        .map_err(|e| format!("Transaction verification error: {}", e))?;
-                    
-                    Ok(())
-                },
-                Err(e) => Err(format!("Zeroslot transaction error: {}", e)),
-            }
-        },
-        Err(e) => Err(format!("Failed to build PumpFun whale emergency sell instruction: {}", e)),
-    }
-}
-
-/// Execute PumpSwap emergency sell with zeroslot
-async fn execute_pumpswap_emergency_sell_with_zeroslot(
-    trade_info: &transaction_parser::TradeInfoFromToken,
-    sell_config: SwapConfig,
-    app_state: Arc<AppState>,
-    logger: &Logger,
-) -> Result<(), String> {
-    let pump_swap = crate::dex::pump_swap::PumpSwap::new(
-        app_state.wallet.clone(),
-        Some(app_state.rpc_client.clone()),
-        Some(app_state.rpc_nonblocking_client.clone()),
-    );
-    
-    match pump_swap.build_swap_from_parsed_data(trade_info, sell_config).await {
-        Ok((keypair, instructions, price)) => {
-            logger.log(format!("🐋 Generated PumpSwap whale emergency sell instruction at price: {}", price));
-            
-            let recent_blockhash = match crate::services::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
-                Some(hash) => hash,
-                None => {
-                    return Err("Failed to get recent blockhash".to_string());
-                }
-            };
-            
-            match crate::core::tx::new_signed_and_send_zeroslot(
-                app_state.zeroslot_rpc_client.clone(),
-                recent_blockhash,
-                &keypair,
-                instructions,
-                logger,
-            ).await {
-                Ok(signatures) => {
-                    if signatures.is_empty() {
-                        return Err("No transaction signature returned".to_string());
-                    }
-                    
-                    let signature = &signatures[0];
-                    logger.log(format!("🐋 ZEROSLOT whale emergency sell transaction sent: {}", signature));
-                    
-                    verify_transaction(&signature.to_string(), app_state.clone(), logger).await
-                        .map_err(|e| format!("Transaction verification error: {}", e))?;
-                    
-                    Ok(())
-                },
-                Err(e) => Err(format!("Zeroslot transaction error: {}", e)),
-            }
-        },
-        Err(e) => Err(format!("Failed to build PumpSwap whale emergency sell instruction: {}", e)),
-    }
-}
-
-/// Execute emergency sell using Jupiter API as fallback
-async fn execute_jupiter_emergency_sell(
-    token_mint: &str,
-    _amount_to_sell: f64,
-    app_state: Arc<AppState>,
-    logger: &Logger,
-) -> Result<(), String> {
-    logger.log(format!("🪐 Attempting Jupiter API emergency sell for {}", token_mint).cyan().to_string());
-    
-    // Create Jupiter client
-    let jupiter_client = crate::services::jupiter_api::JupiterClient::new(app_state.rpc_nonblocking_client.clone());
-    
-    // Get token account address
-    let wallet_pubkey = app_state.wallet.try_pubkey()
-        .map_err(|e| format!("Failed to get wallet pubkey: {}", e))?;
-    
-    let token_pubkey = Pubkey::from_str(token_mint)
-        .map_err(|e| format!("Invalid token mint: {}", e))?;
-    
-    let token_account = get_associated_token_address(&wallet_pubkey, &token_pubkey);
-    
-    // Get actual token balance
-    let token_balance = match app_state.rpc_nonblocking_client.get_token_account(&token_account).await {
-        Ok(Some(account)) => {
-            let amount_value = account.token_amount.amount.parse::<u64>()
-                .map_err(|e| format!("Failed to parse token amount: {}", e))?;
-            amount_value
-        },
-        Ok(None) => {
-            return Err("Token account not found".to_string());
-        },
-        Err(e) => {
-            return Err(format!("Failed to get token balance: {}", e));
-        }
-    };
-    
-    if token_balance == 0 {
-        return Err("No tokens to sell".to_string());
-    }
-    
-    // Execute Jupiter sell with high slippage for emergency
-    match jupiter_client.sell_token_with_jupiter(
-        token_mint,
-        token_balance,
-        2000, // 20% slippage for emergency
-        &app_state.wallet,
-    ).await {
-        Ok(signature) => {
-            logger.log(format!("🪐 Jupiter emergency sell successful: {}", signature).green().to_string());
-            Ok(())
-        },
-        Err(e) => {
-            Err(format!("Jupiter emergency sell failed: {}", e))
-        }
-    }
-}
-
-/// Execute emergency sell with specified method (zeroslot or normal)
-async fn execute_emergency_sell_with_method(
-    trade_info: &transaction_parser::TradeInfoFromToken,
-    sell_config: SwapConfig,
-    app_state: Arc<AppState>,
-    protocol: &SwapProtocol,
-    method: &str,
-    logger: &Logger,
-) -> Result<(), String> {
-    match protocol {
-        SwapProtocol::PumpFun => {
-            if method == "zeroslot" {
-                execute_pumpfun_emergency_sell_with_zeroslot(trade_info, sell_config, app_state, logger).await
-            } else {
-                execute_pumpfun_emergency_sell_with_normal(trade_info, sell_config, app_state, logger).await
-            }
-        },
-        SwapProtocol::PumpSwap => {
-            if method == "zeroslot" {
-                execute_pumpswap_emergency_sell_with_zeroslot(trade_info, sell_config, app_state, logger).await
-            } else {
-                execute_pumpswap_emergency_sell_with_normal(trade_info, sell_config, app_state, logger).await
-            }
-        },
-        SwapProtocol::RaydiumLaunchpad => {
-            if method == "zeroslot" {
-                execute_raydium_emergency_sell_with_zeroslot(trade_info, sell_config, app_state, logger).await
-            } else {
-                execute_raydium_emergency_sell_with_normal(trade_info, sell_config, app_state, logger).await
-            }
-        },
-        SwapProtocol::Auto | SwapProtocol::Unknown => {
-            logger.log("Auto/Unknown protocol, defaulting to PumpFun for emergency sell".yellow().to_string());
-            if method == "zeroslot" {
-                execute_pumpfun_emergency_sell_with_zeroslot(trade_info, sell_config, app_state, logger).await
-            } else {
-                execute_pumpfun_emergency_sell_with_normal(trade_info, sell_config, app_state, logger).await
-            }
-        },
-    }
-}
-
-/// Execute PumpFun emergency sell with normal RPC (fallback)
-async fn execute_pumpfun_emergency_sell_with_normal(
-    trade_info: &transaction_parser::TradeInfoFromToken,
-    sell_config: SwapConfig,
-    app_state: Arc<AppState>,
-    logger: &Logger,
-) -> Result<(), String> {
-    let pump = crate::dex::pump_fun::Pump::new(
-        app_state.rpc_nonblocking_client.clone(),
-        app_state.rpc_client.clone(),
-        app_state.wallet.clone(),
-    );
-    
-    match pump.build_swap_from_parsed_data(trade_info, sell_config).await {
-        Ok((keypair, instructions, price)) => {
-            logger.log(format!("🐋 Generated PumpFun emergency sell (normal RPC) at price: {}", price));
-            
-            let recent_blockhash = match crate::services::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
-                Some(hash) => hash,
-                None => {
-                    return Err("Failed to get recent blockhash".to_string());
-                }
-            };
-            
-            match crate::core::tx::new_signed_and_send_normal(
-                app_state.rpc_nonblocking_client.clone(),
-                recent_blockhash,
-                &keypair,
-                instructions,
-                logger,
-            ).await {
-                Ok(signatures) => {
-                    if signatures.is_empty() {
-                        return Err("No transaction signature returned".to_string());
-                    }
-                    
-                    let signature = &signatures[0];
-                    logger.log(format!("🐋 NORMAL RPC emergency sell transaction sent: {}", signature));
-                    Ok(())
-                },
-                Err(e) => Err(format!("Normal RPC transaction error: {}", e)),
-            }
-        },
-        Err(e) => Err(format!("Failed to build PumpFun emergency sell instruction: {}", e)),
-    }
-}
-
-/// Execute PumpSwap emergency sell with normal RPC (fallback)
-async fn execute_pumpswap_emergency_sell_with_normal(
-    trade_info: &transaction_parser::TradeInfoFromToken,
-    sell_config: SwapConfig,
-    app_state: Arc<AppState>,
-    logger: &Logger,
-) -> Result<(), String> {
-    let pump_swap = crate::dex::pump_swap::PumpSwap::new(
-        app_state.wallet.clone(),
-        Some(app_state.rpc_client.clone()),
-        Some(app_state.rpc_nonblocking_client.clone()),
-    );
-    
-    match pump_swap.build_swap_from_parsed_data(trade_info, sell_config).await {
-        Ok((keypair, instructions, price)) => {
-            logger.log(format!("🐋 Generated PumpSwap emergency sell (normal RPC) at price: {}", price));
-            
-            let recent_blockhash = match crate::services::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
-                Some(hash) => hash,
-                None => {
-                    return Err("Failed to get recent blockhash".to_string());
-                }
-            };
-            
-            match crate::core::tx::new_signed_and_send_normal(
-                app_state.rpc_nonblocking_client.clone(),
-                recent_blockhash,
-                &keypair,
-                instructions,
-                logger,
-            ).await {
-                Ok(signatures) => {
-                    if signatures.is_empty() {
-                        return Err("No transaction signature returned".to_string());
-                    }
-                    
-                    let signature = &signatures[0];
-                    logger.log(format!("🐋 NORMAL RPC emergency sell transaction sent: {}", signature));
-                    Ok(())
-                },
-                Err(e) => Err(format!("Normal RPC transaction error: {}", e)),
-            }
-        },
-        Err(e) => Err(format!("Failed to build PumpSwap emergency sell instruction: {}", e)),
-    }
-}
-
-/// Execute Raydium emergency sell with normal RPC (fallback)
-async fn execute_raydium_emergency_sell_with_normal(
-    trade_info: &transaction_parser::TradeInfoFromToken,
-    sell_config: SwapConfig,
-    app_state: Arc<AppState>,
-    logger: &Logger,
-) -> Result<(), String> {
-    let raydium = crate::dex::raydium_launchpad::Raydium::new(
-        app_state.wallet.clone(),
-        Some(app_state.rpc_client.clone()),
-        Some(app_state.rpc_nonblocking_client.clone()),
-    );
-    
-    match raydium.build_swap_from_parsed_data(trade_info, sell_config).await {
-        Ok((keypair, instructions, price)) => {
-            logger.log(format!("🐋 Generated Raydium emergency sell (normal RPC) at price: {}", price));
-            
-            let recent_blockhash = match crate::services::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
-                Some(hash) => hash,
-                None => {
-                    return Err("Failed to get recent blockhash".to_string());
-                }
-            };
-            
-            match crate::core::tx::new_signed_and_send_normal(
-                app_state.rpc_nonblocking_client.clone(),
-                recent_blockhash,
-                &keypair,
-                instructions,
-                logger,
-            ).await {
-                Ok(signatures) => {
-                    if signatures.is_empty() {
-                        return Err("No transaction signature returned".to_string());
-                    }
-                    
-                    let signature = &signatures[0];
-                    logger.log(format!("🐋 NORMAL RPC emergency sell transaction sent: {}", signature));
-                    Ok(())
-                },
-                Err(e) => Err(format!("Normal RPC transaction error: {}", e)),
-            }
-        },
-        Err(e) => Err(format!("Failed to build Raydium emergency sell instruction: {}", e)),
-    }
-}
-
-/// Enhanced sell execution with comprehensive selling logic
-pub async fn execute_enhanced_sell(
-    token_mint: String,
-    app_state: Arc<AppState>,
-    swap_config: Arc<SwapConfig>,
-) -> Result<(), String> {
-    let logger = Logger::new("[ENHANCED-SELL] => ".green().to_string());
-    
-    // Get token info from global tracking
-    let mut token_info = match BOUGHT_TOKEN_LIST.get_mut(&token_mint) {
-        Some(info) => info,
-        None => {
-            return Err(format!("Token {} not found in tracking list", token_mint));
-        }
-    };
-    
-    // Get selling action based on comprehensive rules
-    let selling_action = token_info.get_selling_action();
-    
-    // Debug logging for tokens with invalid entry price
-    if token_info.entry_price == 0 {
-        logger.log(format!("WARNING: Token {} has entry_price = 0, buy transaction may not be processed yet", token_mint).yellow().to_string());
-    }
-    
-    match selling_action {
-        SellingAction::Hold => {
-            logger.log(format!("Holding token {}", token_mint));
-            return Ok(());
-        },
-        SellingAction::SellAll(reason) => {
-            logger.log(format!("Selling ALL of token {} - Reason: {}", token_mint, reason));
-            execute_sell_all_enhanced(&token_mint, &mut token_info, app_state, swap_config).await
-        }
-    }
-}
-
-/// Execute sell all with zeroslot for maximum speed
-async fn execute_sell_all_enhanced(
-    token_mint: &str,
-    token_info: &mut BoughtTokenInfo,
-    app_state: Arc<AppState>,
-    swap_config: Arc<SwapConfig>,
-) -> Result<(), String> {
-    let logger = Logger::new("[SELL-ALL-ENHANCED] => ".red().to_string());
-    
-    // Get current token balance
-    let wallet_pubkey = app_state.wallet.try_pubkey()
-        .map_err(|e| format!("Failed to get wallet pubkey: {}", e))?;
-    let token_pubkey = Pubkey::from_str(token_mint)
-        .map_err(|e| format!("Invalid token mint: {}", e))?;
-    let ata = get_associated_token_address(&wallet_pubkey, &token_pubkey);
+        .map_err(|e| e.to_string())?;
     
     let token_amount = match app_state.rpc_nonblocking_client.get_token_account(&ata).await {
         Ok(Some(account)) => {
@@ -1785,21 +1026,7 @@ async fn execute_sell_all_enhanced(
     // Create trade info for sell using original trade info
     let trade_info = create_sell_trade_info_from_original(token_mint, token_amount, &token_info.trade_info);
     
-    let result = match token_info.protocol {
-        SwapProtocol::PumpFun => {
-            execute_pumpfun_sell_with_zeroslot(&trade_info, sell_config, app_state.clone(), &logger).await
-        },
-        SwapProtocol::PumpSwap => {
-            execute_pumpswap_sell_with_zeroslot(&trade_info, sell_config, app_state.clone(), &logger).await
-        },
-        SwapProtocol::RaydiumLaunchpad => {
-            execute_raydium_sell_with_zeroslot(&trade_info, sell_config, app_state.clone(), &logger).await
-        },
-        SwapProtocol::Auto | SwapProtocol::Unknown => {
-            logger.log("Auto/Unknown protocol detected, defaulting to PumpFun for sell all".yellow().to_string());
-            execute_pumpfun_sell_with_zeroslot(&trade_info, (*swap_config).clone(), app_state.clone(), &logger).await
-        },
-    };
+    let result = execute_dex_sell(&trade_info, sell_config, app_state.clone(), &token_info.protocol, "zeroslot", &logger).await;
     
     if result.is_ok() {
         // Use comprehensive verification and cleanup
@@ -1832,9 +1059,6 @@ async fn execute_sell_all_enhanced(
     result
 }
 
-/// Execute progressive sell with normal transaction method
-
-
 /// Clean up tracking systems by removing tokens with zero balance
 async fn cleanup_token_tracking(app_state: &Arc<AppState>) {
     let logger = Logger::new("[TRACKING-CLEANUP] => ".blue().to_string());
@@ -1851,7 +1075,9 @@ async fn cleanup_token_tracking(app_state: &Arc<AppState>) {
     for token_mint in tokens_to_check {
         if let Ok(wallet_pubkey) = app_state.wallet.try_pubkey() {
             if let Ok(token_pubkey) = Pubkey::from_str(&token_mint) {
-                let ata = get_associated_token_address(&wallet_pubkey, &token_pubkey);
+                let Ok(ata) = get_wallet_token_address(app_state.rpc_nonblocking_client.clone(), &wallet_pubkey, &token_pubkey).await else {
+                    continue;
+                };
                 
                 match app_state.rpc_nonblocking_client.get_token_account(&ata).await {
                     Ok(account_result) => {
@@ -1875,455 +1101,98 @@ async fn cleanup_token_tracking(app_state: &Arc<AppState>) {
                                 TOKEN_TRACKING.remove(&token_mint);
                                 
                                 // Check if all tokens are sold and stop streaming if needed
-                                check_and_stop_streaming_if_all_sold(&logger).await;
-                            }
-                        }
-                    },
-                    Err(_) => {
-                        // Error getting account, keep in tracking for now
-                    }
-                }
-            }
-        }
-    }
-}
-
-/// Monitor cleanup tasks for token tracking (transaction-driven selling logic handles actual selling)
-async fn start_enhanced_selling_monitor(
-    app_state: Arc<AppState>,
-    _swap_config: Arc<SwapConfig>,
-) {
-    let logger = Logger::new("[ENHANCED-SELLING-MONITOR] => ".cyan().to_string());
-    
-    // Run cleanup every 30 seconds
-    let mut interval = tokio::time::interval(Duration::from_secs(30));
-    let mut cleanup_cycle = 0;
-    
-    loop {
-        interval.tick().await;
-        cleanup_cycle += 1;
-        
-        // Run basic cleanup every cycle (30 seconds)
-        cleanup_token_tracking(&app_state).await;
-        
-        // Run comprehensive cleanup every 4 cycles (2 minutes)
-        if cleanup_cycle >= 4 {
-            periodic_comprehensive_cleanup(app_state.clone(), &logger).await;
-            cleanup_cycle = 0;
-        }
-    }
-}
-
-/// Update token price from TradeInfoFromToken (transaction-driven only)
-/// This function is now only used for updating prices from parsed transaction data
-async fn update_token_price_from_trade_info(
-    token_mint: &str,
-    trade_info: &crate::engine::transaction_parser::TradeInfoFromToken,
-) -> Result<(), String> {
-    if let Some(mut token_info) = BOUGHT_TOKEN_LIST.get_mut(token_mint) {
-        // Use the price from the parsed transaction data directly
-        let current_price = trade_info.price;
-        
-        // Debug logging for price updates
-        println!("DEBUG PRICE UPDATE FROM TRANSACTION: Token {} - Protocol: {:?}, Old: {}, New: {}", 
-            token_mint, token_info.protocol, token_info.current_price, current_price);
-        
-        // Update the price using the enhanced method
-        token_info.update_price(current_price);
-        
-        Ok(())
-    } else {
-        Err(format!("Token {} not found in tracking", token_mint))
-    }
-}
-
-/// Create trade info for selling using original trade info to preserve important fields
-fn create_sell_trade_info_from_original(
-    token_mint: &str,
-    token_amount: f64,
-    original_trade_info: &transaction_parser::TradeInfoFromToken,
-) -> transaction_parser::TradeInfoFromToken {
-    transaction_parser::TradeInfoFromToken {
-        dex_type: original_trade_info.dex_type.clone(),
-        slot: original_trade_info.slot,
-        signature: "enhanced_sell".to_string(),
-        pool_id: original_trade_info.pool_id.clone(),
-        mint: token_mint.to_string(),
-        timestamp: std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs(),
-        is_buy: false,
-        price: original_trade_info.price,
-        is_reverse_when_pump_swap: original_trade_info.is_reverse_when_pump_swap,
-        coin_creator: original_trade_info.coin_creator.clone(), // This is the key field that was missing!
-        sol_change: 0.0,
-        token_change: token_amount,
-        liquidity: original_trade_info.liquidity,
-        virtual_sol_reserves: original_trade_info.virtual_sol_reserves,
-        virtual_token_reserves: original_trade_info.virtual_token_reserves,
-    }
-}
-
-
-
-/// Execute PumpFun sell with zeroslot
-async fn execute_pumpfun_sell_with_zeroslot(
-    trade_info: &transaction_parser::TradeInfoFromToken,
-    sell_config: SwapConfig,
-    app_state: Arc<AppState>,
-    logger: &Logger,
-) -> Result<(), String> {
-    let pump = crate::dex::pump_fun::Pump::new(
-        app_state.rpc_nonblocking_client.clone(),
-        app_state.rpc_client.clone(),
-        app_state.wallet.clone(),
-    );
-    
-    match pump.build_swap_from_parsed_data(trade_info, sell_config).await {
-        Ok((keypair, instructions, price)) => {
-            logger.log(format!("Generated PumpFun sell instruction at price: {}", price));
-            
-            let recent_blockhash = match crate::services::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
-                Some(hash) => hash,
-                None => {
-                    return Err("Failed to get recent blockhash".to_string());
-                }
-            };
-            
-            match crate::core::tx::new_signed_and_send_zeroslot(
-                app_state.zeroslot_rpc_client.clone(),
-                recent_blockhash,
-                &keypair,
-                instructions,
-                logger,
-            ).await {
-                Ok(signatures) => {
-                    if signatures.is_empty() {
-                        return Err("No transaction signature returned".to_string());
-                    }
-                    
-                    let signature = &signatures[0];
-                    logger.log(format!("ZEROSLOT sell transaction sent: {}", signature));
-                    
-                    verify_transaction(&signature.to_string(), app_state.clone(), logger).await
-                        .map_err(|e| format!("Transaction verification error: {}", e))?;
-                    
-                    Ok(())
-                },
-                Err(e) => Err(format!("Transaction error: {}", e)),
-            }
-        },
-        Err(e) => Err(format!("Failed to build PumpFun sell instruction: {}", e)),
-    }
-}
-
-/// Execute PumpSwap sell with zeroslot
-async fn execute_pumpswap_sell_with_zeroslot(
-    trade_info: &transaction_parser::TradeInfoFromToken,
-    sell_config: SwapConfig,
-    app_state: Arc<AppState>,
-    logger: &Logger,
-) -> Result<(), String> {
-    let pump_swap = crate::dex::pump_swap::PumpSwap::new(
-        app_state.wallet.clone(),
-        Some(app_state.rpc_client.clone()),
-        Some(app_state.rpc_nonblocking_client.clone()),
-    );
-    
-    match pump_swap.build_swap_from_parsed_data(trade_info, sell_config).await {
-        Ok((keypair, instructions, price)) => {
-            logger.log(format!("Generated PumpSwap sell instruction at price: {}", price));
-            
-            let recent_blockhash = match crate::services::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
-                Some(hash) => hash,
-                None => {
-                    return Err("Failed to get recent blockhash".to_string());
-                }
-            };
-            
-            match crate::core::tx::new_signed_and_send_zeroslot(
-                app_state.zeroslot_rpc_client.clone(),
-                recent_blockhash,
-                &keypair,
-                instructions,
-                logger,
-            ).await {
-                Ok(signatures) => {
-                    if signatures.is_empty() {
-                        return Err("No transaction signature returned".to_string());
-                    }
-                    
-                    let signature = &signatures[0];
-                    logger.log(format!("🐋 ZEROSLOT whale emergency sell transaction sent: {}", signature));
-                    
-                    verify_transaction(&signature.to_string(), app_state.clone(), logger).await
-                        .map_err(|e| format!("Transaction verification error: {}", e))?;
-                    
-                    Ok(())
-                },
-                Err(e) => Err(format!("Zeroslot transaction error: {}", e)),
-            }
-        },
-        Err(e) => Err(format!("Failed to build PumpSwap whale emergency sell instruction: {}", e)),
-    }
-}
-
-/// Execute Raydium emergency sell with zeroslot
-async fn execute_raydium_emergency_sell_with_zeroslot(
-    trade_info: &transaction_parser::TradeInfoFromToken,
-    sell_config: SwapConfig,
-    app_state: Arc<AppState>,
-    logger: &Logger,
-) -> Result<(), String> {
-    let raydium = crate::dex::raydium_launchpad::Raydium::new(
-        app_state.wallet.clone(),
-        Some(app_state.rpc_client.clone()),
-        Some(app_state.rpc_nonblocking_client.clone()),
-    );
-    
-    match raydium.build_swap_from_parsed_data(trade_info, sell_config).await {
-        Ok((keypair, instructions, price)) => {
-            logger.log(format!("🐋 Generated Raydium whale emergency sell instruction at price: {}", price));
-            
-            let recent_blockhash = match crate::services::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
-                Some(hash) => hash,
-                None => {
-                    return Err("Failed to get recent blockhash".to_string());
-                }
-            };
-            
-            match crate::core::tx::new_signed_and_send_zeroslot(
-                app_state.zeroslot_rpc_client.clone(),
-                recent_blockhash,
-                &keypair,
-                instructions,
-                logger,
-            ).await {
-                Ok(signatures) => {
-                    if signatures.is_empty() {
-                        return Err("No transaction signature returned".to_string());
-                    }
-                    
-                    let signature = &signatures[0];
-                    logger.log(format!("🐋 ZEROSLOT Raydium whale emergency sell transaction sent: {}", signature));
-                    
-                    verify_transaction(&signature.to_string(), app_state.clone(), logger).await
-                        .map_err(|e| format!("Transaction verification error: {}", e))?;
-                    
-                    Ok(())
-                },
-                Err(e) => Err(format!("Zeroslot transaction error: {}", e)),
-            }
-        },
-        Err(e) => Err(format!("Failed to build Raydium whale emergency sell instruction: {}", e)),
-    }
-}
-
-/// Execute PumpFun sell with normal transaction method
-async fn execute_pumpfun_sell_with_normal(
-    trade_info: &transaction_parser::TradeInfoFromToken,
-    sell_config: SwapConfig,
-    app_state: Arc<AppState>,
-    logger: &Logger,
-) -> Result<(), String> {
-    let pump = crate::dex::pump_fun::Pump::new(
-        app_state.rpc_nonblocking_client.clone(),
-        app_state.rpc_client.clone(),
-        app_state.wallet.clone(),
-    );
-    
-    match pump.build_swap_from_parsed_data(trade_info, sell_config).await {
-        Ok((keypair, instructions, price)) => {
-            logger.log(format!("Generated PumpFun sell instruction at price: {}", price));
-            
-            let recent_blockhash = match crate::services::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
-                Some(hash) => hash,
-                None => {
-                    return Err("Failed to get recent blockhash".to_string());
-                }
-            };
-            
-            match crate::core::tx::new_signed_and_send_normal(
-                app_state.rpc_nonblocking_client.clone(),
-                recent_blockhash,
-                &keypair,
-                instructions,
-                logger,
-            ).await {
-                Ok(signatures) => {
-                    if signatures.is_empty() {
-                        return Err("No transaction signature returned".to_string());
-                    }
-                    
-                    let signature = &signatures[0];
-                    logger.log(format!("NORMAL sell transaction sent: {}", signature));
-                    
-                    verify_transaction(&signature.to_string(), app_state.clone(), logger).await
-                        .map_err(|e| format!("Transaction verification error: {}", e))?;
-                    
-                    Ok(())
-                },
-                Err(e) => Err(format!("Transaction error: {}", e)),
+                                check_and_stop_streaming_if_all_sold(&logger).await;
+                            }
+                        }
+                    },
+                    Err(_) => {
+                        // Error getting account, keep in tracking for now
+                    }
+                }
             }
-        },
-        Err(e) => Err(format!("Failed to build PumpFun sell instruction: {}", e)),
+        }
     }
 }
 
-/// Execute PumpSwap sell with normal transaction method
-async fn execute_pumpswap_sell_with_normal(
-    trade_info: &transaction_parser::TradeInfoFromToken,
-    sell_config: SwapConfig,
+/// Monitor cleanup tasks for token tracking (transaction-driven selling logic handles actual selling)
+async fn start_enhanced_selling_monitor(
     app_state: Arc<AppState>,
-    logger: &Logger,
-) -> Result<(), String> {
-    let pump_swap = crate::dex::pump_swap::PumpSwap::new(
-        app_state.wallet.clone(),
-        Some(app_state.rpc_client.clone()),
-        Some(app_state.rpc_nonblocking_client.clone()),
-    );
+    _swap_config: Arc<SwapConfig>,
+) {
+    let logger = Logger::new("[ENHANCED-SELLING-MONITOR] => ".cyan().to_string());
     
-    match pump_swap.build_swap_from_parsed_data(trade_info, sell_config).await {
-        Ok((keypair, instructions, price)) => {
-            logger.log(format!("Generated PumpSwap sell instruction at price: {}", price));
-            
-            let recent_blockhash = match crate::services::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
-                Some(hash) => hash,
-                None => {
-                    return Err("Failed to get recent blockhash".to_string());
-                }
-            };
-            
-            match crate::core::tx::new_signed_and_send_normal(
-                app_state.rpc_nonblocking_client.clone(),
-                recent_blockhash,
-                &keypair,
-                instructions,
-                logger,
-            ).await {
-                Ok(signatures) => {
-                    if signatures.is_empty() {
-                        return Err("No transaction signature returned".to_string());
-                    }
-                    
-                    let signature = &signatures[0];
-                    logger.log(format!("NORMAL sell transaction sent: {}", signature));
-                    
-                    verify_transaction(&signature.to_string(), app_state.clone(), logger).await
-                        .map_err(|e| format!("Transaction verification error: {}", e))?;
-                    
-                    Ok(())
-                },
-                Err(e) => Err(format!("Transaction error: {}", e)),
-            }
-        },
-        Err(e) => Err(format!("Failed to build PumpSwap sell instruction: {}", e)),
+    // Run cleanup every 30 seconds
+    let mut interval = tokio::time::interval(Duration::from_secs(30));
+    let mut cleanup_cycle = 0;
+    
+    loop {
+        interval.tick().await;
+        cleanup_cycle += 1;
+        
+        // Run basic cleanup every cycle (30 seconds)
+        cleanup_token_tracking(&app_state).await;
+        
+        // Run comprehensive cleanup every 4 cycles (2 minutes)
+        if cleanup_cycle >= 4 {
+            periodic_comprehensive_cleanup(app_state.clone(), &logger).await;
+            cleanup_cycle = 0;
+        }
     }
 }
 
-/// Execute Raydium sell with zeroslot
-async fn execute_raydium_sell_with_zeroslot(
-    trade_info: &transaction_parser::TradeInfoFromToken,
-    sell_config: SwapConfig,
-    app_state: Arc<AppState>,
-    logger: &Logger,
+/// Update token price from TradeInfoFromToken (transaction-driven only)
+/// This function is now only used for updating prices from parsed transaction data
+async fn update_token_price_from_trade_info(
+    token_mint: &str,
+    trade_info: &crate::engine::transaction_parser::TradeInfoFromToken,
 ) -> Result<(), String> {
-    let raydium = crate::dex::raydium_launchpad::Raydium::new(
-        app_state.wallet.clone(),
-        Some(app_state.rpc_client.clone()),
-        Some(app_state.rpc_nonblocking_client.clone()),
-    );
-    
-    match raydium.build_swap_from_parsed_data(trade_info, sell_config).await {
-        Ok((keypair, instructions, price)) => {
-            logger.log(format!("Generated Raydium sell instruction at price: {}", price));
-            
-            let recent_blockhash = match crate::services::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
-                Some(hash) => hash,
-                None => {
-                    return Err("Failed to get recent blockhash".to_string());
-                }
-            };
-            
-            match crate::core::tx::new_signed_and_send_zeroslot(
-                app_state.zeroslot_rpc_client.clone(),
-                recent_blockhash,
-                &keypair,
-                instructions,
-                logger,
-            ).await {
-                Ok(signatures) => {
-                    if signatures.is_empty() {
-                        return Err("No transaction signature returned".to_string());
-                    }
-                    
-                    let signature = &signatures[0];
-                    logger.log(format!("ZEROSLOT Raydium sell transaction sent: {}", signature));
-                    
-                    verify_transaction(&signature.to_string(), app_state.clone(), logger).await
-                        .map_err(|e| format!("Transaction verification error: {}", e))?;
-                    
-                    Ok(())
-                },
-                Err(e) => Err(format!("Transaction error: {}", e)),
-            }
-        },
-        Err(e) => Err(format!("Failed to build Raydium sell instruction: {}", e)),
+    if let Some(mut token_info) = BOUGHT_TOKEN_LIST.get_mut(token_mint) {
+        // Use the price from the parsed transaction data directly
+        let current_price = trade_info.price;
+        
+        // Debug logging for price updates
+        println!("DEBUG PRICE UPDATE FROM TRANSACTION: Token {} - Protocol: {:?}, Old: {}, New: {}", 
+            token_mint, token_info.protocol, token_info.current_price, current_price);
+        
+        // Update the price using the enhanced method
+        token_info.update_price(current_price);
+        
+        Ok(())
+    } else {
+        Err(format!("Token {} not found in tracking", token_mint))
     }
 }
 
-/// Execute Raydium sell with normal transaction method
-async fn execute_raydium_sell_with_normal(
-    trade_info: &transaction_parser::TradeInfoFromToken,
-    sell_config: SwapConfig,
-    app_state: Arc<AppState>,
-    logger: &Logger,
-) -> Result<(), String> {
-    let raydium = crate::dex::raydium_launchpad::Raydium::new(
-        app_state.wallet.clone(),
-        Some(app_state.rpc_client.clone()),
-        Some(app_state.rpc_nonblocking_client.clone()),
-    );
-    
-    match raydium.build_swap_from_parsed_data(trade_info, sell_config).await {
-        Ok((keypair, instructions, price)) => {
-            logger.log(format!("Generated Raydium sell instruction at price: {}", price));
-            
-            let recent_blockhash = match crate::services::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
-                Some(hash) => hash,
-                None => {
-                    return Err("Failed to get recent blockhash".to_string());
-                }
-            };
-            
-            match crate::core::tx::new_signed_and_send_normal(
-                app_state.rpc_nonblocking_client.clone(),
-                recent_blockhash,
-                &keypair,
-                instructions,
-                logger,
-            ).await {
-                Ok(signatures) => {
-                    if signatures.is_empty() {
-                        return Err("No transaction signature returned".to_string());
-                    }
-                    
-                    let signature = &signatures[0];
-                    logger.log(format!("NORMAL Raydium sell transaction sent: {}", signature));
-                    
-                    verify_transaction(&signature.to_string(), app_state.clone(), logger).await
-                        .map_err(|e| format!("Transaction verification error: {}", e))?;
-                    
-                    Ok(())
-                },
-                Err(e) => Err(format!("Transaction error: {}", e)),
-            }
-        },
-        Err(e) => Err(format!("Failed to build Raydium sell instruction: {}", e)),
+/// Create trade info for selling using original trade info to preserve important fields
+fn create_sell_trade_info_from_original(
+    token_mint: &str,
+    token_amount: f64,
+    original_trade_info: &transaction_parser::TradeInfoFromToken,
+) -> transaction_parser::TradeInfoFromToken {
+    transaction_parser::TradeInfoFromToken {
+        dex_type: original_trade_info.dex_type.clone(),
+        slot: original_trade_info.slot,
+        signature: "enhanced_sell".to_string(),
+        pool_id: original_trade_info.pool_id.clone(),
+        mint: token_mint.to_string(),
+        timestamp: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        is_buy: false,
+        price: original_trade_info.price,
+        coin_creator: original_trade_info.coin_creator.clone(), // This is the key field that was missing!
+        sol_change: 0.0,
+        token_change: token_amount,
+        liquidity: original_trade_info.liquidity,
+        virtual_sol_reserves: original_trade_info.virtual_sol_reserves,
+        virtual_token_reserves: original_trade_info.virtual_token_reserves,
+        ..original_trade_info.clone()
     }
 }
 
+
+
 /// Execute sell operation for a token
 pub async fn execute_sell(
     token_mint: String,
@@ -2342,31 +1211,20 @@ pub async fn execute_sell(
     // Protocol string for notifications
     let _protocol_str = match protocol {
         SwapProtocol::RaydiumLaunchpad => "RaydiumLaunchpad",
+        SwapProtocol::PumpFun => "PumpFun",
+        SwapProtocol::RaydiumCpmm => "RaydiumCpmm",
+        SwapProtocol::PumpSwap => "PumpSwap",
         _ => "Unknown",
     };
     
     // Create a minimal trade info for notification using the new structure
     let notification_trade_info = transaction_parser::TradeInfoFromToken {
-        dex_type: match protocol {
-            SwapProtocol::PumpSwap => transaction_parser::DexType::PumpSwap,
-            SwapProtocol::PumpFun => transaction_parser::DexType::PumpFun,
-            SwapProtocol::RaydiumLaunchpad => transaction_parser::DexType::RaydiumLaunchpad,
-            _ => transaction_parser::DexType::Unknown,
+        dex_type: match &protocol {
+            SwapProtocol::Auto | SwapProtocol::Unknown => trade_info.dex_type.clone(),
+            other => transaction_parser::DexType::from(other.clone()),
         },
-        slot: trade_info.slot,
-        signature: trade_info.signature.clone(),
-        pool_id: trade_info.pool_id.clone(),
-        mint: trade_info.mint.clone(),
-        timestamp: trade_info.timestamp,
         is_buy: false, // This is a sell notification
-        price: trade_info.price,
-        is_reverse_when_pump_swap: trade_info.is_reverse_when_pump_swap,
-        coin_creator: trade_info.coin_creator.clone(),
-        sol_change: trade_info.sol_change,
-        token_change: trade_info.token_change,
-        liquidity: trade_info.liquidity,
-        virtual_sol_reserves: trade_info.virtual_sol_reserves,
-        virtual_token_reserves: trade_info.virtual_token_reserves,
+        ..trade_info.clone()
     };
 
     // Create a modified swap config for selling
@@ -2387,7 +1245,10 @@ pub async fn execute_sell(
         Ok(pubkey) => pubkey,
         Err(e) => return Err(format!("Invalid token mint address: {}", e)),
     };
-    let ata = get_associated_token_address(&wallet_pubkey, &token_pubkey);
+    let ata = match get_wallet_token_address(app_state.rpc_nonblocking_client.clone(), &wallet_pubkey, &token_pubkey).await {
+        Ok(ata) => ata,
+        Err(e) => return Err(format!("Failed to resolve token account: {}", e)),
+    };
 
     // Get token account and amount
     let token_amount = match app_state.rpc_nonblocking_client.get_token_account(&ata).await {
@@ -2480,337 +1341,10 @@ pub async fn execute_sell(
             
             // Update trade info for this chunk
             let mut chunk_trade_info = notification_trade_info.clone();
-            chunk_trade_info.token_change = amount_to_sell;
-            
-            // Execute sell based on protocol
-            let result = match protocol {
-                SwapProtocol::PumpFun => {
-                    logger.log("Using PumpFun protocol for sell".to_string());
-                    
-                    // Create the PumpFun instance
-                    let pump = crate::dex::pump_fun::Pump::new(
-                        app_state.rpc_nonblocking_client.clone(),
-                        app_state.rpc_client.clone(),
-                        app_state.wallet.clone(),
-                    );
-                    
-                    // Create a minimal trade info struct for the sell
-                    let trade_info_clone = transaction_parser::TradeInfoFromToken {
-                        dex_type: transaction_parser::DexType::PumpFun,
-                        slot: 0,
-                        signature: "standard_sell".to_string(),
-                        pool_id: String::new(),
-                        mint: token_mint.clone(),
-                        timestamp: std::time::SystemTime::now()
-                            .duration_since(std::time::UNIX_EPOCH)
-                            .unwrap_or_default()
-                            .as_secs(),
-                        is_buy: false,
-                        price: 0,
-                        is_reverse_when_pump_swap: false,
-                        coin_creator: None,
-                        sol_change: 0.0,
-                        token_change: amount_to_sell,
-                        liquidity: 0.0,
-                        virtual_sol_reserves: 0,
-                        virtual_token_reserves: 0,
-                    };
-                    
-                    // Build swap instructions for sell
-                    match pump.build_swap_from_parsed_data(&trade_info_clone, sell_config.clone()).await {
-                        Ok((keypair, instructions, price)) => {
-                            logger.log(format!("Generated PumpFun sell instruction at price: {}", price));
-                            // Execute the transaction
-                            match crate::core::tx::new_signed_and_send_zeroslot(
-                                app_state.zeroslot_rpc_client.clone(),
-                                match crate::services::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
-                                    Some(hash) => hash,
-                                    None => {
-                                        logger.log("Failed to get recent blockhash".red().to_string());
-                                        return Err("Failed to get recent blockhash".to_string());
-                                    }
-                                },
-                                &keypair,
-                                instructions,
-                                &logger,
-                            ).await {
-                                Ok(signatures) => {
-                                    if signatures.is_empty() {
-                                        return Err("No transaction signature returned".to_string());
-                                    }
-                                    
-                                    let signature = &signatures[0];
-                                    logger.log(format!("Sell transaction sent: {}", signature));
-                                    
-                                    // Verify transaction
-                                    match verify_transaction(&signature.to_string(), app_state.clone(), &logger).await {
-                                        Ok(verified) => {
-                                            if verified {
-                                                logger.log("Sell transaction verified successfully".to_string());
-                                                
-                                                Ok(())
-                                            } else {
-                                                Err("Sell transaction verification failed".to_string())
-                                            }
-                                        },
-                                        Err(e) => {
-                                            Err(format!("Transaction verification error: {}", e))
-                                        },
-                                    }
-                                },
-                                Err(e) => {
-                                    Err(format!("Transaction error: {}", e))
-                                },
-                            }
-                        },
-                        Err(e) => {
-                            Err(format!("Failed to build PumpFun sell instruction: {}", e))
-                        },
-                    }
-                },
-                SwapProtocol::PumpSwap => {
-                    logger.log("Using PumpSwap protocol for sell".to_string());
-                    
-                    // Create the PumpSwap instance
-                    let pump_swap = crate::dex::pump_swap::PumpSwap::new(
-                        app_state.wallet.clone(),
-                        Some(app_state.rpc_client.clone()),
-                        Some(app_state.rpc_nonblocking_client.clone()),
-                    );
-                    
-                    // Create a minimal trade info struct for the sell
-                    let trade_info_clone = transaction_parser::TradeInfoFromToken {
-                        dex_type: transaction_parser::DexType::PumpSwap,
-                        slot: trade_info.slot,
-                        signature: "standard_sell".to_string(),
-                        pool_id: trade_info.pool_id.clone(),
-                        mint: token_mint.clone(),
-                        timestamp: trade_info.timestamp,
-                        is_buy: false,
-                        price: trade_info.price,
-                        is_reverse_when_pump_swap: trade_info.is_reverse_when_pump_swap,
-                        coin_creator: trade_info.coin_creator.clone(),
-                        sol_change: trade_info.sol_change,
-                        token_change: amount_to_sell,
-                        liquidity: trade_info.liquidity,
-                        virtual_sol_reserves: trade_info.virtual_sol_reserves,
-                        virtual_token_reserves: trade_info.virtual_token_reserves,
-                    };
-                    
-                    // Build swap instructions for sell - use chunk_sell_config
-                    match pump_swap.build_swap_from_parsed_data(&trade_info_clone, sell_config.clone()).await {
-                        Ok((keypair, instructions, price)) => {
-                            // Get recent blockhash from the processor
-                            let recent_blockhash = match crate::services::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
-                                Some(hash) => hash,
-                                None => {
-                                    logger.log("Failed to get recent blockhash".red().to_string());
-                                    return Err("Failed to get recent blockhash".to_string());
-                                }
-                            };
-                            logger.log(format!("Generated PumpSwap sell instruction at price: {}", price));
-                            logger.log(format!("copy transaction {}", trade_info_clone.signature));
-                            
-                            // Execute the transaction
-                            match crate::core::tx::new_signed_and_send_zeroslot(
-                                app_state.zeroslot_rpc_client.clone(),
-                                recent_blockhash,
-                                &keypair,
-                                instructions,
-                                &logger,
-                            ).await {
-                                Ok(signatures) => {
-                                    if signatures.is_empty() {
-                                        return Err("No transaction signature returned".to_string());
-                                    }
-                                    
-                                    let signature = &signatures[0];
-                                    logger.log(format!("Sell transaction sent: {}", signature));
-                                    
-                                    // Verify transaction
-                                    match verify_transaction(&signature.to_string(), app_state.clone(), &logger).await {
-                                        Ok(verified) => {
-                                            if verified {
-                                                logger.log("Sell transaction verified successfully".to_string());
-                                                
-                                                
-                                                Ok(())
-                                            } else {
-                                                
-                                                Err("Sell transaction verification failed".to_string())
-                                            }
-                                        },
-                                        Err(e) => {
-                                            Err(format!("Transaction verification error: {}", e))
-                                        },
-                                    }
-                                },
-                                Err(e) => {
-                                    Err(format!("Transaction error: {}", e))
-                                },
-                            }
-                        },
-                        Err(e) => {
-                            Err(format!("Failed to build PumpSwap sell instruction: {}", e))
-                        },
-                    }
-                },
-                SwapProtocol::RaydiumLaunchpad => {
-                    logger.log("Using Raydium protocol for sell".to_string());
-                    
-                    let raydium = crate::dex::raydium_launchpad::Raydium::new(
-                        app_state.wallet.clone(),
-                        Some(app_state.rpc_client.clone()),
-                        Some(app_state.rpc_nonblocking_client.clone()),
-                    );
-                    
-                    let trade_info_clone = transaction_parser::TradeInfoFromToken {
-                        dex_type: transaction_parser::DexType::RaydiumLaunchpad,
-                        slot: trade_info.slot,
-                        signature: "standard_sell".to_string(),
-                        pool_id: trade_info.pool_id.clone(),
-                        mint: token_mint.clone(),
-                        timestamp: trade_info.timestamp,
-                        is_buy: false,
-                        price: trade_info.price,
-                        is_reverse_when_pump_swap: trade_info.is_reverse_when_pump_swap,
-                        coin_creator: trade_info.coin_creator.clone(),
-                        sol_change: trade_info.sol_change,
-                        token_change: amount_to_sell,
-                        liquidity: trade_info.liquidity,
-                        virtual_sol_reserves: trade_info.virtual_sol_reserves,
-                        virtual_token_reserves: trade_info.virtual_token_reserves,
-                    };
-                    
-                    match raydium.build_swap_from_parsed_data(&trade_info_clone, sell_config.clone()).await {
-                        Ok((keypair, instructions, price)) => {
-                            let recent_blockhash = match crate::services::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
-                                Some(hash) => hash,
-                                None => {
-                                    logger.log("Failed to get recent blockhash".red().to_string());
-                                    return Err("Failed to get recent blockhash".to_string());
-                                }
-                            };
-                            logger.log(format!("Generated Raydium sell instruction at price: {}", price));
-                            
-                            match crate::core::tx::new_signed_and_send_zeroslot(
-                                app_state.zeroslot_rpc_client.clone(),
-                                recent_blockhash,
-                                &keypair,
-                                instructions,
-                                &logger,
-                            ).await {
-                                Ok(signatures) => {
-                                    if signatures.is_empty() {
-                                        return Err("No transaction signature returned".to_string());
-                                    }
-                                    
-                                    let signature = &signatures[0];
-                                    logger.log(format!("Sell transaction sent: {}", signature));
-                                    
-                                    match verify_transaction(&signature.to_string(), app_state.clone(), &logger).await {
-                                        Ok(verified) => {
-                                            if verified {
-                                                logger.log("Sell transaction verified successfully".to_string());
-                                                Ok(())
-                                            } else {
-                                                Err("Sell transaction verification failed".to_string())
-                                            }
-                                        },
-                                        Err(e) => {
-                                            Err(format!("Transaction verification error: {}", e))
-                                        },
-                                    }
-                                },
-                                Err(e) => {
-                                    Err(format!("Transaction error: {}", e))
-                                },
-                            }
-                        },
-                        Err(e) => {
-                            Err(format!("Failed to build Raydium sell instruction: {}", e))
-                        },
-                    }
-                },
-                SwapProtocol::Auto | SwapProtocol::Unknown => {
-                    logger.log("Auto/Unknown protocol detected, defaulting to PumpFun for sell".yellow().to_string());
-                    
-                    let pump = crate::dex::pump_fun::Pump::new(
-                        app_state.rpc_nonblocking_client.clone(),
-                        app_state.rpc_client.clone(),
-                        app_state.wallet.clone(),
-                    );
-                    
-                    let trade_info_clone = transaction_parser::TradeInfoFromToken {
-                        dex_type: transaction_parser::DexType::PumpFun,
-                        slot: 0,
-                        signature: "standard_sell".to_string(),
-                        pool_id: String::new(),
-                        mint: token_mint.clone(),
-                        timestamp: std::time::SystemTime::now()
-                            .duration_since(std::time::UNIX_EPOCH)
-                            .unwrap_or_default()
-                            .as_secs(),
-                        is_buy: false,
-                        price: 0,
-                        is_reverse_when_pump_swap: false,
-                        coin_creator: None,
-                        sol_change: 0.0,
-                        token_change: amount_to_sell,
-                        liquidity: 0.0,
-                        virtual_sol_reserves: 0,
-                        virtual_token_reserves: 0,
-                    };
-                    
-                    match pump.build_swap_from_parsed_data(&trade_info_clone, sell_config.clone()).await {
-                        Ok((keypair, instructions, price)) => {
-                            logger.log(format!("Generated PumpFun sell instruction at price: {}", price));
-                            match crate::core::tx::new_signed_and_send_zeroslot(
-                                app_state.zeroslot_rpc_client.clone(),
-                                match crate::services::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
-                                    Some(hash) => hash,
-                                    None => {
-                                        logger.log("Failed to get recent blockhash".red().to_string());
-                                        return Err("Failed to get recent blockhash".to_string());
-                                    }
-                                },
-                                &keypair,
-                                instructions,
-                                &logger,
-                            ).await {
-                                Ok(signatures) => {
-                                    if signatures.is_empty() {
-                                        return Err("No transaction signature returned".to_string());
-                                    }
-                                    
-                                    let signature = &signatures[0];
-                                    logger.log(format!("Sell transaction sent: {}", signature));
-                                    
-                                    match verify_transaction(&signature.to_string(), app_state.clone(), &logger).await {
-                                        Ok(verified) => {
-                                            if verified {
-                                                logger.log("Sell transaction verified successfully".to_string());
-                                                Ok(())
-                                            } else {
-                                                Err("Sell transaction verification failed".to_string())
-                                            }
-                                        },
-                                        Err(e) => {
-                                            Err(format!("Transaction verification error: {}", e))
-                                        },
-                                    }
-                                },
-                                Err(e) => {
-                                    Err(format!("Transaction error: {}", e))
-                                },
-                            }
-                        },
-                        Err(e) => {
-                            Err(format!("Failed to build PumpFun sell instruction: {}", e))
-                        },
-                    }
-                },
-            };
+            chunk_trade_info.token_change = amount_to_sell;
+            
+            chunk_trade_info.signature = "standard_sell".to_string();
+            let result = execute_dex_sell(&chunk_trade_info, chunk_sell_config, app_state.clone(), &protocol, "zeroslot", &logger).await;
             
             // If any chunk fails, return the error
             if let Err(e) = result {
@@ -2864,242 +1398,39 @@ pub async fn execute_sell(
         sell_config.in_type = SwapInType::Pct; // Use percentage for sell all operations
         sell_config.amount_in = 1.0; // Sell 100% of tokens
         
-        // Execute based on protocol
-        let result = match protocol {
-            SwapProtocol::PumpFun => {
-                logger.log("Using PumpFun protocol for sell".to_string());
-                
-                // Create the PumpFun instance
-                let pump = crate::dex::pump_fun::Pump::new(
-                    app_state.rpc_nonblocking_client.clone(),
-                    app_state.rpc_client.clone(),
-                    app_state.wallet.clone(),
-                );
-                
-                // Create a minimal trade info struct for the sell
-                let trade_info_clone = transaction_parser::TradeInfoFromToken {
-                    dex_type: transaction_parser::DexType::PumpFun,
-                    slot: 0,
-                    signature: "standard_sell".to_string(),
-                    pool_id: String::new(),
-                    mint: token_mint.clone(),
-                    timestamp: std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .unwrap_or_default()
-                        .as_secs(),
-                    is_buy: false,
-                    price: 0,
-                    is_reverse_when_pump_swap: false,
-                    coin_creator: None,
-                    sol_change: 0.0,
-                    token_change: token_amount,
-                    liquidity: 0.0,
-                    virtual_sol_reserves: 0,
-                    virtual_token_reserves: 0,
-                };
-                
-                // Build swap instructions for sell
-                                // Use the new retry mechanism with Jupiter fallback
-                logger.log("🔄 Using retry mechanism with Jupiter fallback".cyan().to_string());
-                match crate::engine::transaction_retry::execute_sell_with_retry_and_fallback(
-                    &trade_info_clone,
-                    sell_config,
-                    app_state.clone(),
-                    &logger,
-                ).await {
-                    Ok(result) => {
-                        if result.success {
-                            if result.used_jupiter_fallback {
-                                logger.log(format!("✅ PumpFun sell succeeded using Jupiter fallback on attempt {}", result.attempt_count).green().to_string());
-                            } else {
-                                logger.log(format!("✅ PumpFun sell succeeded on attempt {}", result.attempt_count).green().to_string());
-                            }
-                            if let Some(signature) = result.signature {
-                                logger.log(format!("Final transaction signature: {}", signature));
-                            }
-                            Ok(())
-                        } else {
-                            Err(result.error.unwrap_or("Unknown selling error".to_string()))
-                        }
-                    },
-                    Err(e) => {
-                        Err(format!("Retry mechanism failed: {}", e))
-                    }
-                }
-            },
-            SwapProtocol::PumpSwap => {
-                logger.log("Using PumpSwap protocol for sell".to_string());
-                
-                // Create the PumpSwap instance
-                let pump_swap = crate::dex::pump_swap::PumpSwap::new(
-                    app_state.wallet.clone(),
-                    Some(app_state.rpc_client.clone()),
-                    Some(app_state.rpc_nonblocking_client.clone()),
-                );
-                
-                // Create a minimal trade info struct for the sell
-                let trade_info_clone = transaction_parser::TradeInfoFromToken {
-                    dex_type: transaction_parser::DexType::PumpSwap,
-                    slot: 0,
-                    signature: "standard_sell".to_string(),
-                    pool_id: String::new(),
-                    mint: token_mint.clone(),
-                    timestamp: std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .unwrap_or_default()
-                        .as_secs(),
-                    is_buy: false,
-                    price: 0,
-                    is_reverse_when_pump_swap: false,
-                    coin_creator: None,
-                    sol_change: 0.0,
-                                    token_change: token_amount,
-                liquidity: 0.0,
-                virtual_sol_reserves: 0,
-                virtual_token_reserves: 0,
-            };
-                
-                // Use the new retry mechanism with Jupiter fallback
-                logger.log("🔄 Using retry mechanism with Jupiter fallback".cyan().to_string());
-                match crate::engine::transaction_retry::execute_sell_with_retry_and_fallback(
-                    &trade_info_clone,
-                    sell_config,
-                    app_state.clone(),
-                    &logger,
-                ).await {
-                    Ok(result) => {
-                        if result.success {
-                            if result.used_jupiter_fallback {
-                                logger.log(format!("✅ PumpSwap sell succeeded using Jupiter fallback on attempt {}", result.attempt_count).green().to_string());
-                            } else {
-                                logger.log(format!("✅ PumpSwap sell succeeded on attempt {}", result.attempt_count).green().to_string());
-                            }
-                            if let Some(signature) = result.signature {
-                                logger.log(format!("Final transaction signature: {}", signature));
-                            }
-                            Ok(())
-                        } else {
-                            Err(result.error.unwrap_or("Unknown selling error".to_string()))
-                        }
-                    },
-                    Err(e) => {
-                        Err(format!("Retry mechanism failed: {}", e))
-                    }
-                }
-            },
-            SwapProtocol::RaydiumLaunchpad => {
-                logger.log("Using Raydium protocol for sell".to_string());
-                
-                let raydium = crate::dex::raydium_launchpad::Raydium::new(
-                    app_state.wallet.clone(),
-                    Some(app_state.rpc_client.clone()),
-                    Some(app_state.rpc_nonblocking_client.clone()),
-                );
-                
-                let trade_info_clone = transaction_parser::TradeInfoFromToken {
-                    dex_type: transaction_parser::DexType::RaydiumLaunchpad,
-                    slot: 0,
-                    signature: "standard_sell".to_string(),
-                    pool_id: trade_info.pool_id.clone(),
-                    mint: token_mint.clone(),
-                    timestamp: trade_info.timestamp,
-                    is_buy: false,
-                    price: trade_info.price,
-                    is_reverse_when_pump_swap: trade_info.is_reverse_when_pump_swap,
-                    coin_creator: trade_info.coin_creator.clone(),
-                    sol_change: trade_info.sol_change,
-                    token_change: token_amount,
-                    liquidity: trade_info.liquidity,
-                    virtual_sol_reserves: trade_info.virtual_sol_reserves,
-                    virtual_token_reserves: trade_info.virtual_token_reserves,
-                };
-                
-                // Use the new retry mechanism with Jupiter fallback
-                logger.log("🔄 Using retry mechanism with Jupiter fallback".cyan().to_string());
-                match crate::engine::transaction_retry::execute_sell_with_retry_and_fallback(
-                    &trade_info_clone,
-                    sell_config,
-                    app_state.clone(),
-                    &logger,
-                ).await {
-                    Ok(result) => {
-                        if result.success {
-                            if result.used_jupiter_fallback {
-                                logger.log(format!("✅ Raydium sell succeeded using Jupiter fallback on attempt {}", result.attempt_count).green().to_string());
-                            } else {
-                                logger.log(format!("✅ Raydium sell succeeded on attempt {}", result.attempt_count).green().to_string());
-                            }
-                            if let Some(signature) = result.signature {
-                                logger.log(format!("Final transaction signature: {}", signature));
-                            }
-                            Ok(())
-                        } else {
-                            Err(result.error.unwrap_or("Unknown selling error".to_string()))
-                        }
-                    },
-                    Err(e) => {
-                        Err(format!("Retry mechanism failed: {}", e))
+        let sell_trade_info = transaction_parser::TradeInfoFromToken {
+            signature: "standard_sell".to_string(),
+            token_change: token_amount,
+            ..notification_trade_info.clone()
+        };
+        
+        // Use the retry mechanism with Jupiter fallback
+        logger.log("🔄 Using retry mechanism with Jupiter fallback".cyan().to_string());
+        let result = match transaction_retry::execute_sell_with_retry_and_fallback(
+            &sell_trade_info,
+            sell_config,
+            app_state.clone(),
+            &protocol,
+            &logger,
+        ).await {
+            Ok(result) => {
+                if result.success {
+                    if result.used_jupiter_fallback {
+                        logger.log(format!("✅ Sell succeeded using Jupiter fallback on attempt {}", result.attempt_count).green().to_string());
+                    } else {
+                        logger.log(format!("✅ {:?} sell succeeded on attempt {}", protocol, result.attempt_count).green().to_string());
                     }
-                }
-            },
-            SwapProtocol::Auto | SwapProtocol::Unknown => {
-                logger.log("Auto/Unknown protocol detected, defaulting to PumpFun for sell".yellow().to_string());
-                
-                let pump = crate::dex::pump_fun::Pump::new(
-                    app_state.rpc_nonblocking_client.clone(),
-                    app_state.rpc_client.clone(),
-                    app_state.wallet.clone(),
-                );
-                
-                let trade_info_clone = transaction_parser::TradeInfoFromToken {
-                    dex_type: transaction_parser::DexType::PumpFun,
-                    slot: 0,
-                    signature: "standard_sell".to_string(),
-                    pool_id: String::new(),
-                    mint: token_mint.clone(),
-                    timestamp: std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .unwrap_or_default()
-                        .as_secs(),
-                    is_buy: false,
-                    price: 0,
-                    is_reverse_when_pump_swap: false,
-                    coin_creator: None,
-                    sol_change: 0.0,
-                    token_change: token_amount,
-                    liquidity: 0.0,
-                    virtual_sol_reserves: 0,
-                    virtual_token_reserves: 0,
-                };
-                
-                // Use the new retry mechanism with Jupiter fallback
-                logger.log("🔄 Using retry mechanism with Jupiter fallback".cyan().to_string());
-                match crate::engine::transaction_retry::execute_sell_with_retry_and_fallback(
-                    &trade_info_clone,
-                    sell_config,
-                    app_state.clone(),
-                    &logger,
-                ).await {
-                    Ok(result) => {
-                        if result.success {
-                            if result.used_jupiter_fallback {
-                                logger.log(format!("✅ Auto/Unknown sell succeeded using Jupiter fallback on attempt {}", result.attempt_count).green().to_string());
-                            } else {
-                                logger.log(format!("✅ Auto/Unknown sell succeeded on attempt {}", result.attempt_count).green().to_string());
-                            }
-                            if let Some(signature) = result.signature {
-                                logger.log(format!("Final transaction signature: {}", signature));
-                            }
-                            Ok(())
-                        } else {
-                            Err(result.error.unwrap_or("Unknown selling error".to_string()))
-                        }
-                    },
-                    Err(e) => {
-                        Err(format!("Retry mechanism failed: {}", e))
+                    if let Some(signature) = result.signature {
+                        logger.log(format!("Final transaction signature: {}", signature));
                     }
+                    Ok(())
+                } else {
+                    Err(result.error.unwrap_or("Unknown selling error".to_string()))
                 }
             },
+            Err(e) => {
+                Err(format!("Retry mechanism failed: {}", e))
+            }
         };
         
         // Log execution time for standard sell
@@ -3115,7 +1446,7 @@ pub async fn execute_sell(
             };
             logger.log(format!("Total sold: {}", sold_count));
             
-            let bought_count = BOUGHT_TOKENS.get(&()).map(|r| *r).unwrap_or(0);
+            let _bought_count = BOUGHT_TOKENS.get(&()).map(|r| *r).unwrap_or(0);
             let _active_tokens: Vec<String> = TOKEN_TRACKING.iter().map(|entry| entry.key().clone()).collect();
             
             // Note: Keeping token account in WALLET_TOKEN_ACCOUNTS for potential future use
@@ -3175,34 +1506,23 @@ async fn process_message_for_target_monitoring(
             None
         };
         
-        let inner_instructions = match &txn.transaction {
-            Some(txn_info) => match &txn_info.meta {
-                Some(meta) => meta.inner_instructions.clone(),
-                None => vec![],
-            },
-            None => vec![],
-        };
-
-        if !inner_instructions.is_empty() {
-            let cpi_log_data = inner_instructions
-                .iter()
-                .flat_map(|inner| &inner.instructions)
-                .find(|ix| ix.data.len() == 368 || ix.data.len() == 266 || ix.data.len() == 270  || ix.data.len() == 146 || ix.data.len() == 170 || ix.data.len() == 138 )
-                .map(|ix| ix.data.clone());
-
-            if let Some(data) = cpi_log_data {
-                let config = config.clone();
-                let logger = logger.clone();
-                let txn = txn.clone();
-                tokio::spawn(async move {
-                    if let Some(parsed_data) = crate::engine::transaction_parser::parse_transaction_data(&txn, &data) {
-                        if parsed_data.mint != "So11111111111111111111111111111111111111112" {
-                            // SNIPER BOT: Handle target wallet transactions differently
-                            let _ = handle_sniper_bot_logic(parsed_data, config, target_signature, &txn, &logger).await;
-                        }
-                    }
-                });
-            }
+        let trades: Vec<_> = transaction_parser::process_transaction(txn)
+            .into_iter()
+            .inspect(record_trade_pools)
+            .filter(|parsed_data| parsed_data.mint != "So11111111111111111111111111111111111111112")
+            .map(launchpad_curve::reprice_trade)
+            .collect();
+        if !trades.is_empty() {
+            let config = config.clone();
+            let logger = logger.clone();
+            let txn = txn.clone();
+            tokio::spawn(async move {
+                // SNIPER BOT: Handle target wallet transactions differently
+                // Trades are handled in instruction order so a buy and sell in one tx stay ordered
+                for parsed_data in trades {
+                    let _ = handle_sniper_bot_logic(parsed_data, config.clone(), target_signature, &txn, &logger).await;
+                }
+            });
         }
     }
     
@@ -3224,45 +1544,189 @@ async fn process_message_for_dex_monitoring(
     
     // Handle transaction messages
     if let Some(UpdateOneof::Transaction(txn)) = &msg.update_oneof {
-        let inner_instructions = match &txn.transaction {
-            Some(txn_info) => match &txn_info.meta {
-                Some(meta) => meta.inner_instructions.clone(),
-                None => vec![],
-            },
-            None => vec![],
-        };
-
-        if !inner_instructions.is_empty() {
-            let cpi_log_data = inner_instructions
-                .iter()
-                .flat_map(|inner| &inner.instructions)
-                .find(|ix| ix.data.len() == 368 || ix.data.len() == 266 || ix.data.len() == 270  || ix.data.len() == 146 || ix.data.len() == 170 || ix.data.len() == 138 )
-                .map(|ix| ix.data.clone());
-
-            if let Some(data) = cpi_log_data {
+        // New pool creation: the initialize transaction may also carry the creator's first buy
+        if let Some(launch) = transaction_parser::parse_launch_event(txn) {
+            if let Some(trade_info) = handle_launch_event(launch, &config, logger) {
                 let config = config.clone();
                 let logger = logger.clone();
-                let txn = txn.clone();
                 tokio::spawn(async move {
-                    if let Some(parsed_data) = crate::engine::transaction_parser::parse_transaction_data(&txn, &data) {
-                        if parsed_data.mint != "So11111111111111111111111111111111111111112" {
-                            // Check if this token mint is in our focus token list
-                            if FOCUS_TOKEN_LIST.contains_key(&parsed_data.mint) {
-                                // Process the transaction for this focus token (no extra logs here)
-                                let _ = handle_sniper_bot_logic(parsed_data, config, None, &txn, &logger).await;
-                            }
-                            // No logging for tokens not in focus list
-                        }
+                    let creator = trade_info.user.clone();
+                    if let Err(e) = handle_target_wallet_buy(trade_info, config, &logger, creator).await {
+                        logger.log(format!("Failed to focus on launched token: {}", e).red().to_string());
                     }
                 });
             }
         }
+
+        // Completed curve: stop launchpad trading and re-point open positions
+        if let Some(migration) = transaction_parser::parse_migration_event(txn) {
+            handle_migration_event(&migration, &config, logger);
+        }
+
+        // Check if each token mint is in our focus token list (no logging for other tokens)
+        let trades: Vec<_> = transaction_parser::process_transaction(txn)
+            .into_iter()
+            .inspect(record_trade_pools)
+            .filter(|parsed_data| {
+                parsed_data.mint != "So11111111111111111111111111111111111111112"
+                    && FOCUS_TOKEN_LIST.contains_key(&parsed_data.mint)
+            })
+            .map(|mut parsed_data| {
+                if let Some(launch) = RECENT_LAUNCHES.get(&parsed_data.mint) {
+                    parsed_data.coin_creator = Some(launch.creator.clone());
+                }
+                launchpad_curve::reprice_trade(parsed_data)
+            })
+            .collect();
+        if !trades.is_empty() {
+            let config = config.clone();
+            let logger = logger.clone();
+            let txn = txn.clone();
+            tokio::spawn(async move {
+                // Process the transaction for each focus token trade (no extra logs here)
+                for parsed_data in trades {
+                    let _ = handle_sniper_bot_logic(parsed_data, config.clone(), None, &txn, &logger).await;
+                }
+            });
+        }
     }
     
     Ok(())  
 }
 
 
+/// SNIPER BOT: Record a brand-new launchpad pool so strategies can react to it. Pools created
+/// by a target wallet are returned as the creator's buy, to be put on the focus list.
+fn handle_launch_event(
+    launch: transaction_parser::LaunchEvent,
+    config: &Arc<SniperConfig>,
+    logger: &Logger,
+) -> Option<transaction_parser::TradeInfoFromToken> {
+    let created_by_target = config.target_addresses.iter().any(|target| target == &launch.creator);
+    if created_by_target {
+        logger.log(format!(
+            "🚀 Target wallet {} launched {} ({}) - mint: {}, pool: {}",
+            launch.creator, launch.name, launch.symbol, launch.base_mint, launch.pool_id
+        ).purple().bold().to_string());
+    }
+    let launch_trade = created_by_target.then(|| launch_trade_info(&launch));
+
+    if RECENT_LAUNCHES.len() >= MAX_RECENT_LAUNCHES {
+        let oldest = RECENT_LAUNCHES
+            .iter()
+            .min_by_key(|entry| entry.value().slot)
+            .map(|entry| entry.key().clone());
+        if let Some((_, evicted)) = oldest.and_then(|oldest| RECENT_LAUNCHES.remove(&oldest)) {
+            launchpad_curve::POOL_CURVE_TYPES.remove(&evicted.pool_id);
+        }
+    }
+    POOL_INDEX.record_launch(&launch);
+    launchpad_curve::POOL_CURVE_TYPES.insert(launch.pool_id.clone(), launchpad_curve::CurveType::from_params(&launch.curve));
+    let creator = launch.creator.clone();
+    RECENT_LAUNCHES.insert(launch.base_mint.clone(), launch);
+
+    match launch_trade {
+        Some(Some(trade_info)) => {
+            logger.log(format!(
+                "🎯 Adding {} to focus list at opening price {:.6} lamports/token",
+                trade_info.mint, trade_info.price
+            ).purple().to_string());
+            Some(trade_info)
+        }
+        Some(None) => {
+            // Without a starting price the focus entry's drop detection has nothing to compare to;
+            // the target's first buy on the pool adds it instead
+            logger.log(format!(
+                "⏳ Opening price of {}'s launch is unknown, waiting for its first trade",
+                creator
+            ).purple().to_string());
+            None
+        }
+        None => None,
+    }
+}
+
+/// The launch as a buy by its creator at the pool's opening price, for the focus list.
+/// `None` when the opening price cannot be derived from the curve parameters.
+fn launch_trade_info(launch: &transaction_parser::LaunchEvent) -> Option<transaction_parser::TradeInfoFromToken> {
+    let curve = launchpad_curve::LaunchpadCurve::from_launch(&launch.curve, launch.vesting.total_locked_amount)?;
+    let quote_mint = Pubkey::from_str(&launch.quote_mint).ok()?;
+    // Lamports per whole token, like the parser's price
+    let quote_price = curve.spot_price()? * 10f64.powi(launch.decimals as i32);
+    let price = quote_mint::quote_price_to_lamports(&quote_mint, quote_price)?;
+    Some(transaction_parser::TradeInfoFromToken {
+        dex_type: transaction_parser::DexType::RaydiumLaunchpad,
+        slot: launch.slot,
+        signature: launch.signature.clone(),
+        pool_id: launch.pool_id.clone(),
+        mint: launch.base_mint.clone(),
+        quote_mint: launch.quote_mint.clone(),
+        timestamp: launch.timestamp,
+        is_buy: true,
+        price,
+        coin_creator: Some(launch.creator.clone()),
+        user: launch.creator.clone(),
+        curve_virtual_base: curve.virtual_base,
+        curve_virtual_quote: curve.virtual_quote,
+        total_base_sell: curve.total_base_sell.unwrap_or_default(),
+        ..Default::default()
+    })
+}
+
+/// Tokens the bot holds or has in focus
+fn is_tracked_mint(mint: &str) -> bool {
+    BOUGHT_TOKEN_LIST.contains_key(mint) || FOCUS_TOKEN_LIST.contains_key(mint)
+}
+
+/// Index the pool of a streamed trade. CPMM pools are only remembered for tracked tokens,
+/// others are looked up when a copied trade needs them.
+fn record_trade_pools(parsed_data: &transaction_parser::TradeInfoFromToken) {
+    POOL_INDEX.record_trade(parsed_data);
+    if is_tracked_mint(&parsed_data.mint) {
+        raydium_cpmm::record_trade(parsed_data);
+    }
+}
+
+/// SNIPER BOT: Drop a migrated token from launchpad focus and hand open positions to the selling engine
+fn handle_migration_event(
+    migration: &transaction_parser::MigrationEvent,
+    config: &Arc<SniperConfig>,
+    logger: &Logger,
+) {
+    let mint = &migration.mint;
+    // Later sells and copied trades find the new pool without a program account scan
+    if migration.venue == transaction_parser::MigrationVenue::RaydiumCpmm && is_tracked_mint(mint) {
+        if let Some(pool_id) = migration.new_pool_id.as_deref().and_then(|pool_id| Pubkey::from_str(pool_id).ok()) {
+            raydium_cpmm::record_pool(mint, &pool_id);
+        }
+    }
+    if FOCUS_TOKEN_LIST.remove(mint).is_some() {
+        logger.log(format!(
+            "🔀 Focus token {} migrated to {:?}, removed from focus list",
+            mint, migration.venue
+        ).yellow().to_string());
+    }
+    if let Some((_removed_key, cancel_token)) = PRICE_MONITORING_TASKS.remove(mint) {
+        cancel_token.cancel();
+    }
+
+    if BOUGHT_TOKEN_LIST.contains_key(mint)
+        || crate::engine::selling_strategy::TOKEN_METRICS.contains_key(mint)
+    {
+        let selling_engine = crate::engine::selling_strategy::SellingEngine::new(
+            config.app_state.clone().into(),
+            Arc::new(config.swap_config.clone()),
+            crate::engine::selling_strategy::SellingConfig::set_from_env(),
+        );
+        selling_engine.handle_migration(migration);
+    } else {
+        // Still record it so later sell attempts skip the completed curve
+        crate::engine::selling_strategy::MIGRATED_TOKENS
+            .entry(mint.clone())
+            .or_insert_with(|| migration.clone());
+    }
+}
+
 /// SNIPER BOT: Main logic for handling both target wallet and DEX monitoring transactions
 async fn handle_sniper_bot_logic(
     parsed_data: transaction_parser::TradeInfoFromToken,
@@ -3271,12 +1735,12 @@ async fn handle_sniper_bot_logic(
     txn: &SubscribeUpdateTransaction,
     logger: &Logger,
 ) -> Result<(), String> {
-    let instruction_type = parsed_data.dex_type.clone();
+    let _instruction_type = parsed_data.dex_type.clone();
     
     // Extract signer from transaction to identify target wallet
-    if let Some(ref target_signature) = target_signature {
+    if let Some(_target_signature) = target_signature {
         // Extract the actual signer from the transaction
-        if let Some(signer) = extract_signer_from_transaction(txn) {
+        if let Some(signer) = extract_trader(&parsed_data, txn) {
             // Check if this transaction is from one of our target wallets
             if config.target_addresses.iter().any(|target| target == &signer) {
                 logger.log(format!(
@@ -3312,12 +1776,8 @@ async fn handle_target_wallet_buy(
     let mint = parsed_data.mint.clone();
     
     // Determine protocol based on instruction type
-    let protocol = match parsed_data.dex_type {
-        transaction_parser::DexType::PumpSwap => SwapProtocol::PumpSwap,
-        transaction_parser::DexType::PumpFun => SwapProtocol::PumpFun,
-        transaction_parser::DexType::RaydiumLaunchpad => SwapProtocol::RaydiumLaunchpad,
-        _ => config.protocol_preference.clone(),
-    };
+    let protocol = SwapProtocol::from_dex_type(&parsed_data.dex_type)
+        .unwrap_or_else(|| config.protocol_preference.clone());
     
     // Check if token already exists in focus list
     if let Some(mut focus_info) = FOCUS_TOKEN_LIST.get_mut(&mint) {
@@ -3330,10 +1790,10 @@ async fn handle_target_wallet_buy(
         
         let focus_info = FocusTokenInfo {
             mint: mint.clone(),
-            initial_price: parsed_data.price as f64,
-            current_price: parsed_data.price as f64,
-            lowest_price: parsed_data.price as f64,
-            highest_price: parsed_data.price as f64,
+            initial_price: parsed_data.price,
+            current_price: parsed_data.price,
+            lowest_price: parsed_data.price,
+            highest_price: parsed_data.price,
             price_dropped: false,
             buy_count: 0,
             sell_count: 0,
@@ -3358,7 +1818,7 @@ async fn handle_target_wallet_buy(
 }
 
 /// SNIPER BOT: Check and increment trade count, remove token if limit reached
-fn check_and_increment_trade_count(mint: &str, logger: &Logger) -> bool {
+fn check_and_increment_trade_count(mint: &str, _logger: &Logger) -> bool {
     if let Some(mut focus_info) = FOCUS_TOKEN_LIST.get_mut(mint) {
         focus_info.total_trades += 1;
         
@@ -3404,8 +1864,8 @@ async fn handle_target_wallet_sell(
             );
             drop(config);
             
-            match selling_engine.unified_emergency_sell(&mint_clone, false, None, None).await {
-                Ok(signature) => {
+            match selling_engine.unified_emergency_sell(&mint_clone, "TARGET_SELL", false, None, None).await {
+                Ok(_signature) => {
                     // Update focus token sell count and check trade limit
                     if let Some(mut focus_info) = FOCUS_TOKEN_LIST.get_mut(&mint_clone) {
                         focus_info.sell_count += 1;
@@ -3458,7 +1918,7 @@ async fn handle_volume_based_buying(
     // Check if token is in focus list
     if let Some(mut focus_info) = FOCUS_TOKEN_LIST.get_mut(&mint) {
         // Update price information
-        let current_price = parsed_data.price as f64;
+        let current_price = parsed_data.price;
         focus_info.current_price = current_price;
         focus_info.last_price_update = Instant::now();
         // Maintain (slot, price) history for slot-aware drop detection
@@ -3526,8 +1986,8 @@ async fn start_price_monitoring(
     
     // Spawn monitoring task
     let mint_clone = mint.clone();
-    let config_clone = config.clone();
-    let logger_clone = logger.clone();
+    let _config_clone = config.clone();
+    let _logger_clone = logger.clone();
     
     tokio::spawn(async move {
         let mut interval = tokio::time::interval(Duration::from_secs(5)); // Check every 5 seconds
@@ -3664,9 +2124,9 @@ async fn handle_parsed_data_for_selling(
     let mint = parsed_data.mint.clone();
     
     // TARGET WALLET SELL DETECTION - Check if this sell is from one of our target wallets
-    if let Some(ref target_signature) = target_signature {
+    if let Some(_target_signature) = target_signature {
         // Extract signer from the target signature - this represents the target wallet that made the transaction
-        if let Some(signer) = extract_signer_from_transaction(&txn) {
+        if let Some(signer) = extract_trader(&parsed_data, txn) {
             // Check if the signer is in our target wallet list
             if config.target_addresses.iter().any(|target| target == &signer) {
                 logger.log(format!(
@@ -3675,7 +2135,7 @@ async fn handle_parsed_data_for_selling(
                 ).purple().bold().to_string());
                 
                 // Check if we own this token
-                if let Some(mut token_info) = BOUGHT_TOKEN_LIST.get_mut(&parsed_data.mint) {
+                if let Some(_token_info) = BOUGHT_TOKEN_LIST.get_mut(&parsed_data.mint) {
                     logger.log(format!(
                         "🚨 We own token {} that target wallet is selling - executing IMMEDIATE COPY SELL",
                         parsed_data.mint
@@ -3698,7 +2158,7 @@ async fn handle_parsed_data_for_selling(
                         drop(config);
                         
                         // Use Copy Target Selling mode (higher priority than regular emergency sell)
-                        let result = selling_engine.unified_emergency_sell(&mint_clone, false, None, None).await;
+                        let result = selling_engine.unified_emergency_sell(&mint_clone, "COPY_TARGET_SELL", false, None, None).await;
                         
                         match result {
                             Ok(signature) => {
@@ -3738,7 +2198,7 @@ async fn handle_parsed_data_for_selling(
             ).red().bold().to_string());
             
             // Check if we own this token
-            if let Some(mut token_info) = BOUGHT_TOKEN_LIST.get_mut(&parsed_data.mint) {
+            if let Some(_token_info) = BOUGHT_TOKEN_LIST.get_mut(&parsed_data.mint) {
                 logger.log(format!(
                     "🚨 We own token {} that whale is selling - executing EMERGENCY SELL via zeroslot",
                     parsed_data.mint
@@ -3762,7 +2222,7 @@ async fn handle_parsed_data_for_selling(
                     drop(config);
                     
                     // Use Whale Emergency Sell mode for faster execution
-                    let result = selling_engine.unified_emergency_sell(&mint_clone, true, None, None).await;
+                    let result = selling_engine.unified_emergency_sell(&mint_clone, "WHALE_SELL", true, None, None).await;
                     
                     match result {
                         Ok(signature) => {
@@ -3789,14 +2249,9 @@ async fn handle_parsed_data_for_selling(
     
     // Log the parsed transaction data
     logger.log(format!(
-        "Token transaction detected for {}: Instruction: {}, Is buy: {}",
+        "Token transaction detected for {}: Instruction: {:?}, Is buy: {}",
         mint,
-        match instruction_type {
-            transaction_parser::DexType::PumpSwap => "PumpSwap",
-            transaction_parser::DexType::PumpFun => "PumpFun",
-            transaction_parser::DexType::RaydiumLaunchpad => "RaydiumLaunchpad",
-            _ => "Unknown",
-        },
+        instruction_type,
         parsed_data.is_buy
     ).green().to_string());
     
@@ -3821,16 +2276,13 @@ async fn handle_parsed_data_for_selling(
     
     // Check if we should sell this token
     match selling_engine.evaluate_sell_conditions(&mint).await {
-        Ok((should_sell, use_whale_emergency)) => {
-            if should_sell {
-                logger.log(format!("Sell conditions met for token: {}", mint).green().to_string());
+        Ok((sell_reason, use_whale_emergency)) => {
+            if let Some(reason) = sell_reason {
+                logger.log(format!("Sell conditions met for token: {} ({})", mint, reason).green().to_string());
                 
                 // Determine protocol to use for selling
-                let protocol = match instruction_type {
-                    transaction_parser::DexType::PumpSwap => SwapProtocol::PumpSwap,
-                    transaction_parser::DexType::PumpFun => SwapProtocol::PumpFun,
-                    _ => config.protocol_preference.clone(),
-                };
+                let protocol = SwapProtocol::from_dex_type(&instruction_type)
+                    .unwrap_or_else(|| config.protocol_preference.clone());
                 
                 if use_whale_emergency {
                     // Whale emergency sell using zeroslot for maximum speed
@@ -3838,11 +2290,11 @@ async fn handle_parsed_data_for_selling(
                     
                     // Get the current PNL to determine whale threshold
                     if let Some(metrics) = crate::engine::selling_strategy::TOKEN_METRICS.get(&mint) {
-                        let pnl = if metrics.entry_price > 0.0 {
-                            (metrics.current_price - metrics.entry_price) / metrics.entry_price * 100.0
-                        } else {
-                            0.0
-                        };
+                        let pnl = crate::engine::selling_strategy::net_pnl_percentage(
+                            metrics.entry_price,
+                            metrics.current_price,
+                            config.app_state.share_fee_fraction(&metrics.protocol),
+                        );
                         
                         if let Some(_whale_threshold) = selling_engine.get_config().dynamic_whale_selling.get_whale_threshold_for_pnl(pnl) {
                             // CRITICAL FIX: Use non-blocking spawned task to prevent bot from getting stuck
@@ -3854,7 +2306,7 @@ async fn handle_parsed_data_for_selling(
                             
                             tokio::spawn(async move {
                                 // Use the existing selling_engine for whale emergency sell
-                                match selling_engine_clone.unified_emergency_sell(&mint_clone, true, Some(&parsed_data_clone), None).await {
+                                match selling_engine_clone.unified_emergency_sell(&mint_clone, reason, true, Some(&parsed_data_clone), None).await {
                                     Ok(signature) => {
                                         logger_clone.log(format!("🐋 Successfully executed whale emergency sell for token: {} with signature: {}", mint_clone, signature).green().bold().to_string());
                                         // Cancel monitoring task for this token since it's been sold
@@ -3867,7 +2319,7 @@ async fn handle_parsed_data_for_selling(
                                         
                                         // Fallback to regular emergency sell
                                         logger_clone.log("Falling back to regular emergency sell".yellow().to_string());
-                                        match selling_engine_clone.unified_emergency_sell(&mint_clone, false, Some(&parsed_data_clone), Some(protocol_clone.clone())).await {
+                                        match selling_engine_clone.unified_emergency_sell(&mint_clone, reason, false, Some(&parsed_data_clone), Some(protocol_clone.clone())).await {
                                             Ok(_) => {
                                                 logger_clone.log(format!("Successfully executed fallback emergency sell for token: {}", mint_clone).green().to_string());
                                                 if let Err(e) = cancel_token_monitoring(&mint_clone, &logger_clone).await {
@@ -3886,10 +2338,10 @@ async fn handle_parsed_data_for_selling(
                             logger.log(format!("🚀 Whale emergency sell task spawned for token: {}, continuing main flow", mint).cyan().to_string());
                         } else {
                             logger.log(format!("🐋 No whale threshold found for PNL {:.2}%, using regular emergency sell", pnl).yellow().to_string());
-                            match selling_engine.unified_emergency_sell(&mint, false, Some(&parsed_data), Some(protocol.clone())).await {
+                            match selling_engine.unified_emergency_sell(&mint, reason, false, Some(&parsed_data), Some(protocol.clone())).await {
                                 Ok(_) => {
                                     logger.log(format!("Successfully executed emergency sell for token: {}", mint).green().to_string());
-                                    if let Err(e) = cancel_token_monitoring(&mint, &logger).await {
+                                    if let Err(e) = cancel_token_monitoring(&mint, logger).await {
                                         logger.log(format!("Failed to cancel monitoring for token {}: {}", mint, e).yellow().to_string());
                                     }
                                 },
@@ -3901,10 +2353,10 @@ async fn handle_parsed_data_for_selling(
                         }
                     } else {
                         logger.log("🐋 No metrics found for whale emergency sell, using regular emergency sell".yellow().to_string());
-                        match selling_engine.unified_emergency_sell(&mint, false, Some(&parsed_data), Some(protocol.clone())).await {
+                        match selling_engine.unified_emergency_sell(&mint, reason, false, Some(&parsed_data), Some(protocol.clone())).await {
                             Ok(_) => {
                                 logger.log(format!("Successfully executed emergency sell for token: {}", mint).green().to_string());
-                                if let Err(e) = cancel_token_monitoring(&mint, &logger).await {
+                                if let Err(e) = cancel_token_monitoring(&mint, logger).await {
                                     logger.log(format!("Failed to cancel monitoring for token {}: {}", mint, e).yellow().to_string());
                                 }
                             },
@@ -3918,11 +2370,11 @@ async fn handle_parsed_data_for_selling(
                     // Regular emergency sell all tokens immediately to prevent further losses
                     logger.log(format!("EMERGENCY SELL ALL triggered for token: {}", mint).red().bold().to_string());
                     
-                    match selling_engine.unified_emergency_sell(&mint, false, Some(&parsed_data), Some(protocol.clone())).await {
+                    match selling_engine.unified_emergency_sell(&mint, reason, false, Some(&parsed_data), Some(protocol.clone())).await {
                         Ok(_) => {
                             logger.log(format!("Successfully executed emergency sell all for token: {}", mint).green().to_string());
                             // Cancel monitoring task for this token since it's been sold
-                            if let Err(e) = cancel_token_monitoring(&mint, &logger).await {
+                            if let Err(e) = cancel_token_monitoring(&mint, logger).await {
                                 logger.log(format!("Failed to cancel monitoring for token {}: {}", mint, e).yellow().to_string());
                             }
                         },
@@ -4118,7 +2570,7 @@ async fn monitor_token_for_selling(
             msg_result = stream.next() => {
                 match msg_result {
                     Some(Ok(msg)) => {
-                        if let Err(e) = process_selling(&msg, &subscribe_tx, config.clone(), &logger).await {
+                        if let Err(e) = process_selling(&msg, &subscribe_tx, config.clone(), logger).await {
                             logger.log(format!("Error processing message: {}", e).red().to_string());
                         }
                         
@@ -4184,227 +2636,32 @@ async fn process_selling(
             None
         };
         
-        // Extract transaction logs and account keys
-        let inner_instructions = match &txn.transaction {
-            Some(txn_info) => match &txn_info.meta {
-                Some(meta) => meta.inner_instructions.clone(),
-                None => vec![],
-            },
-            None => vec![],
-        };
-
-        if !inner_instructions.is_empty() {
-            // Find the largest data payload in inner instructions
-            let mut largest_data: Option<Vec<u8>> = None;
-            let mut largest_size = 0;
-
-            for inner in &inner_instructions {
-                for ix in &inner.instructions {
-                    if ix.data.len() > largest_size {
-                        largest_size = ix.data.len();
-                        largest_data = Some(ix.data.clone());
-                    }
+        if let Some(migration) = transaction_parser::parse_migration_event(txn) {
+            handle_migration_event(&migration, &config, logger);
+        }
+
+        let trades: Vec<_> = transaction_parser::process_transaction(txn)
+            .into_iter()
+            .inspect(record_trade_pools)
+            .filter(|parsed_data| parsed_data.mint != "So11111111111111111111111111111111111111112")
+            .map(launchpad_curve::reprice_trade)
+            .collect();
+        if !trades.is_empty() {
+            let config = config.clone();
+            let logger = logger.clone();
+            let txn = txn.clone();  // Clone the transaction data
+            let target_signature_clone = target_signature; // Clone the signature
+            tokio::spawn(async move {
+                for parsed_data in trades {
+                    let _ = handle_parsed_data_for_selling(parsed_data, config.clone(), &txn, target_signature_clone, &logger).await;
                 }
-            }
-
-            let cpi_log_data = inner_instructions
-            .iter()
-            .flat_map(|inner| &inner.instructions)
-            .find(|ix| ix.data.len() == 368 || ix.data.len() == 266 || ix.data.len() == 270  || ix.data.len() == 146 || ix.data.len() == 170 || ix.data.len() == 138)
-            .map(|ix| ix.data.clone());
-
-           
-            if let Some(data) = cpi_log_data {
-                let config = config.clone();
-                let logger = logger.clone();
-                let txn = txn.clone();  // Clone the transaction data
-                let target_signature_clone = target_signature; // Clone the signature
-                tokio::spawn(async move {
-                    if let Some(parsed_data) = crate::engine::transaction_parser::parse_transaction_data(&txn, &data) {
-                        if parsed_data.mint != "So11111111111111111111111111111111111111112" {
-                        let _ =  handle_parsed_data_for_selling(parsed_data, config, &txn, target_signature_clone, &logger).await;
-                        }
-                    }
-                });
-            }
-            
+            });
         }
     }
     
     Ok(())
 }
 
-
-async fn handle_parsed_data_for_buying(
-    parsed_data: transaction_parser::TradeInfoFromToken,
-    config: Arc<SniperConfig>,
-    target_signature: Option<Signature>,
-    logger: &Logger,
-) -> Result<(), String> {
-    let start_time = Instant::now();
-    let instruction_type = parsed_data.dex_type.clone();
-    
-    // For sell transactions, check if this is a copy target selling their tokens
-    if !parsed_data.is_buy {
-        // This is a sell transaction from a target wallet
-        // IMMEDIATE COPY SELL - Execute without checking BOUGHT_TOKEN_LIST first for maximum speed
-        logger.log(format!(
-            "👥 Copy target selling token {} - executing IMMEDIATE COPY SELL",
-            parsed_data.mint
-        ).purple().bold().to_string());
-        
-        // Clone values before moving into async block
-        let mint_clone = parsed_data.mint.clone();
-        let logger_clone = logger.clone();
-        let config_clone = config.clone();
-        
-        // Execute copy sell in parallel without waiting
-        tokio::spawn(async move {
-            // Execute emergency sell immediately - parallel execution for speed
-            let config = crate::common::config::Config::get().await;
-            let selling_config = crate::engine::selling_strategy::SellingConfig::set_from_env();
-            let selling_engine = crate::engine::selling_strategy::SellingEngine::new(
-                config_clone.app_state.clone().into(),
-                Arc::new(config.swap_config.clone()),
-                selling_config,
-            );
-            drop(config);
-            
-            // Use Copy Target Selling mode (higher priority than regular emergency sell)
-            let copy_sell_future = selling_engine.unified_emergency_sell(&mint_clone, false, None, None);
-            
-            match copy_sell_future.await {
-                Ok(signature) => {
-                    logger_clone.log(format!("✅ Successfully executed copy sell for token: {} with signature: {}", mint_clone, signature).green().to_string());
-                    
-                    // Update tracking after successful sell - verify we actually owned the token
-                    if let Some(mut token_info) = BOUGHT_TOKEN_LIST.get_mut(&mint_clone) {
-                        token_info.current_amount = 0.0;
-                        BOUGHT_TOKEN_LIST.remove(&mint_clone);
-                        TOKEN_TRACKING.remove(&mint_clone);
-                        
-                        // Check if all tokens are sold and stop streaming if needed
-                        check_and_stop_streaming_if_all_sold(&logger_clone).await;
-                        
-                        // Cancel monitoring for this token since it's been sold
-                        if let Err(e) = cancel_token_monitoring(&mint_clone, &logger_clone).await {
-                            logger_clone.log(format!("Failed to cancel monitoring for token {}: {}", mint_clone, e).yellow().to_string());
-                        }
-                        logger_clone.log(format!("🎯 Copy sell verified - we owned token {} and successfully sold", mint_clone).green().to_string());
-                    } else {
-                        logger_clone.log(format!("⚠️ Copy sell executed but we didn't own token {} - no cleanup needed", mint_clone).yellow().to_string());
-                    }
-                },
-                Err(e) => {
-                    logger_clone.log(format!("❌ Copy sell failed for token {}: {}", mint_clone, e).red().bold().to_string());
-                    // Check if we actually owned this token for debugging
-                    if let Some(token_info) = BOUGHT_TOKEN_LIST.get(&mint_clone) {
-                        logger_clone.log(format!("🔍 We owned token {} - Amount: {}, Protocol: {:?}", mint_clone, token_info.current_amount, token_info.protocol).red().to_string());
-                    } else {
-                        logger_clone.log(format!("🔍 We didn't own token {} - copy sell was unnecessary", mint_clone).blue().to_string());
-                    }
-                }
-            }
-        });
-        
-        // Don't proceed with buying logic for sell transactions
-        return Ok(());
-    }
-
-    // Check active tokens count against counter_limit
-    let active_tokens_count = TOKEN_TRACKING.len();
-    let active_token_list: Vec<String> = TOKEN_TRACKING.iter().map(|entry| entry.key().clone()).collect();
-    
-    if active_tokens_count >= config.counter_limit as usize {
-        logger.log(format!(
-            "Skipping buy for token {} - Active tokens ({}) at counter limit ({})",
-            parsed_data.mint,
-            active_tokens_count,
-            config.counter_limit
-        ).yellow().to_string());
-        
-        // Log details about current active tokens for debugging
-        logger.log(format!(
-            "📊 Current active tokens: [{}]",
-            active_token_list.join(", ")
-        ).cyan().to_string());
-
-        return Ok(());
-    }
-    
-    // Extract transaction data
-    logger.log(format!(
-        "{} transaction detected: SOL change: {}, Token change: {}, Is buy: {}",
-        match instruction_type {
-            transaction_parser::DexType::PumpSwap => "PumpSwap",
-            transaction_parser::DexType::PumpFun => "PumpFun",
-            transaction_parser::DexType::RaydiumLaunchpad => "RaydiumLaunchpad",
-            _ => "Unknown",
-        },
-        parsed_data.sol_change,
-        parsed_data.token_change,
-        parsed_data.is_buy
-    ).green().to_string());
-    
-    // Determine protocol based on instruction type
-    let protocol = if matches!(instruction_type, transaction_parser::DexType::PumpSwap) {
-        SwapProtocol::PumpSwap
-    } else if matches!(instruction_type, transaction_parser::DexType::PumpFun) {
-        SwapProtocol::PumpFun
-    } else if matches!(instruction_type, transaction_parser::DexType::RaydiumLaunchpad) {
-        SwapProtocol::RaydiumLaunchpad
-    } else {
-        // Default to the preferred protocol in config if instruction type is unknown
-        config.protocol_preference.clone()
-    };
-    
-    // Handle buy transaction
-    if parsed_data.is_buy {
-        match execute_buy(
-            parsed_data.clone(),
-            config.app_state.clone().into(),
-            Arc::new(config.swap_config.clone()),
-            protocol.clone(),
-        ).await {
-            Err(e) => {
-                logger.log(format!("Error executing buy: {}", e).red().to_string());
-                
-                Err(e) // Return the error from execute_buy
-            },
-            Ok(_) => {      
-                logger.log(format!("Processing time for buy transaction: {:?}", start_time.elapsed()).blue().to_string());
-                logger.log(format!("copied transaction {}", target_signature.clone().unwrap_or_default()).blue().to_string());
-                logger.log(format!("Now starting to monitor this token to sell at a profit").blue().to_string());
-                
-                // Start selling strategy immediately to reduce latency and avoid missed opportunities
-                
-                 // Setup selling strategy based on take profit and stop loss
-                 match setup_selling_strategy(
-                     parsed_data.mint.clone(), 
-                     config.app_state.clone().into(), 
-                     Arc::new(config.swap_config.clone()), 
-                     protocol.clone(),
-                 ).await {
-                     Ok(_) => {
-                         logger.log("Selling strategy set up successfully".green().to_string());
-                         Ok(())
-                     },
-                     Err(e) => {
-                         logger.log(format!("Failed to set up selling strategy: {}", e).red().to_string());
-                         Err(e)
-                     }
-                 }
-            }
-        }
-    } else {
-        // For sell transactions, we don't copy them
-        // We rely on our own take profit and stop loss strategy
-        logger.log(format!("Processing time for buy transaction: {:?}", start_time.elapsed()).blue().to_string());
-        logger.log(format!("Not copying selling transaction - using take profit and stop loss").blue().to_string());
-        Ok(())
-    }
-}
-
 /// Comprehensive token verification and cleanup after selling
 async fn verify_sell_transaction_and_cleanup(
     token_mint: &str,
@@ -4442,7 +2699,14 @@ async fn verify_sell_transaction_and_cleanup(
     // Step 2: Check actual token balance from blockchain
     if let Ok(wallet_pubkey) = app_state.wallet.try_pubkey() {
         if let Ok(token_pubkey) = Pubkey::from_str(token_mint) {
-            let ata = get_associated_token_address(&wallet_pubkey, &token_pubkey);
+            let ata = match get_wallet_token_address(app_state.rpc_nonblocking_client.clone(), &wallet_pubkey, &token_pubkey).await {
+                Ok(ata) => ata,
+                Err(e) => {
+                    logger.log(format!("❌ Error resolving token account for {}: {}", token_mint, e).yellow().to_string());
+                    // Don't remove from tracking if we can't verify
+                    return Ok(false);
+                }
+            };
             
             match app_state.rpc_nonblocking_client.get_token_account(&ata).await {
                 Ok(account_result) => {
@@ -4544,15 +2808,12 @@ async fn periodic_comprehensive_cleanup(app_state: Arc<AppState>, logger: &Logge
     }
     
     for token_mint in tokens_to_check {
-        match verify_sell_transaction_and_cleanup(
+        let _ = verify_sell_transaction_and_cleanup(
             &token_mint,
             None,
             app_state.clone(),
             logger,
-        ).await {
-            Ok(_) => {},
-            Err(_) => {}
-        }
+        ).await;
         
         // Small delay between checks to avoid overwhelming RPC
         tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
@@ -4584,18 +2845,15 @@ pub async fn trigger_comprehensive_cleanup(app_state: Arc<AppState>) -> Result<(
             
             let mut cleaned = 0;
             for token_mint in tokens_to_check {
-                match verify_sell_transaction_and_cleanup(
+                if let Ok(was_cleaned) = verify_sell_transaction_and_cleanup(
                     &token_mint,
                     None,
                     app_state.clone(),
                     &logger,
                 ).await {
-                    Ok(was_cleaned) => {
-                        if was_cleaned {
-                            cleaned += 1;
-                        }
-                    },
-                    Err(_) => {}
+                    if was_cleaned {
+                        cleaned += 1;
+                    }
                 }
             }
             cleaned
@@ -4646,7 +2904,7 @@ pub async fn execute_emergency_sell_via_engine(
     drop(config);
     
     // Execute unified emergency sell (no parsed_data, use metrics instead)
-    match selling_engine.unified_emergency_sell(token_mint, false, None, None).await {
+    match selling_engine.unified_emergency_sell(token_mint, "EMERGENCY_STOP_LOSS", false, None, None).await {
         Ok(signature) => {
             let elapsed = start_time.elapsed();
             logger.log(format!("⚡ Emergency sell executed in {:?} for token: {} with signature: {}", 
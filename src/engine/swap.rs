@@ -1,6 +1,8 @@
 use clap::ValueEnum;
 use serde::Deserialize;
 
+use crate::engine::transaction_parser::{DexType, MigrationVenue};
+
 #[derive(ValueEnum, Debug, Clone, Deserialize, PartialEq)]
 pub enum SwapDirection {
     #[serde(rename = "buy")]
@@ -25,20 +27,49 @@ pub enum SwapInType {
     /// Percentage
     #[serde(rename = "pct")]
     Pct,
+    /// Exact output: tokens to receive on a buy, SOL to receive on a sell
+    #[serde(rename = "exact_out")]
+    ExactOut,
 }
 
-#[derive(ValueEnum, Debug, Clone, Deserialize, PartialEq)]
+#[derive(ValueEnum, Debug, Clone, Deserialize, PartialEq, Eq, Hash)]
+#[derive(Default)]
 pub enum SwapProtocol {
     #[serde(rename = "raydium")]
     RaydiumLaunchpad,
+    #[serde(rename = "pumpfun")]
+    PumpFun,
+    #[serde(rename = "raydium_cpmm")]
+    RaydiumCpmm,
+    #[serde(rename = "pumpswap")]
+    PumpSwap,
     #[serde(rename = "auto")]
+    #[default]
     Auto,
     #[serde(rename = "unknown")]
     Unknown,
 }
 
-impl Default for SwapProtocol {
-    fn default() -> Self {
-        SwapProtocol::Auto
+
+impl SwapProtocol {
+    /// Protocol that trades on the venue a transaction was parsed from
+    pub fn from_dex_type(dex_type: &DexType) -> Option<Self> {
+        match dex_type {
+            DexType::RaydiumLaunchpad => Some(SwapProtocol::RaydiumLaunchpad),
+            DexType::PumpFun => Some(SwapProtocol::PumpFun),
+            DexType::RaydiumCpmm => Some(SwapProtocol::RaydiumCpmm),
+            DexType::PumpSwap => Some(SwapProtocol::PumpSwap),
+            DexType::Unknown => None,
+        }
+    }
+
+    /// Protocol that trades on the venue a curve migrated to; `None` while the venue is pending
+    /// or has no adapter (Raydium AMM v4)
+    pub fn from_migration_venue(venue: &MigrationVenue) -> Option<Self> {
+        match venue {
+            MigrationVenue::RaydiumCpmm => Some(SwapProtocol::RaydiumCpmm),
+            MigrationVenue::PumpSwap => Some(SwapProtocol::PumpSwap),
+            MigrationVenue::RaydiumAmm | MigrationVenue::Pending => None,
+        }
     }
 }
@@ -0,0 +1,386 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::str::FromStr;
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction, UiInstruction, UiLoadedAddresses,
+    UiMessage, UiTransactionStatusMeta, UiTransactionTokenBalance,
+};
+use yellowstone_grpc_proto::geyser::{SubscribeUpdateTransaction, SubscribeUpdateTransactionInfo};
+use yellowstone_grpc_proto::prost::Message as _;
+use yellowstone_grpc_proto::prelude::{
+    CompiledInstruction, InnerInstruction, InnerInstructions, Message, MessageAddressTableLookup,
+    MessageHeader, TokenBalance, Transaction, TransactionError, TransactionStatusMeta, UiTokenAmount,
+};
+
+use crate::dex::quote_mint::KNOWN_QUOTE_MINTS;
+use crate::dex::raydium_cpmm::RAYDIUM_CPMM_AUTHORITY;
+use crate::dex::raydium_launchpad::RAYDIUM_LAUNCHPAD_AUTHORITY;
+use crate::engine::transaction_parser::{self, DexType, LaunchEvent, MigrationEvent, TradeInfoFromToken};
+
+/// A recorded transaction and the parser output expected for it.
+/// Fixtures live in `tests/fixtures/transactions` and are checked by the parser regression suite.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TransactionFixture {
+    pub description: String,
+    pub signature: String,
+    pub slot: u64,
+    /// Base64 of the prost-encoded `SubscribeUpdateTransaction`, as received from Yellowstone
+    pub transaction: String,
+    pub expected_trades: Vec<TradeInfoFromToken>,
+    pub expected_launch: Option<LaunchEvent>,
+    pub expected_migration: Option<MigrationEvent>,
+}
+
+impl TransactionFixture {
+    /// Record a fixture, taking the expected output from the current parser. Fails when the
+    /// parsed base amounts disagree with the vault balance changes the transaction itself
+    /// records; review the remaining fields against an explorer before committing.
+    pub fn record(description: &str, txn: &SubscribeUpdateTransaction) -> Result<Self> {
+        let expected = ParserOutput::parse(txn);
+        check_vault_changes(txn, &expected)?;
+        Ok(Self {
+            description: description.to_string(),
+            signature: txn.transaction
+                .as_ref()
+                .map(|tx_inner| bs58::encode(&tx_inner.signature).into_string())
+                .unwrap_or_default(),
+            slot: txn.slot,
+            transaction: base64::encode(txn.encode_to_vec()),
+            expected_trades: expected.trades,
+            expected_launch: expected.launch,
+            expected_migration: expected.migration,
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read fixture {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse fixture {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let contents = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, contents + "\n")
+            .with_context(|| format!("Failed to write fixture {}", path.display()))
+    }
+
+    /// Decode the recorded `SubscribeUpdateTransaction`
+    pub fn decode_transaction(&self) -> Result<SubscribeUpdateTransaction> {
+        let bytes = base64::decode(&self.transaction)
+            .map_err(|e| anyhow!("Invalid base64 transaction: {}", e))?;
+        SubscribeUpdateTransaction::decode(bytes.as_slice())
+            .map_err(|e| anyhow!("Invalid SubscribeUpdateTransaction: {}", e))
+    }
+}
+
+/// Everything the parser extracts from one transaction, with wall-clock timestamps cleared
+/// so the output is reproducible
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ParserOutput {
+    pub trades: Vec<TradeInfoFromToken>,
+    pub launch: Option<LaunchEvent>,
+    pub migration: Option<MigrationEvent>,
+}
+
+impl ParserOutput {
+    pub fn parse(txn: &SubscribeUpdateTransaction) -> Self {
+        let mut trades = transaction_parser::process_transaction(txn);
+        let mut launch = transaction_parser::parse_launch_event(txn);
+        let mut migration = transaction_parser::parse_migration_event(txn);
+        trades.iter_mut().for_each(|trade| trade.timestamp = 0);
+        if let Some(launch) = launch.as_mut() {
+            launch.timestamp = 0;
+        }
+        if let Some(migration) = migration.as_mut() {
+            migration.timestamp = 0;
+        }
+        Self { trades, launch, migration }
+    }
+}
+
+/// Net raw token change per mint of the accounts held by `owner`, i.e. the pool vaults when
+/// it is the pool authority, from the transaction's pre and post token balances
+pub fn vault_token_changes(txn: &SubscribeUpdateTransaction, owner: &str) -> BTreeMap<String, i128> {
+    let mut changes = BTreeMap::new();
+    let Some(meta) = txn.transaction.as_ref().and_then(|tx_inner| tx_inner.meta.as_ref()) else {
+        return changes;
+    };
+    let amount = |balance: &TokenBalance| {
+        balance.ui_token_amount.as_ref().and_then(|amount| amount.amount.parse::<i128>().ok()).unwrap_or_default()
+    };
+    for balance in meta.post_token_balances.iter().filter(|balance| balance.owner == owner) {
+        *changes.entry(balance.mint.clone()).or_default() += amount(balance);
+    }
+    for balance in meta.pre_token_balances.iter().filter(|balance| balance.owner == owner) {
+        *changes.entry(balance.mint.clone()).or_default() -= amount(balance);
+    }
+    changes.retain(|_, change| *change != 0);
+    changes
+}
+
+/// Owner of the token account the trade's base amount moves through
+fn base_vault_owner(trade: &TradeInfoFromToken) -> Option<String> {
+    match trade.dex_type {
+        DexType::RaydiumLaunchpad => Some(RAYDIUM_LAUNCHPAD_AUTHORITY.to_string()),
+        DexType::RaydiumCpmm => Some(RAYDIUM_CPMM_AUTHORITY.to_string()),
+        // Bonding curves and PumpSwap pools hold their tokens in their own token accounts
+        DexType::PumpFun | DexType::PumpSwap => Some(trade.pool_id.clone()),
+        _ => None,
+    }
+}
+
+/// Check the parsed trades against the base vault balance changes: buys take `amount_out`
+/// tokens out of the vault, sells put `amount_in` in. Migrations drain the vaults without a
+/// trade, so their mints are left out.
+pub fn check_vault_changes(txn: &SubscribeUpdateTransaction, output: &ParserOutput) -> Result<()> {
+    let mut traded: BTreeMap<(String, String), i128> = BTreeMap::new();
+    for trade in &output.trades {
+        let Some(owner) = base_vault_owner(trade) else {
+            continue;
+        };
+        let change = if trade.is_buy { -(trade.amount_out as i128) } else { trade.amount_in as i128 };
+        *traded.entry((owner, trade.mint.clone())).or_default() += change;
+    }
+    for ((owner, mint), change) in &traded {
+        let vault_change = vault_token_changes(txn, owner).get(mint).copied().unwrap_or_default();
+        if *change != vault_change {
+            return Err(anyhow!(
+                "Parsed trades move {} of {} through the pool vault, the balances show {}",
+                change, mint, vault_change
+            ));
+        }
+    }
+    let authority = RAYDIUM_LAUNCHPAD_AUTHORITY.to_string();
+    let migrated = output.migration.as_ref().map(|migration| migration.mint.clone());
+    let quote_mints: Vec<String> = KNOWN_QUOTE_MINTS.iter().map(|mint| mint.to_string()).collect();
+    if let Some(mint) = vault_token_changes(txn, &authority).keys().find(|mint| {
+        !traded.contains_key(&(authority.clone(), (*mint).clone()))
+            && !quote_mints.contains(mint)
+            && migrated.as_ref() != Some(mint)
+    }) {
+        return Err(anyhow!("Pool vault of {} changed but no trade of it was parsed", mint));
+    }
+    Ok(())
+}
+
+/// Convert a JSON-RPC `getTransaction` result (`json` or `base64` encoding) into the
+/// `SubscribeUpdateTransaction` Yellowstone would have streamed for it
+pub fn subscribe_update_from_confirmed(
+    confirmed: &EncodedConfirmedTransactionWithStatusMeta,
+) -> Result<SubscribeUpdateTransaction> {
+    let (signatures, message) = match &confirmed.transaction.transaction {
+        EncodedTransaction::Json(ui_transaction) => {
+            let UiMessage::Raw(raw) = &ui_transaction.message else {
+                return Err(anyhow!("jsonParsed transactions are not supported, use json or base64 encoding"));
+            };
+            let signatures = ui_transaction.signatures
+                .iter()
+                .map(|signature| decode_signature(signature))
+                .collect::<Result<Vec<_>>>()?;
+            let message = Message {
+                header: Some(MessageHeader {
+                    num_required_signatures: raw.header.num_required_signatures as u32,
+                    num_readonly_signed_accounts: raw.header.num_readonly_signed_accounts as u32,
+                    num_readonly_unsigned_accounts: raw.header.num_readonly_unsigned_accounts as u32,
+                }),
+                account_keys: raw.account_keys
+                    .iter()
+                    .map(|key| decode_pubkey(key))
+                    .collect::<Result<Vec<_>>>()?,
+                recent_blockhash: bs58::decode(&raw.recent_blockhash)
+                    .into_vec()
+                    .map_err(|e| anyhow!("Invalid recent blockhash: {}", e))?,
+                instructions: raw.instructions
+                    .iter()
+                    .map(|instruction| {
+                        Ok(CompiledInstruction {
+                            program_id_index: instruction.program_id_index as u32,
+                            accounts: instruction.accounts.clone(),
+                            data: decode_instruction_data(&instruction.data)?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?,
+                versioned: raw.address_table_lookups.is_some(),
+                address_table_lookups: raw.address_table_lookups
+                    .iter()
+                    .flatten()
+                    .map(|lookup| {
+                        Ok(MessageAddressTableLookup {
+                            account_key: decode_pubkey(&lookup.account_key)?,
+                            writable_indexes: lookup.writable_indexes.clone(),
+                            readonly_indexes: lookup.readonly_indexes.clone(),
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?,
+            };
+            (signatures, message)
+        }
+        encoded => {
+            let transaction = encoded
+                .decode()
+                .ok_or_else(|| anyhow!("Unsupported transaction encoding"))?;
+            let header = transaction.message.header();
+            let lookups = transaction.message.address_table_lookups();
+            let message = Message {
+                header: Some(MessageHeader {
+                    num_required_signatures: header.num_required_signatures as u32,
+                    num_readonly_signed_accounts: header.num_readonly_signed_accounts as u32,
+                    num_readonly_unsigned_accounts: header.num_readonly_unsigned_accounts as u32,
+                }),
+                account_keys: transaction.message
+                    .static_account_keys()
+                    .iter()
+                    .map(|key| key.to_bytes().to_vec())
+                    .collect(),
+                recent_blockhash: transaction.message.recent_blockhash().to_bytes().to_vec(),
+                instructions: transaction.message
+                    .instructions()
+                    .iter()
+                    .map(|instruction| CompiledInstruction {
+                        program_id_index: instruction.program_id_index as u32,
+                        accounts: instruction.accounts.clone(),
+                        data: instruction.data.clone(),
+                    })
+                    .collect(),
+                versioned: lookups.is_some(),
+                address_table_lookups: lookups
+                    .into_iter()
+                    .flatten()
+                    .map(|lookup| MessageAddressTableLookup {
+                        account_key: lookup.account_key.to_bytes().to_vec(),
+                        writable_indexes: lookup.writable_indexes.clone(),
+                        readonly_indexes: lookup.readonly_indexes.clone(),
+                    })
+                    .collect(),
+            };
+            let signatures = transaction.signatures
+                .iter()
+                .map(|signature| signature.as_ref().to_vec())
+                .collect();
+            (signatures, message)
+        }
+    };
+
+    let meta = confirmed.transaction.meta
+        .as_ref()
+        .map(status_meta_from_ui)
+        .transpose()?;
+    let signature = signatures
+        .first()
+        .cloned()
+        .ok_or_else(|| anyhow!("Transaction has no signatures"))?;
+
+    Ok(SubscribeUpdateTransaction {
+        transaction: Some(SubscribeUpdateTransactionInfo {
+            signature: signature.clone(),
+            is_vote: false,
+            transaction: Some(Transaction {
+                signatures,
+                message: Some(message),
+            }),
+            meta,
+            index: 0,
+        }),
+        slot: confirmed.slot,
+    })
+}
+
+fn status_meta_from_ui(meta: &UiTransactionStatusMeta) -> Result<TransactionStatusMeta> {
+    let inner_instructions: Option<Vec<_>> = meta.inner_instructions.clone().into();
+    let log_messages: Option<Vec<String>> = meta.log_messages.clone().into();
+    let pre_token_balances: Option<Vec<_>> = meta.pre_token_balances.clone().into();
+    let post_token_balances: Option<Vec<_>> = meta.post_token_balances.clone().into();
+    let loaded_addresses: Option<UiLoadedAddresses> = meta.loaded_addresses.clone().into();
+    let loaded_addresses = loaded_addresses.unwrap_or_default();
+
+    let inner_instructions = inner_instructions
+        .unwrap_or_default()
+        .into_iter()
+        .map(|inner| {
+            let instructions = inner.instructions
+                .into_iter()
+                .map(|instruction| match instruction {
+                    UiInstruction::Compiled(compiled) => Ok(InnerInstruction {
+                        program_id_index: compiled.program_id_index as u32,
+                        accounts: compiled.accounts,
+                        data: decode_instruction_data(&compiled.data)?,
+                        stack_height: compiled.stack_height,
+                    }),
+                    UiInstruction::Parsed(_) => Err(anyhow!("Parsed inner instructions are not supported")),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(InnerInstructions { index: inner.index as u32, instructions })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let token_balances = |balances: Option<Vec<UiTransactionTokenBalance>>| {
+        balances
+            .unwrap_or_default()
+            .into_iter()
+            .map(|balance| TokenBalance {
+                account_index: balance.account_index as u32,
+                mint: balance.mint,
+                ui_token_amount: Some(UiTokenAmount {
+                    ui_amount: balance.ui_token_amount.ui_amount.unwrap_or_default(),
+                    decimals: balance.ui_token_amount.decimals as u32,
+                    amount: balance.ui_token_amount.amount,
+                    ui_amount_string: balance.ui_token_amount.ui_amount_string,
+                }),
+                owner: Option::<String>::from(balance.owner).unwrap_or_default(),
+                program_id: Option::<String>::from(balance.program_id).unwrap_or_default(),
+            })
+            .collect::<Vec<_>>()
+    };
+
+    Ok(TransactionStatusMeta {
+        err: meta.err
+            .as_ref()
+            .map(|err| bincode::serialize(err).map(|err| TransactionError { err }))
+            .transpose()?,
+        fee: meta.fee,
+        pre_balances: meta.pre_balances.clone(),
+        post_balances: meta.post_balances.clone(),
+        inner_instructions,
+        inner_instructions_none: false,
+        log_messages_none: log_messages.is_none(),
+        log_messages: log_messages.unwrap_or_default(),
+        pre_token_balances: token_balances(pre_token_balances),
+        post_token_balances: token_balances(post_token_balances),
+        rewards: Vec::new(),
+        loaded_writable_addresses: loaded_addresses.writable
+            .iter()
+            .map(|key| decode_pubkey(key))
+            .collect::<Result<Vec<_>>>()?,
+        loaded_readonly_addresses: loaded_addresses.readonly
+            .iter()
+            .map(|key| decode_pubkey(key))
+            .collect::<Result<Vec<_>>>()?,
+        return_data: None,
+        return_data_none: true,
+        compute_units_consumed: meta.compute_units_consumed.clone().into(),
+    })
+}
+
+fn decode_pubkey(key: &str) -> Result<Vec<u8>> {
+    Pubkey::from_str(key)
+        .map(|key| key.to_bytes().to_vec())
+        .map_err(|e| anyhow!("Invalid account key {}: {}", key, e))
+}
+
+fn decode_signature(signature: &str) -> Result<Vec<u8>> {
+    Signature::from_str(signature)
+        .map(|signature| signature.as_ref().to_vec())
+        .map_err(|e| anyhow!("Invalid signature {}: {}", signature, e))
+}
+
+/// Instruction data is base58 in the `json` encoding
+fn decode_instruction_data(data: &str) -> Result<Vec<u8>> {
+    bs58::decode(data)
+        .into_vec()
+        .map_err(|e| anyhow!("Invalid instruction data: {}", e))
+}
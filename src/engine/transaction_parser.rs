@@ -5,9 +5,28 @@ use colored::Colorize;
 use crate::common::logger::Logger;
 use lazy_static;
 use yellowstone_grpc_proto::geyser::SubscribeUpdateTransaction;
-use std::time::Instant;
+use serde::{Deserialize, Serialize};
 // Import RAYDIUM_LAUNCHPAD_PROGRAM
-use crate::dex::raydium_launchpad::RAYDIUM_LAUNCHPAD_PROGRAM;
+use crate::dex::raydium_launchpad::{
+    RAYDIUM_LAUNCHPAD_PROGRAM, SOL_MINT,
+    BUY_DISCRIMINATOR, SELL_DISCRIMINATOR, BUY_EXACT_OUT_DISCRIMINATOR, SELL_EXACT_OUT_DISCRIMINATOR,
+    SWAP_ACCOUNT_USER, SWAP_ACCOUNT_POOL_STATE, SWAP_ACCOUNT_USER_BASE_TOKEN, SWAP_ACCOUNT_USER_QUOTE_TOKEN,
+    SWAP_ACCOUNT_BASE_VAULT, SWAP_ACCOUNT_QUOTE_VAULT, SWAP_ACCOUNT_BASE_MINT, SWAP_ACCOUNT_QUOTE_MINT,
+    SWAP_ACCOUNTS_LEN, INITIALIZE_DISCRIMINATOR, INITIALIZE_ACCOUNTS_LEN, INITIALIZE_ACCOUNT_CREATOR,
+    INITIALIZE_ACCOUNT_GLOBAL_CONFIG, INITIALIZE_ACCOUNT_PLATFORM_CONFIG, INITIALIZE_ACCOUNT_POOL_STATE,
+    INITIALIZE_ACCOUNT_BASE_MINT, INITIALIZE_ACCOUNT_QUOTE_MINT,
+    MIGRATE_TO_AMM_DISCRIMINATOR, MIGRATE_TO_AMM_ACCOUNTS_LEN, MIGRATE_TO_AMM_ACCOUNT_BASE_MINT,
+    MIGRATE_TO_AMM_ACCOUNT_AMM_POOL, MIGRATE_TO_AMM_ACCOUNT_POOL_STATE, MIGRATE_TO_AMM_ACCOUNT_BASE_VAULT,
+    MIGRATE_TO_AMM_ACCOUNT_QUOTE_VAULT, MIGRATE_TO_CPSWAP_DISCRIMINATOR, MIGRATE_TO_CPSWAP_ACCOUNTS_LEN,
+    MIGRATE_TO_CPSWAP_ACCOUNT_BASE_MINT, MIGRATE_TO_CPSWAP_ACCOUNT_CPSWAP_POOL,
+    MIGRATE_TO_CPSWAP_ACCOUNT_POOL_STATE, MIGRATE_TO_CPSWAP_ACCOUNT_BASE_VAULT,
+    MIGRATE_TO_CPSWAP_ACCOUNT_QUOTE_VAULT,
+};
+use crate::dex::pool_index::{derive_pool_id, POOL_INDEX};
+use crate::dex::pump_fun::{self, PUMP_FUN_PROGRAM};
+use crate::dex::pump_swap::{self, PUMP_SWAP_PROGRAM};
+use crate::dex::quote_mint::{self, KNOWN_QUOTE_MINTS};
+use crate::dex::raydium_cpmm::{self, RAYDIUM_CPMM_PROGRAM};
 // Create a static logger for this module
 lazy_static::lazy_static! {
     static ref LOGGER: Logger = Logger::new("[PARSER] => ".blue().to_string());
@@ -17,13 +36,235 @@ lazy_static::lazy_static! {
 #[inline]
 fn dex_log(_msg: String) {}
 
-#[derive(Clone, Debug, PartialEq)]
+/// Anchor `emit_cpi!` tag prepended to self-CPI event data (sha256("anchor:event")[..8])
+pub const EVENT_IX_TAG: [u8; 8] = [228, 69, 165, 46, 81, 203, 154, 29];
+/// TradeEvent discriminator ("vdt/007mYe" once base64 encoded in logs). Pump.fun names its
+/// event the same, so events are told apart by the program that emitted them.
+pub const TRADE_EVENT_DISCRIMINATOR: [u8; 8] = [189, 219, 127, 211, 78, 230, 97, 238];
+
+/// Raydium Launchpad PoolCreateEvent discriminator ("l9fiCXahc6" once base64 encoded in logs)
+pub const POOL_CREATE_EVENT_DISCRIMINATOR: [u8; 8] = [151, 215, 226, 9, 118, 161, 115, 174];
+
+// TradeEvent body sizes: the launch layout had no creator_fee / exact_in fields
+const TRADE_EVENT_LEGACY_LEN: usize = 130;
+const TRADE_EVENT_LEN: usize = 139;
+
+/// Launchpad base tokens are always minted with 6 decimals, quote is SOL (9 decimals)
+pub const LAUNCHPAD_TOKEN_DECIMALS: u32 = 6;
+const LAMPORTS_PER_SOL_F64: f64 = 1_000_000_000.0;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub enum DexType {
     RaydiumLaunchpad,
+    PumpFun,
+    RaydiumCpmm,
+    PumpSwap,
+    #[default]
     Unknown,
 }
 
+/// Launchpad pool status as reported in TradeEvent
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PoolStatus {
+    Fund,
+    Migrate,
+    Trade,
+}
+
+impl PoolStatus {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(PoolStatus::Fund),
+            1 => Some(PoolStatus::Migrate),
+            2 => Some(PoolStatus::Trade),
+            _ => None,
+        }
+    }
+}
+
+/// Raydium Launchpad TradeEvent, Borsh layout as emitted by the program
+#[derive(Clone, Debug)]
+pub struct TradeEvent {
+    pub pool_state: Pubkey,
+    pub total_base_sell: u64,
+    pub virtual_base: u64,
+    pub virtual_quote: u64,
+    pub real_base_before: u64,
+    pub real_quote_before: u64,
+    pub real_base_after: u64,
+    pub real_quote_after: u64,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub protocol_fee: u64,
+    pub platform_fee: u64,
+    pub creator_fee: u64,
+    pub share_fee: u64,
+    pub is_buy: bool,
+    pub pool_status: PoolStatus,
+    pub exact_in: bool,
+}
+
+impl TradeEvent {
+    /// Quote reserve used for pricing (virtual + real, in lamports)
+    pub fn quote_reserve(&self) -> u64 {
+        self.virtual_quote.saturating_add(self.real_quote_after)
+    }
+
+    /// Base reserve used for pricing (virtual - real, in raw token units)
+    pub fn base_reserve(&self) -> u64 {
+        self.virtual_base.saturating_sub(self.real_base_after)
+    }
+
+    /// Spot price after the trade in raw quote units per whole token
+    pub fn price(&self) -> f64 {
+        calculate_price(self.quote_reserve(), self.base_reserve())
+    }
+}
+
+/// Price in raw quote units (lamports for SOL pools) per whole token from constant product reserves
+pub fn calculate_price(quote_reserve: u64, base_reserve: u64) -> f64 {
+    if base_reserve == 0 {
+        return 0.0;
+    }
+    quote_reserve as f64 * 10f64.powi(LAUNCHPAD_TOKEN_DECIMALS as i32) / base_reserve as f64
+}
+
+/// Raydium Launchpad swap instruction variants
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SwapInstructionKind {
+    BuyExactIn,
+    BuyExactOut,
+    SellExactIn,
+    SellExactOut,
+}
+
+impl SwapInstructionKind {
+    fn from_discriminator(data: &[u8]) -> Option<Self> {
+        if data.len() < 8 {
+            return None;
+        }
+        match &data[..8] {
+            d if d == BUY_DISCRIMINATOR => Some(SwapInstructionKind::BuyExactIn),
+            d if d == BUY_EXACT_OUT_DISCRIMINATOR => Some(SwapInstructionKind::BuyExactOut),
+            d if d == SELL_DISCRIMINATOR => Some(SwapInstructionKind::SellExactIn),
+            d if d == SELL_EXACT_OUT_DISCRIMINATOR => Some(SwapInstructionKind::SellExactOut),
+            _ => None,
+        }
+    }
+
+    pub fn is_buy(&self) -> bool {
+        matches!(self, SwapInstructionKind::BuyExactIn | SwapInstructionKind::BuyExactOut)
+    }
+
+    pub fn is_exact_in(&self) -> bool {
+        matches!(self, SwapInstructionKind::BuyExactIn | SwapInstructionKind::SellExactIn)
+    }
+}
+
+/// Decoded Raydium Launchpad swap instruction with its accounts resolved
 #[derive(Clone, Debug)]
+pub struct LaunchpadSwapInstruction {
+    pub kind: SwapInstructionKind,
+    pub instruction_index: usize,        // Outer instruction index
+    pub inner_index: Option<usize>,      // Position inside the CPI list when invoked by another program
+    pub amount: u64,                     // amount_in for exact-in, amount_out for exact-out
+    pub other_amount_threshold: u64,     // minimum_amount_out for exact-in, maximum_amount_in for exact-out
+    pub share_fee_rate: u64,
+    pub user: Pubkey,
+    pub pool_state: Pubkey,
+    pub user_base_token: Pubkey,
+    pub user_quote_token: Pubkey,
+    pub base_vault: Pubkey,
+    pub quote_vault: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+}
+
+/// Bonding curve parameters chosen at pool creation
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum CurveParams {
+    Constant {
+        supply: u64,
+        total_base_sell: u64,
+        total_quote_fund_raising: u64,
+        migrate_type: u8,
+    },
+    Fixed {
+        supply: u64,
+        total_quote_fund_raising: u64,
+        migrate_type: u8,
+    },
+    Linear {
+        supply: u64,
+        total_quote_fund_raising: u64,
+        migrate_type: u8,
+    },
+}
+
+/// Creator vesting parameters chosen at pool creation
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct VestingParams {
+    pub total_locked_amount: u64,
+    pub cliff_period: u64,
+    pub unlock_period: u64,
+}
+
+/// Mint metadata, curve and vesting parameters shared by `initialize` and PoolCreateEvent
+#[derive(Clone, Debug)]
+struct LaunchParams {
+    decimals: u8,
+    name: String,
+    symbol: String,
+    uri: String,
+    curve: CurveParams,
+    vesting: VestingParams,
+}
+
+/// New Let's Bonk / Raydium Launchpad pool, built from the initialize instruction and/or PoolCreateEvent
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LaunchEvent {
+    pub slot: u64,
+    pub signature: String,
+    pub timestamp: u64,
+    pub pool_id: String,
+    pub creator: String,
+    pub base_mint: String,
+    pub quote_mint: String,
+    pub global_config: String,
+    pub platform_config: String, // Only known when the initialize instruction was decoded
+    pub decimals: u8,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub curve: CurveParams,
+    pub vesting: VestingParams,
+}
+
+/// Venue a completed curve migrated its liquidity to
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum MigrationVenue {
+    RaydiumAmm,
+    RaydiumCpmm,
+    PumpSwap, // Pump.fun curves, migrated into the canonical PumpSwap pool
+    Pending, // Curve completed (pool status Migrate) but the migrate instruction was not seen yet
+}
+
+/// Curve completion, carrying the old pool, the new AMM pool and the final reserves.
+/// For pump.fun tokens the old pool is the bonding curve.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MigrationEvent {
+    pub slot: u64,
+    pub signature: String,
+    pub timestamp: u64,
+    pub mint: String,
+    pub launchpad_pool_id: String,
+    pub new_pool_id: Option<String>, // None while the venue is Pending
+    pub venue: MigrationVenue,
+    pub final_base_reserve: u64,
+    pub final_quote_reserve: u64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct TradeInfoFromToken {
     // Common fields
     pub dex_type: DexType,
@@ -31,9 +272,10 @@ pub struct TradeInfoFromToken {
     pub signature: String,
     pub pool_id: String,
     pub mint: String,
+    pub quote_mint: String, // Mint the pool is quoted in; price, sol_change and liquidity are converted to SOL
     pub timestamp: u64,
     pub is_buy: bool,
-    pub price: u64,
+    pub price: f64, // Lamports per whole token
     pub is_reverse: bool,
     pub coin_creator: Option<String>,
     pub sol_change: f64,
@@ -41,168 +283,770 @@ pub struct TradeInfoFromToken {
     pub liquidity: f64,  // this is for filtering out small trades
     pub virtual_sol_reserves: u64,
     pub virtual_token_reserves: u64,
+    // Raw TradeEvent amounts (raw quote units / raw token units)
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    // Launchpad curve as the TradeEvent reports it: virtual reserves on constant product pools,
+    // the price ratio on fixed price pools and the Q64.64 slope on linear pools
+    pub curve_virtual_base: u64,
+    pub curve_virtual_quote: u64,
+    pub total_base_sell: u64,
+    pub protocol_fee: u64,
+    pub platform_fee: u64,
+    pub creator_fee: u64,
+    pub share_fee: u64,
+    // Position of the swap in the transaction; inner index is set when the swap was a CPI
+    pub instruction_index: usize,
+    pub inner_instruction_index: Option<usize>,
+    // Accounts resolved from the swap instruction (empty when only the event was seen)
+    pub user: String,
+    pub base_vault: String,
+    pub quote_vault: String,
 }
 
-/// Helper function to check if transaction contains Buy instruction
-fn has_buy_instruction(txn: &SubscribeUpdateTransaction) -> bool {
-    if let Some(tx_inner) = &txn.transaction {
-        if let Some(meta) = &tx_inner.meta {
-            return meta.log_messages.iter().any(|log| {
-                log.contains("Program log: Instruction: Buy") || 
-                log.contains("Program log: Instruction: Swap")
-            });
-        }
+impl TradeInfoFromToken {
+    /// Quote mint of the pool; trades without one are SOL-quoted
+    pub fn quote_mint_pubkey(&self) -> Pubkey {
+        quote_mint::parse_quote_mint(&self.quote_mint)
+    }
+
+    /// Virtual quote reserve in SOL, 0 while a non-SOL quote has no rate
+    pub fn virtual_quote_reserve_sol(&self) -> f64 {
+        quote_mint::quote_to_sol(&self.quote_mint_pubkey(), self.virtual_sol_reserves).unwrap_or_default()
     }
-    false
 }
 
-/// Helper function to check if transaction contains Sell instruction
-fn has_sell_instruction(txn: &SubscribeUpdateTransaction) -> bool {
-    if let Some(tx_inner) = &txn.transaction {
-        if let Some(meta) = &tx_inner.meta {
-            return meta.log_messages.iter().any(|log| {
-                log.contains("Program log: Instruction: Sell") ||
-                log.contains("Program log: Instruction: Swap")
-            });
-        }
+pub fn parse_public_key(buffer: &[u8], offset: usize) -> Option<Pubkey> {
+    if offset + 32 > buffer.len() {
+        return None;
     }
-    false
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&buffer[offset..offset+32]);
+    Some(Pubkey::new_from_array(bytes))
 }
 
-/// Parses the transaction data buffer into a TradeInfoFromToken struct
-pub fn parse_transaction_data(txn: &SubscribeUpdateTransaction, buffer: &[u8]) -> Option<TradeInfoFromToken> {
-    fn parse_public_key(buffer: &[u8], offset: usize) -> Option<String> {
-        if offset + 32 > buffer.len() {
-            return None;
-        }
-        Some(bs58::encode(&buffer[offset..offset+32]).into_string())
+pub fn parse_u64(buffer: &[u8], offset: usize) -> Option<u64> {
+    if offset + 8 > buffer.len() {
+        return None;
     }
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&buffer[offset..offset+8]);
+    Some(u64::from_le_bytes(bytes))
+}
 
-    fn parse_u64(buffer: &[u8], offset: usize) -> Option<u64> {
-        if offset + 8 > buffer.len() {
-            return None;
+pub fn parse_u8(buffer: &[u8], offset: usize) -> Option<u8> {
+    if offset >= buffer.len() {
+        return None;
+    }
+    Some(buffer[offset])
+}
+
+/// Borsh string: u32 length prefix followed by UTF-8 bytes. Returns the string and the next offset.
+fn parse_string(buffer: &[u8], offset: usize) -> Option<(String, usize)> {
+    let len_bytes: [u8; 4] = buffer.get(offset..offset + 4)?.try_into().ok()?;
+    let len = u32::from_le_bytes(len_bytes) as usize;
+    let start = offset + 4;
+    let bytes = buffer.get(start..start.checked_add(len)?)?;
+    Some((String::from_utf8_lossy(bytes).trim_end_matches('\0').to_string(), start + len))
+}
+
+fn parse_curve_params(buffer: &[u8], offset: usize) -> Option<(CurveParams, usize)> {
+    let data = offset + 1;
+    match parse_u8(buffer, offset)? {
+        0 => Some((
+            CurveParams::Constant {
+                supply: parse_u64(buffer, data)?,
+                total_base_sell: parse_u64(buffer, data + 8)?,
+                total_quote_fund_raising: parse_u64(buffer, data + 16)?,
+                migrate_type: parse_u8(buffer, data + 24)?,
+            },
+            data + 25,
+        )),
+        1 => Some((
+            CurveParams::Fixed {
+                supply: parse_u64(buffer, data)?,
+                total_quote_fund_raising: parse_u64(buffer, data + 8)?,
+                migrate_type: parse_u8(buffer, data + 16)?,
+            },
+            data + 17,
+        )),
+        2 => Some((
+            CurveParams::Linear {
+                supply: parse_u64(buffer, data)?,
+                total_quote_fund_raising: parse_u64(buffer, data + 8)?,
+                migrate_type: parse_u8(buffer, data + 16)?,
+            },
+            data + 17,
+        )),
+        _ => None,
+    }
+}
+
+/// MintParams + CurveParams + VestingParams, the argument layout of `initialize`
+fn parse_launch_params(buffer: &[u8], offset: usize) -> Option<LaunchParams> {
+    let decimals = parse_u8(buffer, offset)?;
+    let (name, offset) = parse_string(buffer, offset + 1)?;
+    let (symbol, offset) = parse_string(buffer, offset)?;
+    let (uri, offset) = parse_string(buffer, offset)?;
+    let (curve, offset) = parse_curve_params(buffer, offset)?;
+    let vesting = VestingParams {
+        total_locked_amount: parse_u64(buffer, offset)?,
+        cliff_period: parse_u64(buffer, offset + 8)?,
+        unlock_period: parse_u64(buffer, offset + 16)?,
+    };
+    Some(LaunchParams { decimals, name, symbol, uri, curve, vesting })
+}
+
+/// Strip the `emit_cpi!` tag if present and return the event payload (discriminator + body)
+pub fn event_payload(data: &[u8]) -> &[u8] {
+    if data.len() >= 8 && data[..8] == EVENT_IX_TAG {
+        &data[8..]
+    } else {
+        data
+    }
+}
+
+/// Whether the buffer is a TradeEvent, either raw or wrapped in a self-CPI
+pub fn is_trade_event_data(data: &[u8]) -> bool {
+    let payload = event_payload(data);
+    payload.len() >= 8 + TRADE_EVENT_LEGACY_LEN && payload[..8] == TRADE_EVENT_DISCRIMINATOR
+}
+
+/// Decode a TradeEvent from CPI event data or a base64 decoded `Program data:` log
+pub fn decode_trade_event(data: &[u8]) -> Option<TradeEvent> {
+    if !is_trade_event_data(data) {
+        return None;
+    }
+    let body = &event_payload(data)[8..];
+    let has_creator_fee = body.len() >= TRADE_EVENT_LEN;
+
+    let pool_state = parse_public_key(body, 0)?;
+    let mut offset = 32;
+    let mut next_u64 = || {
+        let value = parse_u64(body, offset);
+        offset += 8;
+        value
+    };
+    let total_base_sell = next_u64()?;
+    let virtual_base = next_u64()?;
+    let virtual_quote = next_u64()?;
+    let real_base_before = next_u64()?;
+    let real_quote_before = next_u64()?;
+    let real_base_after = next_u64()?;
+    let real_quote_after = next_u64()?;
+    let amount_in = next_u64()?;
+    let amount_out = next_u64()?;
+    let protocol_fee = next_u64()?;
+    let platform_fee = next_u64()?;
+    let creator_fee = if has_creator_fee { next_u64()? } else { 0 };
+    let share_fee = next_u64()?;
+
+    let is_buy = parse_u8(body, offset)? == 0;
+    let pool_status = PoolStatus::from_u8(parse_u8(body, offset + 1)?)?;
+    let exact_in = if has_creator_fee { parse_u8(body, offset + 2)? != 0 } else { true };
+
+    Some(TradeEvent {
+        pool_state,
+        total_base_sell,
+        virtual_base,
+        virtual_quote,
+        real_base_before,
+        real_quote_before,
+        real_base_after,
+        real_quote_after,
+        amount_in,
+        amount_out,
+        protocol_fee,
+        platform_fee,
+        creator_fee,
+        share_fee,
+        is_buy,
+        pool_status,
+        exact_in,
+    })
+}
+
+/// Decode a launchpad swap instruction, resolving its account indices against the transaction keys
+pub fn decode_swap_instruction(
+    data: &[u8],
+    accounts: &[u8],
+    account_keys: &[Pubkey],
+) -> Option<LaunchpadSwapInstruction> {
+    let kind = SwapInstructionKind::from_discriminator(data)?;
+    if accounts.len() < SWAP_ACCOUNTS_LEN {
+        return None;
+    }
+    let account = |position: usize| account_keys.get(accounts[position] as usize).copied();
+
+    Some(LaunchpadSwapInstruction {
+        kind,
+        instruction_index: 0,
+        inner_index: None,
+        amount: parse_u64(data, 8)?,
+        other_amount_threshold: parse_u64(data, 16)?,
+        share_fee_rate: parse_u64(data, 24).unwrap_or(0),
+        user: account(SWAP_ACCOUNT_USER)?,
+        pool_state: account(SWAP_ACCOUNT_POOL_STATE)?,
+        user_base_token: account(SWAP_ACCOUNT_USER_BASE_TOKEN)?,
+        user_quote_token: account(SWAP_ACCOUNT_USER_QUOTE_TOKEN)?,
+        base_vault: account(SWAP_ACCOUNT_BASE_VAULT)?,
+        quote_vault: account(SWAP_ACCOUNT_QUOTE_VAULT)?,
+        base_mint: account(SWAP_ACCOUNT_BASE_MINT)?,
+        quote_mint: account(SWAP_ACCOUNT_QUOTE_MINT)?,
+    })
+}
+
+/// Full account key list in index order: static message keys, then the writable and readonly
+/// addresses loaded from address lookup tables (v0 transactions). Instruction account indices
+/// refer to this combined list.
+pub fn transaction_account_keys(txn: &SubscribeUpdateTransaction) -> Vec<Pubkey> {
+    let Some(tx_inner) = &txn.transaction else {
+        return Vec::new();
+    };
+    let static_keys = tx_inner.transaction
+        .as_ref()
+        .and_then(|transaction| transaction.message.as_ref())
+        .map(|message| message.account_keys.as_slice())
+        .unwrap_or_default();
+    let (loaded_writable, loaded_readonly) = tx_inner.meta
+        .as_ref()
+        .map(|meta| (meta.loaded_writable_addresses.as_slice(), meta.loaded_readonly_addresses.as_slice()))
+        .unwrap_or_default();
+
+    // Keep positions aligned even if a key is malformed
+    static_keys
+        .iter()
+        .chain(loaded_writable)
+        .chain(loaded_readonly)
+        .map(|key| Pubkey::try_from(key.as_slice()).unwrap_or_default())
+        .collect()
+}
+
+/// Failed transactions still carry their instructions but changed no state and emitted no events
+pub fn is_failed_transaction(txn: &SubscribeUpdateTransaction) -> bool {
+    txn.transaction
+        .as_ref()
+        .and_then(|tx_inner| tx_inner.meta.as_ref())
+        .is_some_and(|meta| meta.err.is_some())
+}
+
+/// An instruction of a given program found in the transaction, outer or CPI
+pub struct ProgramInstruction<'a> {
+    pub instruction_index: usize,
+    pub inner_index: Option<usize>,
+    pub data: &'a [u8],
+    pub accounts: &'a [u8],
+}
+
+/// All instructions invoking `program` in execution order, each outer instruction followed by its CPIs
+pub fn program_instructions<'a>(
+    txn: &'a SubscribeUpdateTransaction,
+    account_keys: &[Pubkey],
+    program: &Pubkey,
+) -> Vec<ProgramInstruction<'a>> {
+    let mut instructions = Vec::new();
+    let Some(tx_inner) = &txn.transaction else {
+        return instructions;
+    };
+    let Some(message) = tx_inner.transaction.as_ref().and_then(|transaction| transaction.message.as_ref()) else {
+        return instructions;
+    };
+    let is_program = |program_id_index: u32| account_keys.get(program_id_index as usize) == Some(program);
+
+    for (instruction_index, instruction) in message.instructions.iter().enumerate() {
+        if is_program(instruction.program_id_index) {
+            instructions.push(ProgramInstruction {
+                instruction_index,
+                inner_index: None,
+                data: &instruction.data,
+                accounts: &instruction.accounts,
+            });
+        }
+
+        let inner_instructions = tx_inner.meta
+            .iter()
+            .flat_map(|meta| &meta.inner_instructions)
+            .filter(|inner| inner.index as usize == instruction_index)
+            .flat_map(|inner| inner.instructions.iter().enumerate());
+        for (inner_index, inner) in inner_instructions {
+            if is_program(inner.program_id_index) {
+                instructions.push(ProgramInstruction {
+                    instruction_index,
+                    inner_index: Some(inner_index),
+                    data: &inner.data,
+                    accounts: &inner.accounts,
+                });
+            }
         }
-        let mut bytes = [0u8; 8];
-        bytes.copy_from_slice(&buffer[offset..offset+8]);
-        Some(u64::from_le_bytes(bytes))
     }
 
-    fn parse_u8(buffer: &[u8], offset: usize) -> Option<u8> {
-        if offset >= buffer.len() {
-            return None;
+    instructions
+}
+
+/// All launchpad swap instructions among the program's `instructions`, in execution order
+pub fn find_swap_instructions(instructions: &[ProgramInstruction], account_keys: &[Pubkey]) -> Vec<LaunchpadSwapInstruction> {
+    instructions
+        .iter()
+        .filter_map(|instruction| {
+            let mut swap = decode_swap_instruction(instruction.data, instruction.accounts, account_keys)?;
+            swap.instruction_index = instruction.instruction_index;
+            swap.inner_index = instruction.inner_index;
+            Some(swap)
+        })
+        .collect()
+}
+
+/// `Program data:` payloads logged while `program` was executing, each with the index of the
+/// outer instruction it belongs to. Invocations are tracked so CPI events of other programs are skipped.
+pub fn program_data_logs(txn: &SubscribeUpdateTransaction, program: &Pubkey) -> Vec<(usize, Vec<u8>)> {
+    let Some(meta) = txn.transaction.as_ref().and_then(|tx_inner| tx_inner.meta.as_ref()) else {
+        return Vec::new();
+    };
+    let program = program.to_string();
+    let mut data = Vec::new();
+    let mut invoked: Vec<&str> = Vec::new();
+    let mut outer_index: Option<usize> = None;
+    for log in &meta.log_messages {
+        if let Some(payload) = log.strip_prefix("Program data: ") {
+            if invoked.last() == Some(&program.as_str()) {
+                if let Ok(bytes) = base64::decode(payload) {
+                    data.push((outer_index.unwrap_or_default(), bytes));
+                }
+            }
+            continue;
         }
-        Some(buffer[offset])
-    }
-    
-    // Helper function to extract token mint from token balances
-    fn extract_token_info(
-        txn: &SubscribeUpdateTransaction,
-    ) -> String {
-        
-        let mut mint = String::new();
-        
-        // Try to extract from token balances if txn is available
-        if let Some(tx_inner) = &txn.transaction {
-            if let Some(meta) = &tx_inner.meta {
-                // Check post token balances
-                if !meta.post_token_balances.is_empty() {
-                    mint = meta.post_token_balances[0].mint.clone();
-                    
-                    // Skip WSOL and look for the actual token
-                    if mint == "So11111111111111111111111111111111111111112" {
-                        if meta.post_token_balances.len() > 1 {
-                            mint = meta.post_token_balances[1].mint.clone();
-                        }
-                    }
+        let Some(rest) = log.strip_prefix("Program ") else {
+            continue;
+        };
+        let mut parts = rest.split(' ');
+        match (parts.next(), parts.next(), parts.next()) {
+            // Every depth-1 invoke starts the next outer instruction
+            (Some(program_id), Some("invoke"), Some(depth)) => {
+                if depth == "[1]" {
+                    outer_index = Some(outer_index.map_or(0, |index| index + 1));
+                    invoked.clear();
                 }
+                invoked.push(program_id);
             }
+            (Some(_), Some("success"), None) | (Some(_), Some("failed:"), _) => {
+                invoked.pop();
+            }
+            _ => {}
+        }
+    }
+    data
+}
+
+/// TradeEvents in execution order, each with the index of the outer instruction that emitted it.
+/// Self-CPI event data among the program's `instructions` is preferred; the `Program data:` logs
+/// are used when it is absent.
+fn find_trade_events(txn: &SubscribeUpdateTransaction, instructions: &[ProgramInstruction]) -> Vec<(usize, TradeEvent)> {
+    let cpi_events: Vec<(usize, TradeEvent)> = instructions
+        .iter()
+        .filter(|ix| ix.inner_index.is_some())
+        .filter_map(|ix| Some((ix.instruction_index, decode_trade_event(ix.data)?)))
+        .collect();
+    if !cpi_events.is_empty() {
+        return cpi_events;
+    }
+
+    program_data_logs(txn, &RAYDIUM_LAUNCHPAD_PROGRAM)
+        .into_iter()
+        .filter_map(|(index, data)| Some((index, decode_trade_event(&data)?)))
+        .collect()
+}
+
+/// Decoded PoolCreateEvent: pool, creator, global config and the launch parameters
+struct PoolCreateEvent {
+    pool_state: Pubkey,
+    creator: Pubkey,
+    global_config: Pubkey,
+    params: LaunchParams,
+}
+
+fn decode_pool_create_event(data: &[u8]) -> Option<PoolCreateEvent> {
+    let payload = event_payload(data);
+    if payload.len() < 8 || payload[..8] != POOL_CREATE_EVENT_DISCRIMINATOR {
+        return None;
+    }
+    let body = &payload[8..];
+    Some(PoolCreateEvent {
+        pool_state: parse_public_key(body, 0)?,
+        creator: parse_public_key(body, 32)?,
+        global_config: parse_public_key(body, 64)?,
+        params: parse_launch_params(body, 96)?,
+    })
+}
+
+/// Find the PoolCreateEvent, either as self-CPI data or in the `Program data:` logs
+fn find_pool_create_event(txn: &SubscribeUpdateTransaction) -> Option<PoolCreateEvent> {
+    let account_keys = transaction_account_keys(txn);
+    let from_cpi = program_instructions(txn, &account_keys, &RAYDIUM_LAUNCHPAD_PROGRAM)
+        .into_iter()
+        .filter(|ix| ix.inner_index.is_some())
+        .find_map(|ix| decode_pool_create_event(ix.data));
+    from_cpi.or_else(|| {
+        program_data_logs(txn, &RAYDIUM_LAUNCHPAD_PROGRAM)
+            .into_iter()
+            .find_map(|(_, data)| decode_pool_create_event(&data))
+    })
+}
+
+/// Parse a new launchpad pool from the initialize instruction and the PoolCreateEvent
+pub fn parse_launch_event(txn: &SubscribeUpdateTransaction) -> Option<LaunchEvent> {
+    if is_failed_transaction(txn) {
+        return None;
+    }
+    let account_keys = transaction_account_keys(txn);
+    let initialize = program_instructions(txn, &account_keys, &RAYDIUM_LAUNCHPAD_PROGRAM)
+        .into_iter()
+        .find(|instruction| {
+            instruction.data.len() >= 8
+                && instruction.data[..8] == INITIALIZE_DISCRIMINATOR
+                && instruction.accounts.len() >= INITIALIZE_ACCOUNTS_LEN
+        });
+    let event = find_pool_create_event(txn);
+    if initialize.is_none() && event.is_none() {
+        return None;
+    }
+
+    let account = |position: usize| {
+        initialize
+            .as_ref()
+            .and_then(|instruction| account_keys.get(instruction.accounts[position] as usize))
+            .map(|key| key.to_string())
+    };
+    let params = match &event {
+        Some(event) => event.params.clone(),
+        None => parse_launch_params(initialize.as_ref()?.data, 8)?,
+    };
+    let base_mint = match (account(INITIALIZE_ACCOUNT_BASE_MINT), &event) {
+        (Some(mint), _) => mint,
+        (None, Some(event)) => pool_mints(txn, &event.pool_state)?.0.to_string(),
+        (None, None) => return None,
+    };
+
+    Some(LaunchEvent {
+        slot: txn.slot,
+        signature: txn.transaction
+            .as_ref()
+            .map(|tx_inner| bs58::encode(&tx_inner.signature).into_string())
+            .unwrap_or_default(),
+        timestamp: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        pool_id: event.as_ref().map(|event| event.pool_state.to_string())
+            .or_else(|| account(INITIALIZE_ACCOUNT_POOL_STATE))?,
+        creator: event.as_ref().map(|event| event.creator.to_string())
+            .or_else(|| account(INITIALIZE_ACCOUNT_CREATOR))?,
+        base_mint,
+        quote_mint: account(INITIALIZE_ACCOUNT_QUOTE_MINT).unwrap_or_else(|| SOL_MINT.to_string()),
+        global_config: event.as_ref().map(|event| event.global_config.to_string())
+            .or_else(|| account(INITIALIZE_ACCOUNT_GLOBAL_CONFIG))
+            .unwrap_or_default(),
+        platform_config: account(INITIALIZE_ACCOUNT_PLATFORM_CONFIG).unwrap_or_default(),
+        decimals: params.decimals,
+        name: params.name,
+        symbol: params.symbol,
+        uri: params.uri,
+        curve: params.curve,
+        vesting: params.vesting,
+    })
+}
+
+/// Parse a launchpad or pump.fun curve migration
+pub fn parse_migration_event(txn: &SubscribeUpdateTransaction) -> Option<MigrationEvent> {
+    if is_failed_transaction(txn) {
+        return None;
+    }
+    let account_keys = transaction_account_keys(txn);
+    let launchpad_migration = if account_keys.contains(&RAYDIUM_LAUNCHPAD_PROGRAM) {
+        parse_launchpad_migration_event(txn, &account_keys)
+    } else {
+        None
+    };
+    launchpad_migration.or_else(|| {
+        if account_keys.contains(&PUMP_FUN_PROGRAM) {
+            pump_fun::parse_migration_event(txn)
+        } else {
+            None
         }
-        
-        // If we couldn't extract from token balances, use default
-        if mint.is_empty() {
-            mint = "2ivzYvjnKqA4X3dVvPKr7bctGpbxwrXbbxm44TJCpump".to_string();
+    })
+}
+
+/// Parse a launchpad migration, from the migrate_to_amm / migrate_to_cpswap instruction or,
+/// failing that, from a TradeEvent reporting the pool in Migrate status
+fn parse_launchpad_migration_event(txn: &SubscribeUpdateTransaction, account_keys: &[Pubkey]) -> Option<MigrationEvent> {
+    let signature = txn.transaction
+        .as_ref()
+        .map(|tx_inner| bs58::encode(&tx_inner.signature).into_string())
+        .unwrap_or_default();
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    for instruction in program_instructions(txn, account_keys, &RAYDIUM_LAUNCHPAD_PROGRAM) {
+        if instruction.data.len() < 8 {
+            continue;
         }
-        
-        mint
-    }
-    
-    let start_time = Instant::now();
-    
-    // Extract token mint
-    let mint = extract_token_info(&txn);
+        // (venue, base mint, new pool, launchpad pool, base vault, quote vault) account positions
+        let layout = if instruction.data[..8] == MIGRATE_TO_AMM_DISCRIMINATOR
+            && instruction.accounts.len() >= MIGRATE_TO_AMM_ACCOUNTS_LEN
+        {
+            (
+                MigrationVenue::RaydiumAmm,
+                MIGRATE_TO_AMM_ACCOUNT_BASE_MINT,
+                MIGRATE_TO_AMM_ACCOUNT_AMM_POOL,
+                MIGRATE_TO_AMM_ACCOUNT_POOL_STATE,
+                MIGRATE_TO_AMM_ACCOUNT_BASE_VAULT,
+                MIGRATE_TO_AMM_ACCOUNT_QUOTE_VAULT,
+            )
+        } else if instruction.data[..8] == MIGRATE_TO_CPSWAP_DISCRIMINATOR
+            && instruction.accounts.len() >= MIGRATE_TO_CPSWAP_ACCOUNTS_LEN
+        {
+            (
+                MigrationVenue::RaydiumCpmm,
+                MIGRATE_TO_CPSWAP_ACCOUNT_BASE_MINT,
+                MIGRATE_TO_CPSWAP_ACCOUNT_CPSWAP_POOL,
+                MIGRATE_TO_CPSWAP_ACCOUNT_POOL_STATE,
+                MIGRATE_TO_CPSWAP_ACCOUNT_BASE_VAULT,
+                MIGRATE_TO_CPSWAP_ACCOUNT_QUOTE_VAULT,
+            )
+        } else {
+            continue;
+        };
+        let (venue, base_mint, new_pool, pool_state, base_vault, quote_vault) = layout;
+        let account = |position: usize| account_keys.get(instruction.accounts[position] as usize);
+        // A migration whose accounts don't resolve is skipped, not the whole transaction
+        let (Some(mint), Some(launchpad_pool)) = (account(base_mint), account(pool_state)) else {
+            continue;
+        };
+
+        return Some(MigrationEvent {
+            slot: txn.slot,
+            signature,
+            timestamp,
+            mint: mint.to_string(),
+            launchpad_pool_id: launchpad_pool.to_string(),
+            new_pool_id: account(new_pool).map(|key| key.to_string()),
+            venue,
+            // Vaults are drained by the migration, the final reserves are their balances before it
+            final_base_reserve: pre_token_balance(txn, instruction.accounts[base_vault]).unwrap_or_default(),
+            final_quote_reserve: pre_token_balance(txn, instruction.accounts[quote_vault]).unwrap_or_default(),
+        });
+    }
+
+    // The trade that completes the curve reports Migrate status before the migration itself lands
+    let instructions = program_instructions(txn, account_keys, &RAYDIUM_LAUNCHPAD_PROGRAM);
+    let (_, event) = find_trade_events(txn, &instructions)
+        .into_iter()
+        .find(|(_, event)| event.pool_status == PoolStatus::Migrate)?;
+    let mint = match find_swap_instructions(&instructions, account_keys).into_iter().find(|swap| swap.pool_state == event.pool_state) {
+        Some(swap) => swap.base_mint.to_string(),
+        None => pool_mints(txn, &event.pool_state)?.0.to_string(),
+    };
+
+    Some(MigrationEvent {
+        slot: txn.slot,
+        signature,
+        timestamp,
+        mint,
+        launchpad_pool_id: event.pool_state.to_string(),
+        new_pool_id: None,
+        venue: MigrationVenue::Pending,
+        final_base_reserve: event.real_base_after,
+        final_quote_reserve: event.real_quote_after,
+    })
+}
+
+/// Raw token balance of the given account index before the transaction executed
+fn pre_token_balance(txn: &SubscribeUpdateTransaction, account_index: u8) -> Option<u64> {
+    let meta = txn.transaction.as_ref()?.meta.as_ref()?;
+    meta.pre_token_balances
+        .iter()
+        .find(|balance| balance.account_index == account_index as u32)
+        .and_then(|balance| balance.ui_token_amount.as_ref())
+        .and_then(|amount| amount.amount.parse().ok())
+}
+
+/// Mint of a token account touched by the transaction, read from its token balances
+pub fn token_account_mint(txn: &SubscribeUpdateTransaction, account_keys: &[Pubkey], account: &Pubkey) -> Option<Pubkey> {
+    let meta = txn.transaction.as_ref()?.meta.as_ref()?;
+    let account_index = account_keys.iter().position(|key| key == account)? as u32;
+    meta.post_token_balances
+        .iter()
+        .chain(&meta.pre_token_balances)
+        .find(|balance| balance.account_index == account_index)
+        .and_then(|balance| Pubkey::from_str(&balance.mint).ok())
+}
+
+/// Base and quote mint of a launchpad pool the transaction touched without a decodable
+/// instruction: from the pool index, or else by trying each mint in the token balances against
+/// the known quote mints, since pools are PDAs of their mint pair. `None` when neither finds
+/// `pool_state`.
+fn pool_mints(txn: &SubscribeUpdateTransaction, pool_state: &Pubkey) -> Option<(Pubkey, Pubkey)> {
+    if let Some(pool) = POOL_INDEX.get_by_pool_id(&pool_state.to_string()) {
+        return Some((pool.base_mint, pool.quote_mint));
+    }
+    let meta = txn.transaction.as_ref()?.meta.as_ref()?;
+    let mut base_mints: Vec<Pubkey> = meta.post_token_balances
+        .iter()
+        .chain(&meta.pre_token_balances)
+        .filter_map(|balance| Pubkey::from_str(&balance.mint).ok())
+        .filter(|mint| !KNOWN_QUOTE_MINTS.contains(mint))
+        .collect();
+    base_mints.sort();
+    base_mints.dedup();
+    base_mints.into_iter().find_map(|base_mint| {
+        KNOWN_QUOTE_MINTS
+            .iter()
+            .find(|quote_mint| derive_pool_id(&base_mint, quote_mint) == *pool_state)
+            .map(|quote_mint| (base_mint, *quote_mint))
+    })
+}
+
+/// Pair every launchpad swap instruction with the TradeEvent it emitted, in execution order.
+/// An event is matched to the first swap of the same outer instruction and pool. Events without a
+/// decodable swap are still returned when their pool's mints can be derived; swaps without an
+/// event (truncated logs) are dropped, since their instruction only carries slippage bounds, not
+/// the traded amounts.
+fn parse_trades(txn: &SubscribeUpdateTransaction, account_keys: &[Pubkey]) -> Vec<TradeInfoFromToken> {
+    let instructions = program_instructions(txn, account_keys, &RAYDIUM_LAUNCHPAD_PROGRAM);
+    let swaps = find_swap_instructions(&instructions, account_keys);
+    let mut events: Vec<Option<(usize, TradeEvent)>> = find_trade_events(txn, &instructions).into_iter().map(Some).collect();
+    let mut trades = Vec::new();
+
+    for swap in &swaps {
+        let event = events
+            .iter_mut()
+            .find(|slot| {
+                slot.as_ref().is_some_and(|(index, event)| {
+                    *index == swap.instruction_index && event.pool_state == swap.pool_state
+                })
+            })
+            .and_then(|slot| slot.take())
+            .map(|(_, event)| event);
+        if let Some(event) = event {
+            trades.push(build_trade_info(txn, (swap.base_mint, swap.quote_mint), Some(swap), &event));
+        }
+    }
+    for (instruction_index, event) in events.into_iter().flatten() {
+        // Attributing the event to another mint of a bundle or route would be worse than dropping it
+        let Some(mints) = pool_mints(txn, &event.pool_state) else {
+            continue;
+        };
+        let mut trade = build_trade_info(txn, mints, None, &event);
+        trade.instruction_index = instruction_index;
+        trades.push(trade);
+    }
+
+    trades.sort_by_key(|trade| (trade.instruction_index, trade.inner_instruction_index));
+    trades
+}
+
+/// Combine the swap instruction accounts and the TradeEvent amounts into a TradeInfoFromToken.
+/// The swap may be missing, but the event is required: it is the only source of the amounts.
+fn build_trade_info(
+    txn: &SubscribeUpdateTransaction,
+    (base_mint, quote_mint): (Pubkey, Pubkey),
+    swap: Option<&LaunchpadSwapInstruction>,
+    event: &TradeEvent,
+) -> TradeInfoFromToken {
+    let signature = txn.transaction
+        .as_ref()
+        .map(|tx_inner| bs58::encode(&tx_inner.signature).into_string())
+        .unwrap_or_default();
     let timestamp = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap_or_default()
         .as_secs();
-    
-    // Determine if this is a buy or sell based on instruction logs
-    let is_buy = has_buy_instruction(txn);
-    
-    // For Raydium Launchpad, we'll use simplified parsing
-    // In a real implementation, you'd parse the specific Raydium instruction data
-    let price = 1000000000; // Default price in lamports
-    let sol_change = if is_buy { -0.1 } else { 0.1 }; // Example values
-    let token_change = if is_buy { 1000000.0 } else { -1000000.0 }; // Example values
-    let liquidity = 1000.0; // Example liquidity
-    let virtual_sol_reserves = 30000000000; // 30 SOL in lamports
-    let virtual_token_reserves = 1000000000000000; // 1B tokens
-    
-    dex_log(format!("RaydiumLaunchpad {}: {} SOL (Price: {})", 
-        if is_buy { "BUY" } else { "SELL" },
-        sol_change.abs(), 
-        price as f64 / 1_000_000_000.0
+
+    quote_mint::track_quote_mint(&quote_mint);
+    // Quote amounts are converted to SOL; they stay 0 while a non-SOL quote has no rate yet
+    let quote_to_sol = |amount: u64| quote_mint::quote_to_sol(&quote_mint, amount).unwrap_or_default();
+
+    let token_scale = 10f64.powi(LAUNCHPAD_TOKEN_DECIMALS as i32);
+    // Buy: quote in, tokens out. Sell: tokens in, quote out.
+    let (sol_change, token_change) = if event.is_buy {
+        (
+            -quote_to_sol(event.amount_in),
+            event.amount_out as f64 / token_scale,
+        )
+    } else {
+        (
+            quote_to_sol(event.amount_out),
+            -(event.amount_in as f64) / token_scale,
+        )
+    };
+    let price = quote_mint::quote_price_to_lamports(&quote_mint, event.price()).unwrap_or_default();
+
+    dex_log(format!("RaydiumLaunchpad {}: {} SOL (Price: {})",
+        if event.is_buy { "BUY" } else { "SELL" },
+        sol_change.abs(),
+        price / LAMPORTS_PER_SOL_F64
     ).green().to_string());
-    
-    Some(TradeInfoFromToken {
+
+    TradeInfoFromToken {
         dex_type: DexType::RaydiumLaunchpad,
-        slot: 0, // Will be set from transaction data
-        signature: String::new(), // Will be set from transaction data
-        pool_id: String::new(), // Will be set from transaction data
-        mint: mint.clone(),
+        slot: txn.slot,
+        signature,
+        pool_id: event.pool_state.to_string(),
+        mint: base_mint.to_string(),
+        quote_mint: quote_mint.to_string(),
         timestamp,
-        is_buy,
+        is_buy: event.is_buy,
         price,
         is_reverse: false, // Raydium Launchpad doesn't use reverse logic
         coin_creator: None, // Will be extracted from metadata if available
         sol_change,
         token_change,
-        liquidity,
-        virtual_sol_reserves,
-        virtual_token_reserves,
-    })
+        liquidity: quote_to_sol(event.real_quote_after),
+        virtual_sol_reserves: event.quote_reserve(),
+        virtual_token_reserves: event.base_reserve(),
+        amount_in: event.amount_in,
+        amount_out: event.amount_out,
+        real_sol_reserves: event.real_quote_after,
+        real_token_reserves: event.real_base_after,
+        curve_virtual_base: event.virtual_base,
+        curve_virtual_quote: event.virtual_quote,
+        total_base_sell: event.total_base_sell,
+        protocol_fee: event.protocol_fee,
+        platform_fee: event.platform_fee,
+        creator_fee: event.creator_fee,
+        share_fee: event.share_fee,
+        user: swap.map(|swap| swap.user.to_string()).unwrap_or_default(),
+        base_vault: swap.map(|swap| swap.base_vault.to_string()).unwrap_or_default(),
+        quote_vault: swap.map(|swap| swap.quote_vault.to_string()).unwrap_or_default(),
+        instruction_index: swap.map(|swap| swap.instruction_index).unwrap_or_default(),
+        inner_instruction_index: swap.and_then(|swap| swap.inner_index),
+    }
 }
 
-/// Main function to process transaction and extract trade information
-pub fn process_transaction(txn: &SubscribeUpdateTransaction) -> Option<TradeInfoFromToken> {
-    // Check if this transaction involves the Raydium Launchpad program
-    if let Some(tx_inner) = &txn.transaction {
-        if let Some(transaction) = &tx_inner.transaction {
-            if let Some(message) = &transaction.message {
-                // Check if any of the account keys match the Raydium Launchpad program
-                let raydium_program_id = match Pubkey::from_str(&RAYDIUM_LAUNCHPAD_PROGRAM) {
-                    Ok(pubkey) => pubkey,
-                    Err(_) => return None,
-                };
-                
-                if message.account_keys.contains(&raydium_program_id) {
-                    // Extract instruction data if available
-                    if let Some(meta) = &tx_inner.meta {
-                        if let Some(inner_instructions) = &meta.inner_instructions {
-                            for inner_instruction in inner_instructions {
-                                for instruction in &inner_instruction.instructions {
-                                    if let Some(data) = &instruction.data {
-                                        if let Some(trade_info) = parse_transaction_data(txn, data) {
-                                            return Some(trade_info);
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
+/// Main function to process a transaction and extract every launchpad, pump.fun, CPMM and
+/// PumpSwap trade it contains, ordered by instruction index
+pub fn process_transaction(txn: &SubscribeUpdateTransaction) -> Vec<TradeInfoFromToken> {
+    if is_failed_transaction(txn) {
+        return Vec::new();
+    }
+    let account_keys = transaction_account_keys(txn);
+    let mut trades = Vec::new();
+    if account_keys.contains(&RAYDIUM_LAUNCHPAD_PROGRAM) {
+        trades.extend(parse_trades(txn, &account_keys));
     }
-    
-    None
-}
\ No newline at end of file
+    if account_keys.contains(&PUMP_FUN_PROGRAM) {
+        trades.extend(pump_fun::parse_trades(txn, &account_keys));
+    }
+    if account_keys.contains(&RAYDIUM_CPMM_PROGRAM) {
+        trades.extend(raydium_cpmm::parse_trades(txn, &account_keys));
+    }
+    if account_keys.contains(&PUMP_SWAP_PROGRAM) {
+        trades.extend(pump_swap::parse_trades(txn, &account_keys));
+    }
+
+    trades.sort_by_key(|trade| (trade.instruction_index, trade.inner_instruction_index));
+    trades
+}
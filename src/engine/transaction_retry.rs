@@ -1,27 +1,20 @@
+use std::str::FromStr;
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 use anyhow::{anyhow, Result};
-use anchor_client::solana_sdk::{
-    pubkey::Pubkey, 
-    signature::{Signature, Keypair}, 
-    instruction::Instruction,
-    transaction::{VersionedTransaction, Transaction},
-    signer::Signer,
-    hash::Hash,
-};
-use spl_associated_token_account::get_associated_token_address;
+use anchor_client::solana_sdk::{pubkey::Pubkey, signature::Signature, signer::Signer};
 use colored::Colorize;
+use solana_transaction_status::TransactionConfirmationStatus;
 use tokio::time::sleep;
-use base64;
 
 use crate::common::{
     config::{AppState, SwapConfig},
     logger::Logger,
 };
-use crate::engine::swap::SwapDirection;
+use crate::core::token::get_wallet_token_address;
+use crate::engine::swap::SwapProtocol;
 use crate::services::jupiter_api::JupiterClient;
 use crate::engine::transaction_parser::TradeInfoFromToken;
-use crate::core::tx;
 
 /// Maximum number of retry attempts for selling transactions
 const MAX_RETRIES: u32 = 3;
@@ -29,8 +22,8 @@ const MAX_RETRIES: u32 = 3;
 /// Delay between retry attempts
 const RETRY_DELAY: Duration = Duration::from_secs(2);
 
-/// Timeout for transaction verification
-const VERIFICATION_TIMEOUT: Duration = Duration::from_secs(30);
+/// Slippage for the Jupiter fallback, in basis points
+const JUPITER_FALLBACK_SLIPPAGE_BPS: u64 = 2000;
 
 /// Result of a selling transaction attempt
 #[derive(Debug)]
@@ -50,60 +43,72 @@ pub async fn verify_transaction_with_retry(
     max_retries: u32,
 ) -> Result<bool> {
     let mut retry_count = 0;
-    
+
     while retry_count < max_retries {
-        match app_state.rpc_client.get_signature_status(signature).await {
-            Ok(Some(status)) => {
-                if status.confirmation_status == Some(solana_transaction_status::TransactionConfirmationStatus::Confirmed) {
-                    return Ok(true);
-                } else if status.confirmation_status == Some(solana_transaction_status::TransactionConfirmationStatus::Finalized) {
+        match app_state.rpc_nonblocking_client.get_signature_statuses(&[*signature]).await {
+            Ok(response) => match response.value.first().cloned().flatten() {
+                Some(status) if status.err.is_some() => {
+                    return Err(anyhow!("Transaction failed: {:?}", status.err));
+                }
+                Some(status) if matches!(
+                    status.confirmation_status,
+                    Some(TransactionConfirmationStatus::Confirmed | TransactionConfirmationStatus::Finalized)
+                ) => {
                     return Ok(true);
-                } else {
+                }
+                Some(_) => {
                     logger.log(format!("Transaction not confirmed yet, retry {}/{}", retry_count + 1, max_retries));
-                    retry_count += 1;
-                    sleep(RETRY_DELAY).await;
                 }
-            }
-            Ok(None) => {
-                logger.log(format!("Transaction not found, retry {}/{}", retry_count + 1, max_retries));
-                retry_count += 1;
-                sleep(RETRY_DELAY).await;
-            }
+                None => {
+                    logger.log(format!("Transaction not found, retry {}/{}", retry_count + 1, max_retries));
+                }
+            },
             Err(e) => {
                 logger.log(format!("Error verifying transaction: {}, retry {}/{}", e, retry_count + 1, max_retries));
-                retry_count += 1;
-                sleep(RETRY_DELAY).await;
             }
         }
+        retry_count += 1;
+        sleep(RETRY_DELAY).await;
     }
-    
+
     Err(anyhow!("Transaction verification failed after {} retries", max_retries))
 }
 
-/// Execute sell transaction with comprehensive retry logic
-pub async fn execute_sell_with_retry(
+/// Execute a sell on the venue for `protocol` with retries, falling back to Jupiter
+pub async fn execute_sell_with_retry_and_fallback(
     trade_info: &TradeInfoFromToken,
     sell_config: SwapConfig,
     app_state: Arc<AppState>,
+    protocol: &SwapProtocol,
     logger: &Logger,
 ) -> Result<SellTransactionResult> {
     let mut attempt_count = 0;
     let mut last_error = None;
-    
-    // Try Raydium Launchpad first
-    while attempt_count < MAX_RETRIES {
-        attempt_count += 1;
-        logger.log(format!("Sell attempt {}/{} for token {}", attempt_count, MAX_RETRIES, trade_info.mint).yellow().to_string());
-        
-        match execute_raydium_sell_attempt(trade_info, sell_config.clone(), app_state.clone(), logger).await {
-            Ok(signature) => {
-                logger.log(format!("Raydium sell transaction sent: {}", signature).green().to_string());
-                
-                // Verify the transaction
-                match verify_transaction_with_retry(&signature, app_state.clone(), logger, 3).await {
-                    Ok(verified) => {
-                        if verified {
-                            logger.log("Raydium sell transaction verified successfully".green().to_string());
+
+    // Mints that completed their launchpad curve are sold on the venue they migrated to
+    let route = match crate::engine::selling_strategy::route_sell(trade_info, protocol) {
+        Ok(route) => Some(route),
+        Err(e) => {
+            logger.log(format!("{}, skipping venue sell", e).yellow().to_string());
+            last_error = Some(e.to_string());
+            None
+        }
+    };
+
+    // Try the venue first
+    if let Some((protocol, trade_info)) = &route {
+        while attempt_count < MAX_RETRIES {
+            attempt_count += 1;
+            logger.log(format!("Sell attempt {}/{} for token {}", attempt_count, MAX_RETRIES, trade_info.mint).yellow().to_string());
+
+            match execute_dex_sell_attempt(trade_info, sell_config.clone(), app_state.clone(), protocol, logger).await {
+                Ok(signature) => {
+                    logger.log(format!("{:?} sell transaction sent: {}", protocol, signature).green().to_string());
+
+                    // Verify the transaction
+                    match verify_transaction_with_retry(&signature, app_state.clone(), logger, 3).await {
+                        Ok(_) => {
+                            logger.log(format!("{:?} sell transaction verified successfully", protocol).green().to_string());
                             return Ok(SellTransactionResult {
                                 success: true,
                                 signature: Some(signature),
@@ -111,158 +116,138 @@ pub async fn execute_sell_with_retry(
                                 used_jupiter_fallback: false,
                                 attempt_count,
                             });
-                        } else {
-                            last_error = Some("Transaction verification failed".to_string());
+                        }
+                        Err(e) => {
+                            last_error = Some(format!("Transaction verification error: {}", e));
                         }
                     }
-                    Err(e) => {
-                        last_error = Some(format!("Transaction verification error: {}", e));
-                    }
+                }
+                Err(e) => {
+                    logger.log(format!("{:?} sell attempt {} failed: {}", protocol, attempt_count, e).red().to_string());
+                    last_error = Some(format!("{:?} sell failed: {}", protocol, e));
                 }
             }
-            Err(e) => {
-                last_error = Some(format!("Raydium sell failed: {}", e));
-                logger.log(format!("Raydium sell attempt {} failed: {}", attempt_count, e).red().to_string());
+
+            if attempt_count < MAX_RETRIES {
+                sleep(RETRY_DELAY).await;
             }
         }
-        
-        if attempt_count < MAX_RETRIES {
-            sleep(RETRY_DELAY).await;
-        }
     }
-    
-    // If Raydium failed, try Jupiter as fallback
-    logger.log("Raydium sell failed, trying Jupiter fallback...".yellow().to_string());
-    
-    match execute_jupiter_sell_attempt(trade_info, sell_config, app_state.clone(), logger).await {
+
+    // If the venue failed, try Jupiter as fallback
+    logger.log(format!("{:?} sell failed, trying Jupiter fallback...", protocol).yellow().to_string());
+
+    let venue_error = last_error.unwrap_or_default();
+    let error = match execute_jupiter_sell_attempt(trade_info, app_state.clone(), logger).await {
         Ok(signature) => {
             logger.log(format!("Jupiter sell transaction sent: {}", signature).green().to_string());
-            
+
             // Verify the transaction
             match verify_transaction_with_retry(&signature, app_state.clone(), logger, 3).await {
-                Ok(verified) => {
-                    if verified {
-                        logger.log("Jupiter sell transaction verified successfully".green().to_string());
-                        return Ok(SellTransactionResult {
-                            success: true,
-                            signature: Some(signature),
-                            error: None,
-                            used_jupiter_fallback: true,
-                            attempt_count,
-                        });
-                    } else {
-                        last_error = Some("Jupiter transaction verification failed".to_string());
-                    }
-                }
-                Err(e) => {
-                    last_error = Some(format!("Jupiter transaction verification error: {}", e));
+                Ok(_) => {
+                    logger.log("Jupiter sell transaction verified successfully".green().to_string());
+                    return Ok(SellTransactionResult {
+                        success: true,
+                        signature: Some(signature),
+                        error: None,
+                        used_jupiter_fallback: true,
+                        attempt_count,
+                    });
                 }
+                Err(e) => format!("Jupiter transaction verification error: {}", e),
             }
         }
         Err(e) => {
-            last_error = Some(format!("Jupiter sell failed: {}", e));
             logger.log(format!("Jupiter sell failed: {}", e).red().to_string());
+            format!("Jupiter sell failed: {}", e)
         }
-    }
-    
+    };
+
     Ok(SellTransactionResult {
         success: false,
         signature: None,
-        error: last_error,
+        error: Some(format!("{}; {}", venue_error, error)),
         used_jupiter_fallback: true,
         attempt_count,
     })
 }
 
-/// Execute Raydium sell attempt
-async fn execute_raydium_sell_attempt(
+/// Build and send one sell on the venue registered for `protocol`
+async fn execute_dex_sell_attempt(
     trade_info: &TradeInfoFromToken,
     sell_config: SwapConfig,
     app_state: Arc<AppState>,
+    protocol: &SwapProtocol,
     logger: &Logger,
 ) -> Result<Signature> {
-    let raydium = crate::dex::raydium_launchpad::RaydiumLaunchpad::new(
-        app_state.wallet.clone(),
-        Some(app_state.rpc_client.clone()),
-        Some(app_state.rpc_nonblocking_client.clone()),
-    );
+    let dex = app_state.dex_registry.get(protocol)
+        .ok_or_else(|| anyhow!("No venue registered for protocol {:?}", protocol))?;
 
-    let (keypair, instructions, _price) = raydium.build_swap_from_parsed_data(trade_info, sell_config).await
-        .map_err(|e| anyhow!("Failed to build Raydium swap: {}", e))?;
+    let (keypair, instructions, _price) = dex.build_sell(trade_info, sell_config).await
+        .map_err(|e| anyhow!("Failed to build {:?} swap: {}", protocol, e))?;
 
     let recent_blockhash = crate::services::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await
         .ok_or_else(|| anyhow!("Failed to get recent blockhash"))?;
 
-    let signature = crate::core::tx::new_signed_and_send_zeroslot(
-        app_state.zeroslot_rpc_client.clone(),
-        recent_blockhash,
-        &keypair,
-        instructions,
-        logger,
-    ).await
-    .map_err(|e| anyhow!("Failed to send Raydium transaction: {}", e))?;
-
-    if signature.is_empty() {
-        return Err(anyhow!("No signature returned from Raydium transaction"));
-    }
+    let sent = match &app_state.relay_fanout {
+        Some(relay_fanout) => crate::core::tx::new_signed_and_send_fanout(
+            relay_fanout,
+            recent_blockhash,
+            &keypair,
+            instructions,
+            logger,
+            crate::services::priority_fees::FeeIntent::Sell,
+        ).await,
+        None => crate::core::tx::new_signed_and_send_zeroslot(
+            app_state.zeroslot_rpc_client.clone(),
+            recent_blockhash,
+            &keypair,
+            instructions,
+            logger,
+            crate::services::priority_fees::FeeIntent::Sell,
+        ).await,
+    };
+    let signatures = sent.map_err(|e| anyhow!("Failed to send {:?} transaction: {}", protocol, e))?;
 
-    let signature = Signature::from_str(&signature[0])
-        .map_err(|e| anyhow!("Failed to parse signature: {}", e))?;
-    Ok(signature)
+    let signature = signatures.first()
+        .ok_or_else(|| anyhow!("No signature returned from {:?} transaction", protocol))?;
+    Signature::from_str(signature)
+        .map_err(|e| anyhow!("Failed to parse signature: {}", e))
 }
 
-/// Execute Jupiter sell attempt as fallback
+/// Sell the whole wallet balance of the token through Jupiter
 async fn execute_jupiter_sell_attempt(
     trade_info: &TradeInfoFromToken,
-    sell_config: SwapConfig,
     app_state: Arc<AppState>,
     logger: &Logger,
 ) -> Result<Signature> {
-    let jupiter_client = JupiterClient::new();
-    
-    // Get wallet public key
+    let jupiter_client = JupiterClient::new(app_state.rpc_nonblocking_client.clone());
+
     let wallet_pubkey = app_state.wallet.try_pubkey()
         .map_err(|_| anyhow!("Failed to get wallet public key"))?;
-    
-    // Get token mint
     let token_mint = Pubkey::from_str(&trade_info.mint)
         .map_err(|_| anyhow!("Invalid token mint"))?;
-    
-    // Get associated token account
-    let token_account = get_associated_token_address(&wallet_pubkey, &token_mint);
-    
-    // Get SOL mint for WSOL
-    let sol_mint = Pubkey::from_str("So11111111111111111111111111111111111111112")
-        .map_err(|_| anyhow!("Invalid SOL mint"))?;
-    
-    // Get WSOL account
-    let wsol_account = get_associated_token_address(&wallet_pubkey, &sol_mint);
-    
-    // Calculate amount to sell (use a percentage of the token amount)
-    let amount_to_sell = (trade_info.token_change * 0.5) as u64; // Sell 50% of tokens
-    
-    // Get quote from Jupiter
-    let quote = jupiter_client.get_quote(
-        &token_mint,
-        &sol_mint,
-        amount_to_sell,
-        sell_config.slippage_bps,
-    ).await
-    .map_err(|e| anyhow!("Failed to get Jupiter quote: {}", e))?;
-    
-    // Get swap transaction from Jupiter
-    let swap_transaction = jupiter_client.get_swap_transaction(
-        &quote,
-        &wallet_pubkey,
-        &token_account,
-        &wsol_account,
-    ).await
-    .map_err(|e| anyhow!("Failed to get Jupiter swap transaction: {}", e))?;
-    
-    // Send the transaction
-    let signature = app_state.rpc_client.send_and_confirm_transaction(&swap_transaction)
-        .await
-        .map_err(|e| anyhow!("Failed to send Jupiter transaction: {}", e))?;
-    
-    Ok(signature)
-}
\ No newline at end of file
+    let token_account = get_wallet_token_address(app_state.rpc_nonblocking_client.clone(), &wallet_pubkey, &token_mint).await?;
+
+    let token_balance = app_state.rpc_nonblocking_client.get_token_account(&token_account).await
+        .map_err(|e| anyhow!("Failed to get token balance: {}", e))?
+        .ok_or_else(|| anyhow!("Token account not found"))?
+        .token_amount
+        .amount
+        .parse::<u64>()
+        .map_err(|e| anyhow!("Failed to parse token amount: {}", e))?;
+    if token_balance == 0 {
+        return Err(anyhow!("No tokens to sell"));
+    }
+
+    logger.log(format!("Selling {} raw units of {} through Jupiter", token_balance, trade_info.mint));
+    let signature = jupiter_client.sell_token_with_jupiter(
+        &trade_info.mint,
+        token_balance,
+        JUPITER_FALLBACK_SLIPPAGE_BPS,
+        &app_state.wallet,
+    ).await?;
+
+    Signature::from_str(&signature)
+        .map_err(|e| anyhow!("Failed to parse signature: {}", e))
+}
@@ -45,7 +45,7 @@ pub enum ClientError {
     /// Error deserializing data using Borsh
     BorshError(std::io::Error),
     /// Error from Solana RPC client
-    SolanaClientError(anchor_client::solana_client::client_error::ClientError),
+    SolanaClientError(Box<anchor_client::solana_client::client_error::ClientError>),
     /// Error uploading metadata
     UploadMetadataError(Box<dyn std::error::Error>),
     /// Invalid input parameters
@@ -135,7 +135,7 @@ impl std::error::Error for ClientError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             Self::BorshError(err) => Some(err),
-            Self::SolanaClientError(err) => Some(err),
+            Self::SolanaClientError(err) => Some(err.as_ref()),
             Self::UploadMetadataError(err) => Some(err.as_ref()),
             Self::ExternalService(_) => None,
             Self::Redis(_, _) => None,
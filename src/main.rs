@@ -14,21 +14,25 @@
 
 use anchor_client::solana_sdk::signature::Signer;
 use solana_vntr_sniper::{
-    common::{config::Config, constants::RUN_MSG, cache::WALLET_TOKEN_ACCOUNTS},
+    common::{config::Config, constants::RUN_MSG, cache::{MINT_TOKEN_PROGRAMS, WALLET_TOKEN_ACCOUNTS}},
     engine::{
         sniper_bot::{start_target_wallet_monitoring, start_dex_monitoring, SniperConfig},
         swap::SwapProtocol,
     },
     services::{ 
         cache_maintenance, 
+        quote_rates,
+        priority_fees,
+        zeroslot_tips,
         blockhash_processor::BlockhashProcessor,
         jupiter_api::JupiterClient
     },
-    core::token,
+    core::{lookup_table, token},
+    dex::quote_mint,
 };
 use std::sync::Arc;
-use solana_program_pack::Pack;
 use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::commitment_config::CommitmentConfig;
 use anchor_client::solana_sdk::transaction::Transaction;
 use anchor_client::solana_sdk::system_instruction;
 use std::str::FromStr;
@@ -36,6 +40,34 @@ use colored::Colorize;
 use spl_token::instruction::sync_native;
 use spl_token::ui_amount_to_amount;
 use spl_associated_token_account::get_associated_token_address;
+use spl_token_2022::{extension::StateWithExtensions, state::{Account as TokenAccount, Mint}};
+
+/// All token accounts owned by the wallet, paired with the token program that owns each
+fn get_wallet_token_accounts(
+    config: &Config,
+    wallet_pubkey: &Pubkey,
+) -> Result<Vec<(anchor_client::solana_client::rpc_response::RpcKeyedAccount, Pubkey)>, String> {
+    let mut token_accounts = Vec::new();
+    for token_program in [spl_token::ID, spl_token_2022::ID] {
+        let accounts = config.app_state.rpc_client.get_token_accounts_by_owner(
+            wallet_pubkey,
+            anchor_client::solana_client::rpc_request::TokenAccountsFilter::ProgramId(token_program)
+        ).map_err(|e| format!("Failed to get token accounts for {}: {}", token_program, e))?;
+        token_accounts.extend(accounts.into_iter().map(|account| (account, token_program)));
+    }
+    Ok(token_accounts)
+}
+
+/// Mint of a token account returned by `getTokenAccountsByOwner`, which answers in jsonParsed
+/// encoding but may fall back to binary
+fn token_account_mint(account: &anchor_client::solana_client::rpc_response::RpcKeyedAccount) -> Option<Pubkey> {
+    match &account.account.data {
+        solana_account_decoder::UiAccountData::Json(parsed) => {
+            Pubkey::from_str(parsed.parsed.get("info")?.get("mint")?.as_str()?).ok()
+        }
+        data => StateWithExtensions::<TokenAccount>::unpack(&data.decode()?).ok().map(|state| state.base.mint),
+    }
+}
 
 /// Initialize the wallet token account list by fetching all token accounts owned by the wallet
 async fn initialize_token_account_list(config: &Config) {
@@ -44,22 +76,20 @@ async fn initialize_token_account_list(config: &Config) {
     if let Ok(wallet_pubkey) = config.app_state.wallet.try_pubkey() {
         logger.log(format!("Initializing token account list for wallet: {}", wallet_pubkey));
         
-        // Get the token program pubkey
-        let token_program = Pubkey::from_str("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA").unwrap();
-        
-        // Query all token accounts owned by the wallet
-        let accounts = config.app_state.rpc_client.get_token_accounts_by_owner(
-            &wallet_pubkey,
-            anchor_client::solana_client::rpc_request::TokenAccountsFilter::ProgramId(token_program)
-        );
+        // Query all token accounts owned by the wallet, under both token programs
+        let accounts = get_wallet_token_accounts(config, &wallet_pubkey);
         match accounts {
             Ok(accounts) => {
                 logger.log(format!("Found {} existing token accounts", accounts.len()));
                 
-                // Add each token account to our global cache
-                for account in accounts {
+                // Add each token account to our global cache, and remember which program owns its
+                // mint so positions from earlier runs resolve to the right ATA
+                for (account, token_program) in accounts {
                     let account_pubkey = Pubkey::from_str(&account.pubkey).unwrap();
                     WALLET_TOKEN_ACCOUNTS.insert(account_pubkey);
+                    if let Some(mint) = token_account_mint(&account) {
+                        MINT_TOKEN_PROGRAMS.insert(mint, token_program);
+                    }
                     logger.log(format!("✅ Cached token account: {}", account.pubkey ));
                 }
                 
@@ -166,7 +196,7 @@ async fn unwrap_sol(config: &Config) -> Result<(), String> {
     
     // Close the WSOL account to recover SOL
     let close_instruction = token::close_account(
-        wallet_pubkey,
+        &spl_token::ID,
         wsol_account,
         wallet_pubkey,
         wallet_pubkey,
@@ -210,14 +240,8 @@ async fn sell_all_tokens(config: &Config) -> Result<(), String> {
     
     logger.log(format!("🔍 Scanning wallet {} for tokens to sell", wallet_pubkey));
     
-    // Get the token program pubkey
-    let token_program = Pubkey::from_str("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA").unwrap();
-    
     // Query all token accounts owned by the wallet
-    let accounts = config.app_state.rpc_client.get_token_accounts_by_owner(
-        &wallet_pubkey,
-        anchor_client::solana_client::rpc_request::TokenAccountsFilter::ProgramId(token_program)
-    ).map_err(|e| format!("Failed to get token accounts: {}", e))?;
+    let accounts = get_wallet_token_accounts(config, &wallet_pubkey)?;
     
     if accounts.is_empty() {
         logger.log("No token accounts found".to_string());
@@ -236,7 +260,7 @@ async fn sell_all_tokens(config: &Config) -> Result<(), String> {
     let mut failed_count = 0;
     let mut total_sol_received = 0u64;
     
-    for account_info in accounts {
+    for (account_info, _) in accounts {
         let token_account = Pubkey::from_str(&account_info.pubkey)
             .map_err(|_| format!("Invalid token account pubkey: {}", account_info.pubkey))?;
         
@@ -249,8 +273,8 @@ async fn sell_all_tokens(config: &Config) -> Result<(), String> {
             }
         };
         
-        // Parse token account data
-        if let Ok(token_data) = spl_token::state::Account::unpack(&account_data.data) {
+        // Parse token account data (Token-2022 accounts may carry extensions)
+        if let Ok(token_data) = StateWithExtensions::<TokenAccount>::unpack(&account_data.data).map(|state| state.base) {
             // Skip WSOL (wrapped SOL) and accounts with zero balance
             if token_data.mint == spl_token::native_mint::id() || token_data.amount == 0 {
                 continue;
@@ -267,8 +291,8 @@ async fn sell_all_tokens(config: &Config) -> Result<(), String> {
                 }
             };
             
-            let mint_info = match spl_token::state::Mint::unpack(&mint_data.data) {
-                Ok(info) => info,
+            let mint_info = match StateWithExtensions::<Mint>::unpack(&mint_data.data) {
+                Ok(state) => state.base,
                 Err(e) => {
                     logger.log(format!("Failed to parse mint data for {}: {}", token_data.mint, e).yellow().to_string());
                     continue;
@@ -293,7 +317,7 @@ async fn sell_all_tokens(config: &Config) -> Result<(), String> {
     logger.log(format!("💱 Starting to sell {} tokens", tokens_to_sell.len()));
     
     // Sell each token using Jupiter API
-    for (mint, amount, decimals) in tokens_to_sell {
+    for (mint, amount, _decimals) in tokens_to_sell {
         logger.log(format!("💱 Selling token: {}", mint).cyan().to_string());
         
         // First get the quote to show detailed information
@@ -367,14 +391,8 @@ async fn close_all_token_accounts(config: &Config) -> Result<(), String> {
         Err(_) => return Err("Failed to get wallet pubkey".to_string()),
     };
     
-    // Get the token program pubkey
-    let token_program = Pubkey::from_str("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA").unwrap();
-    
     // Query all token accounts owned by the wallet
-    let accounts = config.app_state.rpc_client.get_token_accounts_by_owner(
-        &wallet_pubkey,
-        anchor_client::solana_client::rpc_request::TokenAccountsFilter::ProgramId(token_program)
-    ).map_err(|e| format!("Failed to get token accounts: {}", e))?;
+    let accounts = get_wallet_token_accounts(config, &wallet_pubkey)?;
     
     if accounts.is_empty() {
         logger.log("No token accounts found to close".to_string());
@@ -387,7 +405,7 @@ async fn close_all_token_accounts(config: &Config) -> Result<(), String> {
     let mut failed_count = 0;
     
     // Close each token account
-    for account_info in accounts {
+    for (account_info, token_program) in accounts {
         let token_account = Pubkey::from_str(&account_info.pubkey)
             .map_err(|_| format!("Invalid token account pubkey: {}", account_info.pubkey))?;
         
@@ -402,7 +420,7 @@ async fn close_all_token_accounts(config: &Config) -> Result<(), String> {
         };
         
         // Check if this is a WSOL account with balance
-        if let Ok(token_data) = spl_token::state::Account::unpack(&account_data.data) {
+        if let Ok(token_data) = StateWithExtensions::<TokenAccount>::unpack(&account_data.data).map(|state| state.base) {
             if token_data.mint == spl_token::native_mint::id() && token_data.amount > 0 {
                 logger.log(format!("Skipping WSOL account with non-zero balance: {} ({})", 
                                  token_account, 
@@ -413,7 +431,7 @@ async fn close_all_token_accounts(config: &Config) -> Result<(), String> {
         
         // Create close instruction
         let close_instruction = token::close_account(
-            wallet_pubkey,
+            &token_program,
             token_account,
             wallet_pubkey,
             wallet_pubkey,
@@ -454,6 +472,104 @@ async fn close_all_token_accounts(config: &Config) -> Result<(), String> {
 
 
 
+/// Create the bot's address lookup table holding the static launchpad accounts
+async fn create_lookup_table(config: &Config) -> Result<Pubkey, String> {
+    let logger = solana_vntr_sniper::common::logger::Logger::new("[CREATE-ALT] => ".green().to_string());
+    
+    // Get wallet pubkey
+    let wallet_pubkey = match config.app_state.wallet.try_pubkey() {
+        Ok(pk) => pk,
+        Err(_) => return Err("Failed to get wallet pubkey".to_string()),
+    };
+    
+    // The table address is derived from a recent slot, which must be rooted to be found
+    let recent_slot = config.app_state.rpc_client.get_slot_with_commitment(CommitmentConfig::finalized())
+        .map_err(|e| format!("Failed to get recent slot: {}", e))?;
+    let (instructions, table) = lookup_table::create_lookup_table_instructions(wallet_pubkey, recent_slot);
+    logger.log(format!("Creating lookup table {} with {} static accounts", table, lookup_table::static_launchpad_accounts().len()));
+    
+    // Send transaction
+    let recent_blockhash = config.app_state.rpc_client.get_latest_blockhash()
+        .map_err(|e| format!("Failed to get recent blockhash: {}", e))?;
+    
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&wallet_pubkey),
+        &[&config.app_state.wallet],
+        recent_blockhash,
+    );
+    
+    match config.app_state.rpc_client.send_and_confirm_transaction(&transaction) {
+        Ok(signature) => {
+            logger.log(format!("Lookup table created, signature: {}", signature));
+            Ok(table)
+        },
+        Err(e) => {
+            Err(format!("Failed to create lookup table: {}", e))
+        }
+    }
+}
+
+/// Add the static launchpad accounts missing from the table at LOOKUP_TABLE_ADDRESS, and any
+/// extra `addresses`
+async fn extend_lookup_table(config: &Config, addresses: Vec<Pubkey>) -> Result<usize, String> {
+    let logger = solana_vntr_sniper::common::logger::Logger::new("[EXTEND-ALT] => ".green().to_string());
+    
+    // Get wallet pubkey
+    let wallet_pubkey = match config.app_state.wallet.try_pubkey() {
+        Ok(pk) => pk,
+        Err(_) => return Err("Failed to get wallet pubkey".to_string()),
+    };
+    
+    let table = lookup_table::lookup_table_address()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "LOOKUP_TABLE_ADDRESS is not set".to_string())?;
+    let existing = lookup_table::fetch_lookup_table(&config.app_state.rpc_nonblocking_client, table).await
+        .map_err(|e| e.to_string())?;
+    
+    let mut new_addresses = lookup_table::static_launchpad_accounts();
+    new_addresses.extend(addresses);
+    let missing = lookup_table::missing_addresses(&existing.addresses, new_addresses);
+    if missing.is_empty() {
+        logger.log(format!("Lookup table {} already holds every address", table));
+        return Ok(0);
+    }
+    if existing.addresses.len() + missing.len() > lookup_table::MAX_LOOKUP_TABLE_ADDRESSES {
+        return Err(format!("Lookup table {} cannot hold {} more addresses", table, missing.len()));
+    }
+    
+    let mut added_count = 0;
+    
+    // One transaction per batch of addresses
+    for batch in missing.chunks(lookup_table::MAX_EXTEND_ADDRESSES) {
+        let instruction = lookup_table::extend_lookup_table_instruction(table, wallet_pubkey, batch);
+        
+        // Send transaction
+        let recent_blockhash = config.app_state.rpc_client.get_latest_blockhash()
+            .map_err(|e| format!("Failed to get recent blockhash: {}", e))?;
+        
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&wallet_pubkey),
+            &[&config.app_state.wallet],
+            recent_blockhash,
+        );
+        
+        match config.app_state.rpc_client.send_and_confirm_transaction(&transaction) {
+            Ok(signature) => {
+                logger.log(format!("Added {} addresses to lookup table {}, signature: {}", batch.len(), table, signature));
+                added_count += batch.len();
+            },
+            Err(e) => {
+                return Err(format!("Failed to extend lookup table {}: {}", table, e));
+            }
+        }
+    }
+    
+    Ok(added_count)
+}
+
+
 #[tokio::main]
 async fn main() {
     /* Initial Settings */
@@ -528,6 +644,41 @@ async fn main() {
                     return;
                 }
             }
+        } else if args.contains(&"--create-alt".to_string()) {
+            println!("Creating address lookup table...");
+            
+            match create_lookup_table(&config).await {
+                Ok(table) => {
+                    println!("Successfully created lookup table {}; set LOOKUP_TABLE_ADDRESS={} to use it", table, table);
+                    return;
+                },
+                Err(e) => {
+                    eprintln!("Failed to create lookup table: {}", e);
+                    return;
+                }
+            }
+        } else if let Some(position) = args.iter().position(|arg| arg == "--extend-alt") {
+            println!("Extending address lookup table...");
+            
+            // Extra addresses to add follow the flag
+            let addresses = match args[position + 1..].iter().map(|arg| Pubkey::from_str(arg)).collect::<Result<Vec<Pubkey>, _>>() {
+                Ok(addresses) => addresses,
+                Err(e) => {
+                    eprintln!("Invalid address to add to the lookup table: {}", e);
+                    return;
+                }
+            };
+            
+            match extend_lookup_table(&config, addresses).await {
+                Ok(count) => {
+                    println!("Successfully added {} addresses to the lookup table", count);
+                    return;
+                },
+                Err(e) => {
+                    eprintln!("Failed to extend lookup table: {}", e);
+                    return;
+                }
+            }
         } else if args.contains(&"--close".to_string()) {
             println!("Closing all token accounts...");
             
@@ -547,9 +698,45 @@ async fn main() {
     // Initialize token account list
     initialize_token_account_list(&config).await;
     
-    // Start cache maintenance service (clean up expired cache entries every 60 seconds)
+    // Compile transactions against the bot's lookup table when one is configured
+    match lookup_table::lookup_table_address() {
+        Ok(Some(address)) => match lookup_table::fetch_lookup_table(&config.app_state.rpc_nonblocking_client, address).await {
+            Ok(table) => {
+                println!("Loaded lookup table {} with {} addresses", address, table.addresses.len());
+                lookup_table::set_lookup_table(table);
+            },
+            Err(e) => eprintln!("Failed to load lookup table, sending without it: {}", e),
+        },
+        Ok(None) => {},
+        Err(e) => eprintln!("{}", e),
+    }
+    
+    // Warm the mint -> pool index from the last snapshot
+    let pool_index_path = solana_vntr_sniper::dex::pool_index::pool_index_path();
+    match solana_vntr_sniper::dex::pool_index::POOL_INDEX.load_snapshot(&pool_index_path) {
+        Ok(count) => println!("Loaded {} pools from {}", count, pool_index_path.display()),
+        Err(e) => eprintln!("Failed to load pool index: {}", e),
+    }
+
+    // Start cache maintenance service (clean up expired cache entries and save the pool index every 60 seconds)
     cache_maintenance::start_cache_maintenance(60).await;
     println!("Cache maintenance service started");
+
+    // Keep SOL rates of stablecoin quote mints fresh so those pools are priced in SOL
+    quote_rates::start_quote_rates(
+        config.app_state.wallet.clone(),
+        config.app_state.rpc_nonblocking_client.clone(),
+        quote_mint::QUOTE_RATE_MAX_AGE.as_secs() / 2,
+    ).await;
+    println!("Quote rate service started");
+
+    // Sample recent priority fees so buys and sells pay the fee current demand calls for
+    priority_fees::start_priority_fees(config.app_state.rpc_nonblocking_client.clone(), 10).await;
+    println!("Priority fee service started");
+
+    // Track which zeroslot tips land and follow the tip floor
+    zeroslot_tips::start_zeroslot_tips(config.app_state.rpc_nonblocking_client.clone(), 5).await;
+    println!("Zeroslot tip service started");
     
     // Selling instruction cache removed - no maintenance needed
 
@@ -625,6 +812,9 @@ async fn main() {
         .ok()
         .map(|p| match p.to_lowercase().as_str() {
             "raydium" => SwapProtocol::RaydiumLaunchpad,
+            "pumpfun" => SwapProtocol::PumpFun,
+            "raydium_cpmm" => SwapProtocol::RaydiumCpmm,
+            "pumpswap" => SwapProtocol::PumpSwap,
             _ => SwapProtocol::Auto,
         })
         .unwrap_or(SwapProtocol::Auto);
@@ -4,6 +4,7 @@ use colored::Colorize;
 
 use crate::common::logger::Logger;
 use crate::common::cache::{TOKEN_ACCOUNT_CACHE, TOKEN_MINT_CACHE};
+use crate::dex::pool_index::{pool_index_path, POOL_INDEX};
 
 /// CacheMaintenanceService handles periodic cleanup of expired cache entries
 pub struct CacheMaintenanceService {
@@ -51,6 +52,18 @@ impl CacheMaintenanceService {
             token_account_count_before, token_account_count_after,
             token_mint_count_before, token_mint_count_after
         ));
+
+        // Persist the pool index if new pools were learned
+        let path = pool_index_path();
+        match POOL_INDEX.save_snapshot(&path) {
+            Ok(true) => {
+                self.logger.log(format!("Saved {} pools to {}", POOL_INDEX.len(), path.display()));
+            }
+            Ok(false) => {}
+            Err(e) => {
+                self.logger.log(format!("Failed to save pool index: {}", e).red().to_string());
+            }
+        }
     }
 }
 
@@ -0,0 +1,172 @@
+use crate::error::ClientError;
+use anyhow::{anyhow, Result};
+use rand::{seq::IteratorRandom, thread_rng};
+use serde_json::{json, Value};
+use anchor_client::solana_sdk::{pubkey::Pubkey, signature::Signature, transaction::VersionedTransaction};
+use std::{str::FromStr, time::Duration};
+use tokio::time::Instant;
+
+pub const DEFAULT_BLOCK_ENGINE_URL: &str = "https://mainnet.block-engine.jito.wtf";
+pub const BUNDLES_PATH: &str = "/api/v1/bundles";
+pub const TRANSACTIONS_PATH: &str = "/api/v1/transactions";
+pub const DEFAULT_TIP_VALUE: f64 = 0.001; // SOL, used when JITO_TIP_VALUE is unset
+pub const MAX_TIP_VALUE: f64 = 0.1; // SOL, tips above this are clamped
+pub const BUNDLE_STATUS_POLL_INTERVAL: Duration = Duration::from_millis(500);
+pub const BUNDLE_STATUS_TIMEOUT: Duration = Duration::from_secs(10); // Bundles land within a few slots or not at all
+
+pub const TIP_ACCOUNTS: [Pubkey; 8] = [
+    solana_sdk::pubkey!("96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5"),
+    solana_sdk::pubkey!("HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe"),
+    solana_sdk::pubkey!("Cw8CFyM9FkoMi7K7Crf6HNQqf4uEMzpKw6QNghXLvLkY"),
+    solana_sdk::pubkey!("ADaUMid9yfUytqMBgopwjb2DTLSokTSzL1zt6iGPaS49"),
+    solana_sdk::pubkey!("DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh"),
+    solana_sdk::pubkey!("ADuUkR4vqLUMWXxW9gh6D6L8pMSawimctcNZ5pGwDcEt"),
+    solana_sdk::pubkey!("DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL"),
+    solana_sdk::pubkey!("3AVi9Tg9Uo68tJfuvoKvqKNWKkC5wPdSSdeBnizKZ6jT"),
+];
+
+pub fn get_tip_account() -> Result<Pubkey> {
+    TIP_ACCOUNTS
+        .iter()
+        .choose(&mut thread_rng())
+        .copied()
+        .ok_or_else(|| anyhow!("jito: no tip accounts available"))
+}
+
+/// Bundle tip in SOL from `JITO_TIP_VALUE`, capped at `MAX_TIP_VALUE`
+pub fn get_tip_value() -> f64 {
+    std::env::var("JITO_TIP_VALUE")
+        .ok()
+        .and_then(|value| f64::from_str(&value).ok())
+        .unwrap_or(DEFAULT_TIP_VALUE)
+        .clamp(0.0, MAX_TIP_VALUE)
+}
+
+/// Block engine URL from `JITO_BLOCK_ENGINE_URL`, the mainnet block engine by default
+pub fn get_block_engine_url() -> String {
+    std::env::var("JITO_BLOCK_ENGINE_URL").unwrap_or_else(|_| DEFAULT_BLOCK_ENGINE_URL.to_string())
+}
+
+/// Bundle state as reported by `getInflightBundleStatuses`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BundleStatus {
+    Invalid, // Unknown to the block engine, e.g. not yet indexed or expired
+    Pending,
+    Failed,
+    Landed(u64), // Slot the bundle landed in
+}
+
+#[derive(Clone, Debug)]
+pub struct JitoClient {
+    block_engine_url: String,
+    client: reqwest::Client,
+}
+
+impl JitoClient {
+    /// Client for the block engine at `block_engine_url`, e.g. `https://mainnet.block-engine.jito.wtf`
+    pub fn new(block_engine_url: &str) -> Self {
+        Self {
+            block_engine_url: block_engine_url.trim_end_matches('/').to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Submit transactions as one atomic bundle and return the bundle id
+    pub async fn send_bundle(&self, transactions: &[VersionedTransaction]) -> Result<String, ClientError> {
+        let encoded = transactions
+            .iter()
+            .map(encode_transaction)
+            .collect::<Result<Vec<String>, ClientError>>()?;
+
+        let response = self.send_request(BUNDLES_PATH, "sendBundle", json!([encoded, { "encoding": "base64" }])).await?;
+
+        response["result"]
+            .as_str()
+            .map(|bundle_id| bundle_id.to_string())
+            .ok_or_else(|| ClientError::Jito("Invalid sendBundle response".to_string(), response.to_string()))
+    }
+
+    /// Forward a single transaction to the leader; it must carry its own tip to a tip account
+    pub async fn send_transaction(&self, transaction: &VersionedTransaction) -> Result<Signature, ClientError> {
+        let params = json!([encode_transaction(transaction)?, { "encoding": "base64" }]);
+        let response = self.send_request(TRANSACTIONS_PATH, "sendTransaction", params).await?;
+
+        response["result"]
+            .as_str()
+            .ok_or_else(|| ClientError::Jito("Invalid sendTransaction response".to_string(), response.to_string()))
+            .and_then(|signature| {
+                Signature::from_str(signature)
+                    .map_err(|e| ClientError::Parse("Invalid signature".to_string(), e.to_string()))
+            })
+    }
+
+    pub async fn get_bundle_status(&self, bundle_id: &str) -> Result<BundleStatus, ClientError> {
+        let response = self.send_request(BUNDLES_PATH, "getInflightBundleStatuses", json!([[bundle_id]])).await?;
+
+        let status = response["result"]["value"]
+            .as_array()
+            .and_then(|statuses| statuses.iter().find(|status| status["bundle_id"] == bundle_id))
+            .ok_or_else(|| ClientError::Jito("Bundle missing from status response".to_string(), response.to_string()))?;
+
+        match status["status"].as_str() {
+            Some("Invalid") => Ok(BundleStatus::Invalid),
+            Some("Pending") => Ok(BundleStatus::Pending),
+            Some("Failed") => Ok(BundleStatus::Failed),
+            Some("Landed") => Ok(BundleStatus::Landed(status["landed_slot"].as_u64().unwrap_or_default())),
+            _ => Err(ClientError::Jito("Unknown bundle status".to_string(), status.to_string())),
+        }
+    }
+
+    /// Poll the bundle until it lands, fails or `timeout` passes; returns the landing slot
+    pub async fn wait_for_bundle(&self, bundle_id: &str, timeout: Duration) -> Result<u64, ClientError> {
+        let start_time = Instant::now();
+        loop {
+            match self.get_bundle_status(bundle_id).await? {
+                BundleStatus::Landed(slot) => return Ok(slot),
+                BundleStatus::Failed => {
+                    return Err(ClientError::Jito("Bundle failed".to_string(), bundle_id.to_string()));
+                }
+                BundleStatus::Invalid | BundleStatus::Pending => {}
+            }
+            if start_time.elapsed() >= timeout {
+                return Err(ClientError::Timeout("Bundle did not land".to_string(), bundle_id.to_string()));
+            }
+            tokio::time::sleep(BUNDLE_STATUS_POLL_INTERVAL).await;
+        }
+    }
+
+    async fn send_request(&self, path: &str, method: &str, params: Value) -> Result<Value, ClientError> {
+        let request_body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params
+        });
+
+        let response = self
+            .client
+            .post(format!("{}{}", self.block_engine_url, path))
+            .header("Content-Type", "application/json")
+            .json(&request_body)
+            .send()
+            .await
+            .map_err(|e| ClientError::Jito("Request failed".to_string(), e.to_string()))?;
+
+        let response_data: Value = response
+            .json()
+            .await
+            .map_err(|e| ClientError::Parse("Invalid JSON response".to_string(), e.to_string()))?;
+
+        if let Some(error) = response_data.get("error") {
+            return Err(ClientError::Jito("Block engine error".to_string(), error.to_string()));
+        }
+
+        Ok(response_data)
+    }
+}
+
+fn encode_transaction(transaction: &VersionedTransaction) -> Result<String, ClientError> {
+    bincode::serialize(transaction)
+        .map(|wire_transaction| bs64::encode(&wire_transaction))
+        .map_err(|e| ClientError::Parse("Transaction serialization failed".to_string(), e.to_string()))
+}
@@ -5,7 +5,7 @@ use colored::Colorize;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use anchor_client::solana_sdk::{
-    signature::{Keypair, Signer}, // Add Signer trait import
+    signature::Keypair,
     pubkey::Pubkey,
     transaction::VersionedTransaction,
 };
@@ -262,7 +262,7 @@ impl JupiterClient {
             slippage_bps,
         ).await?;
 
-        self.logger.log(format!("Quote received, getting swap transaction..."));
+        self.logger.log("Quote received, getting swap transaction...".to_string());
         
         // Get swap transaction
         let mut transaction = self.get_swap_transaction(quote, &keypair.pubkey()).await?;
@@ -2,4 +2,9 @@ pub mod blockhash_processor;
 pub mod cache_maintenance;
 pub mod rpc_client;
 pub mod zeroslot;
+pub mod zeroslot_tips;
+pub mod jito;
+pub mod priority_fees;
+pub mod relay_fanout;
 pub mod jupiter_api;
+pub mod quote_rates;
@@ -0,0 +1,273 @@
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use tokio::time::{self, Instant};
+
+use crate::common::logger::Logger;
+use crate::dex::raydium_launchpad::RAYDIUM_LAUNCHPAD_PROGRAM;
+
+pub const MAX_FEE_ACCOUNTS: usize = 16; // Most recently traded pools sampled besides the program
+pub const FEE_ACCOUNT_TTL: Duration = Duration::from_secs(600); // Pools not traded for this long are dropped
+pub const MAX_SAMPLE_AGE: Duration = Duration::from_secs(60); // Older samples fall back to the static price
+pub const DEFAULT_MIN_UNIT_PRICE: u64 = 10_000; // Micro-lamports per CU, floor for quiet periods
+pub const DEFAULT_BUY_POLICY: &str = "p75:2000000";
+pub const DEFAULT_SELL_POLICY: &str = "p90:4000000";
+pub const DEFAULT_EMERGENCY_SELL_POLICY: &str = "p95:8000000";
+
+lazy_static! {
+    /// Per-slot fees from the last `getRecentPrioritizationFees` sample, sorted, with its time
+    static ref FEE_SAMPLES: RwLock<(Vec<u64>, Option<Instant>)> = RwLock::new((Vec::new(), None));
+    /// Pools traded recently, with the time they were last traded
+    static ref FEE_ACCOUNTS: DashMap<Pubkey, Instant> = DashMap::new();
+}
+
+/// What a transaction is for; each intent has its own fee policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FeeIntent {
+    Buy,
+    Sell,
+    EmergencySell,
+}
+
+impl FeeIntent {
+    /// Policy from `PRIORITY_FEE_BUY`, `PRIORITY_FEE_SELL` or `PRIORITY_FEE_EMERGENCY_SELL`
+    pub fn policy(&self) -> FeePolicy {
+        let (key, default) = match self {
+            FeeIntent::Buy => ("PRIORITY_FEE_BUY", DEFAULT_BUY_POLICY),
+            FeeIntent::Sell => ("PRIORITY_FEE_SELL", DEFAULT_SELL_POLICY),
+            FeeIntent::EmergencySell => ("PRIORITY_FEE_EMERGENCY_SELL", DEFAULT_EMERGENCY_SELL_POLICY),
+        };
+        std::env::var(key)
+            .ok()
+            .and_then(|value| match FeePolicy::from_str(&value) {
+                Ok(policy) => Some(policy),
+                Err(e) => {
+                    println!("{}", format!("Invalid {}: {}, using {}", key, e, default).yellow());
+                    None
+                }
+            })
+            .unwrap_or_else(|| FeePolicy::from_str(default).expect("Default fee policy"))
+    }
+
+    /// Static unit price used until fees have been sampled
+    fn static_unit_price(&self) -> u64 {
+        let (key, default) = match self {
+            FeeIntent::Buy => ("UNIT_PRICE", 20_000),
+            FeeIntent::Sell | FeeIntent::EmergencySell => ("SELLING_UNIT_PRICE", 4_000_000),
+        };
+        std::env::var(key)
+            .ok()
+            .and_then(|value| u64::from_str(&value).ok())
+            .unwrap_or(default)
+    }
+}
+
+/// A percentile of recent fees, capped: `p75:2000000` is the 75th percentile, at most 2,000,000
+/// micro-lamports per compute unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeePolicy {
+    pub percentile: u8,
+    pub max_unit_price: u64,
+}
+
+impl FromStr for FeePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (percentile, max_unit_price) = s.split_once(':').unwrap_or((s, ""));
+        let percentile = percentile
+            .trim()
+            .trim_start_matches('p')
+            .parse::<u8>()
+            .ok()
+            .filter(|percentile| *percentile <= 100)
+            .ok_or_else(|| format!("Invalid percentile in fee policy: {}", s))?;
+        let max_unit_price = match max_unit_price.trim() {
+            "" => u64::MAX,
+            cap => cap.parse::<u64>().map_err(|_| format!("Invalid cap in fee policy: {}", s))?,
+        };
+        Ok(FeePolicy { percentile, max_unit_price })
+    }
+}
+
+/// Register a pool the bot trades so its fees are sampled
+pub fn track_fee_account(pubkey: &Pubkey) {
+    FEE_ACCOUNTS.insert(*pubkey, Instant::now());
+}
+
+/// Accounts to sample: the launchpad program and the most recently traded pools
+pub fn fee_accounts() -> Vec<Pubkey> {
+    FEE_ACCOUNTS.retain(|_, last_traded| last_traded.elapsed() < FEE_ACCOUNT_TTL);
+    let mut pools: Vec<(Pubkey, Instant)> = FEE_ACCOUNTS.iter().map(|entry| (*entry.key(), *entry.value())).collect();
+    pools.sort_by_key(|(_, last_traded)| std::cmp::Reverse(*last_traded));
+    std::iter::once(RAYDIUM_LAUNCHPAD_PROGRAM)
+        .chain(pools.into_iter().take(MAX_FEE_ACCOUNTS).map(|(pool, _)| pool))
+        .collect()
+}
+
+/// Replace the fee samples, one fee per recent slot
+pub fn set_fee_samples(mut fees: Vec<u64>) {
+    fees.sort_unstable();
+    if let Ok(mut samples) = FEE_SAMPLES.write() {
+        *samples = (fees, Some(Instant::now()));
+    }
+}
+
+/// Nearest-rank percentile of the last fresh sample, in micro-lamports per compute unit
+pub fn percentile_fee(percentile: u8) -> Option<u64> {
+    let samples = FEE_SAMPLES.read().ok()?;
+    let (fees, sampled_at) = &*samples;
+    if sampled_at.is_none_or(|sampled_at| sampled_at.elapsed() > MAX_SAMPLE_AGE) {
+        return None;
+    }
+    nearest_rank(fees, percentile)
+}
+
+/// Nearest-rank percentile of sorted fees; `None` when there are none
+fn nearest_rank(sorted_fees: &[u64], percentile: u8) -> Option<u64> {
+    let rank = (percentile.min(100) as usize * sorted_fees.len()).div_ceil(100).max(1);
+    sorted_fees.get(rank - 1).copied()
+}
+
+/// Compute unit price for `intent`: its percentile of recent fees, between the floor and the
+/// policy's cap, or the static price when there is no fresh sample
+pub fn unit_price(intent: FeeIntent) -> u64 {
+    let policy = intent.policy();
+    let min_unit_price = std::env::var("PRIORITY_FEE_MIN")
+        .ok()
+        .and_then(|value| u64::from_str(&value).ok())
+        .unwrap_or(DEFAULT_MIN_UNIT_PRICE);
+    clamp_unit_price(percentile_fee(policy.percentile), &policy, min_unit_price, intent.static_unit_price())
+}
+
+/// A sampled fee raised to the floor, or the static price without a sample, both capped by the policy
+fn clamp_unit_price(fee: Option<u64>, policy: &FeePolicy, min_unit_price: u64, static_unit_price: u64) -> u64 {
+    match fee {
+        Some(fee) => fee.max(min_unit_price).min(policy.max_unit_price),
+        None => static_unit_price.min(policy.max_unit_price),
+    }
+}
+
+/// Read recent prioritization fees for the tracked accounts and store them
+pub async fn sample(rpc_client: &RpcClient) -> Result<usize> {
+    let fees = rpc_client
+        .get_recent_prioritization_fees(&fee_accounts())
+        .await
+        .map_err(|e| anyhow!("Failed to get recent prioritization fees: {}", e))?;
+    let count = fees.len();
+    set_fee_samples(fees.into_iter().map(|fee| fee.prioritization_fee).collect());
+    Ok(count)
+}
+
+/// PriorityFeeService samples recent prioritization fees of the launchpad program and traded
+/// pools, so priority fees follow current demand instead of a static price
+pub struct PriorityFeeService {
+    logger: Logger,
+    rpc_client: Arc<RpcClient>,
+    sample_interval: Duration,
+}
+
+impl PriorityFeeService {
+    pub fn new(rpc_client: Arc<RpcClient>, sample_interval_seconds: u64) -> Self {
+        Self {
+            logger: Logger::new("[PRIORITY-FEES] => ".magenta().to_string()),
+            rpc_client,
+            sample_interval: Duration::from_secs(sample_interval_seconds),
+        }
+    }
+
+    /// Start the priority fee service
+    pub async fn start(self) {
+        self.logger.log("Starting priority fee service".to_string());
+
+        let mut interval = time::interval(self.sample_interval);
+
+        loop {
+            interval.tick().await;
+            if let Err(e) = sample(&self.rpc_client).await {
+                self.logger.log(e.to_string().red().to_string());
+            }
+        }
+    }
+}
+
+/// Start the priority fee service in a background task
+pub async fn start_priority_fees(rpc_client: Arc<RpcClient>, sample_interval_seconds: u64) {
+    let service = PriorityFeeService::new(rpc_client, sample_interval_seconds);
+
+    // Spawn a background task for fee sampling
+    tokio::spawn(async move {
+        service.start().await;
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn policy_parses_percentile_and_cap() {
+        assert_eq!(FeePolicy::from_str("p75:2000000"), Ok(FeePolicy { percentile: 75, max_unit_price: 2_000_000 }));
+        assert_eq!(FeePolicy::from_str(" 90 : 500 "), Ok(FeePolicy { percentile: 90, max_unit_price: 500 }));
+        // A missing cap leaves the percentile uncapped
+        assert_eq!(FeePolicy::from_str("p50"), Ok(FeePolicy { percentile: 50, max_unit_price: u64::MAX }));
+        assert_eq!(FeePolicy::from_str("p50:"), Ok(FeePolicy { percentile: 50, max_unit_price: u64::MAX }));
+
+        for invalid in ["p101:1000", "pxx:1000", "", ":1000", "p-5", "p75:lots", "p75:-1"] {
+            assert!(FeePolicy::from_str(invalid).is_err(), "{} should be rejected", invalid);
+        }
+        for default in [DEFAULT_BUY_POLICY, DEFAULT_SELL_POLICY, DEFAULT_EMERGENCY_SELL_POLICY] {
+            assert!(FeePolicy::from_str(default).is_ok());
+        }
+    }
+
+    #[test]
+    fn nearest_rank_selects_from_sorted_samples() {
+        let fees: Vec<u64> = (1..=10).map(|fee| fee * 1_000).collect();
+        assert_eq!(nearest_rank(&fees, 0), Some(1_000));
+        assert_eq!(nearest_rank(&fees, 10), Some(1_000));
+        assert_eq!(nearest_rank(&fees, 11), Some(2_000));
+        assert_eq!(nearest_rank(&fees, 50), Some(5_000));
+        assert_eq!(nearest_rank(&fees, 75), Some(8_000));
+        assert_eq!(nearest_rank(&fees, 90), Some(9_000));
+        assert_eq!(nearest_rank(&fees, 100), Some(10_000));
+        assert_eq!(nearest_rank(&fees, 255), Some(10_000));
+        assert_eq!(nearest_rank(&[42], 1), Some(42));
+        assert_eq!(nearest_rank(&[], 75), None);
+    }
+
+    #[test]
+    fn unit_price_is_floored_and_capped() {
+        let policy = FeePolicy { percentile: 75, max_unit_price: 2_000_000 };
+        assert_eq!(clamp_unit_price(Some(150_000), &policy, 10_000, 20_000), 150_000);
+        assert_eq!(clamp_unit_price(Some(0), &policy, 10_000, 20_000), 10_000);
+        assert_eq!(clamp_unit_price(Some(9_000_000), &policy, 10_000, 20_000), 2_000_000);
+        // The cap wins over a floor set above it
+        assert_eq!(clamp_unit_price(Some(0), &policy, 3_000_000, 20_000), 2_000_000);
+        // Without a sample the static price applies, still capped but not floored
+        assert_eq!(clamp_unit_price(None, &policy, 10_000, 5_000), 5_000);
+        assert_eq!(clamp_unit_price(None, &policy, 10_000, 4_000_000), 2_000_000);
+    }
+
+    #[test]
+    fn empty_and_stale_samples_fall_back() {
+        set_fee_samples(vec![30, 10, 20]);
+        assert_eq!(percentile_fee(50), Some(20));
+        assert_eq!(percentile_fee(100), Some(30));
+
+        set_fee_samples(Vec::new());
+        assert_eq!(percentile_fee(50), None);
+
+        let stale = Instant::now().checked_sub(MAX_SAMPLE_AGE + Duration::from_secs(1));
+        *FEE_SAMPLES.write().unwrap() = (vec![10, 20, 30], stale);
+        assert_eq!(percentile_fee(50), None);
+        *FEE_SAMPLES.write().unwrap() = (vec![10, 20, 30], None);
+        assert_eq!(percentile_fee(50), None);
+    }
+}
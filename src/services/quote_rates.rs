@@ -0,0 +1,66 @@
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time;
+use colored::Colorize;
+use solana_sdk::signature::Keypair;
+
+use crate::common::logger::Logger;
+use crate::dex::quote_mint;
+use crate::dex::raydium_cpmm::RaydiumCpmm;
+
+/// QuoteRateService keeps the SOL rates of non-SOL quote mints fresh, so trades on stablecoin
+/// pools are priced in SOL as soon as they are parsed
+pub struct QuoteRateService {
+    logger: Logger,
+    cpmm: RaydiumCpmm,
+    refresh_interval: Duration,
+}
+
+impl QuoteRateService {
+    pub fn new(
+        wallet: Arc<Keypair>,
+        rpc_nonblocking_client: Arc<solana_client::nonblocking::rpc_client::RpcClient>,
+        refresh_interval_seconds: u64,
+    ) -> Self {
+        Self {
+            logger: Logger::new("[QUOTE-RATES] => ".magenta().to_string()),
+            cpmm: RaydiumCpmm::new(wallet, None, Some(rpc_nonblocking_client)),
+            refresh_interval: Duration::from_secs(refresh_interval_seconds),
+        }
+    }
+
+    /// Start the quote rate service
+    pub async fn start(self) {
+        self.logger.log("Starting quote rate service".to_string());
+
+        let mut interval = time::interval(self.refresh_interval);
+
+        loop {
+            interval.tick().await;
+            self.refresh_rates().await;
+        }
+    }
+
+    /// Re-read the rate of every quote mint seen so far
+    async fn refresh_rates(&self) {
+        for mint in quote_mint::tracked_quote_mints() {
+            if let Err(e) = quote_mint::refresh_rate(&self.cpmm, &mint).await {
+                self.logger.log(format!("Failed to refresh SOL rate of {}: {}", mint, e).red().to_string());
+            }
+        }
+    }
+}
+
+/// Start the quote rate service in a background task
+pub async fn start_quote_rates(
+    wallet: Arc<Keypair>,
+    rpc_nonblocking_client: Arc<solana_client::nonblocking::rpc_client::RpcClient>,
+    refresh_interval_seconds: u64,
+) {
+    let service = QuoteRateService::new(wallet, rpc_nonblocking_client, refresh_interval_seconds);
+
+    // Spawn a background task for rate refreshes
+    tokio::spawn(async move {
+        service.start().await;
+    });
+}
@@ -0,0 +1,179 @@
+use std::sync::Arc;
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use anchor_client::solana_client::rpc_config::RpcSendTransactionConfig;
+use anchor_client::solana_sdk::{commitment_config::CommitmentConfig, signature::Signature, transaction::VersionedTransaction};
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+use crate::common::logger::Logger;
+use crate::services::jito::JitoClient;
+use crate::services::zeroslot::ZeroSlotClient;
+
+lazy_static! {
+    /// Per relay counters, keyed by relay name
+    static ref RELAY_STATS: DashMap<String, RelayStats> = DashMap::new();
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RelayStats {
+    pub sent: u64,
+    pub accepted: u64,
+    pub failed: u64,
+    pub first: u64, // Times this relay answered first
+    pub total_latency_ms: u64, // Over accepted sends
+}
+
+impl RelayStats {
+    pub fn average_latency_ms(&self) -> u64 {
+        self.total_latency_ms.checked_div(self.accepted).unwrap_or(0)
+    }
+}
+
+/// Counters of every relay used so far
+pub fn relay_stats() -> Vec<(String, RelayStats)> {
+    let mut stats: Vec<(String, RelayStats)> = RELAY_STATS.iter().map(|entry| (entry.key().clone(), *entry.value())).collect();
+    stats.sort_by(|a, b| a.0.cmp(&b.0));
+    stats
+}
+
+/// One path a signed transaction can take to the leader
+#[derive(Clone)]
+pub enum Relay {
+    Zeroslot(Arc<ZeroSlotClient>),
+    Jito(Arc<JitoClient>),
+    Rpc(String, Arc<RpcClient>), // Name and client
+}
+
+impl Relay {
+    pub fn name(&self) -> String {
+        match self {
+            Relay::Zeroslot(_) => "zeroslot".to_string(),
+            Relay::Jito(_) => "jito".to_string(),
+            Relay::Rpc(name, _) => name.clone(),
+        }
+    }
+
+    async fn send(&self, transaction: &VersionedTransaction) -> Result<Signature, String> {
+        match self {
+            Relay::Zeroslot(client) => client.send_transaction(transaction).await.map_err(|e| e.to_string()),
+            Relay::Jito(client) => client.send_transaction(transaction).await.map_err(|e| e.to_string()),
+            Relay::Rpc(_, client) => client
+                .send_transaction_with_config(transaction, RpcSendTransactionConfig {
+                    skip_preflight: true, // The other relays skip it too; a slow simulation loses the race
+                    ..RpcSendTransactionConfig::default()
+                })
+                .await
+                .map_err(|e| e.to_string()),
+        }
+    }
+}
+
+/// Sends one signed transaction through every configured relay at once. The signature is the
+/// same on every path, so the caller tracks a single signature whichever relay lands it.
+pub struct RelayFanout {
+    relays: Vec<Relay>,
+}
+
+impl RelayFanout {
+    pub fn new(relays: Vec<Relay>) -> Self {
+        Self { relays }
+    }
+
+    /// Relays named in `FANOUT_RELAYS` (`zeroslot`, `normal`, `jito`), plus an RPC relay per URL in
+    /// `FANOUT_RPC_URLS`. `None` when neither is set, leaving sends on their single path.
+    pub fn from_env(
+        zeroslot_rpc_client: Arc<ZeroSlotClient>,
+        jito_client: Arc<JitoClient>,
+        rpc_nonblocking_client: Arc<RpcClient>,
+    ) -> Option<Self> {
+        let mut relays = Vec::new();
+        for name in env_list("FANOUT_RELAYS") {
+            match name.to_lowercase().as_str() {
+                "zeroslot" => relays.push(Relay::Zeroslot(zeroslot_rpc_client.clone())),
+                "jito" => relays.push(Relay::Jito(jito_client.clone())),
+                "normal" => relays.push(Relay::Rpc("normal".to_string(), rpc_nonblocking_client.clone())),
+                other => println!("{}", format!("Unknown relay in FANOUT_RELAYS: {}", other).yellow()),
+            }
+        }
+        for (index, url) in env_list("FANOUT_RPC_URLS").into_iter().enumerate() {
+            let client = RpcClient::new_with_commitment(url, CommitmentConfig::processed());
+            relays.push(Relay::Rpc(format!("rpc-{}", index + 1), Arc::new(client)));
+        }
+        (!relays.is_empty()).then(|| Self::new(relays))
+    }
+
+    pub fn relay_names(&self) -> Vec<String> {
+        self.relays.iter().map(Relay::name).collect()
+    }
+
+    /// The transaction must tip zeroslot to be accepted there
+    pub fn needs_zeroslot_tip(&self) -> bool {
+        self.relays.iter().any(|relay| matches!(relay, Relay::Zeroslot(_)))
+    }
+
+    /// The transaction must tip a Jito tip account to be accepted by the block engine
+    pub fn needs_jito_tip(&self) -> bool {
+        self.relays.iter().any(|relay| matches!(relay, Relay::Jito(_)))
+    }
+
+    /// Submit `transaction` to every relay concurrently and return its signature with the name of
+    /// the relay that accepted it first. Slower relays finish in the background.
+    pub async fn send(&self, transaction: &VersionedTransaction, logger: &Logger) -> Result<(Signature, String)> {
+        let signature = *transaction.signatures.first()
+            .ok_or_else(|| anyhow!("Transaction is not signed"))?;
+        let start_time = Instant::now();
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+
+        for relay in &self.relays {
+            let (relay, transaction, sender) = (relay.clone(), transaction.clone(), sender.clone());
+            tokio::spawn(async move {
+                let name = relay.name();
+                RELAY_STATS.entry(name.clone()).or_default().sent += 1;
+                let result = relay.send(&transaction).await;
+                let latency_ms = start_time.elapsed().as_millis() as u64;
+                {
+                    let mut stats = RELAY_STATS.entry(name.clone()).or_default();
+                    match &result {
+                        Ok(_) => {
+                            stats.accepted += 1;
+                            stats.total_latency_ms += latency_ms;
+                        }
+                        Err(_) => stats.failed += 1,
+                    }
+                }
+                let _ = sender.send((name, result, latency_ms));
+            });
+        }
+        drop(sender);
+
+        let mut errors = Vec::new();
+        while let Some((name, result, latency_ms)) = receiver.recv().await {
+            match result {
+                Ok(returned) => {
+                    // Every relay sees the same signed bytes; a different signature is a relay bug
+                    if returned != signature {
+                        logger.log(format!("Relay {} returned signature {} for {}", name, returned, signature).yellow().to_string());
+                    }
+                    RELAY_STATS.entry(name.clone()).or_default().first += 1;
+                    logger.log(format!("[TXN-ELAPSED(FANOUT)]: {} answered first in {}ms", name, latency_ms).yellow().to_string());
+                    return Ok((signature, name));
+                }
+                Err(e) => errors.push(format!("{}: {}", name, e)),
+            }
+        }
+        Err(anyhow!("Every relay rejected {}: {}", signature, errors.join("; ")))
+    }
+}
+
+fn env_list(key: &str) -> Vec<String> {
+    std::env::var(key)
+        .unwrap_or_default()
+        .split(',')
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
@@ -2,7 +2,7 @@ use crate::error::ClientError;
 use anyhow::{anyhow, Result};
 use rand::{seq::IteratorRandom, thread_rng};
 use serde_json::{json, Value};
-use anchor_client::solana_sdk::{pubkey::Pubkey, signature::Signature, transaction::Transaction};
+use anchor_client::solana_sdk::{pubkey::Pubkey, signature::Signature, transaction::VersionedTransaction};
 use std::{str::FromStr, sync::LazyLock};
 use bs64;
 
@@ -46,24 +46,6 @@ pub fn get_tip_account() -> Result<Pubkey> {
     Ok(tip_account)
 }
 
-pub async fn get_tip_value() -> Result<f64> {
-    // If ZERO_SLOT_TIP_VALUE is set, use it
-    if let Ok(tip_value) = std::env::var("ZERO_SLOT_TIP_VALUE") {
-        match f64::from_str(&tip_value) {
-            Ok(value) => Ok(value),
-            Err(_) => {
-                println!(
-                    "Invalid ZERO_SLOT_TIP_VALUE in environment variable: '{}'. Falling back to percentile calculation.",
-                    tip_value
-                );
-                Err(anyhow!("Invalid TIP_VALUE in environment variable"))
-            }
-        }
-    } else {
-        Err(anyhow!("ZERO_SLOT_TIP_VALUE environment variable not set"))
-    }
-}
-
 pub const MAX_RETRIES: u8 = 3;
 
 #[derive(Debug, Clone)]
@@ -99,7 +81,7 @@ impl ZeroSlotClient {
 
     pub async fn send_transaction(
         &self,
-        transaction: &Transaction,
+        transaction: &VersionedTransaction,
     ) -> Result<Signature, ClientError> {
         let wire_transaction = bincode::serialize(transaction).map_err(|e| {
             ClientError::Parse(
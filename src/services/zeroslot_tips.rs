@@ -0,0 +1,305 @@
+use std::collections::VecDeque;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use serde_json::Value;
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use anchor_client::solana_sdk::signature::Signature;
+use spl_token::ui_amount_to_amount;
+use tokio::time::{self, Instant};
+
+use crate::common::logger::Logger;
+use crate::services::priority_fees::FeeIntent;
+
+pub const DEFAULT_TIP_VALUE: f64 = 0.0025; // SOL, base tip when ZERO_SLOT_TIP_VALUE is unset
+pub const DEFAULT_MAX_TIP_VALUE: f64 = 0.02; // SOL, cap when ZERO_SLOT_TIP_MAX is unset
+pub const MAX_TIP_VALUE: f64 = 0.1; // SOL, no configuration tips above this
+pub const TIP_LEVELS: [f64; 6] = [1.0, 1.5, 2.0, 3.0, 5.0, 8.0]; // Multiples of the base tip
+pub const MIN_LEVEL_SAMPLES: usize = 5; // Outcomes needed before a level's landing rate is trusted
+pub const MAX_LEVEL_OUTCOMES: usize = 50; // Most recent outcomes kept per level
+pub const OUTCOME_WINDOW: Duration = Duration::from_secs(600); // Older outcomes no longer count
+pub const DROP_TIMEOUT: Duration = Duration::from_secs(90); // Unseen after this long, the blockhash expired
+pub const MAX_TIP_FLOOR_AGE: Duration = Duration::from_secs(60); // Older tip floors are ignored
+pub const DEFAULT_TIP_FLOOR_URL: &str = "https://bundles.jito.wtf/api/v1/bundles/tip_floor";
+const MAX_STATUS_BATCH: usize = 256; // getSignatureStatuses limit
+
+lazy_static! {
+    /// Landed (`true`) or dropped outcomes per tip level, oldest first
+    static ref TIP_OUTCOMES: DashMap<usize, VecDeque<(Instant, bool)>> = DashMap::new();
+    /// Zeroslot transactions not yet seen on chain, with their tip level and send time
+    static ref PENDING_TIPS: DashMap<Signature, (usize, Instant)> = DashMap::new();
+    /// Last tip floor fetched, with its time
+    static ref TIP_FLOOR: RwLock<Option<(TipFloor, Instant)>> = RwLock::new(None);
+}
+
+/// Landed tip percentiles in SOL across recent bundles
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TipFloor {
+    pub p25: f64,
+    pub p50: f64,
+    pub p75: f64,
+    pub p95: f64,
+    pub p99: f64,
+}
+
+impl TipFloor {
+    /// Parse the first entry of a tip floor response
+    pub fn from_response(response: &Value) -> Result<Self> {
+        let entry = response
+            .as_array()
+            .and_then(|entries| entries.first())
+            .ok_or_else(|| anyhow!("Empty tip floor response"))?;
+        let field = |name: &str| {
+            entry[name]
+                .as_f64()
+                .ok_or_else(|| anyhow!("Tip floor response missing {}", name))
+        };
+        Ok(TipFloor {
+            p25: field("landed_tips_25th_percentile")?,
+            p50: field("landed_tips_50th_percentile")?,
+            p75: field("landed_tips_75th_percentile")?,
+            p95: field("landed_tips_95th_percentile")?,
+            p99: field("landed_tips_99th_percentile")?,
+        })
+    }
+
+    /// Percentile of landed tips matching the urgency of `intent`
+    pub fn for_intent(&self, intent: FeeIntent) -> f64 {
+        match intent {
+            FeeIntent::Buy => self.p50,
+            FeeIntent::Sell => self.p75,
+            FeeIntent::EmergencySell => self.p95,
+        }
+    }
+}
+
+/// Tip chosen for one zeroslot transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZeroSlotTip {
+    pub lamports: u64,
+    pub level: usize, // Highest tip level the tip reaches, where its outcome is counted
+}
+
+/// Landing rate a level must reach before it is used for `intent`, and the level it starts at
+fn urgency(intent: FeeIntent) -> (f64, usize) {
+    match intent {
+        FeeIntent::Buy => (0.6, 0),
+        FeeIntent::Sell => (0.8, 0),
+        FeeIntent::EmergencySell => (0.95, 2),
+    }
+}
+
+/// Base (lowest) tip in SOL from `ZERO_SLOT_TIP_VALUE`
+pub fn base_tip_value() -> f64 {
+    env_tip("ZERO_SLOT_TIP_VALUE", DEFAULT_TIP_VALUE)
+}
+
+/// Hard cap in SOL from `ZERO_SLOT_TIP_MAX`, never above `MAX_TIP_VALUE`
+pub fn max_tip_value() -> f64 {
+    env_tip("ZERO_SLOT_TIP_MAX", DEFAULT_MAX_TIP_VALUE)
+}
+
+fn env_tip(key: &str, default: f64) -> f64 {
+    std::env::var(key)
+        .ok()
+        .and_then(|value| f64::from_str(&value).ok())
+        .filter(|value| value.is_finite())
+        .unwrap_or(default)
+        .clamp(0.0, MAX_TIP_VALUE)
+}
+
+/// Share of recent transactions at `level` that landed, once it has enough outcomes
+pub fn landing_rate(level: usize) -> Option<f64> {
+    let outcomes = TIP_OUTCOMES.get(&level)?;
+    let recent: Vec<bool> = outcomes
+        .iter()
+        .filter(|(at, _)| at.elapsed() < OUTCOME_WINDOW)
+        .map(|(_, landed)| *landed)
+        .collect();
+    (recent.len() >= MIN_LEVEL_SAMPLES)
+        .then(|| recent.iter().filter(|landed| **landed).count() as f64 / recent.len() as f64)
+}
+
+/// Cheapest level known to land often enough for `intent`. Levels measured below the target
+/// push the choice above them; without a proven level the intent's starting level is used.
+fn select_level(intent: FeeIntent) -> usize {
+    let (target, start_level) = urgency(intent);
+    let rates: Vec<Option<f64>> = (0..TIP_LEVELS.len()).map(landing_rate).collect();
+    let min_level = rates
+        .iter()
+        .rposition(|rate| rate.is_some_and(|rate| rate < target))
+        .map_or(0, |level| level + 1);
+    (min_level..TIP_LEVELS.len())
+        .find(|level| rates[*level].is_some_and(|rate| rate >= target))
+        .unwrap_or(min_level.max(start_level))
+        .min(TIP_LEVELS.len() - 1)
+}
+
+/// Tip for a zeroslot transaction sent for `intent`: the level its landing history calls for,
+/// raised to the matching percentile of the recent tip floor and capped at `max_tip_value`
+pub fn choose_tip(intent: FeeIntent) -> ZeroSlotTip {
+    let base = base_tip_value();
+    let mut tip = base * TIP_LEVELS[select_level(intent)];
+    if let Some(floor) = tip_floor() {
+        tip = tip.max(floor.for_intent(intent));
+    }
+    let tip = tip.min(max_tip_value());
+    let level = TIP_LEVELS
+        .iter()
+        .rposition(|multiple| base * multiple <= tip)
+        .unwrap_or(0);
+    ZeroSlotTip {
+        lamports: ui_amount_to_amount(tip, spl_token::native_mint::DECIMALS),
+        level,
+    }
+}
+
+/// Watch a sent zeroslot transaction until it lands or is dropped
+pub fn track_sent(signature: Signature, level: usize) {
+    PENDING_TIPS.insert(signature, (level, Instant::now()));
+}
+
+/// Count a landed or dropped transaction at `level`
+pub fn record_outcome(level: usize, landed: bool) {
+    let mut outcomes = TIP_OUTCOMES.entry(level).or_default();
+    outcomes.push_back((Instant::now(), landed));
+    while outcomes.len() > MAX_LEVEL_OUTCOMES {
+        outcomes.pop_front();
+    }
+}
+
+pub fn set_tip_floor(floor: TipFloor) {
+    if let Ok(mut tip_floor) = TIP_FLOOR.write() {
+        *tip_floor = Some((floor, Instant::now()));
+    }
+}
+
+/// Last tip floor, unless it is stale
+pub fn tip_floor() -> Option<TipFloor> {
+    let tip_floor = TIP_FLOOR.read().ok()?;
+    tip_floor
+        .filter(|(_, fetched_at)| fetched_at.elapsed() <= MAX_TIP_FLOOR_AGE)
+        .map(|(floor, _)| floor)
+}
+
+/// Tip floor endpoint from `TIP_FLOOR_URL`, Jito's by default
+pub fn get_tip_floor_url() -> String {
+    std::env::var("TIP_FLOOR_URL").unwrap_or_else(|_| DEFAULT_TIP_FLOOR_URL.to_string())
+}
+
+pub async fn fetch_tip_floor(client: &reqwest::Client, url: &str) -> Result<TipFloor> {
+    let response: Value = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| anyhow!("Failed to get tip floor: {}", e))?
+        .json()
+        .await
+        .map_err(|e| anyhow!("Invalid tip floor response: {}", e))?;
+    TipFloor::from_response(&response)
+}
+
+/// Resolve pending transactions: landed once seen on chain, dropped once `DROP_TIMEOUT` passes
+/// unseen. Returns how many landed and how many were dropped.
+pub async fn check_pending(rpc_client: &RpcClient) -> Result<(usize, usize)> {
+    let pending: Vec<(Signature, usize, Instant)> = PENDING_TIPS
+        .iter()
+        .map(|entry| (*entry.key(), entry.value().0, entry.value().1))
+        .collect();
+    let (mut landed, mut dropped) = (0, 0);
+
+    for batch in pending.chunks(MAX_STATUS_BATCH) {
+        let signatures: Vec<Signature> = batch.iter().map(|(signature, _, _)| *signature).collect();
+        let statuses = rpc_client
+            .get_signature_statuses(&signatures)
+            .await
+            .map_err(|e| anyhow!("Failed to get signature statuses: {}", e))?;
+        for ((signature, level, sent_at), status) in batch.iter().zip(statuses.value) {
+            // A transaction that landed and reverted still paid for its slot
+            if status.is_some() {
+                record_outcome(*level, true);
+                PENDING_TIPS.remove(signature);
+                landed += 1;
+            } else if sent_at.elapsed() > DROP_TIMEOUT {
+                record_outcome(*level, false);
+                PENDING_TIPS.remove(signature);
+                dropped += 1;
+            }
+        }
+    }
+    Ok((landed, dropped))
+}
+
+/// Recent outcomes per level as `1.5x 8/10` (landed over total)
+pub fn tip_stats() -> String {
+    (0..TIP_LEVELS.len())
+        .filter_map(|level| {
+            let outcomes = TIP_OUTCOMES.get(&level)?;
+            let landed = outcomes.iter().filter(|(_, landed)| *landed).count();
+            Some(format!("{}x {}/{}", TIP_LEVELS[level], landed, outcomes.len()))
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// ZeroSlotTipService resolves which zeroslot transactions landed at which tip level and keeps
+/// the tip floor fresh, so tips follow what actually lands
+pub struct ZeroSlotTipService {
+    logger: Logger,
+    rpc_client: Arc<RpcClient>,
+    client: reqwest::Client,
+    tip_floor_url: String,
+    update_interval: Duration,
+}
+
+impl ZeroSlotTipService {
+    pub fn new(rpc_client: Arc<RpcClient>, update_interval_seconds: u64) -> Self {
+        Self {
+            logger: Logger::new("[ZEROSLOT-TIPS] => ".magenta().to_string()),
+            rpc_client,
+            client: reqwest::Client::new(),
+            tip_floor_url: get_tip_floor_url(),
+            update_interval: Duration::from_secs(update_interval_seconds),
+        }
+    }
+
+    /// Start the zeroslot tip service
+    pub async fn start(self) {
+        self.logger.log("Starting zeroslot tip service".to_string());
+
+        let mut interval = time::interval(self.update_interval);
+
+        loop {
+            interval.tick().await;
+            match check_pending(&self.rpc_client).await {
+                Ok((0, 0)) => {}
+                Ok((landed, dropped)) => {
+                    self.logger.log(format!("{} landed, {} dropped; by tip level: {}", landed, dropped, tip_stats()));
+                }
+                Err(e) => {
+                    self.logger.log(e.to_string().red().to_string());
+                }
+            }
+            match fetch_tip_floor(&self.client, &self.tip_floor_url).await {
+                Ok(floor) => set_tip_floor(floor),
+                Err(e) => {
+                    self.logger.log(e.to_string().red().to_string());
+                }
+            }
+        }
+    }
+}
+
+/// Start the zeroslot tip service in a background task
+pub async fn start_zeroslot_tips(rpc_client: Arc<RpcClient>, update_interval_seconds: u64) {
+    let service = ZeroSlotTipService::new(rpc_client, update_interval_seconds);
+
+    // Spawn a background task for landing checks and tip floor updates
+    tokio::spawn(async move {
+        service.start().await;
+    });
+}
@@ -0,0 +1,217 @@
+//! Jito landing mode against a local stand-in for the block engine: the bundle carries the swap
+//! and a tip transfer, and its status is polled until it lands or fails.
+
+use std::sync::{Arc, Mutex};
+use serde_json::{json, Value};
+use solana_sdk::{
+    hash::Hash,
+    message::VersionedMessage,
+    signature::{Keypair, Signer},
+    system_instruction,
+    system_program,
+    transaction::VersionedTransaction,
+};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use solana_vntr_sniper::common::logger::Logger;
+use solana_vntr_sniper::core::tx::new_signed_and_send_jito;
+use solana_vntr_sniper::services::jito::{self, JitoClient};
+use solana_vntr_sniper::services::priority_fees::FeeIntent;
+
+const BUNDLE_ID: &str = "b7f1c2d3e4";
+
+/// Block engine stand-in: answers `sendBundle` with `BUNDLE_ID` (or `send_error`) and each
+/// `getInflightBundleStatuses` with the next of `statuses`, repeating the last one
+struct BlockEngine {
+    url: String,
+    requests: Arc<Mutex<Vec<Value>>>,
+}
+
+impl BlockEngine {
+    async fn start(statuses: &[&'static str], send_error: Option<Value>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("Failed to bind stand-in");
+        let url = format!("http://{}", listener.local_addr().expect("Stand-in address"));
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let statuses: Arc<Mutex<Vec<&'static str>>> = Arc::new(Mutex::new(statuses.iter().rev().copied().collect()));
+        let recorded = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let (recorded, statuses, send_error) = (recorded.clone(), statuses.clone(), send_error.clone());
+                tokio::spawn(async move {
+                    let request = read_request(stream).await;
+                    let Some((mut stream, body)) = request else { return };
+                    let response = match body["method"].as_str() {
+                        Some("sendBundle") => match &send_error {
+                            Some(error) => json!({ "jsonrpc": "2.0", "id": 1, "error": error }),
+                            None => json!({ "jsonrpc": "2.0", "id": 1, "result": BUNDLE_ID }),
+                        },
+                        Some("getInflightBundleStatuses") => {
+                            let status = {
+                                let mut statuses = statuses.lock().unwrap();
+                                if statuses.len() > 1 { statuses.pop() } else { statuses.last().copied() }
+                            };
+                            json!({ "jsonrpc": "2.0", "id": 1, "result": {
+                                "context": { "slot": 280_000_000 },
+                                "value": [{
+                                    "bundle_id": BUNDLE_ID,
+                                    "status": status,
+                                    "landed_slot": if status == Some("Landed") { json!(280_000_001) } else { Value::Null },
+                                }],
+                            }})
+                        }
+                        _ => json!({ "jsonrpc": "2.0", "id": 1, "error": { "code": -32601, "message": "Method not found" } }),
+                    };
+                    recorded.lock().unwrap().push(body);
+                    let payload = response.to_string();
+                    let reply = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        payload.len(),
+                        payload
+                    );
+                    let _ = stream.write_all(reply.as_bytes()).await;
+                });
+            }
+        });
+        Self { url, requests }
+    }
+
+    fn requests(&self, method: &str) -> Vec<Value> {
+        self.requests.lock().unwrap().iter().filter(|request| request["method"] == method).cloned().collect()
+    }
+}
+
+/// Read one HTTP request and return its JSON body
+async fn read_request(mut stream: TcpStream) -> Option<(TcpStream, Value)> {
+    let mut raw = Vec::new();
+    let mut buffer = [0u8; 4096];
+    loop {
+        let read = stream.read(&mut buffer).await.ok()?;
+        if read == 0 {
+            return None;
+        }
+        raw.extend_from_slice(&buffer[..read]);
+        let text = String::from_utf8_lossy(&raw);
+        if let Some(header_end) = text.find("\r\n\r\n") {
+            let content_length = text[..header_end]
+                .lines()
+                .find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    name.eq_ignore_ascii_case("content-length").then(|| value.trim().parse::<usize>().ok())?
+                })
+                .unwrap_or(0);
+            if raw.len() >= header_end + 4 + content_length {
+                let body = serde_json::from_slice(&raw[header_end + 4..header_end + 4 + content_length]).ok()?;
+                return Some((stream, body));
+            }
+        }
+    }
+}
+
+fn decode_bundle(send_bundle: &Value) -> Vec<VersionedTransaction> {
+    send_bundle["params"][0]
+        .as_array()
+        .expect("Bundle transactions")
+        .iter()
+        .map(|encoded| {
+            let wire = base64::decode(encoded.as_str().expect("Encoded transaction")).expect("Base64 transaction");
+            bincode::deserialize(&wire).expect("Wire transaction")
+        })
+        .collect()
+}
+
+fn swap_instructions(keypair: &Keypair) -> Vec<solana_sdk::instruction::Instruction> {
+    vec![system_instruction::transfer(&keypair.pubkey(), &keypair.pubkey(), 1)]
+}
+
+#[tokio::test]
+async fn bundle_carries_swap_and_tip_and_lands() {
+    let engine = BlockEngine::start(&["Invalid", "Pending", "Landed"], None).await;
+    let keypair = Keypair::new();
+    let logger = Logger::new("[JITO-TEST] => ".to_string());
+
+    let signatures = new_signed_and_send_jito(
+        Arc::new(JitoClient::new(&engine.url)),
+        Hash::new_unique(),
+        &keypair,
+        swap_instructions(&keypair),
+        &logger,
+        FeeIntent::EmergencySell,
+    )
+    .await
+    .expect("Bundle should land");
+
+    let send_bundle = engine.requests("sendBundle");
+    assert_eq!(send_bundle.len(), 1);
+    assert_eq!(send_bundle[0]["params"][1]["encoding"], "base64");
+    let bundle = decode_bundle(&send_bundle[0]);
+    assert_eq!(bundle.len(), 2, "Bundle is the swap followed by the tip");
+
+    // The swap comes first and its signature is what the caller tracks
+    let (swap, tip) = (&bundle[0], &bundle[1]);
+    assert!(matches!(swap.message, VersionedMessage::V0(_)), "Bundle transactions are v0");
+    assert_eq!(signatures, vec![swap.signatures[0].to_string()]);
+    assert_eq!(swap.message.recent_blockhash(), tip.message.recent_blockhash());
+    assert!(swap.verify_with_results().iter().all(|verified| *verified), "Swap is signed");
+    assert!(tip.verify_with_results().iter().all(|verified| *verified), "Tip is signed");
+
+    // The tip is a single system transfer from the wallet to a Jito tip account
+    let account_keys = tip.message.static_account_keys();
+    assert_eq!(tip.message.instructions().len(), 1);
+    let instruction = &tip.message.instructions()[0];
+    assert_eq!(account_keys[instruction.program_id_index as usize], system_program::id());
+    let expected_tip = system_instruction::transfer(
+        &keypair.pubkey(),
+        &account_keys[instruction.accounts[1] as usize],
+        (jito::get_tip_value() * 1_000_000_000.0).round() as u64,
+    );
+    assert!(jito::TIP_ACCOUNTS.contains(&expected_tip.accounts[1].pubkey));
+    assert_eq!(instruction.data, expected_tip.data);
+    assert_eq!(account_keys[instruction.accounts[0] as usize], keypair.pubkey());
+
+    // Polled until the bundle landed
+    let statuses = engine.requests("getInflightBundleStatuses");
+    assert_eq!(statuses.len(), 3);
+    assert_eq!(statuses[0]["params"], json!([[BUNDLE_ID]]));
+}
+
+#[tokio::test]
+async fn failed_bundle_is_an_error() {
+    let engine = BlockEngine::start(&["Pending", "Failed"], None).await;
+    let keypair = Keypair::new();
+    let logger = Logger::new("[JITO-TEST] => ".to_string());
+
+    let result = new_signed_and_send_jito(
+        Arc::new(JitoClient::new(&engine.url)),
+        Hash::new_unique(),
+        &keypair,
+        swap_instructions(&keypair),
+        &logger,
+        FeeIntent::EmergencySell,
+    )
+    .await;
+
+    let error = result.expect_err("A failed bundle must not report a signature").to_string();
+    assert!(error.contains(BUNDLE_ID), "{}", error);
+}
+
+#[tokio::test]
+async fn rejected_bundle_is_an_error() {
+    let rejection = json!({ "code": -32602, "message": "bundle must contain a tip" });
+    let engine = BlockEngine::start(&["Landed"], Some(rejection)).await;
+    let keypair = Keypair::new();
+    let logger = Logger::new("[JITO-TEST] => ".to_string());
+
+    let result = new_signed_and_send_jito(
+        Arc::new(JitoClient::new(&engine.url)),
+        Hash::new_unique(),
+        &keypair,
+        swap_instructions(&keypair),
+        &logger,
+        FeeIntent::EmergencySell,
+    )
+    .await;
+
+    let error = result.expect_err("A rejected bundle must not report a signature").to_string();
+    assert!(error.contains("bundle must contain a tip"), "{}", error);
+    assert!(engine.requests("getInflightBundleStatuses").is_empty(), "A rejected bundle is not polled");
+}
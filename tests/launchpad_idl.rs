@@ -0,0 +1,480 @@
+//! Launchpad instruction builders checked against the program IDL in `idl/raydium_launchpad.json`:
+//! discriminators, account order, signer and writable flags, fixed addresses, PDAs and the
+//! argument layout of every swap the bot sends. The `PoolState`, `GlobalConfig` and
+//! `PlatformConfig` decoders and the `TradeEvent` decoder are checked against the IDL types.
+//! Update the IDL when the program is upgraded and this suite shows where the code drifted.
+
+use std::path::Path;
+use serde_json::{json, Value};
+use solana_sdk::{
+    hash::hash,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_vntr_sniper::core::token::get_associated_token_address_for_program;
+use solana_vntr_sniper::dex::pool_index::{derive_pool_id, derive_pool_vault};
+use solana_vntr_sniper::dex::quote_mint::USD1_MINT;
+use solana_vntr_sniper::dex::raydium_launchpad::{
+    create_buy_accounts, create_sell_accounts, create_swap_instruction, GlobalConfig, PlatformConfig,
+    PoolState, BUY_DISCRIMINATOR, BUY_EXACT_OUT_DISCRIMINATOR, GLOBAL_CONFIG_DISCRIMINATOR,
+    PLATFORM_CONFIG_DISCRIMINATOR, POOL_STATE_BASE_MINT_OFFSET, POOL_STATE_DISCRIMINATOR, POOL_STATE_LEN,
+    RAYDIUM_GLOBAL_CONFIG, RAYDIUM_LAUNCHPAD_PROGRAM, RAYDIUM_PLATFORM_CONFIG, SELL_DISCRIMINATOR,
+    SELL_EXACT_OUT_DISCRIMINATOR, SOL_MINT, TOKEN_2022_PROGRAM, TOKEN_PROGRAM,
+};
+use solana_vntr_sniper::engine::swap::SwapDirection;
+use solana_vntr_sniper::engine::transaction_parser::{decode_trade_event, TRADE_EVENT_DISCRIMINATOR};
+
+/// Every swap the bot emits, by IDL instruction name
+const EMITTED: [(&str, SwapDirection, [u8; 8]); 4] = [
+    ("buy_exact_in", SwapDirection::Buy, BUY_DISCRIMINATOR),
+    ("buy_exact_out", SwapDirection::Buy, BUY_EXACT_OUT_DISCRIMINATOR),
+    ("sell_exact_in", SwapDirection::Sell, SELL_DISCRIMINATOR),
+    ("sell_exact_out", SwapDirection::Sell, SELL_EXACT_OUT_DISCRIMINATOR),
+];
+
+fn load_idl() -> Value {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("idl/raydium_launchpad.json");
+    let raw = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Failed to read {}: {}", path.display(), e));
+    serde_json::from_str(&raw).unwrap_or_else(|e| panic!("Invalid IDL {}: {}", path.display(), e))
+}
+
+fn idl_instruction<'a>(idl: &'a Value, name: &str) -> &'a Value {
+    idl["instructions"]
+        .as_array()
+        .and_then(|instructions| instructions.iter().find(|instruction| instruction["name"] == name))
+        .unwrap_or_else(|| panic!("Instruction {} not in the IDL", name))
+}
+
+fn bytes(value: &Value) -> Vec<u8> {
+    value
+        .as_array()
+        .unwrap_or_else(|| panic!("Expected a byte array, got {}", value))
+        .iter()
+        .map(|byte| byte.as_u64().and_then(|byte| u8::try_from(byte).ok()).expect("Invalid byte"))
+        .collect()
+}
+
+fn pubkey(value: &Value) -> Pubkey {
+    value.as_str().and_then(|key| key.parse().ok()).unwrap_or_else(|| panic!("Invalid address {}", value))
+}
+
+/// Check `instruction` against IDL instruction `name` and return the remaining accounts, past
+/// the ones the IDL lists. Every mismatch is collected into `errors`.
+fn verify_instruction<'a>(
+    idl: &Value,
+    name: &str,
+    instruction: &'a Instruction,
+    args: &[u64],
+    errors: &mut Vec<String>,
+) -> &'a [AccountMeta] {
+    let program_id = pubkey(&idl["address"]);
+    let idl_instruction = idl_instruction(idl, name);
+    if instruction.program_id != program_id {
+        errors.push(format!("{}: program {} instead of {}", name, instruction.program_id, program_id));
+    }
+
+    // Discriminator, checked against Anchor's derivation so a hand-edited IDL is caught too
+    let discriminator = bytes(&idl_instruction["discriminator"]);
+    if discriminator[..] != hash(format!("global:{}", name).as_bytes()).to_bytes()[..8] {
+        errors.push(format!("{}: IDL discriminator is not the Anchor sighash", name));
+    }
+    if instruction.data.get(..8) != Some(&discriminator[..]) {
+        errors.push(format!("{}: discriminator {:?} instead of {:?}", name, instruction.data.get(..8), discriminator));
+    }
+
+    // Data layout: the discriminator followed by each argument, little endian
+    let idl_args = idl_instruction["args"].as_array().expect("IDL args");
+    let mut offset = 8;
+    for (arg, expected) in idl_args.iter().zip(args.iter().chain(std::iter::repeat(&u64::MAX))) {
+        let arg_name = arg["name"].as_str().unwrap_or_default();
+        assert_eq!(arg["type"], "u64", "{}.{}: only u64 arguments are supported", name, arg_name);
+        let value = instruction.data.get(offset..offset + 8).map(|raw| u64::from_le_bytes(raw.try_into().unwrap()));
+        if value != Some(*expected) {
+            errors.push(format!("{}.{}: {:?} instead of {}", name, arg_name, value, expected));
+        }
+        offset += 8;
+    }
+    if instruction.data.len() != offset {
+        errors.push(format!("{}: data is {} bytes, the IDL layout is {}", name, instruction.data.len(), offset));
+    }
+    if idl_args.len() != args.len() {
+        errors.push(format!("{}: IDL has {} arguments, the test passed {}", name, idl_args.len(), args.len()));
+    }
+
+    // Accounts: order, flags, fixed addresses and PDAs
+    let idl_accounts = idl_instruction["accounts"].as_array().expect("IDL accounts");
+    if instruction.accounts.len() < idl_accounts.len() {
+        errors.push(format!("{}: {} accounts, the IDL lists {}", name, instruction.accounts.len(), idl_accounts.len()));
+        return &[];
+    }
+    let account_key = |path: &str| {
+        idl_accounts
+            .iter()
+            .position(|account| account["name"] == path)
+            .map(|index| instruction.accounts[index].pubkey)
+            .unwrap_or_else(|| panic!("{}: seed account {} not in the IDL", name, path))
+    };
+    for (index, (idl_account, meta)) in idl_accounts.iter().zip(&instruction.accounts).enumerate() {
+        let account_name = idl_account["name"].as_str().unwrap_or_default();
+        let signer = idl_account["signer"].as_bool().unwrap_or(false);
+        let writable = idl_account["writable"].as_bool().unwrap_or(false);
+        if meta.is_signer != signer || meta.is_writable != writable {
+            errors.push(format!(
+                "{}: account {} ({}) is signer={} writable={}, the IDL has signer={} writable={}",
+                name, index, account_name, meta.is_signer, meta.is_writable, signer, writable
+            ));
+        }
+        if !idl_account["address"].is_null() && meta.pubkey != pubkey(&idl_account["address"]) {
+            errors.push(format!("{}: account {} ({}) is {}, the IDL fixes {}", name, index, account_name, meta.pubkey, idl_account["address"]));
+        }
+        if let Some(seeds) = idl_account["pda"]["seeds"].as_array() {
+            let seeds: Vec<Vec<u8>> = seeds
+                .iter()
+                .map(|seed| match seed["kind"].as_str() {
+                    Some("const") => bytes(&seed["value"]),
+                    Some("account") => account_key(seed["path"].as_str().unwrap_or_default()).to_bytes().to_vec(),
+                    kind => panic!("{}: unsupported seed kind {:?}", name, kind),
+                })
+                .collect();
+            let seeds: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+            let (expected, _) = Pubkey::find_program_address(&seeds, &program_id);
+            if meta.pubkey != expected {
+                errors.push(format!("{}: account {} ({}) is {}, the PDA is {}", name, index, account_name, meta.pubkey, expected));
+            }
+        }
+    }
+    &instruction.accounts[idl_accounts.len()..]
+}
+
+/// A pool's swap accounts as the bot builds them
+fn swap_accounts(direction: &SwapDirection, quote_mint: Pubkey, base_program: Pubkey) -> Vec<AccountMeta> {
+    let user = Pubkey::new_unique();
+    let base_mint = Pubkey::new_unique();
+    let pool_id = derive_pool_id(&base_mint, &quote_mint);
+    let configs = if quote_mint == SOL_MINT {
+        (RAYDIUM_GLOBAL_CONFIG, RAYDIUM_PLATFORM_CONFIG)
+    } else {
+        (Pubkey::new_unique(), Pubkey::new_unique())
+    };
+    let build = match direction {
+        SwapDirection::Buy => create_buy_accounts,
+        SwapDirection::Sell => create_sell_accounts,
+    };
+    build(
+        pool_id,
+        user,
+        configs,
+        base_mint,
+        quote_mint,
+        get_associated_token_address_for_program(&user, &base_mint, &base_program),
+        get_associated_token_address_for_program(&user, &quote_mint, &TOKEN_PROGRAM),
+        derive_pool_vault(&pool_id, &base_mint),
+        derive_pool_vault(&pool_id, &quote_mint),
+        &base_program,
+        &TOKEN_PROGRAM,
+    )
+    .expect("Failed to build swap accounts")
+}
+
+#[test]
+fn idl_covers_every_emitted_swap() {
+    let idl = load_idl();
+    assert_eq!(pubkey(&idl["address"]), RAYDIUM_LAUNCHPAD_PROGRAM);
+    for (name, _, _) in EMITTED {
+        idl_instruction(&idl, name);
+    }
+}
+
+#[test]
+fn swap_instructions_match_idl() {
+    let idl = load_idl();
+    let share_fee_receiver = Pubkey::new_unique();
+    let mut errors = Vec::new();
+    for (name, direction, discriminator) in EMITTED {
+        for quote_mint in [SOL_MINT, USD1_MINT] {
+            for base_program in [TOKEN_PROGRAM, TOKEN_2022_PROGRAM] {
+                for share_fee in [None, Some((2_500, share_fee_receiver))] {
+                    let accounts = swap_accounts(&direction, quote_mint, base_program);
+                    let instruction = create_swap_instruction(
+                        RAYDIUM_LAUNCHPAD_PROGRAM,
+                        discriminator,
+                        1_000_000,
+                        990_000,
+                        accounts,
+                        share_fee,
+                    );
+                    let share_fee_rate = share_fee.map_or(0, |(rate, _)| rate);
+                    let remaining = verify_instruction(&idl, name, &instruction, &[1_000_000, 990_000, share_fee_rate], &mut errors);
+
+                    // The only remaining account is the share fee receiver, receiving quote tokens
+                    let expected_remaining: Vec<AccountMeta> = share_fee
+                        .map(|(_, receiver)| AccountMeta::new(receiver, false))
+                        .into_iter()
+                        .collect();
+                    if remaining != expected_remaining.as_slice() {
+                        errors.push(format!("{}: remaining accounts {:?} instead of {:?}", name, remaining, expected_remaining));
+                    }
+                }
+            }
+        }
+    }
+    assert!(errors.is_empty(), "Launchpad builders drifted from the IDL:\n{}", errors.join("\n"));
+}
+
+fn idl_type<'a>(idl: &'a Value, name: &str) -> &'a Value {
+    idl["types"]
+        .as_array()
+        .and_then(|types| types.iter().find(|ty| ty["name"] == name))
+        .map(|ty| &ty["type"])
+        .unwrap_or_else(|| panic!("Type {} not in the IDL", name))
+}
+
+fn idl_fields(idl: &Value, name: &str) -> Vec<String> {
+    idl_type(idl, name)["fields"]
+        .as_array()
+        .unwrap_or_else(|| panic!("{} is not a struct", name))
+        .iter()
+        .map(|field| field["name"].as_str().unwrap_or_default().to_string())
+        .collect()
+}
+
+fn idl_discriminator(idl: &Value, section: &str, name: &str) -> Vec<u8> {
+    let entry = idl[section]
+        .as_array()
+        .and_then(|entries| entries.iter().find(|entry| entry["name"] == name))
+        .unwrap_or_else(|| panic!("{} not in the IDL {}", name, section));
+    bytes(&entry["discriminator"])
+}
+
+/// Borsh-encode a sample value of IDL type `ty` into `out` and return it as JSON. Every scalar
+/// gets a distinct value, so a decoder reading the wrong offset shows up as a mismatch. Enums
+/// take their last variant.
+fn sample(idl: &Value, ty: &Value, seed: &mut u64, out: &mut Vec<u8>) -> Value {
+    *seed += 1;
+    let n = *seed;
+    if let Some(primitive) = ty.as_str() {
+        return match primitive {
+            "u8" => {
+                let value = (n % 250) as u8 + 1;
+                out.push(value);
+                json!(value)
+            }
+            "u16" => {
+                let value = 1_000 + n as u16;
+                out.extend_from_slice(&value.to_le_bytes());
+                json!(value)
+            }
+            "u64" => {
+                let value = n << 40 | n;
+                out.extend_from_slice(&value.to_le_bytes());
+                json!(value)
+            }
+            "bool" => {
+                out.push(1);
+                json!(true)
+            }
+            "pubkey" => {
+                let key = Pubkey::new_from_array([n as u8; 32]);
+                out.extend_from_slice(&key.to_bytes());
+                json!(key.to_string())
+            }
+            other => panic!("Unsupported IDL type {}", other),
+        };
+    }
+    if let Some([element, len]) = ty["array"].as_array().map(Vec::as_slice) {
+        let len = len.as_u64().expect("Array length");
+        return Value::Array((0..len).map(|_| sample(idl, element, seed, out)).collect());
+    }
+    let name = ty["defined"]["name"].as_str().unwrap_or_else(|| panic!("Unsupported IDL type {}", ty));
+    sample_defined(idl, name, seed, out)
+}
+
+fn sample_defined(idl: &Value, name: &str, seed: &mut u64, out: &mut Vec<u8>) -> Value {
+    let ty = idl_type(idl, name);
+    match ty["kind"].as_str() {
+        Some("struct") => {
+            let mut fields = serde_json::Map::new();
+            for field in ty["fields"].as_array().expect("Struct fields") {
+                let value = sample(idl, &field["type"], seed, out);
+                fields.insert(field["name"].as_str().unwrap_or_default().to_string(), value);
+            }
+            Value::Object(fields)
+        }
+        Some("enum") => {
+            let variant = ty["variants"].as_array().expect("Enum variants").len() - 1;
+            out.push(variant as u8);
+            json!(variant)
+        }
+        kind => panic!("{}: unsupported type kind {:?}", name, kind),
+    }
+}
+
+/// Account or event data for IDL type `name`: the discriminator followed by a sample body
+fn sample_data(idl: &Value, section: &str, name: &str) -> (Vec<u8>, Value) {
+    let mut data = idl_discriminator(idl, section, name);
+    let value = sample_defined(idl, name, &mut 0, &mut data);
+    (data, value)
+}
+
+/// Compare decoded fields with the sample and flag IDL fields the decoder does not read
+macro_rules! check_fields {
+    ($errors:expr, $idl:expr, $type_name:expr, $expected:expr, skip: [$($skip:literal),*], { $($field:literal => $actual:expr),* $(,)? }) => {{
+        let checked = [$($field,)* $($skip,)*];
+        for field in idl_fields($idl, $type_name) {
+            if !checked.contains(&field.as_str()) {
+                $errors.push(format!("{}.{}: in the IDL but not decoded", $type_name, field));
+            }
+        }
+        $(
+            if json!($actual) != $expected[$field] {
+                $errors.push(format!("{}.{}: decoded {} instead of {}", $type_name, $field, json!($actual), $expected[$field]));
+            }
+        )*
+    }};
+}
+
+#[test]
+fn account_and_event_discriminators_match_idl() {
+    let idl = load_idl();
+    for (section, prefix, name, discriminator) in [
+        ("accounts", "account", "PoolState", POOL_STATE_DISCRIMINATOR),
+        ("accounts", "account", "GlobalConfig", GLOBAL_CONFIG_DISCRIMINATOR),
+        ("accounts", "account", "PlatformConfig", PLATFORM_CONFIG_DISCRIMINATOR),
+        ("events", "event", "TradeEvent", TRADE_EVENT_DISCRIMINATOR),
+    ] {
+        let idl_discriminator = idl_discriminator(&idl, section, name);
+        assert_eq!(idl_discriminator[..], hash(format!("{}:{}", prefix, name).as_bytes()).to_bytes()[..8], "{}: IDL discriminator is not the Anchor sighash", name);
+        assert_eq!(idl_discriminator[..], discriminator[..], "{}: discriminator constant differs from the IDL", name);
+    }
+}
+
+#[test]
+fn pool_state_decoder_matches_idl() {
+    let idl = load_idl();
+    let (data, expected) = sample_data(&idl, "accounts", "PoolState");
+    assert_eq!(data.len() as u64, POOL_STATE_LEN, "POOL_STATE_LEN differs from the IDL layout");
+    let base_mint = expected["base_mint"].as_str().and_then(|key| key.parse::<Pubkey>().ok()).unwrap();
+    assert_eq!(
+        data.windows(32).position(|window| window == base_mint.as_ref()),
+        Some(POOL_STATE_BASE_MINT_OFFSET),
+        "POOL_STATE_BASE_MINT_OFFSET differs from the IDL layout"
+    );
+
+    let state = PoolState::decode(&data).unwrap();
+    let vesting = &state.vesting_schedule;
+    let mut errors = Vec::new();
+    check_fields!(errors, &idl, "PoolState", expected, skip: [], {
+        "epoch" => state.epoch,
+        "auth_bump" => state.auth_bump,
+        "status" => state.status,
+        "base_decimals" => state.base_decimals,
+        "quote_decimals" => state.quote_decimals,
+        "migrate_type" => state.migrate_type,
+        "supply" => state.supply,
+        "total_base_sell" => state.total_base_sell,
+        "virtual_base" => state.virtual_base,
+        "virtual_quote" => state.virtual_quote,
+        "real_base" => state.real_base,
+        "real_quote" => state.real_quote,
+        "total_quote_fund_raising" => state.total_quote_fund_raising,
+        "quote_protocol_fee" => state.quote_protocol_fee,
+        "platform_fee" => state.platform_fee,
+        "migrate_fee" => state.migrate_fee,
+        "vesting_schedule" => json!({
+            "total_locked_amount": vesting.total_locked_amount,
+            "cliff_period": vesting.cliff_period,
+            "unlock_period": vesting.unlock_period,
+            "start_time": vesting.start_time,
+            "allocated_share_amount": vesting.allocated_share_amount,
+        }),
+        "global_config" => state.global_config.to_string(),
+        "platform_config" => state.platform_config.to_string(),
+        "base_mint" => state.base_mint.to_string(),
+        "quote_mint" => state.quote_mint.to_string(),
+        "base_vault" => state.base_vault.to_string(),
+        "quote_vault" => state.quote_vault.to_string(),
+        "creator" => state.creator.to_string(),
+        "padding" => state.padding,
+    });
+    assert!(errors.is_empty(), "PoolState decoder drifted from the IDL:\n{}", errors.join("\n"));
+}
+
+#[test]
+fn global_config_decoder_matches_idl() {
+    let idl = load_idl();
+    let (data, expected) = sample_data(&idl, "accounts", "GlobalConfig");
+    let config = GlobalConfig::decode(&data).unwrap();
+    let mut errors = Vec::new();
+    check_fields!(errors, &idl, "GlobalConfig", expected, skip: [], {
+        "epoch" => config.epoch,
+        "curve_type" => config.curve_type,
+        "index" => config.index,
+        "migrate_fee" => config.migrate_fee,
+        "trade_fee_rate" => config.trade_fee_rate,
+        "max_share_fee_rate" => config.max_share_fee_rate,
+        "min_base_supply" => config.min_base_supply,
+        "max_lock_rate" => config.max_lock_rate,
+        "min_base_sell_rate" => config.min_base_sell_rate,
+        "min_base_migrate_rate" => config.min_base_migrate_rate,
+        "min_quote_fund_raising" => config.min_quote_fund_raising,
+        "quote_mint" => config.quote_mint.to_string(),
+        "protocol_fee_owner" => config.protocol_fee_owner.to_string(),
+        "migrate_fee_owner" => config.migrate_fee_owner.to_string(),
+        "migrate_to_amm_wallet" => config.migrate_to_amm_wallet.to_string(),
+        "migrate_to_cpswap_wallet" => config.migrate_to_cpswap_wallet.to_string(),
+        "padding" => config.padding,
+    });
+    assert!(errors.is_empty(), "GlobalConfig decoder drifted from the IDL:\n{}", errors.join("\n"));
+}
+
+#[test]
+fn platform_config_decoder_matches_idl() {
+    let idl = load_idl();
+    let (data, expected) = sample_data(&idl, "accounts", "PlatformConfig");
+    let config = PlatformConfig::decode(&data).unwrap();
+    let mut errors = Vec::new();
+    check_fields!(errors, &idl, "PlatformConfig", expected, skip: ["padding"], {
+        "epoch" => config.epoch,
+        "platform_fee_wallet" => config.platform_fee_wallet.to_string(),
+        "platform_nft_wallet" => config.platform_nft_wallet.to_string(),
+        "platform_scale" => config.platform_scale,
+        "creator_scale" => config.creator_scale,
+        "burn_scale" => config.burn_scale,
+        "fee_rate" => config.fee_rate,
+        "name" => config.name.to_vec(),
+        "web" => config.web.to_vec(),
+        "img" => config.img.to_vec(),
+        "cpswap_config" => config.cpswap_config.map(|key| key.to_string()),
+        "creator_fee_rate" => config.creator_fee_rate,
+    });
+    assert!(errors.is_empty(), "PlatformConfig decoder drifted from the IDL:\n{}", errors.join("\n"));
+}
+
+#[test]
+fn trade_event_decoder_matches_idl() {
+    let idl = load_idl();
+    let (data, expected) = sample_data(&idl, "events", "TradeEvent");
+    let event = decode_trade_event(&data).expect("TradeEvent sample did not decode");
+    let mut errors = Vec::new();
+    check_fields!(errors, &idl, "TradeEvent", expected, skip: [], {
+        "pool_state" => event.pool_state.to_string(),
+        "total_base_sell" => event.total_base_sell,
+        "virtual_base" => event.virtual_base,
+        "virtual_quote" => event.virtual_quote,
+        "real_base_before" => event.real_base_before,
+        "real_quote_before" => event.real_quote_before,
+        "real_base_after" => event.real_base_after,
+        "real_quote_after" => event.real_quote_after,
+        "amount_in" => event.amount_in,
+        "amount_out" => event.amount_out,
+        "protocol_fee" => event.protocol_fee,
+        "platform_fee" => event.platform_fee,
+        "creator_fee" => event.creator_fee,
+        "share_fee" => event.share_fee,
+        "trade_direction" => if event.is_buy { 0 } else { 1 },
+        "pool_status" => event.pool_status as u8,
+        "exact_in" => event.exact_in,
+    });
+    assert!(errors.is_empty(), "TradeEvent decoder drifted from the IDL:\n{}", errors.join("\n"));
+}